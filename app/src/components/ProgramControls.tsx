@@ -15,6 +15,32 @@ import {
 } from '@solana/spl-token'
 
 const PROGRAM_ID = new PublicKey(idl.address)
+const [PLATFORM_CONFIG] = PublicKey.findProgramAddressSync([Buffer.from('platform_config')], PROGRAM_ID)
+
+const [INSURANCE_VAULT] = PublicKey.findProgramAddressSync([Buffer.from('insurance_vault')], PROGRAM_ID)
+
+// Platform accounts every launch needs. The creator bond is only passed when the platform
+// currently requires a bond.
+async function launchAccounts(program: Program, mint: PublicKey) {
+  const config: any = await (program.account as any).platformConfig.fetch(PLATFORM_CONFIG)
+  const creatorBond = config.bondLamports.isZero()
+    ? null
+    : PublicKey.findProgramAddressSync([Buffer.from('creator_bond'), mint.toBuffer()], PROGRAM_ID)[0]
+  return {
+    platformConfig: PLATFORM_CONFIG,
+    creatorBond,
+    devnetFaucet: null,
+    featuredGauge: null,
+    priceUpdate: null,
+    treasury: config.treasury as PublicKey,
+    insuranceVault: INSURANCE_VAULT,
+    launchConfig: PublicKey.findProgramAddressSync([Buffer.from('launch_config'), mint.toBuffer()], PROGRAM_ID)[0],
+    creatorReputation: PublicKey.findProgramAddressSync(
+      [Buffer.from('reputation'), program.provider.publicKey!.toBuffer()],
+      PROGRAM_ID
+    )[0],
+  }
+}
 
 export default function ProgramControls() {
   const { connection } = useConnection()
@@ -57,6 +83,7 @@ export default function ProgramControls() {
           feeWithdrawAuthority: wallet.publicKey,
           freezeAuthority: wallet.publicKey,
           tokenProgram: TOKEN_2022_PROGRAM_ID,
+          ...(await launchAccounts(program, mint.publicKey)),
           systemProgram: SystemProgram.programId,
           rent: SYSVAR_RENT_PUBKEY,
         })
@@ -85,6 +112,7 @@ export default function ProgramControls() {
           mintAuthority: wallet.publicKey,
           freezeAuthority: wallet.publicKey,
           tokenProgram: TOKEN_2022_PROGRAM_ID,
+          ...(await launchAccounts(program, mint.publicKey)),
           systemProgram: SystemProgram.programId,
           rent: SYSVAR_RENT_PUBKEY,
         })
@@ -0,0 +1,997 @@
+//! Decoders for the events the program logs, for indexers reading transaction logs from RPC,
+//! webhooks or a Geyser plugin.
+//!
+//! Every event starts with its pinned discriminator and a `version` byte. Later versions only
+//! append fields, so these decoders keep reading the fields they know from newer events.
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use borsh::BorshDeserialize;
+use solana_pubkey::Pubkey;
+
+use crate::{
+    state::{ComplianceFlags, DecodeError, NttMode, TimelockAccountMeta},
+    ID,
+};
+
+/// Event layout version these decoders were written against
+pub const EVENT_VERSION: u8 = 1;
+
+/// An event of the launchpad, with the discriminator the program pins for it.
+pub trait Event: BorshDeserialize {
+    const DISCRIMINATOR: [u8; 8];
+}
+
+macro_rules! events {
+    ($($ty:ident = $discriminator:expr),* $(,)?) => {
+        $(impl Event for $ty {
+            const DISCRIMINATOR: [u8; 8] = $discriminator;
+        })*
+
+        /// Any launchpad event
+        #[derive(Clone, Debug, PartialEq, Eq)]
+        pub enum LaunchpadEvent {
+            $($ty($ty),)*
+        }
+
+        /// Decode an event from its discriminator and data. Unknown discriminators give `None`
+        /// so an indexer can skip events added after it was built.
+        pub fn decode(data: &[u8]) -> Result<Option<LaunchpadEvent>, DecodeError> {
+            let Some((discriminator, mut body)) = data.split_first_chunk::<8>() else {
+                return Ok(None);
+            };
+            $(if *discriminator == $ty::DISCRIMINATOR {
+                return $ty::deserialize(&mut body).map(|event| Some(LaunchpadEvent::$ty(event))).map_err(DecodeError::Borsh);
+            })*
+            Ok(None)
+        }
+    };
+}
+
+events!(
+    StreamCreated = [93, 150, 91, 15, 166, 8, 251, 166],
+    StreamWithdrawn = [229, 224, 216, 237, 68, 225, 122, 75],
+    StreamCancelled = [91, 215, 29, 237, 194, 6, 184, 92],
+    StreamClosed = [14, 206, 32, 10, 248, 82, 169, 234],
+    OtcOfferCreated = [251, 3, 25, 253, 65, 120, 192, 10],
+    OtcOfferFilled = [30, 5, 200, 165, 242, 217, 17, 166],
+    OtcOfferCancelled = [45, 101, 199, 241, 255, 137, 157, 151],
+    AirdropBatchSent = [81, 241, 178, 115, 116, 170, 66, 101],
+    AirdropSwept = [95, 83, 208, 221, 184, 59, 198, 101],
+    AirdropClosed = [196, 85, 30, 72, 165, 151, 163, 126],
+    PointsAwarded = [201, 95, 152, 50, 215, 83, 188, 38],
+    PointsRedeemed = [57, 161, 97, 67, 102, 120, 85, 147],
+    QuestCompleted = [185, 225, 220, 84, 209, 183, 217, 238],
+    SnapshotPosted = [224, 99, 100, 104, 153, 13, 20, 15],
+    ProposalCreated = [186, 8, 160, 108, 81, 13, 51, 206],
+    VoteCast = [39, 53, 195, 104, 188, 17, 225, 213],
+    ProposalFinalized = [159, 104, 210, 220, 86, 209, 61, 51],
+    VoteLockCreated = [231, 17, 170, 98, 123, 221, 153, 211],
+    GaugeVoteCast = [210, 66, 171, 101, 180, 152, 105, 57],
+    LaunchFeatured = [181, 122, 3, 14, 211, 214, 52, 132],
+    TreasuryDisbursed = [181, 163, 45, 130, 26, 197, 100, 210],
+    RecurringPaymentExecuted = [167, 174, 91, 30, 102, 117, 232, 180],
+    NttConfigured = [52, 30, 161, 16, 41, 46, 28, 100],
+    EpochRolledOver = [248, 151, 208, 212, 152, 58, 23, 231],
+    MintPaused = [190, 101, 215, 127, 210, 131, 124, 246],
+    MintUnpaused = [71, 38, 143, 177, 243, 7, 181, 14],
+    AccountExpanded = [76, 6, 6, 201, 199, 67, 177, 147],
+    PoolRegistered = [77, 114, 165, 230, 33, 230, 135, 215],
+    PoolDeregistered = [199, 101, 13, 77, 137, 141, 22, 17],
+    EmissionMinted = [127, 4, 53, 212, 212, 89, 247, 244],
+    CreatorBondPosted = [201, 158, 104, 122, 159, 5, 78, 81],
+    CreatorBondRefunded = [98, 147, 45, 177, 189, 203, 59, 194],
+    LaunchRetired = [21, 154, 235, 55, 241, 242, 248, 245],
+    LaunchFlagged = [224, 170, 162, 183, 228, 160, 19, 111],
+    DisputeResolved = [121, 64, 249, 153, 139, 128, 236, 187],
+    BondSlashed = [59, 7, 252, 195, 234, 156, 42, 54],
+    TokenVerified = [153, 4, 13, 117, 185, 18, 143, 16],
+    VerificationRevoked = [29, 254, 168, 162, 205, 215, 229, 176],
+    AuditPublished = [85, 195, 57, 7, 104, 105, 114, 189],
+    InsuranceClaimPaid = [192, 156, 171, 111, 107, 227, 78, 164],
+    ExemptTransferRecorded = [146, 167, 139, 121, 173, 47, 189, 154],
+    FeeRebateClaimed = [4, 181, 171, 161, 91, 96, 2, 198],
+    DelegatedFeesWithdrawn = [30, 69, 47, 131, 75, 55, 152, 177],
+    FeeDestinationsQueued = [224, 171, 163, 246, 178, 213, 148, 180],
+    OpQueued = [229, 17, 74, 251, 142, 170, 106, 39],
+    OpExecuted = [221, 15, 212, 29, 35, 252, 255, 78],
+    OpCancelled = [170, 253, 174, 29, 126, 177, 170, 190],
+    CheckedIn = [211, 80, 198, 244, 196, 84, 212, 150],
+    VoucherClaimed = [45, 117, 206, 58, 70, 216, 79, 135],
+    GiftCreated = [149, 59, 123, 115, 215, 127, 14, 194],
+    GiftClaimed = [70, 176, 25, 29, 237, 141, 185, 163],
+    GiftCancelled = [223, 4, 128, 62, 95, 139, 48, 179],
+    WrapperCreated = [184, 253, 133, 68, 124, 86, 149, 255],
+    Wrapped = [11, 127, 145, 31, 206, 134, 73, 130],
+    Unwrapped = [25, 86, 93, 80, 145, 113, 86, 93],
+    ExchangeRegistered = [56, 94, 77, 243, 47, 68, 185, 14],
+    ExchangeApproved = [205, 118, 63, 246, 2, 25, 115, 188],
+    ExchangeRevoked = [162, 124, 96, 65, 126, 3, 45, 196],
+    PartnerRegistered = [17, 156, 161, 97, 14, 245, 207, 212],
+    PartnerUpdated = [252, 75, 196, 136, 205, 179, 24, 155],
+    PartnerFeesWithdrawn = [255, 247, 184, 82, 31, 124, 81, 163],
+    PlatformTokensStaked = [204, 252, 79, 160, 182, 108, 69, 222],
+    PlatformTokensUnstaked = [213, 37, 206, 202, 175, 172, 76, 7],
+    StakingRewardsDistributed = [21, 111, 78, 12, 59, 48, 127, 209],
+    StakingRewardsClaimed = [251, 169, 50, 96, 7, 92, 141, 137],
+    LaunchScheduled = [238, 26, 84, 92, 241, 165, 254, 231],
+    AllocationPlanCreated = [215, 233, 9, 142, 98, 154, 162, 152],
+    AllocationReleased = [13, 30, 122, 132, 167, 177, 23, 108],
+    ComplianceFlagsUpdated = [170, 33, 31, 11, 82, 58, 250, 92],
+    WalletAttested = [119, 224, 21, 16, 122, 86, 119, 167],
+    ComplianceFrozen = [232, 246, 124, 95, 227, 64, 69, 152],
+    ComplianceThawed = [188, 205, 139, 201, 77, 215, 97, 109],
+    ComplianceOfficerSet = [125, 217, 221, 134, 15, 95, 79, 229],
+    FreezeOrderIssued = [55, 170, 248, 91, 102, 107, 123, 159],
+    FreezeOrderLifted = [145, 8, 147, 74, 54, 49, 218, 233],
+    IndexFeedInitialized = [238, 172, 33, 33, 94, 244, 104, 102],
+    IndexMultiplierUpdated = [136, 125, 59, 235, 153, 141, 179, 174],
+    InterestRateGovernorInitialized = [49, 156, 152, 218, 192, 158, 113, 98],
+    InterestRateUpdated = [175, 35, 117, 108, 182, 73, 172, 114],
+    AirdropRecipientSkipped = [41, 2, 28, 208, 187, 187, 239, 187],
+    AllocationPlanClosed = [120, 165, 246, 33, 28, 181, 211, 64],
+    AirdropSkippedClaimed = [141, 230, 126, 217, 232, 203, 226, 6],
+    AllocationRevoked = [40, 66, 35, 103, 104, 167, 148, 159],
+    AllocationBeneficiaryProposed = [185, 117, 184, 67, 10, 45, 164, 251],
+    AllocationBeneficiaryChanged = [46, 136, 183, 244, 120, 90, 104, 197],
+    DividendPoolCreated = [79, 251, 246, 255, 170, 123, 89, 18],
+    DividendsFunded = [197, 53, 221, 159, 242, 255, 49, 243],
+    DividendStaked = [215, 152, 195, 19, 126, 43, 244, 135],
+    DividendUnstaked = [1, 221, 224, 182, 232, 37, 144, 120],
+    DividendsDistributed = [117, 213, 170, 55, 19, 80, 211, 101],
+    DividendsClaimed = [47, 49, 16, 88, 139, 132, 245, 16],
+    TokenMetadataInitialized = [34, 56, 137, 145, 43, 8, 101, 95],
+    MetaplexMetadataCreated = [168, 74, 109, 120, 173, 67, 201, 103],
+    MetadataLocked = [50, 191, 57, 252, 124, 239, 162, 209],
+);
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct StreamCreated {
+    pub version: u8,
+    pub stream: Pubkey,
+    pub sender: Pubkey,
+    pub recipient: Pubkey,
+    pub mint: Pubkey,
+    pub deposited: u64,
+    pub start_time: i64,
+    pub end_time: i64,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct StreamWithdrawn {
+    pub version: u8,
+    pub stream: Pubkey,
+    /// Tokens taken out of the vault
+    pub amount: u64,
+    /// Transfer fee withheld from the payout
+    pub fee: u64,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct StreamCancelled {
+    pub version: u8,
+    pub stream: Pubkey,
+    pub paid_to_recipient: u64,
+    pub refunded: u64,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct StreamClosed {
+    pub version: u8,
+    pub stream: Pubkey,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct OtcOfferCreated {
+    pub version: u8,
+    pub offer: Pubkey,
+    pub maker: Pubkey,
+    pub mint_a: Pubkey,
+    pub amount_a: u64,
+    pub mint_b: Pubkey,
+    pub amount_b: u64,
+    pub counterparty: Option<Pubkey>,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct OtcOfferFilled {
+    pub version: u8,
+    pub offer: Pubkey,
+    pub taker: Pubkey,
+    /// Tokens of mint A released from escrow, and the fee withheld from them
+    pub amount_a: u64,
+    pub fee_a: u64,
+    /// Tokens of mint B paid by the taker, and the fee withheld from them
+    pub amount_b: u64,
+    pub fee_b: u64,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct OtcOfferCancelled {
+    pub version: u8,
+    pub offer: Pubkey,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct AirdropRecipientSkipped {
+    pub version: u8,
+    pub campaign: Pubkey,
+    pub index: u32,
+    pub wallet: Pubkey,
+    pub amount: u64,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct AirdropSkippedClaimed {
+    pub version: u8,
+    pub campaign: Pubkey,
+    pub index: u32,
+    pub wallet: Pubkey,
+    pub amount: u64,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct AirdropBatchSent {
+    pub version: u8,
+    pub campaign: Pubkey,
+    pub recipients: u32,
+    pub amount: u64,
+    pub cursor: u32,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct AirdropSwept {
+    pub version: u8,
+    pub campaign: Pubkey,
+    pub amount: u64,
+    pub burned: bool,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct AirdropClosed {
+    pub version: u8,
+    pub campaign: Pubkey,
+    /// Remainder sent back to the creator
+    pub returned: u64,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PointsAwarded {
+    pub version: u8,
+    pub points_program: Pubkey,
+    pub operator: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PointsRedeemed {
+    pub version: u8,
+    pub points_program: Pubkey,
+    pub holder: Pubkey,
+    pub amount: u64,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct QuestCompleted {
+    pub version: u8,
+    pub quest: Pubkey,
+    pub wallet: Pubkey,
+    pub attestor: Pubkey,
+    pub quests_completed: u32,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct SnapshotPosted {
+    pub version: u8,
+    pub snapshot: Pubkey,
+    pub mint: Pubkey,
+    pub slot: u64,
+    pub merkle_root: [u8; 32],
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ProposalCreated {
+    pub version: u8,
+    pub proposal: Pubkey,
+    pub snapshot: Pubkey,
+    pub proposer: Pubkey,
+    pub voting_ends_at: i64,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct VoteCast {
+    pub version: u8,
+    pub proposal: Pubkey,
+    pub voter: Pubkey,
+    pub support: bool,
+    pub weight: u64,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ProposalFinalized {
+    pub version: u8,
+    pub proposal: Pubkey,
+    pub passed: bool,
+    pub yes_weight: u64,
+    pub no_weight: u64,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct VoteLockCreated {
+    pub version: u8,
+    pub vote_lock: Pubkey,
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub unlock_at: i64,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct GaugeVoteCast {
+    pub version: u8,
+    pub epoch: u64,
+    pub voter: Pubkey,
+    pub mint: Pubkey,
+    pub weight: u64,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct LaunchFeatured {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub epoch: u64,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct TreasuryDisbursed {
+    pub version: u8,
+    pub treasury: Pubkey,
+    pub index: u64,
+    pub spender: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub fee: u64,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct RecurringPaymentExecuted {
+    pub version: u8,
+    pub payment: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub fee: u64,
+    pub next_due: i64,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct NttConfigured {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub ntt_manager: Pubkey,
+    pub mode: NttMode,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct EpochRolledOver {
+    pub version: u8,
+    pub epoch: u64,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct MintPaused {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub paused_by: Pubkey,
+    pub paused_until: i64,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct MintUnpaused {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub unpaused_by: Pubkey,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct AccountExpanded {
+    pub version: u8,
+    pub account: Pubkey,
+    /// Entries the account can now hold
+    pub capacity: u32,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PoolRegistered {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub pool_vault: Pubkey,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PoolDeregistered {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub pool_vault: Pubkey,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct EmissionMinted {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub total_minted: u64,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CreatorBondPosted {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub lamports: u64,
+    pub unlock_at: i64,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CreatorBondRefunded {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub lamports: u64,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct LaunchRetired {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub active_launches: u32,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct LaunchFlagged {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub reporter: Pubkey,
+    pub opened_at: i64,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct DisputeResolved {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub upheld: bool,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct BondSlashed {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    /// Bond plus the bond account's rent, moved to the insurance vault
+    pub lamports: u64,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct TokenVerified {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub verifier: Pubkey,
+    pub attestation_uri: String,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct VerificationRevoked {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub verifier: Pubkey,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct AuditPublished {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub auditor: Pubkey,
+    pub report_hash: [u8; 32],
+    pub report_uri: String,
+    pub passed: bool,
+    pub revision: u32,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct InsuranceClaimPaid {
+    pub version: u8,
+    pub recipient: Pubkey,
+    pub lamports: u64,
+    pub claim_uri: String,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ExemptTransferRecorded {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub wallet: Pubkey,
+    pub fee: u64,
+    pub rebate_owed: u64,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct FeeRebateClaimed {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub wallet: Pubkey,
+    /// Amount the wallet received
+    pub rebate: u64,
+    /// Amount taken out of the fee vault, including the fee withheld on the payout
+    pub gross_amount: u64,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct DelegatedFeesWithdrawn {
+    pub version: u8,
+    pub mint: Pubkey,
+    /// Delegate or authority that withdrew
+    pub signer: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct FeeDestinationsQueued {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub destinations: Vec<Pubkey>,
+    pub applies_at: i64,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct OpQueued {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub op_id: u64,
+    pub program_id: Pubkey,
+    pub accounts: Vec<TimelockAccountMeta>,
+    pub data: Vec<u8>,
+    pub eta: i64,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct OpExecuted {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub op_id: u64,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct OpCancelled {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub op_id: u64,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct VoucherClaimed {
+    pub version: u8,
+    pub campaign: Pubkey,
+    pub wallet: Pubkey,
+    pub amount: u64,
+    pub nonce: u64,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct GiftCreated {
+    pub version: u8,
+    pub gift: Pubkey,
+    pub sender: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub expires_at: i64,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct GiftClaimed {
+    pub version: u8,
+    pub gift: Pubkey,
+    pub recipient_token_account: Pubkey,
+    pub amount: u64,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct GiftCancelled {
+    pub version: u8,
+    pub gift: Pubkey,
+    pub refunded: u64,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PlatformTokensStaked {
+    pub version: u8,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub total_staked: u64,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PlatformTokensUnstaked {
+    pub version: u8,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub total_staked: u64,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct StakingRewardsDistributed {
+    pub version: u8,
+    pub epoch: u64,
+    pub rewards: u64,
+    pub reward_per_share: u128,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct StakingRewardsClaimed {
+    pub version: u8,
+    pub owner: Pubkey,
+    pub lamports: u64,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct DividendPoolCreated {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub reward_mint: Pubkey,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct DividendsFunded {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub funder: Pubkey,
+    pub amount: u64,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct DividendStaked {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub total_staked: u64,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct DividendUnstaked {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub total_staked: u64,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct DividendsDistributed {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub epoch: u64,
+    pub rewards: u64,
+    pub reward_per_share: u128,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct DividendsClaimed {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub fee: u64,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct TokenMetadataInitialized {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub update_authority: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub duplicate_symbol: bool,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct MetaplexMetadataCreated {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub metadata: Pubkey,
+    pub update_authority: Pubkey,
+    pub is_mutable: bool,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct MetadataLocked {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub locked_at: i64,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ComplianceFlagsUpdated {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub flags: Option<ComplianceFlags>,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct WalletAttested {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub wallet: Pubkey,
+    pub accredited: bool,
+    pub expires_at: i64,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ComplianceFrozen {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub token_account: Pubkey,
+    pub wallet: Pubkey,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ComplianceThawed {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub token_account: Pubkey,
+    pub wallet: Pubkey,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ComplianceOfficerSet {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub officer: Option<Pubkey>,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct FreezeOrderIssued {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub token_account: Pubkey,
+    pub officer: Pubkey,
+    pub justification_uri: String,
+    pub justification_hash: [u8; 32],
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct FreezeOrderLifted {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub token_account: Pubkey,
+    pub officer: Pubkey,
+    pub justification_uri: String,
+    pub justification_hash: [u8; 32],
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct IndexFeedInitialized {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub feed_id: [u8; 32],
+    pub base_price: i64,
+    pub exponent: i32,
+    pub max_deviation_bps: u16,
+    pub min_update_interval_seconds: i64,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct IndexMultiplierUpdated {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub price: i64,
+    pub target_multiplier: u64,
+    pub multiplier: u64,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct InterestRateGovernorInitialized {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub admin: Pubkey,
+    pub rate_bps: i16,
+    pub min_rate_bps: i16,
+    pub max_rate_bps: i16,
+    pub min_update_interval_seconds: i64,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct InterestRateUpdated {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub rate_bps: i16,
+    pub min_rate_bps: i16,
+    pub max_rate_bps: i16,
+    pub governed: bool,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct AllocationPlanClosed {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub swept: u64,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct AllocationPlanCreated {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub total_supply: u64,
+    pub buckets: u8,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct AllocationReleased {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub index: u8,
+    pub amount: u64,
+    pub fee: u64,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct AllocationRevoked {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub index: u8,
+    pub returned: u64,
+    pub fee: u64,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct AllocationBeneficiaryProposed {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub index: u8,
+    pub beneficiary: Pubkey,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct AllocationBeneficiaryChanged {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub index: u8,
+    pub previous: Pubkey,
+    pub beneficiary: Pubkey,
+    pub released: u64,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct LaunchScheduled {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub opens_at: i64,
+    pub day: i64,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PartnerRegistered {
+    pub version: u8,
+    pub partner: Pubkey,
+    pub authority: Pubkey,
+    pub namespace: String,
+    pub fee_share_bps: u16,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PartnerUpdated {
+    pub version: u8,
+    pub partner: Pubkey,
+    pub fee_share_bps: u16,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PartnerFeesWithdrawn {
+    pub version: u8,
+    pub partner: Pubkey,
+    pub recipient: Pubkey,
+    pub lamports: u64,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ExchangeRegistered {
+    pub version: u8,
+    pub listing: Pubkey,
+    pub mint: Pubkey,
+    pub exchange: Pubkey,
+    pub wallets: u8,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ExchangeApproved {
+    pub version: u8,
+    pub listing: Pubkey,
+    pub mint: Pubkey,
+    pub exchange: Pubkey,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ExchangeRevoked {
+    pub version: u8,
+    pub listing: Pubkey,
+    pub mint: Pubkey,
+    pub exchange: Pubkey,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct WrapperCreated {
+    pub version: u8,
+    pub wrapper: Pubkey,
+    pub mint: Pubkey,
+    pub wrapped_mint: Pubkey,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Wrapped {
+    pub version: u8,
+    pub wrapper: Pubkey,
+    pub owner: Pubkey,
+    pub deposited: u64,
+    pub minted: u64,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Unwrapped {
+    pub version: u8,
+    pub wrapper: Pubkey,
+    pub owner: Pubkey,
+    pub burned: u64,
+    pub received: u64,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CheckedIn {
+    pub version: u8,
+    pub event: Pubkey,
+    pub wallet: Pubkey,
+    pub attendees: u32,
+}
+
+const PROGRAM_DATA: &str = "Program data: ";
+
+/// Decode the launchpad events in a transaction's log messages, in emission order. Only data
+/// logged while the launchpad itself is executing is read, so events of other programs in the
+/// same transaction (including ones invoked by the launchpad) are skipped.
+pub fn parse_logs<S: AsRef<str>>(logs: &[S]) -> Result<Vec<LaunchpadEvent>, DecodeError> {
+    let program = ID.to_string();
+    let mut stack: Vec<bool> = Vec::new();
+    let mut events = Vec::new();
+    for log in logs {
+        let log = log.as_ref();
+        if let Some(data) = log.strip_prefix(PROGRAM_DATA) {
+            if stack.last() == Some(&true) {
+                let Ok(data) = STANDARD.decode(data) else {
+                    continue;
+                };
+                events.extend(decode(&data)?);
+            }
+        } else if let Some(rest) = log.strip_prefix("Program ") {
+            // Invocation frames: "Program <id> invoke [n]", "Program <id> success" and
+            // "Program <id> failed: <error>"; `msg!` output is "Program log: ..."
+            let mut words = rest.split(' ');
+            let (Some(id), Some(action)) = (words.next(), words.next()) else {
+                continue;
+            };
+            if id.parse::<Pubkey>().is_err() {
+                continue;
+            }
+            match action {
+                "invoke" => stack.push(id == program),
+                "success" | "failed:" => {
+                    stack.pop();
+                }
+                _ => {}
+            }
+        }
+    }
+    Ok(events)
+}
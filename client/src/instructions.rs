@@ -0,0 +1,1330 @@
+//! Instruction builders. Account order and writability match the program's `Accounts` structs;
+//! optional accounts left out are passed as the program id, as Anchor expects.
+
+use borsh::BorshSerialize;
+use solana_instruction::{AccountMeta, Instruction};
+use solana_pubkey::Pubkey;
+
+use crate::{
+    instruction_discriminator, pda,
+    state::{
+        AllocationBucket, ComplianceFlags, DividendPool, Gift, InterestRateBounds, QueuedOp,
+        TimelockAccountMeta, TokenMetadataParams, Wrapper,
+    },
+    ID, INSTRUCTIONS_SYSVAR_ID, METAPLEX_METADATA_PROGRAM_ID, RENT_SYSVAR_ID, SYSTEM_PROGRAM_ID,
+    TOKEN_2022_PROGRAM_ID,
+};
+
+fn instruction(name: &str, args: impl BorshSerialize, accounts: Vec<AccountMeta>) -> Instruction {
+    let mut data = instruction_discriminator(name).to_vec();
+    args.serialize(&mut data)
+        .expect("serializing to a Vec can't fail");
+    Instruction {
+        program_id: ID,
+        accounts,
+        data,
+    }
+}
+
+fn optional(account: Option<Pubkey>, writable: bool) -> AccountMeta {
+    match account {
+        Some(key) if writable => AccountMeta::new(key, false),
+        Some(key) => AccountMeta::new_readonly(key, false),
+        None => AccountMeta::new_readonly(ID, false),
+    }
+}
+
+/// Accounts shared by the creation instructions.
+#[derive(Clone, Copy, Debug)]
+pub struct LaunchAccounts {
+    /// Creator, paying rent and fees
+    pub payer: Pubkey,
+    /// Fresh keypair for the mint
+    pub mint: Pubkey,
+    pub mint_authority: Pubkey,
+    pub freeze_authority: Pubkey,
+    /// Platform treasury, as in PlatformConfig
+    pub treasury: Pubkey,
+    /// Post the platform's creator bond
+    pub with_bond: bool,
+    /// Open a devnet faucet for the mint
+    pub with_faucet: bool,
+    /// Gauge epoch account proving the launch is featured
+    pub featured_gauge: Option<Pubkey>,
+    /// Pyth SOL/USD price update, for USD-priced launch fees
+    pub price_update: Option<Pubkey>,
+    /// Platform attestor co-signing the launch, marking it platform-reviewed
+    pub attestor: Option<Pubkey>,
+    /// White-label partner the launch is made through, see [`pda::partner`]
+    pub partner: Option<Pubkey>,
+    /// Pass the platform staking pool, needed while launch fees are shared with stakers
+    pub with_staking: bool,
+}
+
+impl LaunchAccounts {
+    fn metas(
+        &self,
+        fee_withdraw_authority: Option<AccountMeta>,
+        template: Option<Pubkey>,
+        with_rent: bool,
+    ) -> Vec<AccountMeta> {
+        let mut metas = vec![
+            AccountMeta::new(self.payer, true),
+            AccountMeta::new(self.mint, true),
+            AccountMeta::new_readonly(self.mint_authority, true),
+        ];
+        metas.extend(fee_withdraw_authority);
+        metas.push(AccountMeta::new_readonly(self.freeze_authority, false));
+        if let Some(template) = template {
+            metas.push(AccountMeta::new_readonly(template, false));
+        }
+        metas.extend([
+            AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
+            AccountMeta::new(pda::platform_config().0, false),
+            optional(
+                self.with_bond.then(|| pda::creator_bond(&self.mint).0),
+                true,
+            ),
+            optional(self.with_faucet.then(|| pda::faucet(&self.mint).0), true),
+            AccountMeta::new(self.treasury, false),
+            AccountMeta::new(pda::insurance_vault().0, false),
+            optional(self.featured_gauge, false),
+            optional(self.price_update, false),
+            match self.attestor {
+                Some(attestor) => AccountMeta::new_readonly(attestor, true),
+                None => optional(None, false),
+            },
+            optional(self.partner, true),
+            optional(self.with_staking.then(|| pda::platform_staking().0), true),
+            AccountMeta::new(pda::launch_config(&self.mint).0, false),
+            AccountMeta::new(pda::creator_reputation(&self.payer).0, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        ]);
+        if with_rent {
+            metas.push(AccountMeta::new_readonly(RENT_SYSVAR_ID, false));
+        }
+        metas
+    }
+}
+
+/// `create_taxed_token`
+pub fn create_taxed_token(
+    launch: &LaunchAccounts,
+    fee_withdraw_authority: &Pubkey,
+    decimals: u8,
+    transfer_fee_basis_points: u16,
+    maximum_fee: u64,
+) -> Instruction {
+    instruction(
+        "create_taxed_token",
+        (decimals, transfer_fee_basis_points, maximum_fee),
+        launch.metas(
+            Some(AccountMeta::new_readonly(*fee_withdraw_authority, true)),
+            None,
+            true,
+        ),
+    )
+}
+
+/// `create_soulbound_token`
+pub fn create_soulbound_token(launch: &LaunchAccounts, decimals: u8) -> Instruction {
+    instruction(
+        "create_soulbound_token",
+        decimals,
+        launch.metas(None, None, true),
+    )
+}
+
+/// `create_from_template`; the fee withdraw authority is required for taxed templates only
+pub fn create_from_template(
+    launch: &LaunchAccounts,
+    fee_withdraw_authority: Option<&Pubkey>,
+    template: &Pubkey,
+) -> Instruction {
+    let fee_withdraw_authority = match fee_withdraw_authority {
+        Some(authority) => AccountMeta::new_readonly(*authority, true),
+        None => optional(None, false),
+    };
+    instruction(
+        "create_from_template",
+        (),
+        launch.metas(Some(fee_withdraw_authority), Some(*template), false),
+    )
+}
+
+/// `transfer_with_fee`, recording any fee rebate owed and optionally stats
+#[allow(clippy::too_many_arguments)]
+pub fn transfer_with_fee(
+    source: &Pubkey,
+    mint: &Pubkey,
+    destination: &Pubkey,
+    authority: &Pubkey,
+    amount: u64,
+    decimals: u8,
+    fee: u64,
+    with_stats: bool,
+    with_pool_registry: bool,
+) -> Instruction {
+    instruction(
+        "transfer_with_fee",
+        (amount, decimals, fee),
+        vec![
+            AccountMeta::new(*source, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(*destination, false),
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
+            AccountMeta::new(pda::fee_exemption_list(mint).0, false),
+            optional(with_stats.then(|| pda::mint_stats(mint).0), true),
+            optional(
+                with_pool_registry.then(|| pda::pool_registry(mint).0),
+                false,
+            ),
+        ],
+    )
+}
+
+/// `set_compliance_flags`, signed by the mint's creator; `None` clears them
+pub fn set_compliance_flags(
+    creator: &Pubkey,
+    mint: &Pubkey,
+    flags: Option<ComplianceFlags>,
+) -> Instruction {
+    instruction(
+        "set_compliance_flags",
+        flags,
+        vec![
+            AccountMeta::new_readonly(*creator, true),
+            AccountMeta::new(pda::launch_config(mint).0, false),
+        ],
+    )
+}
+
+/// `attest_wallet`, signed by the launch's compliance attestor
+pub fn attest_wallet(
+    attestor: &Pubkey,
+    mint: &Pubkey,
+    wallet: &Pubkey,
+    jurisdictions_hash: [u8; 32],
+    accredited: bool,
+    expires_at: i64,
+) -> Instruction {
+    instruction(
+        "attest_wallet",
+        (*wallet, jurisdictions_hash, accredited, expires_at),
+        vec![
+            AccountMeta::new(*attestor, true),
+            AccountMeta::new_readonly(pda::launch_config(mint).0, false),
+            AccountMeta::new(pda::wallet_attestation(mint, wallet).0, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        ],
+    )
+}
+
+/// `freeze_noncompliant`, signed by the creator or compliance attestor
+pub fn freeze_noncompliant(
+    authority: &Pubkey,
+    mint: &Pubkey,
+    token_account: &Pubkey,
+    wallet: &Pubkey,
+) -> Instruction {
+    compliance_freeze(
+        "freeze_noncompliant",
+        authority,
+        mint,
+        token_account,
+        wallet,
+    )
+}
+
+/// `thaw_compliant`; permissionless, but the wallet must be attested
+pub fn thaw_compliant(
+    authority: &Pubkey,
+    mint: &Pubkey,
+    token_account: &Pubkey,
+    wallet: &Pubkey,
+) -> Instruction {
+    compliance_freeze("thaw_compliant", authority, mint, token_account, wallet)
+}
+
+fn compliance_freeze(
+    name: &str,
+    authority: &Pubkey,
+    mint: &Pubkey,
+    token_account: &Pubkey,
+    wallet: &Pubkey,
+) -> Instruction {
+    instruction(
+        name,
+        (),
+        vec![
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new_readonly(pda::launch_config(mint).0, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(*token_account, false),
+            AccountMeta::new_readonly(*wallet, false),
+            AccountMeta::new_readonly(pda::wallet_attestation(mint, wallet).0, false),
+            AccountMeta::new_readonly(pda::freeze_order(mint, token_account).0, false),
+            AccountMeta::new_readonly(pda::compliance_authority(mint).0, false),
+            AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
+        ],
+    )
+}
+
+/// `set_compliance_officer`, signed by the mint's creator; `None` removes the officer
+pub fn set_compliance_officer(
+    creator: &Pubkey,
+    mint: &Pubkey,
+    officer: Option<Pubkey>,
+) -> Instruction {
+    instruction(
+        "set_compliance_officer",
+        officer,
+        vec![
+            AccountMeta::new_readonly(*creator, true),
+            AccountMeta::new(pda::launch_config(mint).0, false),
+        ],
+    )
+}
+
+/// `issue_freeze_order`, signed by the mint's compliance officer
+pub fn issue_freeze_order(
+    officer: &Pubkey,
+    mint: &Pubkey,
+    token_account: &Pubkey,
+    justification_uri: &str,
+    justification_hash: [u8; 32],
+) -> Instruction {
+    instruction(
+        "issue_freeze_order",
+        (justification_uri.to_string(), justification_hash),
+        vec![
+            AccountMeta::new(*officer, true),
+            AccountMeta::new_readonly(pda::launch_config(mint).0, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(*token_account, false),
+            AccountMeta::new(pda::freeze_order(mint, token_account).0, false),
+            AccountMeta::new_readonly(pda::compliance_authority(mint).0, false),
+            AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        ],
+    )
+}
+
+/// `lift_freeze_order`, signed by the mint's compliance officer
+pub fn lift_freeze_order(
+    officer: &Pubkey,
+    mint: &Pubkey,
+    token_account: &Pubkey,
+    justification_uri: &str,
+    justification_hash: [u8; 32],
+) -> Instruction {
+    instruction(
+        "lift_freeze_order",
+        (justification_uri.to_string(), justification_hash),
+        vec![
+            AccountMeta::new_readonly(*officer, true),
+            AccountMeta::new_readonly(pda::launch_config(mint).0, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(*token_account, false),
+            AccountMeta::new(pda::freeze_order(mint, token_account).0, false),
+            AccountMeta::new_readonly(pda::compliance_authority(mint).0, false),
+            AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
+        ],
+    )
+}
+
+/// `withdraw_from_stream`, signed by the recipient
+pub fn withdraw_from_stream(
+    stream: &Pubkey,
+    recipient: &Pubkey,
+    mint: &Pubkey,
+    vault: &Pubkey,
+    recipient_token_account: &Pubkey,
+) -> Instruction {
+    instruction(
+        "withdraw_from_stream",
+        (),
+        vec![
+            AccountMeta::new_readonly(*recipient, true),
+            AccountMeta::new(*stream, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(*vault, false),
+            AccountMeta::new(*recipient_token_account, false),
+            AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
+            AccountMeta::new_readonly(pda::mint_pause(mint).0, false),
+        ],
+    )
+}
+
+/// `close_stream`; permissionless, rent goes to the sender. `sender_token_account` is only
+/// needed when tokens are left in the vault.
+pub fn close_stream(
+    stream: &Pubkey,
+    sender: &Pubkey,
+    mint: &Pubkey,
+    vault: &Pubkey,
+    sender_token_account: Option<Pubkey>,
+) -> Instruction {
+    instruction(
+        "close_stream",
+        (),
+        vec![
+            AccountMeta::new(*sender, false),
+            AccountMeta::new(*stream, false),
+            AccountMeta::new(*mint, false),
+            AccountMeta::new(*vault, false),
+            optional(sender_token_account, true),
+            AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
+        ],
+    )
+}
+
+/// `airdrop_batch`, paying the next recipients into `destinations` in list order
+pub fn airdrop_batch(
+    campaign: &Pubkey,
+    mint: &Pubkey,
+    vault: &Pubkey,
+    destinations: &[Pubkey],
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*campaign, false),
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new(*vault, false),
+        AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
+        AccountMeta::new_readonly(pda::mint_pause(mint).0, false),
+    ];
+    accounts.extend(
+        destinations
+            .iter()
+            .map(|destination| AccountMeta::new(*destination, false)),
+    );
+    instruction("airdrop_batch", (), accounts)
+}
+
+/// `claim_skipped_airdrop`, paying list entry `index` into the recipient's `destination`;
+/// permissionless
+pub fn claim_skipped_airdrop(
+    campaign: &Pubkey,
+    mint: &Pubkey,
+    vault: &Pubkey,
+    destination: &Pubkey,
+    index: u32,
+) -> Instruction {
+    instruction(
+        "claim_skipped_airdrop",
+        index,
+        vec![
+            AccountMeta::new(*campaign, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(*vault, false),
+            AccountMeta::new(*destination, false),
+            AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
+            AccountMeta::new_readonly(pda::mint_pause(mint).0, false),
+        ],
+    )
+}
+
+/// `mint_emission`; permissionless
+pub fn mint_emission(mint: &Pubkey, vault: &Pubkey) -> Instruction {
+    instruction(
+        "mint_emission",
+        (),
+        vec![
+            AccountMeta::new(pda::emission_schedule(mint).0, false),
+            AccountMeta::new(*mint, false),
+            AccountMeta::new(*vault, false),
+            AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
+            AccountMeta::new_readonly(pda::mint_pause(mint).0, false),
+        ],
+    )
+}
+
+/// `release_allocation` of the plan's `index`th bucket; permissionless
+pub fn release_allocation(mint: &Pubkey, index: u8, bucket: &AllocationBucket) -> Instruction {
+    instruction(
+        "release_allocation",
+        index,
+        vec![
+            AccountMeta::new(pda::allocation_plan(mint).0, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(bucket.vault, false),
+            AccountMeta::new(bucket.beneficiary, false),
+            AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
+            AccountMeta::new_readonly(pda::mint_pause(mint).0, false),
+        ],
+    )
+}
+
+/// `revoke_allocation` of a revocable bucket, signed by the plan's creator; the unvested part
+/// goes to `destination`
+pub fn revoke_allocation(
+    creator: &Pubkey,
+    mint: &Pubkey,
+    index: u8,
+    bucket: &AllocationBucket,
+    destination: &Pubkey,
+) -> Instruction {
+    instruction(
+        "revoke_allocation",
+        index,
+        vec![
+            AccountMeta::new_readonly(*creator, true),
+            AccountMeta::new(pda::allocation_plan(mint).0, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(bucket.vault, false),
+            AccountMeta::new(*destination, false),
+            AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
+            AccountMeta::new_readonly(pda::mint_pause(mint).0, false),
+        ],
+    )
+}
+
+/// `propose_allocation_beneficiary`, signed by the owner of the bucket's current beneficiary
+/// account
+pub fn propose_allocation_beneficiary(
+    owner: &Pubkey,
+    mint: &Pubkey,
+    index: u8,
+    bucket: &AllocationBucket,
+    new_beneficiary: &Pubkey,
+) -> Instruction {
+    instruction(
+        "propose_allocation_beneficiary",
+        index,
+        vec![
+            AccountMeta::new_readonly(*owner, true),
+            AccountMeta::new(pda::allocation_plan(mint).0, false),
+            AccountMeta::new_readonly(bucket.beneficiary, false),
+            AccountMeta::new_readonly(*new_beneficiary, false),
+        ],
+    )
+}
+
+/// `accept_allocation_beneficiary`, signed by the plan's creator; releases what has vested to
+/// the current beneficiary first
+pub fn accept_allocation_beneficiary(
+    creator: &Pubkey,
+    mint: &Pubkey,
+    index: u8,
+    bucket: &AllocationBucket,
+) -> Instruction {
+    instruction(
+        "accept_allocation_beneficiary",
+        index,
+        vec![
+            AccountMeta::new_readonly(*creator, true),
+            AccountMeta::new(pda::allocation_plan(mint).0, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(bucket.vault, false),
+            AccountMeta::new(bucket.beneficiary, false),
+            AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
+            AccountMeta::new_readonly(pda::mint_pause(mint).0, false),
+        ],
+    )
+}
+
+/// `close_allocation_plan`, signed by the plan's creator, once every locked bucket is released
+pub fn close_allocation_plan(
+    creator: &Pubkey,
+    mint: &Pubkey,
+    buckets: &[AllocationBucket],
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*creator, true),
+        AccountMeta::new(pda::allocation_plan(mint).0, false),
+        AccountMeta::new(*mint, false),
+        AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
+    ];
+    for bucket in buckets.iter().filter(|bucket| bucket.kind.is_locked()) {
+        accounts.push(AccountMeta::new(bucket.vault, false));
+        accounts.push(AccountMeta::new(bucket.beneficiary, false));
+    }
+    instruction("close_allocation_plan", (), accounts)
+}
+
+/// `update_index_multiplier` from a Pyth price update of the mint's index feed; permissionless
+pub fn update_index_multiplier(mint: &Pubkey, price_update: &Pubkey) -> Instruction {
+    instruction(
+        "update_index_multiplier",
+        (),
+        vec![
+            AccountMeta::new(pda::index_feed(mint).0, false),
+            AccountMeta::new(*mint, false),
+            AccountMeta::new_readonly(*price_update, false),
+            AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
+        ],
+    )
+}
+
+/// `set_interest_rate`, signed by the governor's admin; the rate must be within its bounds
+pub fn set_interest_rate(admin: &Pubkey, mint: &Pubkey, rate_bps: i16) -> Instruction {
+    instruction(
+        "set_interest_rate",
+        rate_bps,
+        vec![
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new(pda::interest_rate_governor(mint).0, false),
+            AccountMeta::new(*mint, false),
+            AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
+        ],
+    )
+}
+
+/// `govern_interest_rate`, signed by the mint's timelock: pass it to `queue_op`
+pub fn govern_interest_rate(
+    mint: &Pubkey,
+    rate_bps: i16,
+    bounds: Option<InterestRateBounds>,
+) -> Instruction {
+    instruction(
+        "govern_interest_rate",
+        (rate_bps, bounds),
+        vec![
+            AccountMeta::new_readonly(pda::timelock(mint).0, true),
+            AccountMeta::new(pda::interest_rate_governor(mint).0, false),
+            AccountMeta::new(*mint, false),
+            AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
+        ],
+    )
+}
+
+/// `crank_tax_holiday`; permissionless
+pub fn crank_tax_holiday(mint: &Pubkey) -> Instruction {
+    instruction(
+        "crank_tax_holiday",
+        (),
+        vec![
+            AccountMeta::new(*mint, false),
+            AccountMeta::new_readonly(pda::tax_holiday_schedule(mint).0, false),
+            AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
+        ],
+    )
+}
+
+/// `execute_recurring_payment`; permissionless
+pub fn execute_recurring_payment(
+    mint: &Pubkey,
+    payment_id: u64,
+    vault: &Pubkey,
+    destination: &Pubkey,
+) -> Instruction {
+    let treasury = pda::project_treasury(mint).0;
+    instruction(
+        "execute_recurring_payment",
+        (),
+        vec![
+            AccountMeta::new(treasury, false),
+            AccountMeta::new(pda::recurring_payment(&treasury, payment_id).0, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(*vault, false),
+            AccountMeta::new(*destination, false),
+            AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
+            AccountMeta::new_readonly(pda::mint_pause(mint).0, false),
+        ],
+    )
+}
+
+/// `claim_from_faucet`, signed by the wallet
+pub fn claim_from_faucet(
+    wallet: &Pubkey,
+    mint: &Pubkey,
+    vault: &Pubkey,
+    destination: &Pubkey,
+) -> Instruction {
+    instruction(
+        "claim_from_faucet",
+        (),
+        vec![
+            AccountMeta::new(*wallet, true),
+            AccountMeta::new_readonly(pda::faucet(mint).0, false),
+            AccountMeta::new(pda::faucet_claim(mint, wallet).0, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(*vault, false),
+            AccountMeta::new(*destination, false),
+            AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(pda::mint_pause(mint).0, false),
+        ],
+    )
+}
+
+/// `claim_fee_rebate`, signed by the exempt wallet
+pub fn claim_fee_rebate(
+    wallet: &Pubkey,
+    mint: &Pubkey,
+    fee_vault: &Pubkey,
+    destination: &Pubkey,
+) -> Instruction {
+    instruction(
+        "claim_fee_rebate",
+        (),
+        vec![
+            AccountMeta::new_readonly(*wallet, true),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(pda::fee_exemption_list(mint).0, false),
+            AccountMeta::new(*fee_vault, false),
+            AccountMeta::new(*destination, false),
+            AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
+            AccountMeta::new_readonly(pda::mint_pause(mint).0, false),
+        ],
+    )
+}
+
+/// `sync_creator_fees`, adding a launch's new fees to its creator's profile; permissionless
+pub fn sync_creator_fees(mint: &Pubkey, creator: &Pubkey) -> Instruction {
+    instruction(
+        "sync_creator_fees",
+        (),
+        vec![
+            AccountMeta::new(pda::mint_stats(mint).0, false),
+            AccountMeta::new_readonly(pda::launch_config(mint).0, false),
+            AccountMeta::new(pda::creator_reputation(creator).0, false),
+        ],
+    )
+}
+
+/// `retire_launch`, releasing an unbonded launch from its creator's active launches once the
+/// bond lock period has passed; permissionless
+pub fn retire_launch(mint: &Pubkey, creator: &Pubkey) -> Instruction {
+    instruction(
+        "retire_launch",
+        (),
+        vec![
+            AccountMeta::new_readonly(pda::platform_config().0, false),
+            AccountMeta::new(pda::launch_config(mint).0, false),
+            AccountMeta::new(pda::creator_reputation(creator).0, false),
+        ],
+    )
+}
+
+/// `rollover_epoch` opening `epoch`'s leaderboard, and freezing the previous epoch's when
+/// `freeze_previous` is set (it must exist)
+pub fn rollover_epoch(payer: &Pubkey, epoch: u64, freeze_previous: bool) -> Instruction {
+    let previous = freeze_previous.then(|| pda::leaderboard(epoch.saturating_sub(1)).0);
+    instruction(
+        "rollover_epoch",
+        (),
+        vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new(pda::leaderboard(epoch).0, false),
+            optional(previous, true),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        ],
+    )
+}
+
+/// `update_leaderboard` with a mint's stats for `epoch`
+pub fn update_leaderboard(mint: &Pubkey, epoch: u64) -> Instruction {
+    instruction(
+        "update_leaderboard",
+        (),
+        vec![
+            AccountMeta::new_readonly(pda::mint_stats(mint).0, false),
+            AccountMeta::new(pda::leaderboard(epoch).0, false),
+        ],
+    )
+}
+
+/// `withdraw_delegated_fees`, signed by the delegate (to its fixed destination) or its authority
+pub fn withdraw_delegated_fees(
+    signer: &Pubkey,
+    mint: &Pubkey,
+    destination: &Pubkey,
+) -> Instruction {
+    instruction(
+        "withdraw_delegated_fees",
+        (),
+        vec![
+            AccountMeta::new_readonly(*signer, true),
+            AccountMeta::new(*mint, false),
+            AccountMeta::new(pda::fee_delegate(mint).0, false),
+            AccountMeta::new(*destination, false),
+            AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
+        ],
+    )
+}
+
+/// `claim_gift`, signed by the gift link's keypair; any other key can pay the fee.
+pub fn claim_gift(
+    claim_key: &Pubkey,
+    gift: &Gift,
+    recipient_token_account: &Pubkey,
+) -> Instruction {
+    instruction(
+        "claim_gift",
+        (),
+        vec![
+            AccountMeta::new_readonly(*claim_key, true),
+            AccountMeta::new(pda::gift(claim_key).0, false),
+            AccountMeta::new(gift.sender, false),
+            AccountMeta::new(gift.mint, false),
+            AccountMeta::new(gift.vault, false),
+            AccountMeta::new(*recipient_token_account, false),
+            AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
+            AccountMeta::new_readonly(pda::mint_pause(&gift.mint).0, false),
+        ],
+    )
+}
+
+fn wrap_accounts(
+    owner: &Pubkey,
+    wrapper: &Wrapper,
+    taxed_token_account: &Pubkey,
+    wrapped_token_account: &Pubkey,
+) -> Vec<AccountMeta> {
+    vec![
+        AccountMeta::new_readonly(*owner, true),
+        AccountMeta::new_readonly(pda::wrapper(&wrapper.mint).0, false),
+        AccountMeta::new_readonly(wrapper.mint, false),
+        AccountMeta::new(wrapper.wrapped_mint, false),
+        AccountMeta::new(wrapper.vault, false),
+        AccountMeta::new(*taxed_token_account, false),
+        AccountMeta::new(*wrapped_token_account, false),
+        AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
+        AccountMeta::new_readonly(pda::mint_pause(&wrapper.mint).0, false),
+    ]
+}
+
+/// `wrap`: `amount` of the taxed mint in, the companion out 1:1 after the transfer fee
+pub fn wrap(
+    owner: &Pubkey,
+    wrapper: &Wrapper,
+    taxed_token_account: &Pubkey,
+    wrapped_token_account: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    instruction(
+        "wrap",
+        (amount,),
+        wrap_accounts(owner, wrapper, taxed_token_account, wrapped_token_account),
+    )
+}
+
+/// `unwrap`: `amount` of the companion burned, the same amount of the taxed mint paid out
+pub fn unwrap(
+    owner: &Pubkey,
+    wrapper: &Wrapper,
+    taxed_token_account: &Pubkey,
+    wrapped_token_account: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    instruction(
+        "unwrap",
+        (amount,),
+        wrap_accounts(owner, wrapper, taxed_token_account, wrapped_token_account),
+    )
+}
+
+/// Domain separating claim vouchers from other messages the voucher signer signs
+pub const VOUCHER_CLAIM_DOMAIN: &[u8] = b"launchpad:voucher_claim";
+
+/// Message a campaign's voucher signer signs to let `wallet` claim `amount` once
+pub fn voucher_claim_message(
+    campaign: &Pubkey,
+    mint: &Pubkey,
+    wallet: &Pubkey,
+    amount: u64,
+    nonce: u64,
+) -> Vec<u8> {
+    [
+        VOUCHER_CLAIM_DOMAIN,
+        campaign.as_ref(),
+        mint.as_ref(),
+        wallet.as_ref(),
+        &amount.to_le_bytes(),
+        &nonce.to_le_bytes(),
+    ]
+    .concat()
+}
+
+/// Voucher being redeemed from an airdrop campaign
+#[derive(Clone, Copy, Debug)]
+pub struct VoucherClaim {
+    pub campaign: Pubkey,
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+    pub wallet: Pubkey,
+    /// Wallet's token account of the mint
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub nonce: u64,
+}
+
+/// `claim_voucher`. Must directly follow an ed25519 instruction verifying the voucher
+/// signer's signature over [`voucher_claim_message`].
+pub fn claim_voucher(payer: &Pubkey, claim: &VoucherClaim) -> Instruction {
+    instruction(
+        "claim_voucher",
+        (claim.wallet, claim.amount, claim.nonce),
+        vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(claim.campaign, false),
+            AccountMeta::new(
+                pda::voucher_nonce_page(&claim.campaign, claim.nonce).0,
+                false,
+            ),
+            AccountMeta::new_readonly(claim.mint, false),
+            AccountMeta::new(claim.vault, false),
+            AccountMeta::new(claim.destination, false),
+            AccountMeta::new_readonly(INSTRUCTIONS_SYSVAR_ID, false),
+            AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
+            AccountMeta::new_readonly(pda::mint_pause(&claim.mint).0, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        ],
+    )
+}
+
+/// Domain separating check-in vouchers from other messages the organizer key signs
+pub const CHECK_IN_VOUCHER_DOMAIN: &[u8] = b"launchpad:check_in";
+
+/// Message the organizer signs to let `wallet` check in to `event`
+pub fn check_in_message(event: &Pubkey, wallet: &Pubkey) -> Vec<u8> {
+    [CHECK_IN_VOUCHER_DOMAIN, event.as_ref(), wallet.as_ref()].concat()
+}
+
+/// `check_in`. Must directly follow an ed25519 instruction verifying the organizer's
+/// signature over [`check_in_message`].
+pub fn check_in(
+    attendee: &Pubkey,
+    series: &Pubkey,
+    event_id: u32,
+    badge_account: &Pubkey,
+) -> Instruction {
+    let event = pda::attendance_event(series, event_id).0;
+    instruction(
+        "check_in",
+        (),
+        vec![
+            AccountMeta::new(*attendee, true),
+            AccountMeta::new_readonly(*series, false),
+            AccountMeta::new(event, false),
+            AccountMeta::new(pda::attendance(&event, attendee).0, false),
+            AccountMeta::new(pda::attendance_badge(&event).0, false),
+            AccountMeta::new(*badge_account, false),
+            AccountMeta::new_readonly(INSTRUCTIONS_SYSVAR_ID, false),
+            AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        ],
+    )
+}
+
+/// `get_vesting_state` of a mint's allocation plan; simulate it and decode the return data as an
+/// [`AllocationPlanView`](crate::views::AllocationPlanView)
+pub fn get_vesting_state(mint: &Pubkey) -> Instruction {
+    instruction(
+        "get_vesting_state",
+        (),
+        vec![AccountMeta::new_readonly(
+            pda::allocation_plan(mint).0,
+            false,
+        )],
+    )
+}
+
+/// `get_mint_health`; simulate it and decode the return data as a
+/// [`MintHealthView`](crate::views::MintHealthView)
+pub fn get_mint_health(mint: &Pubkey) -> Instruction {
+    instruction(
+        "get_mint_health",
+        (),
+        vec![AccountMeta::new_readonly(*mint, false)],
+    )
+}
+
+/// `get_declared_fees`; simulate it and decode the return data as a
+/// [`DeclaredFeesView`](crate::views::DeclaredFeesView). Pass `has_exemption_list` when the
+/// mint has one, so the view counts its wallets.
+pub fn get_declared_fees(mint: &Pubkey, has_exemption_list: bool) -> Instruction {
+    instruction(
+        "get_declared_fees",
+        (),
+        vec![
+            AccountMeta::new_readonly(*mint, false),
+            optional(
+                has_exemption_list.then(|| pda::fee_exemption_list(mint).0),
+                false,
+            ),
+        ],
+    )
+}
+
+/// `stake_platform_tokens` from the owner's platform-token account
+pub fn stake_platform_tokens(
+    owner: &Pubkey,
+    platform_mint: &Pubkey,
+    vault: &Pubkey,
+    owner_token_account: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    instruction(
+        "stake_platform_tokens",
+        amount,
+        vec![
+            AccountMeta::new(*owner, true),
+            AccountMeta::new_readonly(pda::platform_config().0, false),
+            AccountMeta::new(pda::platform_staking().0, false),
+            AccountMeta::new(pda::stake_position(owner).0, false),
+            AccountMeta::new_readonly(*platform_mint, false),
+            AccountMeta::new(*vault, false),
+            AccountMeta::new(*owner_token_account, false),
+            AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(pda::mint_pause(platform_mint).0, false),
+        ],
+    )
+}
+
+/// `unstake_platform_tokens` back to the owner's platform-token account
+pub fn unstake_platform_tokens(
+    owner: &Pubkey,
+    platform_mint: &Pubkey,
+    vault: &Pubkey,
+    owner_token_account: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    instruction(
+        "unstake_platform_tokens",
+        amount,
+        vec![
+            AccountMeta::new_readonly(*owner, true),
+            AccountMeta::new_readonly(pda::platform_config().0, false),
+            AccountMeta::new(pda::platform_staking().0, false),
+            AccountMeta::new(pda::stake_position(owner).0, false),
+            AccountMeta::new_readonly(*platform_mint, false),
+            AccountMeta::new(*vault, false),
+            AccountMeta::new(*owner_token_account, false),
+            AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
+        ],
+    )
+}
+
+/// `schedule_launch`, listing the mint on the calendar page of `opens_at`'s day
+pub fn schedule_launch(creator: &Pubkey, mint: &Pubkey, opens_at: i64) -> Instruction {
+    instruction(
+        "schedule_launch",
+        opens_at,
+        vec![
+            AccountMeta::new(*creator, true),
+            AccountMeta::new_readonly(pda::launch_config(mint).0, false),
+            AccountMeta::new(pda::mint_pause(mint).0, false),
+            AccountMeta::new(
+                pda::launch_calendar(opens_at / pda::SECONDS_PER_DAY).0,
+                false,
+            ),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        ],
+    )
+}
+
+/// `claim_staking_rewards`, paid to the staker in lamports
+pub fn claim_staking_rewards(owner: &Pubkey) -> Instruction {
+    instruction(
+        "claim_staking_rewards",
+        (),
+        vec![
+            AccountMeta::new(*owner, true),
+            AccountMeta::new(pda::platform_staking().0, false),
+            AccountMeta::new(pda::stake_position(owner).0, false),
+        ],
+    )
+}
+
+/// `activate_stake` for `owner`'s position; permissionless
+pub fn activate_stake(owner: &Pubkey) -> Instruction {
+    instruction(
+        "activate_stake",
+        (),
+        vec![
+            AccountMeta::new(pda::platform_staking().0, false),
+            AccountMeta::new(pda::stake_position(owner).0, false),
+        ],
+    )
+}
+
+/// `init_dividend_pool`, signed by the mint's creator; both vaults are owned by the pool PDA
+pub fn init_dividend_pool(
+    creator: &Pubkey,
+    mint: &Pubkey,
+    reward_mint: &Pubkey,
+    stake_vault: &Pubkey,
+    reward_vault: &Pubkey,
+) -> Instruction {
+    instruction(
+        "init_dividend_pool",
+        (),
+        vec![
+            AccountMeta::new(*creator, true),
+            AccountMeta::new_readonly(pda::launch_config(mint).0, false),
+            AccountMeta::new(pda::dividend_pool(mint).0, false),
+            AccountMeta::new_readonly(*reward_mint, false),
+            AccountMeta::new_readonly(*stake_vault, false),
+            AccountMeta::new_readonly(*reward_vault, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        ],
+    )
+}
+
+/// `fund_dividends` from the funder's reward-token account; permissionless
+pub fn fund_dividends(
+    funder: &Pubkey,
+    pool: &DividendPool,
+    funder_token_account: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    instruction(
+        "fund_dividends",
+        amount,
+        vec![
+            AccountMeta::new_readonly(*funder, true),
+            AccountMeta::new(pda::dividend_pool(&pool.mint).0, false),
+            AccountMeta::new_readonly(pool.reward_mint, false),
+            AccountMeta::new(pool.reward_vault, false),
+            AccountMeta::new(*funder_token_account, false),
+            AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
+        ],
+    )
+}
+
+/// `stake_for_dividends` from the owner's token account of the pool's mint
+pub fn stake_for_dividends(
+    owner: &Pubkey,
+    pool: &DividendPool,
+    owner_token_account: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    instruction(
+        "stake_for_dividends",
+        amount,
+        vec![
+            AccountMeta::new(*owner, true),
+            AccountMeta::new(pda::dividend_pool(&pool.mint).0, false),
+            AccountMeta::new(pda::dividend_position(&pool.mint, owner).0, false),
+            AccountMeta::new_readonly(pool.mint, false),
+            AccountMeta::new(pool.stake_vault, false),
+            AccountMeta::new(*owner_token_account, false),
+            AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(pda::mint_pause(&pool.mint).0, false),
+        ],
+    )
+}
+
+/// `unstake_from_dividends` back to the owner's token account of the pool's mint
+pub fn unstake_from_dividends(
+    owner: &Pubkey,
+    pool: &DividendPool,
+    owner_token_account: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    instruction(
+        "unstake_from_dividends",
+        amount,
+        vec![
+            AccountMeta::new_readonly(*owner, true),
+            AccountMeta::new(pda::dividend_pool(&pool.mint).0, false),
+            AccountMeta::new(pda::dividend_position(&pool.mint, owner).0, false),
+            AccountMeta::new_readonly(pool.mint, false),
+            AccountMeta::new(pool.stake_vault, false),
+            AccountMeta::new(*owner_token_account, false),
+            AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
+        ],
+    )
+}
+
+/// `claim_dividends`, paid into the owner's reward-token account
+pub fn claim_dividends(
+    owner: &Pubkey,
+    pool: &DividendPool,
+    owner_reward_account: &Pubkey,
+) -> Instruction {
+    instruction(
+        "claim_dividends",
+        (),
+        vec![
+            AccountMeta::new_readonly(*owner, true),
+            AccountMeta::new(pda::dividend_pool(&pool.mint).0, false),
+            AccountMeta::new(pda::dividend_position(&pool.mint, owner).0, false),
+            AccountMeta::new_readonly(pool.reward_mint, false),
+            AccountMeta::new(pool.reward_vault, false),
+            AccountMeta::new(*owner_reward_account, false),
+            AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
+        ],
+    )
+}
+
+/// `activate_dividend_stake` for `owner`'s position in the mint's pool; permissionless
+pub fn activate_dividend_stake(mint: &Pubkey, owner: &Pubkey) -> Instruction {
+    instruction(
+        "activate_dividend_stake",
+        (),
+        vec![
+            AccountMeta::new(pda::dividend_pool(mint).0, false),
+            AccountMeta::new(pda::dividend_position(mint, owner).0, false),
+        ],
+    )
+}
+
+/// `init_token_metadata`, signed by the creator (who pays and becomes the update authority)
+/// and the mint authority
+pub fn init_token_metadata(
+    creator: &Pubkey,
+    mint: &Pubkey,
+    mint_authority: &Pubkey,
+    metadata: &TokenMetadataParams,
+) -> Instruction {
+    instruction(
+        "init_token_metadata",
+        metadata,
+        vec![
+            AccountMeta::new(*creator, true),
+            AccountMeta::new_readonly(pda::launch_config(mint).0, false),
+            AccountMeta::new(*mint, false),
+            AccountMeta::new_readonly(*mint_authority, true),
+            AccountMeta::new(pda::symbol_index(&metadata.symbol).0, false),
+            AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        ],
+    )
+}
+
+/// `init_metaplex_metadata`, signed by the creator and the mint authority
+pub fn init_metaplex_metadata(
+    creator: &Pubkey,
+    mint: &Pubkey,
+    mint_authority: &Pubkey,
+) -> Instruction {
+    instruction(
+        "init_metaplex_metadata",
+        (),
+        vec![
+            AccountMeta::new(*creator, true),
+            AccountMeta::new_readonly(pda::launch_config(mint).0, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*mint_authority, true),
+            AccountMeta::new(pda::metaplex_metadata(mint).0, false),
+            AccountMeta::new_readonly(METAPLEX_METADATA_PROGRAM_ID, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        ],
+    )
+}
+
+/// `lock_metadata`, signed by the creator
+pub fn lock_metadata(creator: &Pubkey, mint: &Pubkey) -> Instruction {
+    instruction(
+        "lock_metadata",
+        (),
+        vec![
+            AccountMeta::new_readonly(*creator, true),
+            AccountMeta::new(pda::launch_config(mint).0, false),
+            AccountMeta::new(*mint, false),
+            AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
+            AccountMeta::new(pda::metaplex_metadata(mint).0, false),
+            AccountMeta::new_readonly(METAPLEX_METADATA_PROGRAM_ID, false),
+        ],
+    )
+}
+
+/// `withdraw_partner_fees`, signed by the partner's authority
+pub fn withdraw_partner_fees(
+    authority: &Pubkey,
+    namespace: &str,
+    recipient: &Pubkey,
+) -> Instruction {
+    instruction(
+        "withdraw_partner_fees",
+        (),
+        vec![
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new(pda::partner(namespace).0, false),
+            AccountMeta::new(*recipient, false),
+        ],
+    )
+}
+
+/// `register_exchange`, signed and paid for by the exchange
+pub fn register_exchange(
+    exchange: &Pubkey,
+    mint: &Pubkey,
+    name: &str,
+    wallets: &[Pubkey],
+) -> Instruction {
+    instruction(
+        "register_exchange",
+        (name.to_string(), wallets.to_vec()),
+        vec![
+            AccountMeta::new(*exchange, true),
+            AccountMeta::new_readonly(pda::fee_exemption_list(mint).0, false),
+            AccountMeta::new(pda::exchange_listing(mint, exchange).0, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        ],
+    )
+}
+
+/// `approve_exchange`, signed by the exemption list authority
+pub fn approve_exchange(authority: &Pubkey, mint: &Pubkey, exchange: &Pubkey) -> Instruction {
+    instruction(
+        "approve_exchange",
+        (),
+        vec![
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new(pda::fee_exemption_list(mint).0, false),
+            AccountMeta::new(pda::exchange_listing(mint, exchange).0, false),
+        ],
+    )
+}
+
+/// `revoke_exchange`, signed by the exchange or the exemption list authority
+pub fn revoke_exchange(signer: &Pubkey, mint: &Pubkey, exchange: &Pubkey) -> Instruction {
+    instruction(
+        "revoke_exchange",
+        (),
+        vec![
+            AccountMeta::new_readonly(*signer, true),
+            AccountMeta::new(pda::fee_exemption_list(mint).0, false),
+            AccountMeta::new(pda::exchange_listing(mint, exchange).0, false),
+            AccountMeta::new(*exchange, false),
+        ],
+    )
+}
+
+/// `queue_op`: queue `ix` on the mint's timelock as op `op_id`, the timelock's `next_op_id`
+pub fn queue_op(admin: &Pubkey, mint: &Pubkey, op_id: u64, ix: &Instruction) -> Instruction {
+    let timelock = pda::timelock(mint).0;
+    let accounts: Vec<TimelockAccountMeta> = ix
+        .accounts
+        .iter()
+        .map(|meta| TimelockAccountMeta {
+            pubkey: meta.pubkey,
+            is_signer: meta.is_signer,
+            is_writable: meta.is_writable,
+        })
+        .collect();
+    instruction(
+        "queue_op",
+        (ix.program_id, accounts, ix.data.clone()),
+        vec![
+            AccountMeta::new(*admin, true),
+            AccountMeta::new(timelock, false),
+            AccountMeta::new(pda::timelock_op(&timelock, op_id).0, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        ],
+    )
+}
+
+/// `execute_op`, passing the op's accounts as remaining accounts. The timelock PDA signs
+/// inside the program, so no account is a signer here.
+pub fn execute_op(admin: &Pubkey, op: &QueuedOp) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(op.timelock, false),
+        AccountMeta::new(pda::timelock_op(&op.timelock, op.id).0, false),
+        AccountMeta::new(*admin, false),
+        AccountMeta::new_readonly(op.program_id, false),
+    ];
+    accounts.extend(op.accounts.iter().map(|meta| AccountMeta {
+        pubkey: meta.pubkey,
+        is_signer: false,
+        is_writable: meta.is_writable,
+    }));
+    instruction("execute_op", (), accounts)
+}
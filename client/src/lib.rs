@@ -0,0 +1,44 @@
+//! Off-chain client for the taxed token launchpad: PDA derivation, instruction builders and
+//! account decoders, without pulling in the Anchor runtime.
+
+pub mod events;
+pub mod instructions;
+pub mod pda;
+pub mod state;
+pub mod views;
+
+use solana_pubkey::{pubkey, Pubkey};
+use solana_sha256_hasher::hashv;
+
+/// Launchpad program id
+pub const ID: Pubkey = pubkey!("9zZZdmpER8Pw9QJMwSyd8cvV8swbZWeqfJG3Gz2HhVGz");
+pub const TOKEN_2022_PROGRAM_ID: Pubkey = pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+pub const SYSTEM_PROGRAM_ID: Pubkey = pubkey!("11111111111111111111111111111111");
+pub const RENT_SYSVAR_ID: Pubkey = pubkey!("SysvarRent111111111111111111111111111111111");
+pub const INSTRUCTIONS_SYSVAR_ID: Pubkey = pubkey!("Sysvar1nstructions1111111111111111111111111");
+pub const ASSOCIATED_TOKEN_PROGRAM_ID: Pubkey =
+    pubkey!("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTm6ZyhdCbu");
+pub const METAPLEX_METADATA_PROGRAM_ID: Pubkey =
+    pubkey!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+
+/// Anchor instruction discriminator: the first 8 bytes of sha256("global:<name>")
+pub fn instruction_discriminator(name: &str) -> [u8; 8] {
+    discriminator("global:", name)
+}
+
+/// Anchor account discriminator: the first 8 bytes of sha256("account:<Name>")
+pub fn account_discriminator(name: &str) -> [u8; 8] {
+    discriminator("account:", name)
+}
+
+/// Anchor event discriminator: the first 8 bytes of sha256("event:<Name>")
+pub fn event_discriminator(name: &str) -> [u8; 8] {
+    discriminator("event:", name)
+}
+
+fn discriminator(namespace: &str, name: &str) -> [u8; 8] {
+    let hash = hashv(&[namespace.as_bytes(), name.as_bytes()]);
+    let mut discriminator = [0u8; 8];
+    discriminator.copy_from_slice(&hash.to_bytes()[..8]);
+    discriminator
+}
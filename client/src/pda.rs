@@ -0,0 +1,311 @@
+//! Program-derived addresses, with the same seeds the program checks.
+
+use solana_pubkey::Pubkey;
+
+use crate::{ASSOCIATED_TOKEN_PROGRAM_ID, ID, METAPLEX_METADATA_PROGRAM_ID, TOKEN_2022_PROGRAM_ID};
+
+pub const PLATFORM_CONFIG_SEED: &[u8] = b"platform_config";
+pub const INSURANCE_VAULT_SEED: &[u8] = b"insurance_vault";
+pub const CREATOR_BOND_SEED: &[u8] = b"creator_bond";
+pub const LAUNCH_CONFIG_SEED: &[u8] = b"launch_config";
+pub const REPUTATION_SEED: &[u8] = b"reputation";
+pub const LAUNCH_INFO_SEED: &[u8] = b"launch_info";
+pub const TEMPLATE_SEED: &[u8] = b"template";
+pub const TAX_HOLIDAY_SEED: &[u8] = b"tax_holiday";
+pub const FEE_EXEMPTION_SEED: &[u8] = b"fee_exemptions";
+pub const MINT_STATS_SEED: &[u8] = b"mint_stats";
+pub const STREAM_SEED: &[u8] = b"stream";
+pub const OTC_OFFER_SEED: &[u8] = b"otc_offer";
+pub const AIRDROP_SEED: &[u8] = b"airdrop";
+pub const LEADERBOARD_SEED: &[u8] = b"leaderboard";
+pub const MINT_PAUSE_SEED: &[u8] = b"mint_pause";
+pub const POOL_REGISTRY_SEED: &[u8] = b"pool_registry";
+pub const EMISSION_SEED: &[u8] = b"emission";
+pub const FAUCET_SEED: &[u8] = b"faucet";
+pub const FAUCET_CLAIM_SEED: &[u8] = b"faucet_claim";
+pub const PROJECT_TREASURY_SEED: &[u8] = b"project_treasury";
+pub const RECURRING_PAYMENT_SEED: &[u8] = b"recurring_payment";
+pub const VOTE_LOCK_SEED: &[u8] = b"ve_lock";
+pub const GAUGE_EPOCH_SEED: &[u8] = b"gauge_epoch";
+pub const FEE_DELEGATE_SEED: &[u8] = b"fee_delegate";
+pub const TIMELOCK_SEED: &[u8] = b"timelock";
+pub const TIMELOCK_OP_SEED: &[u8] = b"timelock_op";
+pub const EVENT_SERIES_SEED: &[u8] = b"event_series";
+pub const ATTENDANCE_EVENT_SEED: &[u8] = b"attendance_event";
+pub const ATTENDANCE_BADGE_SEED: &[u8] = b"attendance_badge";
+pub const ATTENDANCE_SEED: &[u8] = b"attendance";
+pub const GIFT_SEED: &[u8] = b"gift";
+pub const WRAPPER_SEED: &[u8] = b"wrapper";
+pub const WRAPPED_MINT_SEED: &[u8] = b"wrapped_mint";
+pub const EXCHANGE_LISTING_SEED: &[u8] = b"exchange_listing";
+pub const PARTNER_SEED: &[u8] = b"partner";
+pub const SYMBOL_INDEX_SEED: &[u8] = b"symbol_index";
+pub const METAPLEX_METADATA_SEED: &[u8] = b"metadata";
+pub const PLATFORM_STAKING_SEED: &[u8] = b"platform_staking";
+pub const STAKE_POSITION_SEED: &[u8] = b"stake_position";
+pub const DIVIDEND_POOL_SEED: &[u8] = b"dividend_pool";
+pub const DIVIDEND_POSITION_SEED: &[u8] = b"dividend_position";
+pub const LAUNCH_CALENDAR_SEED: &[u8] = b"launch_calendar";
+pub const ALLOCATION_PLAN_SEED: &[u8] = b"allocation_plan";
+pub const INDEX_FEED_SEED: &[u8] = b"index_feed";
+pub const INTEREST_RATE_GOVERNOR_SEED: &[u8] = b"interest_rate_governor";
+pub const WALLET_ATTESTATION_SEED: &[u8] = b"wallet_attestation";
+pub const COMPLIANCE_AUTHORITY_SEED: &[u8] = b"compliance_authority";
+pub const FREEZE_ORDER_SEED: &[u8] = b"freeze_order";
+pub const VOUCHER_NONCES_SEED: &[u8] = b"voucher_nonces";
+pub const NONCE_PAGE_BYTES: usize = 1024;
+/// Nonces tracked by one bitmap page
+pub const NONCES_PER_PAGE: u64 = 8 * NONCE_PAGE_BYTES as u64;
+/// Length of one launch calendar page
+pub const SECONDS_PER_DAY: i64 = 86_400;
+
+fn find(seeds: &[&[u8]]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(seeds, &ID)
+}
+
+pub fn platform_config() -> (Pubkey, u8) {
+    find(&[PLATFORM_CONFIG_SEED])
+}
+
+pub fn insurance_vault() -> (Pubkey, u8) {
+    find(&[INSURANCE_VAULT_SEED])
+}
+
+pub fn creator_bond(mint: &Pubkey) -> (Pubkey, u8) {
+    find(&[CREATOR_BOND_SEED, mint.as_ref()])
+}
+
+pub fn launch_config(mint: &Pubkey) -> (Pubkey, u8) {
+    find(&[LAUNCH_CONFIG_SEED, mint.as_ref()])
+}
+
+pub fn creator_reputation(creator: &Pubkey) -> (Pubkey, u8) {
+    find(&[REPUTATION_SEED, creator.as_ref()])
+}
+
+pub fn launch_info(mint: &Pubkey) -> (Pubkey, u8) {
+    find(&[LAUNCH_INFO_SEED, mint.as_ref()])
+}
+
+pub fn tax_holiday_schedule(mint: &Pubkey) -> (Pubkey, u8) {
+    find(&[TAX_HOLIDAY_SEED, mint.as_ref()])
+}
+
+pub fn fee_exemption_list(mint: &Pubkey) -> (Pubkey, u8) {
+    find(&[FEE_EXEMPTION_SEED, mint.as_ref()])
+}
+
+pub fn mint_stats(mint: &Pubkey) -> (Pubkey, u8) {
+    find(&[MINT_STATS_SEED, mint.as_ref()])
+}
+
+pub fn wallet_attestation(mint: &Pubkey, wallet: &Pubkey) -> (Pubkey, u8) {
+    find(&[WALLET_ATTESTATION_SEED, mint.as_ref(), wallet.as_ref()])
+}
+
+pub fn freeze_order(mint: &Pubkey, token_account: &Pubkey) -> (Pubkey, u8) {
+    find(&[FREEZE_ORDER_SEED, mint.as_ref(), token_account.as_ref()])
+}
+
+/// Freeze authority to create a compliance launch's mint with
+pub fn compliance_authority(mint: &Pubkey) -> (Pubkey, u8) {
+    find(&[COMPLIANCE_AUTHORITY_SEED, mint.as_ref()])
+}
+
+pub fn stream(sender: &Pubkey, mint: &Pubkey, stream_id: u64) -> (Pubkey, u8) {
+    find(&[
+        STREAM_SEED,
+        sender.as_ref(),
+        mint.as_ref(),
+        &stream_id.to_le_bytes(),
+    ])
+}
+
+pub fn otc_offer(maker: &Pubkey, offer_id: u64) -> (Pubkey, u8) {
+    find(&[OTC_OFFER_SEED, maker.as_ref(), &offer_id.to_le_bytes()])
+}
+
+pub fn airdrop_campaign(creator: &Pubkey, mint: &Pubkey, campaign_id: u64) -> (Pubkey, u8) {
+    find(&[
+        AIRDROP_SEED,
+        creator.as_ref(),
+        mint.as_ref(),
+        &campaign_id.to_le_bytes(),
+    ])
+}
+
+pub fn leaderboard(epoch: u64) -> (Pubkey, u8) {
+    find(&[LEADERBOARD_SEED, &epoch.to_le_bytes()])
+}
+
+pub fn mint_pause(mint: &Pubkey) -> (Pubkey, u8) {
+    find(&[MINT_PAUSE_SEED, mint.as_ref()])
+}
+
+pub fn pool_registry(mint: &Pubkey) -> (Pubkey, u8) {
+    find(&[POOL_REGISTRY_SEED, mint.as_ref()])
+}
+
+pub fn emission_schedule(mint: &Pubkey) -> (Pubkey, u8) {
+    find(&[EMISSION_SEED, mint.as_ref()])
+}
+
+pub fn allocation_plan(mint: &Pubkey) -> (Pubkey, u8) {
+    find(&[ALLOCATION_PLAN_SEED, mint.as_ref()])
+}
+
+/// Feed record of an index token, and its multiplier authority
+pub fn index_feed(mint: &Pubkey) -> (Pubkey, u8) {
+    find(&[INDEX_FEED_SEED, mint.as_ref()])
+}
+
+/// Associated token account of `wallet` for a Token-2022 `mint`; pass these to `airdrop_batch`
+/// so recipients whose account is frozen are skipped rather than failing the batch
+pub fn associated_token_account(wallet: &Pubkey, mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            wallet.as_ref(),
+            TOKEN_2022_PROGRAM_ID.as_ref(),
+            mint.as_ref(),
+        ],
+        &ASSOCIATED_TOKEN_PROGRAM_ID,
+    )
+    .0
+}
+
+/// Rate bounds of an interest-bearing token, and its rate authority
+pub fn interest_rate_governor(mint: &Pubkey) -> (Pubkey, u8) {
+    find(&[INTEREST_RATE_GOVERNOR_SEED, mint.as_ref()])
+}
+
+pub fn faucet(mint: &Pubkey) -> (Pubkey, u8) {
+    find(&[FAUCET_SEED, mint.as_ref()])
+}
+
+pub fn faucet_claim(mint: &Pubkey, wallet: &Pubkey) -> (Pubkey, u8) {
+    find(&[FAUCET_CLAIM_SEED, mint.as_ref(), wallet.as_ref()])
+}
+
+pub fn project_treasury(mint: &Pubkey) -> (Pubkey, u8) {
+    find(&[PROJECT_TREASURY_SEED, mint.as_ref()])
+}
+
+pub fn recurring_payment(treasury: &Pubkey, payment_id: u64) -> (Pubkey, u8) {
+    find(&[
+        RECURRING_PAYMENT_SEED,
+        treasury.as_ref(),
+        &payment_id.to_le_bytes(),
+    ])
+}
+
+pub fn vote_lock(mint: &Pubkey, owner: &Pubkey) -> (Pubkey, u8) {
+    find(&[VOTE_LOCK_SEED, mint.as_ref(), owner.as_ref()])
+}
+
+pub fn fee_delegate(mint: &Pubkey) -> (Pubkey, u8) {
+    find(&[FEE_DELEGATE_SEED, mint.as_ref()])
+}
+
+pub fn timelock(mint: &Pubkey) -> (Pubkey, u8) {
+    find(&[TIMELOCK_SEED, mint.as_ref()])
+}
+
+pub fn timelock_op(timelock: &Pubkey, op_id: u64) -> (Pubkey, u8) {
+    find(&[TIMELOCK_OP_SEED, timelock.as_ref(), &op_id.to_le_bytes()])
+}
+
+pub fn event_series(organizer: &Pubkey, series_id: u64) -> (Pubkey, u8) {
+    find(&[
+        EVENT_SERIES_SEED,
+        organizer.as_ref(),
+        &series_id.to_le_bytes(),
+    ])
+}
+
+pub fn attendance_event(series: &Pubkey, event_id: u32) -> (Pubkey, u8) {
+    find(&[
+        ATTENDANCE_EVENT_SEED,
+        series.as_ref(),
+        &event_id.to_le_bytes(),
+    ])
+}
+
+pub fn attendance_badge(event: &Pubkey) -> (Pubkey, u8) {
+    find(&[ATTENDANCE_BADGE_SEED, event.as_ref()])
+}
+
+pub fn attendance(event: &Pubkey, wallet: &Pubkey) -> (Pubkey, u8) {
+    find(&[ATTENDANCE_SEED, event.as_ref(), wallet.as_ref()])
+}
+
+/// Bitmap page holding `nonce`'s spent bit
+pub fn voucher_nonce_page(campaign: &Pubkey, nonce: u64) -> (Pubkey, u8) {
+    find(&[
+        VOUCHER_NONCES_SEED,
+        campaign.as_ref(),
+        &(nonce / NONCES_PER_PAGE).to_le_bytes(),
+    ])
+}
+
+pub fn gift(claim_key: &Pubkey) -> (Pubkey, u8) {
+    find(&[GIFT_SEED, claim_key.as_ref()])
+}
+
+/// Calendar page of one UTC day, counted from the Unix epoch
+pub fn launch_calendar(day: i64) -> (Pubkey, u8) {
+    find(&[LAUNCH_CALENDAR_SEED, &day.to_le_bytes()])
+}
+
+pub fn platform_staking() -> (Pubkey, u8) {
+    find(&[PLATFORM_STAKING_SEED])
+}
+
+pub fn stake_position(owner: &Pubkey) -> (Pubkey, u8) {
+    find(&[STAKE_POSITION_SEED, owner.as_ref()])
+}
+
+pub fn dividend_pool(mint: &Pubkey) -> (Pubkey, u8) {
+    find(&[DIVIDEND_POOL_SEED, mint.as_ref()])
+}
+
+pub fn dividend_position(mint: &Pubkey, owner: &Pubkey) -> (Pubkey, u8) {
+    find(&[DIVIDEND_POSITION_SEED, mint.as_ref(), owner.as_ref()])
+}
+
+pub fn partner(namespace: &str) -> (Pubkey, u8) {
+    find(&[PARTNER_SEED, namespace.as_bytes()])
+}
+
+/// Index of the launches using `symbol`, matched case-insensitively
+pub fn symbol_index(symbol: &str) -> (Pubkey, u8) {
+    find(&[SYMBOL_INDEX_SEED, symbol.to_ascii_uppercase().as_bytes()])
+}
+
+/// Metaplex metadata account of `mint`, created by `init_metaplex_metadata`
+pub fn metaplex_metadata(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            METAPLEX_METADATA_SEED,
+            METAPLEX_METADATA_PROGRAM_ID.as_ref(),
+            mint.as_ref(),
+        ],
+        &METAPLEX_METADATA_PROGRAM_ID,
+    )
+}
+
+pub fn exchange_listing(mint: &Pubkey, exchange: &Pubkey) -> (Pubkey, u8) {
+    find(&[EXCHANGE_LISTING_SEED, mint.as_ref(), exchange.as_ref()])
+}
+
+pub fn wrapper(mint: &Pubkey) -> (Pubkey, u8) {
+    find(&[WRAPPER_SEED, mint.as_ref()])
+}
+
+/// Fee-free companion mint of `mint`
+pub fn wrapped_mint(mint: &Pubkey) -> (Pubkey, u8) {
+    find(&[WRAPPED_MINT_SEED, mint.as_ref()])
+}
+
+pub fn gauge_epoch(epoch: u64) -> (Pubkey, u8) {
+    find(&[GAUGE_EPOCH_SEED, &epoch.to_le_bytes()])
+}
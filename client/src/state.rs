@@ -0,0 +1,637 @@
+//! Borsh mirrors of the program's accounts, decoded straight from account data.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_pubkey::Pubkey;
+
+use crate::{
+    account_discriminator,
+    pda::{NONCES_PER_PAGE, NONCE_PAGE_BYTES},
+};
+
+#[derive(Debug)]
+pub enum DecodeError {
+    /// Data is shorter than the discriminator or doesn't start with the expected one
+    WrongAccountType,
+    Borsh(std::io::Error),
+}
+
+impl std::fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DecodeError::WrongAccountType => write!(f, "account data is not of the expected type"),
+            DecodeError::Borsh(err) => write!(f, "failed to decode account: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+/// An account type of the launchpad, named as in the program for its discriminator.
+pub trait ProgramAccount: BorshDeserialize {
+    const NAME: &'static str;
+
+    /// Decode account data, checking the discriminator. Trailing bytes left by `max_len`
+    /// allocations or realloc growth are ignored.
+    fn decode(data: &[u8]) -> Result<Self, DecodeError> {
+        if data.len() < 8 || data[..8] != account_discriminator(Self::NAME) {
+            return Err(DecodeError::WrongAccountType);
+        }
+        Self::deserialize(&mut &data[8..]).map_err(DecodeError::Borsh)
+    }
+}
+
+macro_rules! program_account {
+    ($($ty:ident),* $(,)?) => {
+        $(impl ProgramAccount for $ty {
+            const NAME: &'static str = stringify!($ty);
+        })*
+    };
+}
+
+program_account!(
+    PlatformConfig,
+    LaunchConfig,
+    CreatorReputation,
+    Stream,
+    AirdropCampaign,
+    EmissionSchedule,
+    Faucet,
+    FaucetClaim,
+    MintStats,
+    TaxHolidaySchedule,
+    ProjectTreasury,
+    RecurringPayment,
+    Leaderboard,
+    FeeDelegate,
+    Timelock,
+    QueuedOp,
+    EventSeries,
+    AttendanceEvent,
+    Attendance,
+    VoucherNoncePage,
+    Gift,
+    Wrapper,
+    ExchangeListing,
+    Partner,
+    PlatformStaking,
+    StakePosition,
+    LaunchCalendar,
+    AllocationPlan,
+    WalletAttestation,
+    FreezeOrder,
+    IndexFeed,
+    InterestRateGovernor,
+    DividendPool,
+    SymbolIndex,
+);
+
+#[derive(BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TokenKind {
+    Taxed {
+        transfer_fee_basis_points: u16,
+        maximum_fee: u64,
+    },
+    Soulbound,
+    Indexed,
+    InterestBearing {
+        rate_bps: i16,
+    },
+}
+
+#[derive(BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NttMode {
+    Locking,
+    Burning,
+}
+
+#[derive(BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EmissionCurve {
+    Halving { periods_per_halving: u32 },
+    LinearDecay { total_periods: u32 },
+}
+
+#[derive(BorshDeserialize, Clone, Debug)]
+pub struct PlatformConfig {
+    pub admin: Pubkey,
+    pub verifier: Pubkey,
+    pub treasury: Pubkey,
+    pub launch_fee_lamports: u64,
+    pub insurance_fee_bps: u16,
+    pub bond_lamports: u64,
+    pub bond_lock_seconds: i64,
+    pub snapshotter: Pubkey,
+    pub platform_mint: Pubkey,
+    pub featured_fee_discount_bps: u16,
+    pub launch_fee_usd: u64,
+    pub sol_usd_feed_id: [u8; 32],
+    pub max_price_age_seconds: i64,
+    pub max_price_confidence_bps: u16,
+    pub max_active_launches: u32,
+    pub launch_cooldown_seconds: i64,
+    pub bond_escalation_bps: u16,
+    pub launch_attestor: Option<Pubkey>,
+    pub attestation_required: bool,
+    pub staking_fee_bps: u16,
+    pub demand_free_launches: u32,
+    pub demand_fee_step_bps: u16,
+    pub max_demand_multiplier_bps: u32,
+    pub demand_epoch: u64,
+    pub epoch_launches: u32,
+    pub bump: u8,
+}
+
+#[derive(BorshDeserialize, Clone, Debug)]
+pub struct LaunchConfig {
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub created_at: i64,
+    pub decimals: u8,
+    pub kind: TokenKind,
+    pub verified: bool,
+    pub verified_at: i64,
+    pub attestation_uri: String,
+    pub featured: bool,
+    pub featured_epoch: u64,
+    pub ntt_manager: Option<Pubkey>,
+    pub ntt_mode: Option<NttMode>,
+    pub platform_reviewed: bool,
+    pub partner: Option<Pubkey>,
+    pub compliance: Option<ComplianceFlags>,
+    pub compliance_officer: Option<Pubkey>,
+    pub metadata_locked: bool,
+    pub unbonded_active: bool,
+    pub bump: u8,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ComplianceFlags {
+    pub attestor: Pubkey,
+    pub restricted_jurisdictions_hash: [u8; 32],
+    pub accreditation_required: bool,
+}
+
+/// Creator profile and track record
+#[derive(BorshDeserialize, Clone, Debug)]
+pub struct CreatorReputation {
+    pub creator: Pubkey,
+    pub launches: u32,
+    pub bonds_refunded: u32,
+    pub disputes: u32,
+    pub disputes_upheld: u32,
+    pub slashes: u32,
+    pub score: i64,
+    pub active_launches: u32,
+    pub last_launch_at: i64,
+    pub verified_launches: u32,
+    pub total_fees: u128,
+    /// Newest first
+    pub recent_launches: Vec<Pubkey>,
+    pub bump: u8,
+}
+
+#[derive(BorshDeserialize, Clone, Debug)]
+pub struct Stream {
+    pub sender: Pubkey,
+    pub recipient: Pubkey,
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+    pub stream_id: u64,
+    pub deposited: u64,
+    pub withdrawn: u64,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub bump: u8,
+}
+
+#[derive(BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AirdropRecipient {
+    pub wallet: Pubkey,
+    pub amount: u64,
+}
+
+#[derive(BorshDeserialize, Clone, Debug)]
+pub struct AirdropCampaign {
+    pub creator: Pubkey,
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+    pub campaign_id: u64,
+    pub recipients: Vec<AirdropRecipient>,
+    pub cursor: u32,
+    pub skipped: Vec<u32>,
+    pub expires_at: i64,
+    pub voucher_signer: Option<Pubkey>,
+    pub bump: u8,
+}
+
+impl AirdropCampaign {
+    /// Recipients not paid yet, in payout order
+    pub fn pending(&self) -> &[AirdropRecipient] {
+        self.recipients
+            .get(self.cursor as usize..)
+            .unwrap_or_default()
+    }
+}
+
+#[derive(BorshDeserialize, Clone, Debug)]
+pub struct Gift {
+    pub sender: Pubkey,
+    pub claim_key: Pubkey,
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+    pub amount: u64,
+    pub expires_at: i64,
+    pub bump: u8,
+}
+
+#[derive(BorshDeserialize, Clone, Debug)]
+pub struct LaunchCalendar {
+    pub day: i64,
+    pub launches: Vec<ScheduledLaunch>,
+    pub bump: u8,
+}
+
+#[derive(BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ScheduledLaunch {
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub opens_at: i64,
+}
+
+#[derive(BorshDeserialize, Clone, Debug)]
+pub struct PlatformStaking {
+    pub vault: Pubkey,
+    pub ledger: StakeLedger,
+    pub bump: u8,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct StakeLedger {
+    pub total_staked: u64,
+    pub warming_stake: u64,
+    pub reward_per_share: u128,
+    pub pending_rewards: u64,
+    pub reward_dust: u128,
+    pub rolled_epoch: u64,
+    pub total_rewards: u64,
+}
+
+#[derive(BorshDeserialize, Clone, Debug)]
+pub struct DividendPool {
+    pub mint: Pubkey,
+    pub reward_mint: Pubkey,
+    pub stake_vault: Pubkey,
+    pub reward_vault: Pubkey,
+    pub ledger: StakeLedger,
+    pub bump: u8,
+}
+
+#[derive(BorshDeserialize, Clone, Debug)]
+pub struct StakePosition {
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub warming: u64,
+    pub warming_epoch: u64,
+    pub reward_debt: u128,
+    pub unclaimed: u64,
+    pub bump: u8,
+}
+
+#[derive(BorshDeserialize, Clone, Debug)]
+pub struct Partner {
+    pub authority: Pubkey,
+    pub namespace: String,
+    pub fee_share_bps: u16,
+    pub branding_uri: String,
+    pub launches: u64,
+    pub fees_earned: u64,
+    pub bump: u8,
+}
+
+#[derive(BorshDeserialize, Clone, Debug)]
+pub struct ExchangeListing {
+    pub mint: Pubkey,
+    pub exchange: Pubkey,
+    pub name: String,
+    pub wallets: Vec<Pubkey>,
+    pub approved: bool,
+    pub bump: u8,
+}
+
+#[derive(BorshDeserialize, Clone, Debug)]
+pub struct Wrapper {
+    pub mint: Pubkey,
+    pub wrapped_mint: Pubkey,
+    pub vault: Pubkey,
+    pub bump: u8,
+}
+
+#[derive(BorshDeserialize, Clone, Debug)]
+pub struct VoucherNoncePage {
+    pub campaign: Pubkey,
+    pub page: u64,
+    pub bits: [u8; NONCE_PAGE_BYTES],
+    pub bump: u8,
+}
+
+impl VoucherNoncePage {
+    /// Whether `nonce`, which must belong to this page, has been claimed
+    pub fn is_used(&self, nonce: u64) -> bool {
+        let index = (nonce % NONCES_PER_PAGE) as usize;
+        self.bits[index / 8] & (1 << (index % 8)) != 0
+    }
+}
+
+#[derive(BorshDeserialize, Clone, Debug)]
+pub struct EmissionSchedule {
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+    pub start_time: i64,
+    pub period_seconds: i64,
+    pub initial_per_period: u64,
+    pub curve: EmissionCurve,
+    pub minted: u64,
+    pub bump: u8,
+}
+
+#[derive(BorshDeserialize, Clone, Debug)]
+pub struct WalletAttestation {
+    pub mint: Pubkey,
+    pub wallet: Pubkey,
+    pub attestor: Pubkey,
+    pub jurisdictions_hash: [u8; 32],
+    pub accredited: bool,
+    pub expires_at: i64,
+    pub bump: u8,
+}
+
+#[derive(BorshDeserialize, Clone, Debug)]
+pub struct FreezeOrder {
+    pub mint: Pubkey,
+    pub token_account: Pubkey,
+    pub officer: Pubkey,
+    pub justification_uri: String,
+    pub justification_hash: [u8; 32],
+    pub issued_at: i64,
+    pub lifted_at: i64,
+    pub bump: u8,
+}
+
+impl FreezeOrder {
+    pub fn is_active(&self) -> bool {
+        self.issued_at != 0 && self.lifted_at == 0
+    }
+}
+
+#[derive(BorshDeserialize, Clone, Debug)]
+pub struct IndexFeed {
+    pub mint: Pubkey,
+    pub feed_id: [u8; 32],
+    pub base_price: i64,
+    pub exponent: i32,
+    /// Scaled by `MULTIPLIER_ONE`
+    pub multiplier: u64,
+    pub max_deviation_bps: u16,
+    pub min_update_interval_seconds: i64,
+    pub max_price_age_seconds: i64,
+    pub max_confidence_bps: u16,
+    pub last_publish_time: i64,
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+/// Fixed-point scale of `IndexFeed::multiplier`
+pub const MULTIPLIER_ONE: u64 = 1_000_000_000;
+
+/// Launches using a symbol, see [`crate::pda::symbol_index`]; more than one flags a reused symbol
+#[derive(BorshDeserialize, Clone, Debug)]
+pub struct SymbolIndex {
+    pub symbol: String,
+    pub first_mint: Pubkey,
+    pub launches: u32,
+    pub bump: u8,
+}
+
+/// Name, symbol and URI for `init_token_metadata`
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct TokenMetadataParams {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InterestRateBounds {
+    pub min_rate_bps: i16,
+    pub max_rate_bps: i16,
+    pub min_update_interval_seconds: i64,
+}
+
+#[derive(BorshDeserialize, Clone, Debug)]
+pub struct InterestRateGovernor {
+    pub mint: Pubkey,
+    pub admin: Pubkey,
+    pub bounds: InterestRateBounds,
+    pub rate_bps: i16,
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+#[derive(BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AllocationKind {
+    Team,
+    Liquidity,
+    Sale,
+    Treasury,
+}
+
+impl AllocationKind {
+    /// Whether the bucket is held by the plan and released over time
+    pub fn is_locked(self) -> bool {
+        matches!(self, AllocationKind::Team | AllocationKind::Liquidity)
+    }
+}
+
+#[derive(BorshDeserialize, Clone, Debug)]
+pub struct AllocationPlan {
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub total_supply: u64,
+    pub created_at: i64,
+    pub buckets: Vec<AllocationBucket>,
+    pub bump: u8,
+}
+
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct AllocationBucket {
+    pub kind: AllocationKind,
+    pub bps: u16,
+    pub amount: u64,
+    pub vault: Pubkey,
+    pub beneficiary: Pubkey,
+    pub vesting_start: i64,
+    pub vesting_end: i64,
+    pub released: u64,
+    pub revocable: bool,
+    pub pending_beneficiary: Option<Pubkey>,
+}
+
+#[derive(BorshDeserialize, Clone, Debug)]
+pub struct Faucet {
+    pub mint: Pubkey,
+    pub claim_amount: u64,
+    pub bump: u8,
+}
+
+#[derive(BorshDeserialize, Clone, Debug)]
+pub struct FaucetClaim {
+    pub wallet: Pubkey,
+    pub last_claim_day: i64,
+    pub bump: u8,
+}
+
+#[derive(BorshDeserialize, Clone, Debug)]
+pub struct MintStats {
+    pub mint: Pubkey,
+    pub transfers: u64,
+    pub volume: u128,
+    pub fees: u128,
+    pub last_transfer_slot: u64,
+    pub epoch: u64,
+    pub epoch_volume: u64,
+    pub epoch_fees: u64,
+    pub buy_volume: u128,
+    pub sell_volume: u128,
+    pub fees_synced: u128,
+    pub bump: u8,
+}
+
+#[derive(BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TaxHolidayWindow {
+    pub start_epoch: u64,
+    pub end_epoch: u64,
+}
+
+#[derive(BorshDeserialize, Clone, Debug)]
+pub struct TaxHolidaySchedule {
+    pub mint: Pubkey,
+    pub admin: Pubkey,
+    pub transfer_fee_basis_points: u16,
+    pub maximum_fee: u64,
+    pub windows: Vec<TaxHolidayWindow>,
+    pub bump: u8,
+}
+
+#[derive(BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PeriodCap {
+    pub period_seconds: i64,
+    pub cap: u64,
+    pub period_start: i64,
+    pub spent: u64,
+}
+
+#[derive(BorshDeserialize, Clone, Debug)]
+pub struct ProjectTreasury {
+    pub mint: Pubkey,
+    pub admin: Pubkey,
+    pub spenders: Vec<Pubkey>,
+    pub spending: PeriodCap,
+    pub disbursements: u64,
+    pub bump: u8,
+}
+
+#[derive(BorshDeserialize, Clone, Debug)]
+pub struct RecurringPayment {
+    pub treasury: Pubkey,
+    pub payment_id: u64,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub interval_seconds: i64,
+    pub next_due: i64,
+    pub payments_made: u64,
+    pub bump: u8,
+}
+
+#[derive(BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LeaderboardEntry {
+    pub mint: Pubkey,
+    pub volume: u64,
+    pub fees: u64,
+}
+
+#[derive(BorshDeserialize, Clone, Debug)]
+pub struct Leaderboard {
+    pub epoch: u64,
+    pub entries: Vec<LeaderboardEntry>,
+    pub finalized: bool,
+    pub bump: u8,
+}
+
+#[derive(BorshDeserialize, Clone, Debug)]
+pub struct FeeDelegate {
+    pub mint: Pubkey,
+    pub authority: Pubkey,
+    pub delegate: Pubkey,
+    pub destination: Pubkey,
+    pub withdrawals: PeriodCap,
+    pub allowed_destinations: Vec<Pubkey>,
+    pub allowlist_delay_seconds: i64,
+    pub pending_destinations: Vec<Pubkey>,
+    pub pending_at: i64,
+    pub bump: u8,
+}
+
+#[derive(BorshDeserialize, Clone, Debug)]
+pub struct Timelock {
+    pub mint: Pubkey,
+    pub admin: Pubkey,
+    pub delay_seconds: i64,
+    pub next_op_id: u64,
+    pub bump: u8,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TimelockAccountMeta {
+    pub pubkey: Pubkey,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+#[derive(BorshDeserialize, Clone, Debug)]
+pub struct EventSeries {
+    pub organizer: Pubkey,
+    pub series_id: u64,
+    pub name: String,
+    pub events_created: u32,
+    pub bump: u8,
+}
+
+#[derive(BorshDeserialize, Clone, Debug)]
+pub struct AttendanceEvent {
+    pub series: Pubkey,
+    pub event_id: u32,
+    pub badge_mint: Pubkey,
+    pub starts_at: i64,
+    pub ends_at: i64,
+    pub max_attendees: u32,
+    pub attendees: u32,
+    pub bump: u8,
+}
+
+#[derive(BorshDeserialize, Clone, Debug)]
+pub struct Attendance {
+    pub event: Pubkey,
+    pub wallet: Pubkey,
+    pub checked_in_at: i64,
+    pub bump: u8,
+}
+
+#[derive(BorshDeserialize, Clone, Debug)]
+pub struct QueuedOp {
+    pub timelock: Pubkey,
+    pub id: u64,
+    pub program_id: Pubkey,
+    pub accounts: Vec<TimelockAccountMeta>,
+    pub data: Vec<u8>,
+    pub eta: i64,
+    pub bump: u8,
+}
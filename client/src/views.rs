@@ -0,0 +1,90 @@
+//! Return values of the program's `get_*` instructions, read from a simulated transaction's
+//! return data.
+//!
+//! Every view starts with a `version` byte and later versions only append fields, so trailing
+//! bytes are ignored.
+
+use borsh::BorshDeserialize;
+use solana_pubkey::Pubkey;
+
+use crate::state::{AllocationKind, DecodeError};
+
+/// View layout version these decoders were written against
+pub const VIEW_VERSION: u8 = 1;
+
+/// Decode a view from the return data of a `get_*` instruction.
+pub fn decode<T: BorshDeserialize>(return_data: &[u8]) -> Result<T, DecodeError> {
+    T::deserialize(&mut &return_data[..]).map_err(DecodeError::Borsh)
+}
+
+/// Holder of one of a mint's authorities
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub enum AuthorityHolder {
+    Renounced,
+    EmissionSchedule,
+    TaxHolidaySchedule,
+    FeeDelegate,
+    Timelock,
+    /// Any other key, free to use the authority at will
+    Key(Pubkey),
+}
+
+/// Fee set to take over from a later epoch
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct DeclaredFee {
+    pub transfer_fee_basis_points: u16,
+    pub maximum_fee: u64,
+    pub epoch: u64,
+}
+
+/// Return value of `get_declared_fees`
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct DeclaredFeesView {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub transfer_fee_basis_points: u16,
+    pub maximum_fee: u64,
+    pub scheduled_fee: Option<DeclaredFee>,
+    pub transfer_fee_config_authority: Option<Pubkey>,
+    pub exempt_wallets: Option<u32>,
+}
+
+/// One bucket of [`AllocationPlanView`]
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct AllocationBucketView {
+    pub kind: AllocationKind,
+    pub beneficiary: Pubkey,
+    pub vault: Pubkey,
+    pub amount: u64,
+    pub vesting_start: i64,
+    pub vesting_end: i64,
+    pub unlocked: u64,
+    pub released: u64,
+    pub releasable: u64,
+    pub revocable: bool,
+}
+
+/// Return value of `get_vesting_state`
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct AllocationPlanView {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub total_supply: u64,
+    pub buckets: Vec<AllocationBucketView>,
+}
+
+/// Return value of `get_mint_health`
+#[derive(BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct MintHealthView {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub mint_authority: AuthorityHolder,
+    pub freeze_authority: AuthorityHolder,
+    pub fee_mutable: bool,
+    pub transfer_fee_config_authority: Option<AuthorityHolder>,
+    pub withdraw_withheld_authority: Option<AuthorityHolder>,
+    pub transfer_fee_basis_points: Option<u16>,
+    pub transfer_hook_program: Option<Pubkey>,
+    pub permanent_delegate: Option<Pubkey>,
+}
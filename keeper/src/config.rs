@@ -0,0 +1,100 @@
+//! Keeper configuration, read from a TOML file.
+
+use std::{path::Path, str::FromStr};
+
+use serde::{de::Error as _, Deserialize, Deserializer};
+use solana_pubkey::Pubkey;
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    pub rpc_url: String,
+    /// Keypair paying for the crank transactions
+    pub keypair: String,
+    /// Seconds to sleep between passes
+    #[serde(default = "default_interval")]
+    pub interval_seconds: u64,
+    /// Open each epoch's leaderboard and freeze the previous one
+    #[serde(default)]
+    pub rollover_epochs: bool,
+    #[serde(default)]
+    pub mints: Vec<MintConfig>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MintConfig {
+    #[serde(deserialize_with = "pubkey")]
+    pub mint: Pubkey,
+    /// Sweep withheld transfer fees from holder accounts into the mint
+    #[serde(default)]
+    pub harvest_fees: bool,
+    /// Withdraw the mint's withheld fees through its FeeDelegate; the keeper key must be the
+    /// delegate
+    #[serde(default)]
+    pub withdraw_fees: bool,
+    /// Crank the mint's tax holiday schedule once per epoch
+    #[serde(default)]
+    pub tax_holiday: bool,
+    /// Mint whatever the emission schedule has released
+    #[serde(default)]
+    pub emission: bool,
+    /// Send unlocked team and liquidity allocations to their beneficiaries
+    #[serde(default)]
+    pub allocations: bool,
+    /// Pyth price update account to move an index token's multiplier with, e.g. one kept fresh
+    /// by Pyth's price feed sponsorship
+    #[serde(default, deserialize_with = "optional_pubkey")]
+    pub index_price_update: Option<Pubkey>,
+    /// Push the mint's stats into the current epoch's leaderboard
+    #[serde(default)]
+    pub leaderboard: bool,
+    /// Add the mint's new transfer fees to its creator's profile
+    #[serde(default)]
+    pub creator_fees: bool,
+}
+
+fn default_interval() -> u64 {
+    60
+}
+
+fn pubkey<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Pubkey, D::Error> {
+    let key = String::deserialize(deserializer)?;
+    Pubkey::from_str(&key).map_err(D::Error::custom)
+}
+
+fn optional_pubkey<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Pubkey>, D::Error> {
+    pubkey(deserializer).map(Some)
+}
+
+impl Config {
+    pub fn load(path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let text = std::fs::read_to_string(path)?;
+        Ok(toml::from_str(&text)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_example_config() {
+        let config: Config = toml::from_str(include_str!("../keeper.example.toml")).unwrap();
+        assert!(config.rollover_epochs);
+        assert_eq!(config.mints.len(), 1);
+        assert!(config.mints[0].harvest_fees && config.mints[0].tax_holiday);
+        assert!(!config.mints[0].emission);
+        assert!(config.mints[0].index_price_update.is_none());
+    }
+
+    #[test]
+    fn defaults_and_bad_keys() {
+        let config: Config = toml::from_str("rpc_url = \"http://localhost:8899\"\nkeypair = \"id.json\"").unwrap();
+        assert_eq!(config.interval_seconds, 60);
+        assert!(!config.rollover_epochs && config.mints.is_empty());
+
+        let bad = "rpc_url = \"x\"\nkeypair = \"y\"\n[[mints]]\nmint = \"not-a-key\"";
+        assert!(toml::from_str::<Config>(bad).is_err());
+    }
+}
@@ -0,0 +1,222 @@
+//! One pass over the configured cranks. Every transaction is simulated first so cranks with
+//! nothing to do (no fees withheld, nothing released yet, leaderboard already open) cost
+//! nothing.
+
+use std::collections::HashMap;
+
+use solana_account_decoder_client_types::UiAccountEncoding;
+use solana_commitment_config::CommitmentConfig;
+use solana_instruction::Instruction;
+use solana_keypair::Keypair;
+use solana_pubkey::Pubkey;
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_rpc_client_api::{
+    config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+    filter::{Memcmp, RpcFilterType},
+};
+use solana_signer::Signer;
+use solana_transaction::Transaction;
+use spl_token_2022::{
+    extension::{transfer_fee::TransferFeeAmount, BaseStateWithExtensions, StateWithExtensions},
+    state::Account as TokenAccount,
+};
+use taxed_token_launchpad_client::{
+    instructions, pda,
+    state::{self, ProgramAccount},
+    TOKEN_2022_PROGRAM_ID,
+};
+
+use crate::config::Config;
+
+type Error = Box<dyn std::error::Error>;
+
+/// Source accounts per harvest instruction, keeping the transaction under the size limit
+const MAX_HARVEST_SOURCES: usize = 20;
+
+pub struct Keeper {
+    rpc: RpcClient,
+    payer: Keypair,
+    /// Epoch each mint's tax holiday was last cranked in; the crank only has work once per epoch
+    tax_holiday_cranked: HashMap<Pubkey, u64>,
+}
+
+impl Keeper {
+    pub fn new(rpc_url: &str, payer: Keypair) -> Self {
+        Self {
+            rpc: RpcClient::new_with_commitment(rpc_url.to_string(), CommitmentConfig::confirmed()),
+            payer,
+            tax_holiday_cranked: HashMap::new(),
+        }
+    }
+
+    /// Run every configured crank once. A failing crank is logged and doesn't stop the others.
+    pub fn run_pass(&mut self, config: &Config) -> Result<(), Error> {
+        let epoch = self.rpc.get_epoch_info()?.epoch;
+
+        if config.rollover_epochs {
+            report("rollover_epoch", self.rollover_epoch(epoch));
+        }
+        for mint in &config.mints {
+            if mint.harvest_fees {
+                report("harvest_fees", self.harvest_fees(&mint.mint));
+            }
+            if mint.withdraw_fees {
+                report("withdraw_delegated_fees", self.withdraw_delegated_fees(&mint.mint));
+            }
+            if mint.tax_holiday {
+                report("crank_tax_holiday", self.crank_tax_holiday(&mint.mint, epoch));
+            }
+            if mint.emission {
+                report("mint_emission", self.mint_emission(&mint.mint));
+            }
+            if mint.allocations {
+                report("release_allocation", self.release_allocations(&mint.mint));
+            }
+            if let Some(price_update) = &mint.index_price_update {
+                report("update_index_multiplier", self.update_index_multiplier(&mint.mint, price_update));
+            }
+            if mint.leaderboard {
+                report("update_leaderboard", self.update_leaderboard(&mint.mint, epoch));
+            }
+            if mint.creator_fees {
+                report("sync_creator_fees", self.sync_creator_fees(&mint.mint));
+            }
+        }
+        Ok(())
+    }
+
+    fn rollover_epoch(&self, epoch: u64) -> Result<(), Error> {
+        if self.account_data(&pda::leaderboard(epoch).0)?.is_some() {
+            return Ok(());
+        }
+        let previous = match epoch.checked_sub(1) {
+            Some(previous) => self.account_data(&pda::leaderboard(previous).0)?,
+            None => None,
+        };
+        let freeze_previous = match previous {
+            Some(data) => !state::Leaderboard::decode(&data)?.finalized,
+            None => false,
+        };
+        self.send("rollover_epoch", &[instructions::rollover_epoch(&self.payer.pubkey(), epoch, freeze_previous)])
+    }
+
+    /// Move withheld fees from every holder account of `mint` into the mint, where the
+    /// withdraw authority can collect them in one instruction.
+    fn harvest_fees(&self, mint: &Pubkey) -> Result<(), Error> {
+        let accounts = self.rpc.get_program_accounts_with_config(
+            &TOKEN_2022_PROGRAM_ID,
+            RpcProgramAccountsConfig {
+                filters: Some(vec![RpcFilterType::Memcmp(Memcmp::new_base58_encoded(0, mint.as_ref()))]),
+                account_config: RpcAccountInfoConfig {
+                    encoding: Some(UiAccountEncoding::Base64),
+                    ..RpcAccountInfoConfig::default()
+                },
+                ..RpcProgramAccountsConfig::default()
+            },
+        )?;
+        let sources: Vec<Pubkey> = accounts
+            .into_iter()
+            .filter(|(_, account)| withheld_amount(&account.data) > 0)
+            .map(|(address, _)| address)
+            .collect();
+
+        for chunk in sources.chunks(MAX_HARVEST_SOURCES) {
+            let chunk: Vec<&Pubkey> = chunk.iter().collect();
+            let ix = spl_token_2022::extension::transfer_fee::instruction::harvest_withheld_tokens_to_mint(
+                &TOKEN_2022_PROGRAM_ID,
+                mint,
+                &chunk,
+            )?;
+            self.send("harvest_fees", &[ix])?;
+        }
+        Ok(())
+    }
+
+    fn withdraw_delegated_fees(&self, mint: &Pubkey) -> Result<(), Error> {
+        let Some(data) = self.account_data(&pda::fee_delegate(mint).0)? else {
+            return Err(format!("no fee delegate for {mint}").into());
+        };
+        let fee_delegate = state::FeeDelegate::decode(&data)?;
+        let ix = instructions::withdraw_delegated_fees(&self.payer.pubkey(), mint, &fee_delegate.destination);
+        self.send("withdraw_delegated_fees", &[ix])
+    }
+
+    fn crank_tax_holiday(&mut self, mint: &Pubkey, epoch: u64) -> Result<(), Error> {
+        if self.tax_holiday_cranked.get(mint) == Some(&epoch) {
+            return Ok(());
+        }
+        self.send("crank_tax_holiday", &[instructions::crank_tax_holiday(mint)])?;
+        self.tax_holiday_cranked.insert(*mint, epoch);
+        Ok(())
+    }
+
+    fn mint_emission(&self, mint: &Pubkey) -> Result<(), Error> {
+        let Some(data) = self.account_data(&pda::emission_schedule(mint).0)? else {
+            return Err(format!("no emission schedule for {mint}").into());
+        };
+        let schedule = state::EmissionSchedule::decode(&data)?;
+        self.send("mint_emission", &[instructions::mint_emission(mint, &schedule.vault)])
+    }
+
+    fn release_allocations(&self, mint: &Pubkey) -> Result<(), Error> {
+        let Some(data) = self.account_data(&pda::allocation_plan(mint).0)? else {
+            return Err(format!("no allocation plan for {mint}").into());
+        };
+        let plan = state::AllocationPlan::decode(&data)?;
+        for (index, bucket) in plan.buckets.iter().enumerate() {
+            if bucket.released < bucket.amount {
+                self.send("release_allocation", &[instructions::release_allocation(mint, index as u8, bucket)])?;
+            }
+        }
+        Ok(())
+    }
+
+    fn update_index_multiplier(&self, mint: &Pubkey, price_update: &Pubkey) -> Result<(), Error> {
+        self.send("update_index_multiplier", &[instructions::update_index_multiplier(mint, price_update)])
+    }
+
+    fn update_leaderboard(&self, mint: &Pubkey, epoch: u64) -> Result<(), Error> {
+        self.send("update_leaderboard", &[instructions::update_leaderboard(mint, epoch)])
+    }
+
+    fn sync_creator_fees(&self, mint: &Pubkey) -> Result<(), Error> {
+        let Some(data) = self.account_data(&pda::launch_config(mint).0)? else {
+            return Err(format!("{mint} was not launched through the launchpad").into());
+        };
+        let launch_config = state::LaunchConfig::decode(&data)?;
+        self.send("sync_creator_fees", &[instructions::sync_creator_fees(mint, &launch_config.creator)])
+    }
+
+    fn account_data(&self, address: &Pubkey) -> Result<Option<Vec<u8>>, Error> {
+        let account = self.rpc.get_account_with_commitment(address, self.rpc.commitment())?.value;
+        Ok(account.map(|account| account.data))
+    }
+
+    /// Simulate, then send if the simulation succeeds. A failed simulation means the crank has
+    /// nothing to do right now and is not an error.
+    fn send(&self, crank: &str, ixs: &[Instruction]) -> Result<(), Error> {
+        let blockhash = self.rpc.get_latest_blockhash()?;
+        let tx = Transaction::new_signed_with_payer(ixs, Some(&self.payer.pubkey()), &[&self.payer], blockhash);
+        if let Some(err) = self.rpc.simulate_transaction(&tx)?.value.err {
+            println!("{crank}: skipped, {err}");
+            return Ok(());
+        }
+        let signature = self.rpc.send_and_confirm_transaction(&tx)?;
+        println!("{crank}: sent {signature}");
+        Ok(())
+    }
+}
+
+/// Fees withheld in a Token-2022 account, or 0 if the data isn't one
+fn withheld_amount(data: &[u8]) -> u64 {
+    StateWithExtensions::<TokenAccount>::unpack(data)
+        .ok()
+        .and_then(|account| account.get_extension::<TransferFeeAmount>().ok().map(|fee| u64::from(fee.withheld_amount)))
+        .unwrap_or(0)
+}
+
+fn report(crank: &str, result: Result<(), Error>) {
+    if let Err(err) = result {
+        eprintln!("{crank} failed: {err}");
+    }
+}
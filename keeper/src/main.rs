@@ -0,0 +1,54 @@
+//! Reference keeper for the launchpad's permissionless cranks.
+//!
+//! Usage: `launchpad-keeper <config.toml> [--once]`. See `keeper.example.toml`.
+
+mod config;
+mod cranks;
+
+use std::{path::PathBuf, process::ExitCode, thread, time::Duration};
+
+use config::Config;
+use cranks::Keeper;
+use solana_keypair::read_keypair_file;
+
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), std::env::var_os("HOME")) {
+        (Some(rest), Some(home)) => PathBuf::from(home).join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let once = args.iter().any(|arg| arg == "--once");
+    let Some(config_path) = args.iter().find(|arg| !arg.starts_with("--")) else {
+        eprintln!("usage: launchpad-keeper <config.toml> [--once]");
+        return ExitCode::FAILURE;
+    };
+
+    let config = match Config::load(&expand_home(config_path)) {
+        Ok(config) => config,
+        Err(err) => {
+            eprintln!("failed to load {config_path}: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let payer = match read_keypair_file(expand_home(&config.keypair)) {
+        Ok(payer) => payer,
+        Err(err) => {
+            eprintln!("failed to read keypair {}: {err}", config.keypair);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut keeper = Keeper::new(&config.rpc_url, payer);
+    loop {
+        if let Err(err) = keeper.run_pass(&config) {
+            eprintln!("pass failed: {err}");
+        }
+        if once {
+            return ExitCode::SUCCESS;
+        }
+        thread::sleep(Duration::from_secs(config.interval_seconds));
+    }
+}
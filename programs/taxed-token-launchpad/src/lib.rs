@@ -1,16 +1,89 @@
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program::{program::invoke, system_instruction};
+use anchor_lang::solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program::{invoke, invoke_signed},
+    system_instruction,
+};
+use solana_sha256_hasher::hashv;
 use anchor_lang::solana_program::sysvar::rent::Rent;
-use anchor_lang::solana_program::program_pack::Pack;
-use anchor_lang::solana_program::pubkey::Pubkey; 
+use anchor_lang::solana_program::pubkey::Pubkey;
 
 // Import spl-token-2022 instruction constructors and state types
 use spl_token_2022::instruction as token_instruction;
+use spl_token_2022::state::Account as Token2022Account;
 use spl_token_2022::state::Mint as Token2022Mint;
-use spl_token_2022::extension::transfer_fee::instruction::initialize_transfer_fee_config;
+use spl_token_2022::extension::transfer_fee::instruction::{
+    harvest_withheld_tokens_to_mint, initialize_transfer_fee_config, set_transfer_fee,
+    withdraw_withheld_tokens_from_mint,
+};
 use spl_token_2022::extension::transfer_fee::TransferFeeConfig;
-use spl_token_2022::extension::non_transferable::NonTransferable;
-use spl_token_2022::extension::ExtensionType;
+use spl_token_2022::extension::{permanent_delegate::PermanentDelegate, transfer_hook::TransferHook};
+use spl_token_2022::extension::{BaseStateWithExtensions, ExtensionType, StateWithExtensions};
+use spl_token_2022::extension::scaled_ui_amount::instruction as scaled_ui_amount_instruction;
+use spl_token_2022::extension::interest_bearing_mint::instruction as interest_bearing_instruction;
+use spl_token_2022::extension::metadata_pointer::instruction as metadata_pointer_instruction;
+use anchor_spl::token_2022_extensions::spl_pod::optional_keys::OptionalNonZeroPubkey;
+use anchor_spl::token_2022_extensions::spl_token_metadata_interface::{
+    instruction as token_metadata_instruction, state::TokenMetadata,
+};
+
+pub mod math;
+pub mod voucher;
+
+/// Log `[instruction] key=value ...` for an instruction's inputs when built with the
+/// `verbose-logs` feature. Compiles to nothing otherwise, so the values aren't even evaluated.
+macro_rules! trace_ix {
+    ($ix:literal $(, $key:ident = $value:expr)* $(,)?) => {
+        #[cfg(feature = "verbose-logs")]
+        msg!(concat!("[", $ix, "]" $(, " ", stringify!($key), "={}")*) $(, $value)*);
+    };
+}
+
+/// Check that a vault still covers what the program's accounting says it owes, failing the
+/// transaction if not. Compiled in only with the `invariant-checks` feature.
+macro_rules! invariant {
+    ($name:literal, $holds:expr) => {
+        #[cfg(feature = "invariant-checks")]
+        if !$holds? {
+            msg!(concat!("Invariant violated: ", $name));
+            return Err(MyError::InvariantViolated.into());
+        }
+    };
+}
+
+/// Borrow the accounts every single-mint creation instruction shares out of its context, for
+/// `launch`. The instructions' account structs all name these fields the same way.
+macro_rules! launch_accounts {
+    ($ctx:ident) => {
+        LaunchAccounts {
+            payer: &$ctx.accounts.payer,
+            mint: &$ctx.accounts.mint,
+            mint_authority: $ctx.accounts.mint_authority.key(),
+            freeze_authority: $ctx.accounts.freeze_authority.key(),
+            token_program: &$ctx.accounts.token_program,
+            platform_config: &mut $ctx.accounts.platform_config,
+            creator_bond: $ctx.accounts.creator_bond.as_mut(),
+            devnet_faucet: $ctx.accounts.devnet_faucet.as_deref_mut(),
+            treasury: &$ctx.accounts.treasury,
+            insurance_vault: &mut $ctx.accounts.insurance_vault,
+            featured_gauge: $ctx.accounts.featured_gauge.as_deref(),
+            price_update: $ctx.accounts.price_update.as_deref(),
+            attestor: $ctx.accounts.attestor.as_ref().map(|attestor| attestor.key()),
+            partner: $ctx.accounts.partner.as_mut(),
+            staking_pool: $ctx.accounts.staking_pool.as_mut(),
+            launch_config: &mut $ctx.accounts.launch_config,
+            creator_reputation: &mut $ctx.accounts.creator_reputation,
+            system_program: &$ctx.accounts.system_program,
+            creator_bond_bump: $ctx.bumps.creator_bond,
+            devnet_faucet_bump: $ctx.bumps.devnet_faucet,
+            launch_config_bump: $ctx.bumps.launch_config,
+            creator_reputation_bump: $ctx.bumps.creator_reputation,
+        }
+    };
+}
+
+#[cfg(feature = "test-utils")]
+pub mod test_utils;
 
 declare_id!("9zZZdmpER8Pw9QJMwSyd8cvV8swbZWeqfJG3Gz2HhVGz");
 
@@ -21,256 +94,14137 @@ pub mod taxed_token_launchpad {
     /// Create a new Taxed Token mint with Transfer Fee Extension configured.
     /// The created mint's mint authority will be `mint_authority` and the withdraw authority
     /// for withheld fees will be `fee_withdrawal_authority`.
+    ///
+    /// Breaking for callers of the original mint-only version: a launch now also takes the
+    /// platform config, treasury, insurance vault, launch config and creator reputation
+    /// accounts, plus the optional bond, faucet, gauge, price update, attestor, partner and
+    /// staking pool slots (pass the program id for those left out).
     pub fn create_taxed_token(
         ctx: Context<CreateTaxedToken>,
         decimals: u8,
         transfer_fee_basis_points: u16,
         maximum_fee: u64,
     ) -> Result<()> {
+        trace_ix!(
+            "create_taxed_token",
+            mint = ctx.accounts.mint.key(),
+            creator = ctx.accounts.payer.key(),
+            mint_authority = ctx.accounts.mint_authority.key(),
+            fee_withdraw_authority = ctx.accounts.fee_withdraw_authority.key(),
+            decimals = decimals,
+            fee_bps = transfer_fee_basis_points,
+            maximum_fee = maximum_fee,
+        );
         // Basic validation
         if transfer_fee_basis_points as u32 > 10_000 {
             return Err(MyError::InvalidFeeConfig.into());
         }
+        let kind = TokenKind::Taxed { transfer_fee_basis_points, maximum_fee };
 
-        // Create mint account with required space and rent
-        let rent = Rent::get()?;
-        // Calculate the exact account length required for a Mint with TransferFee extension
-        let mint_space = ExtensionType::try_calculate_account_len::<Token2022Mint>(&[ExtensionType::TransferFeeConfig])?;
-        msg!("mint_space total: {}", mint_space);
-        let lamports = rent.minimum_balance(mint_space);
+        launch(launch_accounts!(ctx), decimals, kind, Some(&ctx.accounts.fee_withdraw_authority.key()))
+    }
 
-        invoke(
-            &system_instruction::create_account(
-                &ctx.accounts.payer.key,
-                &ctx.accounts.mint.key(),
-                lamports,
-                mint_space as u64,
-                &ctx.accounts.token_program.key(),
-            ),
-            &[
-                ctx.accounts.payer.to_account_info(),
-                ctx.accounts.mint.to_account_info(),
-                ctx.accounts.system_program.to_account_info(),
-            ],
+    /// Create a new Soulbound (non-transferable) mint. Tokens can be minted but transfers will fail.
+    /// Takes the same launch accounts as `create_taxed_token`, with the same break for callers
+    /// of the original version.
+    pub fn create_soulbound_token(
+        ctx: Context<CreateSoulboundToken>,
+        decimals: u8,
+    ) -> Result<()> {
+        trace_ix!(
+            "create_soulbound_token",
+            mint = ctx.accounts.mint.key(),
+            creator = ctx.accounts.payer.key(),
+            mint_authority = ctx.accounts.mint_authority.key(),
+            decimals = decimals,
+        );
+        launch(launch_accounts!(ctx), decimals, TokenKind::Soulbound, None)
+    }
+
+    /// Publish a named launch preset. Anyone can publish; templates from the platform admin are
+    /// flagged `official`.
+    pub fn publish_template(
+        ctx: Context<PublishTemplate>,
+        name: String,
+        decimals: u8,
+        kind: TokenKind,
+    ) -> Result<()> {
+        trace_ix!(
+            "publish_template",
+            template = ctx.accounts.template.key(),
+            publisher = ctx.accounts.publisher.key(),
+            decimals = decimals,
+        );
+        if name.len() > MAX_NAME_LEN {
+            return Err(MyError::NameTooLong.into());
+        }
+        kind.validate()?;
+
+        let template = &mut ctx.accounts.template;
+        template.publisher = ctx.accounts.publisher.key();
+        template.official = ctx.accounts.publisher.key() == ctx.accounts.platform_config.admin;
+        template.name = name;
+        template.decimals = decimals;
+        template.kind = kind;
+        template.bump = ctx.bumps.template;
+
+        Ok(())
+    }
+
+    /// Remove a template, returning its rent to the publisher.
+    pub fn retire_template(_ctx: Context<RetireTemplate>) -> Result<()> {
+        trace_ix!(
+            "retire_template",
+            template = _ctx.accounts.template.key(),
+            publisher = _ctx.accounts.publisher.key(),
+        );
+        Ok(())
+    }
+
+    /// Create a new mint with the extension set, fee parameters and decimals of a template.
+    pub fn create_from_template(ctx: Context<CreateFromTemplate>) -> Result<()> {
+        trace_ix!(
+            "create_from_template",
+            mint = ctx.accounts.mint.key(),
+            creator = ctx.accounts.payer.key(),
+            template = ctx.accounts.template.key(),
+            mint_authority = ctx.accounts.mint_authority.key(),
+        );
+        let decimals = ctx.accounts.template.decimals;
+        let kind = ctx.accounts.template.kind;
+
+        let fee_withdraw_authority = ctx.accounts.fee_withdraw_authority.as_ref().map(|a| a.key());
+        launch(launch_accounts!(ctx), decimals, kind, fee_withdraw_authority.as_ref())
+    }
+
+    /// Create a new mint under the caller with the same decimals and extension/fee settings an
+    /// existing launch was created with.
+    pub fn clone_token_config(ctx: Context<CloneTokenConfig>) -> Result<()> {
+        trace_ix!(
+            "clone_token_config",
+            mint = ctx.accounts.mint.key(),
+            creator = ctx.accounts.payer.key(),
+            source = ctx.accounts.source_launch_config.key(),
+            mint_authority = ctx.accounts.mint_authority.key(),
+        );
+        let decimals = ctx.accounts.source_launch_config.decimals;
+        let kind = ctx.accounts.source_launch_config.kind;
+
+        let fee_withdraw_authority = ctx.accounts.fee_withdraw_authority.as_ref().map(|a| a.key());
+        launch(launch_accounts!(ctx), decimals, kind, fee_withdraw_authority.as_ref())
+    }
+
+    /// Create up to `MAX_BATCH_MINTS` PDA-derived mints with shared settings in one transaction,
+    /// each with its own LaunchConfig. `remaining_accounts` holds a `(mint, launch_config)` pair
+    /// per mint, in index order. Returns the created mint addresses.
+    /// Batches don't post creator bonds, so they are refused while the platform requires one.
+    /// The whole batch is one launch as far as the creator's cooldown goes, but each mint
+    /// counts towards their active launches.
+    pub fn create_tokens_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CreateTokensBatch<'info>>,
+        batch_id: u64,
+        decimals: u8,
+        kind: TokenKind,
+    ) -> Result<Vec<Pubkey>> {
+        trace_ix!(
+            "create_tokens_batch",
+            creator = ctx.accounts.payer.key(),
+            batch_id = batch_id,
+            count = ctx.remaining_accounts.len(),
+            decimals = decimals,
+            mint_authority = ctx.accounts.mint_authority.key(),
+        );
+        kind.validate()?;
+        let pairs = ctx.remaining_accounts;
+        if pairs.is_empty() || pairs.len() % 2 != 0 || pairs.len() / 2 > MAX_BATCH_MINTS {
+            return Err(MyError::InvalidBatch.into());
+        }
+        if ctx.accounts.platform_config.bond_lamports > 0 {
+            return Err(MyError::CreatorBondRequired.into());
+        }
+        ensure_launch_cooldown(&ctx.accounts.platform_config, &ctx.accounts.creator_reputation)?;
+
+        let payer_key = ctx.accounts.payer.key();
+        let batch_id_bytes = batch_id.to_le_bytes();
+        let mut mints = Vec::with_capacity(pairs.len() / 2);
+
+        for (index, pair) in pairs.chunks(2).enumerate() {
+            let (mint_info, launch_config_info) = (&pair[0], &pair[1]);
+            let index_bytes = [index as u8];
+
+            let (mint_key, mint_bump) = Pubkey::find_program_address(
+                &[BATCH_MINT_SEED, payer_key.as_ref(), &batch_id_bytes, &index_bytes],
+                &crate::ID,
+            );
+            let (launch_config_key, launch_config_bump) =
+                Pubkey::find_program_address(&[LAUNCH_CONFIG_SEED, mint_key.as_ref()], &crate::ID);
+            if mint_info.key() != mint_key || launch_config_info.key() != launch_config_key {
+                return Err(MyError::InvalidBatch.into());
+            }
+
+            collect_launch_fee(
+                &mut ctx.accounts.platform_config,
+                &ctx.accounts.payer,
+                &ctx.accounts.treasury,
+                &mut ctx.accounts.insurance_vault,
+                &ctx.accounts.system_program,
+                false,
+                ctx.accounts.price_update.as_deref(),
+                ctx.accounts.partner.as_mut(),
+                ctx.accounts.staking_pool.as_mut(),
+            )?;
+
+            let mint_seeds: &[&[u8]] = &[
+                BATCH_MINT_SEED,
+                payer_key.as_ref(),
+                &batch_id_bytes,
+                &index_bytes,
+                &[mint_bump],
+            ];
+            create_token_mint(
+                &ctx.accounts.payer,
+                mint_info,
+                &ctx.accounts.token_program,
+                &ctx.accounts.system_program,
+                &ctx.accounts.mint_authority.key(),
+                &ctx.accounts.freeze_authority.key(),
+                ctx.accounts.fee_withdraw_authority.as_ref().map(|a| a.key),
+                decimals,
+                kind,
+                true,
+                &[mint_seeds],
+            )?;
+
+            let launch_config_seeds: &[&[u8]] =
+                &[LAUNCH_CONFIG_SEED, mint_key.as_ref(), &[launch_config_bump]];
+            create_pda_account(
+                &ctx.accounts.payer,
+                launch_config_info,
+                &ctx.accounts.system_program,
+                8 + LaunchConfig::INIT_SPACE,
+                &[launch_config_seeds],
+            )?;
+            let mut launch_config = LaunchConfig {
+                mint: mint_key,
+                creator: payer_key,
+                created_at: 0,
+                decimals,
+                kind,
+                verified: false,
+                verified_at: 0,
+                attestation_uri: String::new(),
+                featured: false,
+                featured_epoch: 0,
+                ntt_manager: None,
+                ntt_mode: None,
+                platform_reviewed: false,
+                partner: None,
+                compliance: None,
+                compliance_officer: None,
+                metadata_locked: false,
+                unbonded_active: false,
+                bump: launch_config_bump,
+            };
+            record_launch(
+                &ctx.accounts.platform_config,
+                &mut launch_config,
+                &mut ctx.accounts.creator_reputation,
+                &mint_key,
+                &payer_key,
+                decimals,
+                kind,
+                ctx.accounts.attestor.as_ref().map(|attestor| attestor.key()),
+                ctx.accounts.partner.as_mut(),
+                false,
+                launch_config_bump,
+                ctx.bumps.creator_reputation,
+            )?;
+            launch_config.try_serialize(&mut &mut launch_config_info.try_borrow_mut_data()?[..])?;
+
+            mints.push(mint_key);
+        }
+
+        Ok(mints)
+    }
+
+    /// Escrow `deposit` tokens and stream them to `recipient` linearly, per second, between
+    /// `start_time` and `end_time`. The stream total is what actually arrives in the escrow
+    /// after the deposit's transfer fee.
+    pub fn create_stream(
+        ctx: Context<CreateStream>,
+        stream_id: u64,
+        recipient: Pubkey,
+        deposit: u64,
+        start_time: i64,
+        end_time: i64,
+    ) -> Result<()> {
+        trace_ix!(
+            "create_stream",
+            mint = ctx.accounts.mint.key(),
+            sender = ctx.accounts.sender.key(),
+            recipient = recipient,
+            stream_id = stream_id,
+            deposit = deposit,
+            start_time = start_time,
+            end_time = end_time,
+        );
+        ensure_not_paused(&ctx.accounts.mint_pause)?;
+        if deposit == 0 || end_time <= start_time {
+            return Err(MyError::InvalidStream.into());
+        }
+
+        let stream_key = ctx.accounts.stream.key();
+        let vault = token_account(&ctx.accounts.vault)?;
+        if vault.mint != ctx.accounts.mint.key() || vault.owner != stream_key {
+            return Err(MyError::InvalidTokenAccount.into());
+        }
+
+        let fee = transfer_tokens(
+            &ctx.accounts.token_program,
+            &ctx.accounts.sender_token_account,
+            &ctx.accounts.mint,
+            &ctx.accounts.vault,
+            &ctx.accounts.sender,
+            &[],
+            deposit,
         )?;
+        let deposited = deposit - fee;
 
-        // Initialize the transfer fee config extension **before** initializing the mint
-        let init_tf_ix = initialize_transfer_fee_config(
-            &ctx.accounts.token_program.key(),
-            &ctx.accounts.mint.key(),
-            Some(&ctx.accounts.mint_authority.key()),
-            Some(&ctx.accounts.fee_withdraw_authority.key()),
-            transfer_fee_basis_points,
-            maximum_fee,
+        let stream = &mut ctx.accounts.stream;
+        stream.sender = ctx.accounts.sender.key();
+        stream.recipient = recipient;
+        stream.mint = ctx.accounts.mint.key();
+        stream.vault = ctx.accounts.vault.key();
+        stream.stream_id = stream_id;
+        stream.deposited = deposited;
+        stream.withdrawn = 0;
+        stream.start_time = start_time;
+        stream.end_time = end_time;
+        stream.bump = ctx.bumps.stream;
+
+        emit!(StreamCreated {
+            version: EVENT_VERSION,
+            stream: stream_key,
+            sender: stream.sender,
+            recipient,
+            mint: stream.mint,
+            deposited,
+            start_time,
+            end_time,
+        });
+
+        Ok(())
+    }
+
+    /// Pay the recipient everything that has accrued and not been withdrawn yet. The payout's
+    /// own transfer fee is withheld from what the recipient receives.
+    pub fn withdraw_from_stream(ctx: Context<WithdrawFromStream>) -> Result<()> {
+        trace_ix!(
+            "withdraw_from_stream",
+            stream = ctx.accounts.stream.key(),
+            mint = ctx.accounts.mint.key(),
+            recipient = ctx.accounts.recipient.key(),
+        );
+        ensure_not_paused(&ctx.accounts.mint_pause)?;
+        let now = Clock::get()?.unix_timestamp;
+        let stream = &ctx.accounts.stream;
+        let available = stream.vested_amount(now).saturating_sub(stream.withdrawn);
+        if available == 0 {
+            return Err(MyError::NothingToClaim.into());
+        }
+
+        let seeds: &[&[u8]] = &[
+            STREAM_SEED,
+            stream.sender.as_ref(),
+            stream.mint.as_ref(),
+            &stream.stream_id.to_le_bytes(),
+            &[stream.bump],
+        ];
+        let fee = transfer_tokens(
+            &ctx.accounts.token_program,
+            &ctx.accounts.vault,
+            &ctx.accounts.mint,
+            &ctx.accounts.recipient_token_account,
+            &stream.to_account_info(),
+            &[seeds],
+            available,
         )?;
 
-        invoke(
-            &init_tf_ix,
-            &[
-                ctx.accounts.mint.to_account_info(),
-                ctx.accounts.mint_authority.to_account_info(),
-                ctx.accounts.fee_withdraw_authority.to_account_info(),
-                ctx.accounts.token_program.to_account_info(),
-                ctx.accounts.rent.to_account_info(),
-            ],
+        let stream = &mut ctx.accounts.stream;
+        stream.withdrawn = stream.withdrawn.checked_add(available).ok_or(MyError::MathOverflow)?;
+        invariant!(
+            "stream vault covers the unwithdrawn deposit",
+            vault_covers(&ctx.accounts.vault, stream.deposited.saturating_sub(stream.withdrawn))
+        );
+
+        emit!(StreamWithdrawn {
+            version: EVENT_VERSION,
+            stream: stream.key(),
+            amount: available,
+            fee,
+        });
+
+        Ok(())
+    }
+
+    /// Stop a stream: the recipient is paid what has accrued, the sender gets the rest of the
+    /// vault back and the vault and stream account are closed to the sender.
+    pub fn cancel_stream(ctx: Context<CancelStream>) -> Result<()> {
+        trace_ix!(
+            "cancel_stream",
+            stream = ctx.accounts.stream.key(),
+            mint = ctx.accounts.mint.key(),
+            sender = ctx.accounts.sender.key(),
+        );
+        ensure_not_paused(&ctx.accounts.mint_pause)?;
+        let now = Clock::get()?.unix_timestamp;
+        let stream = &ctx.accounts.stream;
+        let vested = stream.vested_amount(now);
+        let owed_to_recipient = vested.saturating_sub(stream.withdrawn);
+        let refund = token_account(&ctx.accounts.vault)?.amount.saturating_sub(owed_to_recipient);
+
+        let stream_id_bytes = stream.stream_id.to_le_bytes();
+        let seeds: &[&[u8]] = &[
+            STREAM_SEED,
+            stream.sender.as_ref(),
+            stream.mint.as_ref(),
+            &stream_id_bytes,
+            &[stream.bump],
+        ];
+        if owed_to_recipient > 0 {
+            transfer_tokens(
+                &ctx.accounts.token_program,
+                &ctx.accounts.vault,
+                &ctx.accounts.mint,
+                &ctx.accounts.recipient_token_account,
+                &stream.to_account_info(),
+                &[seeds],
+                owed_to_recipient,
+            )?;
+        }
+        if refund > 0 {
+            transfer_tokens(
+                &ctx.accounts.token_program,
+                &ctx.accounts.vault,
+                &ctx.accounts.mint,
+                &ctx.accounts.sender_token_account,
+                &stream.to_account_info(),
+                &[seeds],
+                refund,
+            )?;
+        }
+        close_vault(
+            &ctx.accounts.token_program,
+            &ctx.accounts.mint,
+            &ctx.accounts.vault,
+            &stream.to_account_info(),
+            &ctx.accounts.sender,
+            &[seeds],
         )?;
 
-        // Initialize the mint via token-2022 program CPI
-        let init_mint_ix = token_instruction::initialize_mint(
-            &ctx.accounts.token_program.key(),
-            &ctx.accounts.mint.key(),
-            &ctx.accounts.mint_authority.key(),
-            Some(&ctx.accounts.freeze_authority.key()),
-            decimals,
+        emit!(StreamCancelled {
+            version: EVENT_VERSION,
+            stream: stream.key(),
+            paid_to_recipient: owed_to_recipient,
+            refunded: refund,
+        });
+
+        Ok(())
+    }
+
+    /// Open an OTC offer: escrow `amount_a` of `mint_a` in exchange for exactly `amount_b` of
+    /// `mint_b` delivered to the maker. Restrict the fill to `counterparty` when it is set.
+    pub fn create_otc_offer(
+        ctx: Context<CreateOtcOffer>,
+        offer_id: u64,
+        amount_a: u64,
+        amount_b: u64,
+        counterparty: Option<Pubkey>,
+    ) -> Result<()> {
+        trace_ix!(
+            "create_otc_offer",
+            maker = ctx.accounts.maker.key(),
+            mint_a = ctx.accounts.mint_a.key(),
+            mint_b = ctx.accounts.mint_b.key(),
+            offer_id = offer_id,
+            amount_a = amount_a,
+            amount_b = amount_b,
+        );
+        ensure_not_paused(&ctx.accounts.mint_pause)?;
+        if amount_a == 0 || amount_b == 0 || ctx.accounts.mint_a.key() == ctx.accounts.mint_b.key() {
+            return Err(MyError::InvalidOtcOffer.into());
+        }
+
+        let offer_key = ctx.accounts.offer.key();
+        let vault = token_account(&ctx.accounts.vault)?;
+        if vault.mint != ctx.accounts.mint_a.key() || vault.owner != offer_key {
+            return Err(MyError::InvalidTokenAccount.into());
+        }
+
+        let fee = transfer_tokens(
+            &ctx.accounts.token_program,
+            &ctx.accounts.maker_token_account_a,
+            &ctx.accounts.mint_a,
+            &ctx.accounts.vault,
+            &ctx.accounts.maker,
+            &[],
+            amount_a,
         )?;
 
-        invoke(
-            &init_mint_ix,
-            &[
-                ctx.accounts.mint.to_account_info(),
-                ctx.accounts.rent.to_account_info(),
-                ctx.accounts.token_program.to_account_info(),
-            ],
+        let offer = &mut ctx.accounts.offer;
+        offer.maker = ctx.accounts.maker.key();
+        offer.offer_id = offer_id;
+        offer.mint_a = ctx.accounts.mint_a.key();
+        offer.mint_b = ctx.accounts.mint_b.key();
+        offer.vault = ctx.accounts.vault.key();
+        offer.amount_a = amount_a - fee;
+        offer.amount_b = amount_b;
+        offer.counterparty = counterparty;
+        offer.bump = ctx.bumps.offer;
+
+        emit!(OtcOfferCreated {
+            version: EVENT_VERSION,
+            offer: offer_key,
+            maker: offer.maker,
+            mint_a: offer.mint_a,
+            amount_a: offer.amount_a,
+            mint_b: offer.mint_b,
+            amount_b,
+            counterparty,
+        });
+
+        Ok(())
+    }
+
+    /// Fill an OTC offer atomically. The taker pays whatever gross amount of `mint_b` makes the
+    /// maker receive exactly `amount_b`, and receives the escrowed `mint_a` less its own fee.
+    /// Anything else left in the vault goes back to the maker, and the vault is closed.
+    pub fn fill_otc_offer(ctx: Context<FillOtcOffer>) -> Result<()> {
+        trace_ix!(
+            "fill_otc_offer",
+            offer = ctx.accounts.offer.key(),
+            taker = ctx.accounts.taker.key(),
+            mint_a = ctx.accounts.mint_a.key(),
+            mint_b = ctx.accounts.mint_b.key(),
+        );
+        ensure_not_paused(&ctx.accounts.mint_pause)?;
+        let offer = &ctx.accounts.offer;
+        if let Some(counterparty) = offer.counterparty {
+            if counterparty != ctx.accounts.taker.key() {
+                return Err(MyError::Unauthorized.into());
+            }
+        }
+        let maker_account_a = token_account(&ctx.accounts.maker_token_account_a)?;
+        let maker_account_b = token_account(&ctx.accounts.maker_token_account_b)?;
+        if maker_account_a.mint != offer.mint_a
+            || maker_account_a.owner != offer.maker
+            || maker_account_b.mint != offer.mint_b
+            || maker_account_b.owner != offer.maker
+        {
+            return Err(MyError::InvalidTokenAccount.into());
+        }
+
+        let gross_b = pre_fee_amount(&ctx.accounts.mint_b, offer.amount_b)?;
+        let fee_b = transfer_tokens(
+            &ctx.accounts.token_program,
+            &ctx.accounts.taker_token_account_b,
+            &ctx.accounts.mint_b,
+            &ctx.accounts.maker_token_account_b,
+            &ctx.accounts.taker,
+            &[],
+            gross_b,
+        )?;
+
+        let offer_id_bytes = offer.offer_id.to_le_bytes();
+        let seeds: &[&[u8]] = &[
+            OTC_OFFER_SEED,
+            offer.maker.as_ref(),
+            &offer_id_bytes,
+            &[offer.bump],
+        ];
+        let fee_a = transfer_tokens(
+            &ctx.accounts.token_program,
+            &ctx.accounts.vault,
+            &ctx.accounts.mint_a,
+            &ctx.accounts.taker_token_account_a,
+            &offer.to_account_info(),
+            &[seeds],
+            offer.amount_a,
+        )?;
+        let leftover = token_account(&ctx.accounts.vault)?.amount;
+        if leftover > 0 {
+            transfer_tokens(
+                &ctx.accounts.token_program,
+                &ctx.accounts.vault,
+                &ctx.accounts.mint_a,
+                &ctx.accounts.maker_token_account_a,
+                &offer.to_account_info(),
+                &[seeds],
+                leftover,
+            )?;
+        }
+        close_vault(
+            &ctx.accounts.token_program,
+            &ctx.accounts.mint_a,
+            &ctx.accounts.vault,
+            &offer.to_account_info(),
+            &ctx.accounts.maker,
+            &[seeds],
         )?;
 
+        emit!(OtcOfferFilled {
+            version: EVENT_VERSION,
+            offer: offer.key(),
+            taker: ctx.accounts.taker.key(),
+            amount_a: offer.amount_a,
+            fee_a,
+            amount_b: gross_b,
+            fee_b,
+        });
+
         Ok(())
     }
 
-    /// Create a new Soulbound (non-transferable) mint. Tokens can be minted but transfers will fail.
-    pub fn create_soulbound_token(
-        ctx: Context<CreateSoulboundToken>,
-        decimals: u8,
-    ) -> Result<()> {
-        // Create mint account
-        let rent = Rent::get()?;
-        // Calculate exact account length for a Mint with NonTransferable extension
-        let mint_space = ExtensionType::try_calculate_account_len::<Token2022Mint>(&[ExtensionType::NonTransferable])?;
-        msg!("mint_space total: {}", mint_space);
-        let lamports = rent.minimum_balance(mint_space);
+    /// Withdraw an unfilled OTC offer, return the vault's whole balance to the maker and close it.
+    pub fn cancel_otc_offer(ctx: Context<CancelOtcOffer>) -> Result<()> {
+        trace_ix!(
+            "cancel_otc_offer",
+            offer = ctx.accounts.offer.key(),
+            maker = ctx.accounts.maker.key(),
+        );
+        let offer = &ctx.accounts.offer;
+        let offer_id_bytes = offer.offer_id.to_le_bytes();
+        let seeds: &[&[u8]] = &[
+            OTC_OFFER_SEED,
+            offer.maker.as_ref(),
+            &offer_id_bytes,
+            &[offer.bump],
+        ];
+        let refunded = token_account(&ctx.accounts.vault)?.amount;
+        transfer_tokens(
+            &ctx.accounts.token_program,
+            &ctx.accounts.vault,
+            &ctx.accounts.mint_a,
+            &ctx.accounts.maker_token_account_a,
+            &offer.to_account_info(),
+            &[seeds],
+            refunded,
+        )?;
+        close_vault(
+            &ctx.accounts.token_program,
+            &ctx.accounts.mint_a,
+            &ctx.accounts.vault,
+            &offer.to_account_info(),
+            &ctx.accounts.maker,
+            &[seeds],
+        )?;
 
-        invoke(
+        emit!(OtcOfferCancelled { version: EVENT_VERSION, offer: offer.key() });
+
+        Ok(())
+    }
+
+    /// Escrow `amount` of `mint` as a gift claimable by whoever holds the secret key of
+    /// `claim_key`, e.g. shared as a link, until `expires_at`. The deposit's own transfer fee
+    /// is withheld from the gift. `vault` is a token account of the mint owned by the gift PDA.
+    pub fn create_gift(ctx: Context<CreateGift>, claim_key: Pubkey, amount: u64, expires_at: i64) -> Result<()> {
+        trace_ix!(
+            "create_gift",
+            mint = ctx.accounts.mint.key(),
+            sender = ctx.accounts.sender.key(),
+            claim_key = claim_key,
+            amount = amount,
+            expires_at = expires_at,
+        );
+        ensure_not_paused(&ctx.accounts.mint_pause)?;
+        if amount == 0 || expires_at <= Clock::get()?.unix_timestamp {
+            return Err(MyError::InvalidGift.into());
+        }
+        let gift_key = ctx.accounts.gift.key();
+        let vault = token_account(&ctx.accounts.vault)?;
+        if vault.mint != ctx.accounts.mint.key() || vault.owner != gift_key {
+            return Err(MyError::InvalidTokenAccount.into());
+        }
+
+        let fee = transfer_tokens(
+            &ctx.accounts.token_program,
+            &ctx.accounts.sender_token_account,
+            &ctx.accounts.mint,
+            &ctx.accounts.vault,
+            &ctx.accounts.sender,
+            &[],
+            amount,
+        )?;
+
+        let gift = &mut ctx.accounts.gift;
+        gift.sender = ctx.accounts.sender.key();
+        gift.claim_key = claim_key;
+        gift.mint = ctx.accounts.mint.key();
+        gift.vault = ctx.accounts.vault.key();
+        gift.amount = amount - fee;
+        gift.expires_at = expires_at;
+        gift.bump = ctx.bumps.gift;
+
+        emit!(GiftCreated {
+            version: EVENT_VERSION,
+            gift: gift_key,
+            sender: gift.sender,
+            mint: gift.mint,
+            amount: gift.amount,
+            expires_at,
+        });
+
+        Ok(())
+    }
+
+    /// Pay a gift out to any token account of its mint. Signed by the gift's claim key, so only
+    /// the link holder can choose where it goes; anyone can pay the transaction fee. The vault's
+    /// whole balance is paid out, including anything sent to it after the gift was made, so the
+    /// vault and gift can be closed to the sender.
+    pub fn claim_gift(ctx: Context<ClaimGift>) -> Result<()> {
+        trace_ix!(
+            "claim_gift",
+            gift = ctx.accounts.gift.key(),
+            recipient_token_account = ctx.accounts.recipient_token_account.key(),
+        );
+        ensure_not_paused(&ctx.accounts.mint_pause)?;
+        let gift = &ctx.accounts.gift;
+        if Clock::get()?.unix_timestamp >= gift.expires_at {
+            return Err(MyError::GiftExpired.into());
+        }
+
+        let amount = token_account(&ctx.accounts.vault)?.amount;
+        let seeds: &[&[u8]] = &[GIFT_SEED, gift.claim_key.as_ref(), &[gift.bump]];
+        transfer_tokens(
+            &ctx.accounts.token_program,
+            &ctx.accounts.vault,
+            &ctx.accounts.mint,
+            &ctx.accounts.recipient_token_account,
+            &gift.to_account_info(),
+            &[seeds],
+            amount,
+        )?;
+        close_vault(
+            &ctx.accounts.token_program,
+            &ctx.accounts.mint,
+            &ctx.accounts.vault,
+            &gift.to_account_info(),
+            &ctx.accounts.sender,
+            &[seeds],
+        )?;
+
+        emit!(GiftClaimed {
+            version: EVENT_VERSION,
+            gift: gift.key(),
+            recipient_token_account: ctx.accounts.recipient_token_account.key(),
+            amount,
+        });
+
+        Ok(())
+    }
+
+    /// Take back a gift that hasn't been claimed, expired or not, and close it. Like
+    /// `claim_gift`, this empties the vault's whole balance.
+    pub fn cancel_gift(ctx: Context<CancelGift>) -> Result<()> {
+        trace_ix!(
+            "cancel_gift",
+            gift = ctx.accounts.gift.key(),
+            sender = ctx.accounts.sender.key(),
+        );
+        let gift = &ctx.accounts.gift;
+        let refunded = token_account(&ctx.accounts.vault)?.amount;
+        let seeds: &[&[u8]] = &[GIFT_SEED, gift.claim_key.as_ref(), &[gift.bump]];
+        transfer_tokens(
+            &ctx.accounts.token_program,
+            &ctx.accounts.vault,
+            &ctx.accounts.mint,
+            &ctx.accounts.sender_token_account,
+            &gift.to_account_info(),
+            &[seeds],
+            refunded,
+        )?;
+        close_vault(
+            &ctx.accounts.token_program,
+            &ctx.accounts.mint,
+            &ctx.accounts.vault,
+            &gift.to_account_info(),
+            &ctx.accounts.sender,
+            &[seeds],
+        )?;
+
+        emit!(GiftCancelled {
+            version: EVENT_VERSION,
+            gift: gift.key(),
+            refunded,
+        });
+
+        Ok(())
+    }
+
+    /// Set up the fee-free companion of a taxed launch mint: a plain Token-2022 mint with the
+    /// same decimals, minted 1:1 against tokens held in `vault`, a token account of the mint
+    /// owned by the wrapper PDA. Permissionless; there is one wrapper per mint.
+    pub fn init_wrapper(ctx: Context<InitWrapper>) -> Result<()> {
+        trace_ix!("init_wrapper", mint = ctx.accounts.mint.key(), payer = ctx.accounts.payer.key());
+        if !matches!(ctx.accounts.launch_config.kind, TokenKind::Taxed { .. }) {
+            return Err(MyError::NotWrappable.into());
+        }
+        let mint_key = ctx.accounts.mint.key();
+        let wrapper_key = ctx.accounts.wrapper.key();
+        let vault = token_account(&ctx.accounts.vault)?;
+        if vault.mint != mint_key || vault.owner != wrapper_key {
+            return Err(MyError::InvalidTokenAccount.into());
+        }
+
+        let (decimals, _) = mint_decimals_and_fee(&ctx.accounts.mint, 0)?;
+        let wrapped_mint = &ctx.accounts.wrapped_mint;
+        let mint_space = ExtensionType::try_calculate_account_len::<Token2022Mint>(&[])?;
+        let lamports = Rent::get()?.minimum_balance(mint_space);
+        let wrapped_mint_seeds: &[&[u8]] = &[WRAPPED_MINT_SEED, mint_key.as_ref(), &[ctx.bumps.wrapped_mint]];
+        invoke_signed(
             &system_instruction::create_account(
-                &ctx.accounts.payer.key,
-                &ctx.accounts.mint.key(),
+                ctx.accounts.payer.key,
+                wrapped_mint.key,
                 lamports,
                 mint_space as u64,
-                &ctx.accounts.token_program.key(),
+                ctx.accounts.token_program.key,
             ),
             &[
                 ctx.accounts.payer.to_account_info(),
-                ctx.accounts.mint.to_account_info(),
+                wrapped_mint.to_account_info(),
                 ctx.accounts.system_program.to_account_info(),
             ],
+            &[wrapped_mint_seeds],
         )?;
+        // No freeze authority: venues that reject fee-on-transfer tokens tend to reject freezable ones too
+        let init_mint_ix =
+            token_instruction::initialize_mint2(ctx.accounts.token_program.key, wrapped_mint.key, &wrapper_key, None, decimals)?;
+        invoke(&init_mint_ix, &[wrapped_mint.to_account_info(), ctx.accounts.token_program.to_account_info()])?;
 
-        // Initialize non-transferable extension **before** initializing the mint
-        let init_nt_ix = token_instruction::initialize_non_transferable_mint(
-            &ctx.accounts.token_program.key(),
-            &ctx.accounts.mint.key(),
-        )?;
+        let wrapper = &mut ctx.accounts.wrapper;
+        wrapper.mint = mint_key;
+        wrapper.wrapped_mint = wrapped_mint.key();
+        wrapper.vault = ctx.accounts.vault.key();
+        wrapper.bump = ctx.bumps.wrapper;
 
-        invoke(
-            &init_nt_ix,
-            &[
-                ctx.accounts.mint.to_account_info(),
-                ctx.accounts.token_program.to_account_info(),
-            ],
+        emit!(WrapperCreated {
+            version: EVENT_VERSION,
+            wrapper: wrapper_key,
+            mint: mint_key,
+            wrapped_mint: wrapper.wrapped_mint,
+        });
+
+        Ok(())
+    }
+
+    /// Deposit `amount` of the taxed mint and receive the companion 1:1 against what reaches the
+    /// vault. The deposit pays the mint's transfer fee, so wrapping costs the same as a transfer.
+    pub fn wrap(ctx: Context<WrapTokens>, amount: u64) -> Result<()> {
+        trace_ix!(
+            "wrap",
+            wrapper = ctx.accounts.wrapper.key(),
+            owner = ctx.accounts.owner.key(),
+            amount = amount,
+        );
+        ensure_not_paused(&ctx.accounts.mint_pause)?;
+        let fee = transfer_tokens(
+            &ctx.accounts.token_program,
+            &ctx.accounts.taxed_token_account,
+            &ctx.accounts.mint,
+            &ctx.accounts.vault,
+            &ctx.accounts.owner,
+            &[],
+            amount,
         )?;
+        let minted = amount - fee;
+        if minted == 0 {
+            return Err(MyError::InvalidWrapAmount.into());
+        }
 
-        // Initialize the mint
-        let init_mint_ix = token_instruction::initialize_mint(
-            &ctx.accounts.token_program.key(),
-            &ctx.accounts.mint.key(),
-            &ctx.accounts.mint_authority.key(),
-            Some(&ctx.accounts.freeze_authority.key()),
+        let wrapper = &ctx.accounts.wrapper;
+        let seeds: &[&[u8]] = &[WRAPPER_SEED, wrapper.mint.as_ref(), &[wrapper.bump]];
+        let (decimals, _) = mint_decimals_and_fee(&ctx.accounts.wrapped_mint, minted)?;
+        let mint_ix = token_instruction::mint_to_checked(
+            ctx.accounts.token_program.key,
+            ctx.accounts.wrapped_mint.key,
+            ctx.accounts.wrapped_token_account.key,
+            &wrapper.key(),
+            &[],
+            minted,
             decimals,
         )?;
-
-        invoke(
-            &init_mint_ix,
+        invoke_signed(
+            &mint_ix,
             &[
-                ctx.accounts.mint.to_account_info(),
-                ctx.accounts.rent.to_account_info(),
+                ctx.accounts.wrapped_mint.to_account_info(),
+                ctx.accounts.wrapped_token_account.to_account_info(),
+                wrapper.to_account_info(),
                 ctx.accounts.token_program.to_account_info(),
             ],
+            &[seeds],
         )?;
 
+        emit!(Wrapped {
+            version: EVENT_VERSION,
+            wrapper: wrapper.key(),
+            owner: ctx.accounts.owner.key(),
+            deposited: amount,
+            minted,
+        });
+
         Ok(())
     }
 
-    /// Transfer using TransferCheckedWithFee CPI — useful for tests and clients to use
-    pub fn transfer_with_fee(
-        ctx: Context<TransferWithFee>,
-        amount: u64,
-        decimals: u8,
-        fee: u64,
-    ) -> Result<()> {
-        let ix = spl_token_2022::extension::transfer_fee::instruction::transfer_checked_with_fee(
-            &ctx.accounts.token_program.key(),
-            &ctx.accounts.source.key(),
-            &ctx.accounts.mint.key(),
-            &ctx.accounts.destination.key(),
-            &ctx.accounts.authority.key(),
+    /// Burn `amount` of the companion and take the same amount of the taxed mint out of the
+    /// vault. The payout pays the mint's transfer fee, like any transfer of it.
+    pub fn unwrap(ctx: Context<WrapTokens>, amount: u64) -> Result<()> {
+        trace_ix!(
+            "unwrap",
+            wrapper = ctx.accounts.wrapper.key(),
+            owner = ctx.accounts.owner.key(),
+            amount = amount,
+        );
+        ensure_not_paused(&ctx.accounts.mint_pause)?;
+        if amount == 0 {
+            return Err(MyError::InvalidWrapAmount.into());
+        }
+        let (decimals, _) = mint_decimals_and_fee(&ctx.accounts.wrapped_mint, amount)?;
+        let burn_ix = token_instruction::burn_checked(
+            ctx.accounts.token_program.key,
+            ctx.accounts.wrapped_token_account.key,
+            ctx.accounts.wrapped_mint.key,
+            ctx.accounts.owner.key,
             &[],
             amount,
             decimals,
-            fee,
         )?;
-
         invoke(
-            &ix,
+            &burn_ix,
             &[
+                ctx.accounts.wrapped_token_account.to_account_info(),
+                ctx.accounts.wrapped_mint.to_account_info(),
+                ctx.accounts.owner.to_account_info(),
                 ctx.accounts.token_program.to_account_info(),
-                ctx.accounts.source.to_account_info(),
-                ctx.accounts.mint.to_account_info(),
-                ctx.accounts.destination.to_account_info(),
-                ctx.accounts.authority.to_account_info(),
             ],
         )?;
 
+        let wrapper = &ctx.accounts.wrapper;
+        let seeds: &[&[u8]] = &[WRAPPER_SEED, wrapper.mint.as_ref(), &[wrapper.bump]];
+        let fee = transfer_tokens(
+            &ctx.accounts.token_program,
+            &ctx.accounts.vault,
+            &ctx.accounts.mint,
+            &ctx.accounts.taxed_token_account,
+            &wrapper.to_account_info(),
+            &[seeds],
+            amount,
+        )?;
+
+        emit!(Unwrapped {
+            version: EVENT_VERSION,
+            wrapper: wrapper.key(),
+            owner: ctx.accounts.owner.key(),
+            burned: amount,
+            received: amount - fee,
+        });
+
         Ok(())
     }
-}
 
-#[derive(Accounts)]
-pub struct CreateTaxedToken<'info> {
-    /// The payer who funds account creation and will be mint authority by default
-    #[account(mut)]
-    pub payer: Signer<'info>,
+    /// Start a push-style airdrop of `mint`. The creator funds `vault`, a token account owned
+    /// by the campaign PDA, and loads the recipient list with `add_airdrop_recipients`.
+    /// Payouts stop at `expires_at`, after which the rest can be swept.
+    pub fn init_airdrop(ctx: Context<InitAirdrop>, campaign_id: u64, expires_at: i64) -> Result<()> {
+        trace_ix!(
+            "init_airdrop",
+            mint = ctx.accounts.mint.key(),
+            creator = ctx.accounts.creator.key(),
+            campaign_id = campaign_id,
+            expires_at = expires_at,
+        );
+        if expires_at <= Clock::get()?.unix_timestamp {
+            return Err(MyError::InvalidAirdropExpiry.into());
+        }
+        let vault = token_account(&ctx.accounts.vault)?;
+        if vault.mint != ctx.accounts.mint.key() || vault.owner != ctx.accounts.campaign.key() {
+            return Err(MyError::InvalidTokenAccount.into());
+        }
 
-    /// The mint account to be created (passed as system account)
-    /// CHECK: this is a Keypair signer for the new mint account
-    #[account(mut)]
-    pub mint: Signer<'info>,
+        let campaign = &mut ctx.accounts.campaign;
+        campaign.creator = ctx.accounts.creator.key();
+        campaign.mint = ctx.accounts.mint.key();
+        campaign.vault = ctx.accounts.vault.key();
+        campaign.campaign_id = campaign_id;
+        campaign.recipients = Vec::new();
+        campaign.cursor = 0;
+        campaign.skipped = Vec::new();
+        campaign.expires_at = expires_at;
+        campaign.voucher_signer = None;
+        campaign.bump = ctx.bumps.campaign;
+        Ok(())
+    }
 
-    /// Authority that will be set as the mint authority
-    /// CHECK: this account must be a signer (the mint authority)
-    pub mint_authority: Signer<'info>,
+    /// Let an off-chain key hand out claims on the campaign's vault with `claim_voucher`,
+    /// alongside or instead of the recipient list, or stop it with `None`. Creator only.
+    pub fn set_voucher_signer(ctx: Context<SetVoucherSigner>, voucher_signer: Option<Pubkey>) -> Result<()> {
+        trace_ix!(
+            "set_voucher_signer",
+            campaign = ctx.accounts.campaign.key(),
+            creator = ctx.accounts.creator.key(),
+        );
+        ctx.accounts.campaign.voucher_signer = voucher_signer;
+        Ok(())
+    }
 
-    /// Authority that will be allowed to withdraw withheld fees
-    /// CHECK: this account must be a signer (fee withdraw authority)
-    pub fee_withdraw_authority: Signer<'info>,
+    /// Append recipients to an airdrop list. Entries already paid out are never revisited.
+    pub fn add_airdrop_recipients(
+        ctx: Context<AddAirdropRecipients>,
+        recipients: Vec<AirdropRecipient>,
+    ) -> Result<()> {
+        trace_ix!(
+            "add_airdrop_recipients",
+            campaign = ctx.accounts.campaign.key(),
+            creator = ctx.accounts.creator.key(),
+            count = recipients.len(),
+        );
+        let campaign = &mut ctx.accounts.campaign;
+        if campaign.recipients.len() + recipients.len() > AirdropCampaign::capacity(campaign.to_account_info().data_len()) {
+            return Err(MyError::AirdropListFull.into());
+        }
+        campaign.recipients.extend(recipients);
+        Ok(())
+    }
 
-    /// Optional freeze authority
-    /// CHECK: any pubkey
-    pub freeze_authority: UncheckedAccount<'info>,
+    /// Pay the next `remaining_accounts.len()` recipients (at most MAX_AIRDROP_BATCH) from the
+    /// campaign vault, starting at the cursor. `remaining_accounts` holds each recipient's token
+    /// account in list order. Recipients receive their configured amount less the transfer fee.
+    /// A recipient whose associated token account is passed frozen is skipped, so it can't hold
+    /// up the list, and recorded in the campaign's skipped entries for `claim_skipped_airdrop`.
+    /// A missing account fails the batch: open recipients' accounts before cranking past them.
+    pub fn airdrop_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AirdropBatch<'info>>,
+    ) -> Result<()> {
+        trace_ix!(
+            "airdrop_batch",
+            campaign = ctx.accounts.campaign.key(),
+            mint = ctx.accounts.mint.key(),
+            count = ctx.remaining_accounts.len(),
+        );
+        ensure_not_paused(&ctx.accounts.mint_pause)?;
+        let destinations = ctx.remaining_accounts;
+        let campaign = &ctx.accounts.campaign;
+        if Clock::get()?.unix_timestamp >= campaign.expires_at {
+            return Err(MyError::AirdropExpired.into());
+        }
+        let start = campaign.cursor as usize;
+        if destinations.is_empty()
+            || destinations.len() > MAX_AIRDROP_BATCH
+            || start + destinations.len() > campaign.recipients.len()
+        {
+            return Err(MyError::InvalidBatch.into());
+        }
 
-    /// The token-2022 program
-    /// CHECK: must be the SPL Token-2022 program id
-    pub token_program: UncheckedAccount<'info>,
+        let campaign_id_bytes = campaign.campaign_id.to_le_bytes();
+        let seeds: &[&[u8]] = &[
+            AIRDROP_SEED,
+            campaign.creator.as_ref(),
+            campaign.mint.as_ref(),
+            &campaign_id_bytes,
+            &[campaign.bump],
+        ];
+        let mut total = 0u64;
+        let mut skipped = Vec::new();
+        for (index, (recipient, destination)) in campaign.recipients[start..].iter().zip(destinations).enumerate() {
+            if airdrop_destination_frozen(destination, &campaign.mint, &recipient.wallet)? {
+                let index = (start + index) as u32;
+                emit!(AirdropRecipientSkipped {
+                    version: EVENT_VERSION,
+                    campaign: campaign.key(),
+                    index,
+                    wallet: recipient.wallet,
+                    amount: recipient.amount,
+                });
+                skipped.push(index);
+                continue;
+            }
+            transfer_tokens(
+                &ctx.accounts.token_program,
+                &ctx.accounts.vault,
+                &ctx.accounts.mint,
+                destination,
+                &campaign.to_account_info(),
+                &[seeds],
+                recipient.amount,
+            )?;
+            total = total.checked_add(recipient.amount).ok_or(MyError::MathOverflow)?;
+        }
 
-    pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
-}
+        let campaign = &mut ctx.accounts.campaign;
+        if campaign.skipped.len() + skipped.len() > MAX_AIRDROP_SKIPPED {
+            return Err(MyError::AirdropSkipListFull.into());
+        }
+        campaign.skipped.extend(skipped);
+        campaign.cursor += destinations.len() as u32;
 
-#[derive(Accounts)]
+        emit!(AirdropBatchSent {
+            version: EVENT_VERSION,
+            campaign: campaign.key(),
+            recipients: destinations.len() as u32,
+            amount: total,
+            cursor: campaign.cursor,
+        });
+
+        Ok(())
+    }
+
+    /// Pay list entry `index`, skipped by `airdrop_batch` over a frozen account, into the
+    /// recipient's token account `destination` once it can receive again. Anyone can submit the
+    /// claim; the tokens only ever go to the recipient.
+    pub fn claim_skipped_airdrop(ctx: Context<ClaimSkippedAirdrop>, index: u32) -> Result<()> {
+        trace_ix!(
+            "claim_skipped_airdrop",
+            campaign = ctx.accounts.campaign.key(),
+            index = index,
+        );
+        ensure_not_paused(&ctx.accounts.mint_pause)?;
+        let campaign = &ctx.accounts.campaign;
+        if Clock::get()?.unix_timestamp >= campaign.expires_at {
+            return Err(MyError::AirdropExpired.into());
+        }
+        let position = campaign
+            .skipped
+            .iter()
+            .position(|skipped| *skipped == index)
+            .ok_or(MyError::AirdropEntryNotSkipped)?;
+        let recipient = campaign.recipients[index as usize];
+        let destination = token_account(&ctx.accounts.destination)?;
+        if destination.mint != campaign.mint || destination.owner != recipient.wallet {
+            return Err(MyError::InvalidTokenAccount.into());
+        }
+
+        let campaign_id_bytes = campaign.campaign_id.to_le_bytes();
+        let seeds: &[&[u8]] = &[
+            AIRDROP_SEED,
+            campaign.creator.as_ref(),
+            campaign.mint.as_ref(),
+            &campaign_id_bytes,
+            &[campaign.bump],
+        ];
+        transfer_tokens(
+            &ctx.accounts.token_program,
+            &ctx.accounts.vault,
+            &ctx.accounts.mint,
+            &ctx.accounts.destination,
+            &campaign.to_account_info(),
+            &[seeds],
+            recipient.amount,
+        )?;
+
+        let campaign = &mut ctx.accounts.campaign;
+        campaign.skipped.swap_remove(position);
+
+        emit!(AirdropSkippedClaimed {
+            version: EVENT_VERSION,
+            campaign: campaign.key(),
+            index,
+            wallet: recipient.wallet,
+            amount: recipient.amount,
+        });
+
+        Ok(())
+    }
+
+    /// After the airdrop window closes, return whatever is left in the vault to the creator,
+    /// or burn it when `burn` is set, and close the vault and campaign.
+    pub fn sweep_unclaimed(ctx: Context<SweepUnclaimed>, burn: bool) -> Result<()> {
+        trace_ix!(
+            "sweep_unclaimed",
+            campaign = ctx.accounts.campaign.key(),
+            mint = ctx.accounts.mint.key(),
+            creator = ctx.accounts.creator.key(),
+            burn = burn,
+        );
+        let campaign = &ctx.accounts.campaign;
+        if Clock::get()?.unix_timestamp < campaign.expires_at {
+            return Err(MyError::AirdropNotExpired.into());
+        }
+
+        let remaining = token_account(&ctx.accounts.vault)?.amount;
+        let campaign_id_bytes = campaign.campaign_id.to_le_bytes();
+        let seeds: &[&[u8]] = &[
+            AIRDROP_SEED,
+            campaign.creator.as_ref(),
+            campaign.mint.as_ref(),
+            &campaign_id_bytes,
+            &[campaign.bump],
+        ];
+        if remaining > 0 {
+            if burn {
+                let (decimals, _) = mint_decimals_and_fee(&ctx.accounts.mint, remaining)?;
+                let burn_ix = token_instruction::burn_checked(
+                    ctx.accounts.token_program.key,
+                    ctx.accounts.vault.key,
+                    ctx.accounts.mint.key,
+                    &campaign.key(),
+                    &[],
+                    remaining,
+                    decimals,
+                )?;
+                invoke_signed(
+                    &burn_ix,
+                    &[
+                        ctx.accounts.vault.to_account_info(),
+                        ctx.accounts.mint.to_account_info(),
+                        campaign.to_account_info(),
+                        ctx.accounts.token_program.to_account_info(),
+                    ],
+                    &[seeds],
+                )?;
+            } else {
+                let destination = ctx
+                    .accounts
+                    .creator_token_account
+                    .as_ref()
+                    .ok_or(MyError::InvalidTokenAccount)?;
+                transfer_tokens(
+                    &ctx.accounts.token_program,
+                    &ctx.accounts.vault,
+                    &ctx.accounts.mint,
+                    destination,
+                    &campaign.to_account_info(),
+                    &[seeds],
+                    remaining,
+                )?;
+            }
+        }
+        close_vault(
+            &ctx.accounts.token_program,
+            &ctx.accounts.mint,
+            &ctx.accounts.vault,
+            &campaign.to_account_info(),
+            &ctx.accounts.creator,
+            &[seeds],
+        )?;
+
+        emit!(AirdropSwept {
+            version: EVENT_VERSION,
+            campaign: campaign.key(),
+            amount: remaining,
+            burned: burn,
+        });
+
+        Ok(())
+    }
+
+    /// Pay `amount` from the campaign's vault to `wallet` on a voucher from the campaign's
+    /// voucher signer: an ed25519 instruction right before this one, verifying its signature
+    /// over `voucher_claim_message(campaign, mint, wallet, amount, nonce)`. Each nonce pays out
+    /// once, tracked in the campaign's nonce bitmap; the first claim in a page pays its rent.
+    /// Anyone can submit the claim; the tokens only ever go to `wallet`.
+    pub fn claim_voucher(ctx: Context<ClaimVoucher>, wallet: Pubkey, amount: u64, nonce: u64) -> Result<()> {
+        trace_ix!(
+            "claim_voucher",
+            campaign = ctx.accounts.campaign.key(),
+            wallet = wallet,
+            amount = amount,
+            nonce = nonce,
+        );
+        ensure_not_paused(&ctx.accounts.mint_pause)?;
+        let campaign = &ctx.accounts.campaign;
+        let voucher_signer = campaign.voucher_signer.ok_or(MyError::InvalidVoucher)?;
+        voucher::verify(
+            &ctx.accounts.instructions,
+            &voucher_signer,
+            &voucher_claim_message(&campaign.key(), &campaign.mint, &wallet, amount, nonce),
+        )?;
+        if Clock::get()?.unix_timestamp >= campaign.expires_at {
+            return Err(MyError::AirdropExpired.into());
+        }
+        let destination = token_account(&ctx.accounts.destination)?;
+        if destination.mint != campaign.mint || destination.owner != wallet {
+            return Err(MyError::InvalidTokenAccount.into());
+        }
+
+        let campaign_id_bytes = campaign.campaign_id.to_le_bytes();
+        let seeds: &[&[u8]] = &[
+            AIRDROP_SEED,
+            campaign.creator.as_ref(),
+            campaign.mint.as_ref(),
+            &campaign_id_bytes,
+            &[campaign.bump],
+        ];
+        transfer_tokens(
+            &ctx.accounts.token_program,
+            &ctx.accounts.vault,
+            &ctx.accounts.mint,
+            &ctx.accounts.destination,
+            &campaign.to_account_info(),
+            &[seeds],
+            amount,
+        )?;
+
+        let nonces = &mut ctx.accounts.nonces;
+        nonces.campaign = campaign.key();
+        nonces.page = nonce / NONCES_PER_PAGE;
+        nonces.bump = ctx.bumps.nonces;
+        nonces.consume(nonce)?;
+
+        emit!(VoucherClaimed {
+            version: EVENT_VERSION,
+            campaign: campaign.key(),
+            wallet,
+            amount,
+            nonce,
+        });
+
+        Ok(())
+    }
+
+    /// Create the loyalty points program of a launch: a soulbound points mint whose mint and
+    /// freeze authority is the points program PDA. Only the launch creator can set it up.
+    pub fn init_points_program(ctx: Context<InitPointsProgram>, decimals: u8) -> Result<()> {
+        trace_ix!(
+            "init_points_program",
+            points_mint = ctx.accounts.points_mint.key(),
+            creator = ctx.accounts.creator.key(),
+            decimals = decimals,
+        );
+        let points_key = ctx.accounts.points_program.key();
+        let points = &mut ctx.accounts.points_program;
+        points.launch_mint = ctx.accounts.launch_config.mint;
+        points.admin = ctx.accounts.creator.key();
+        points.points_mint = ctx.accounts.points_mint.key();
+        points.total_awarded = 0;
+        points.total_redeemed = 0;
+        points.bump = ctx.bumps.points_program;
+
+        let mint_bump = ctx.bumps.points_mint;
+        let mint_seeds: &[&[u8]] = &[POINTS_MINT_SEED, points_key.as_ref(), &[mint_bump]];
+        create_token_mint(
+            &ctx.accounts.creator,
+            &ctx.accounts.points_mint,
+            &ctx.accounts.token_program,
+            &ctx.accounts.system_program,
+            &points_key,
+            &points_key,
+            None,
+            decimals,
+            TokenKind::Soulbound,
+            false,
+            &[mint_seeds],
+        )
+    }
+
+    /// Let `operator` award up to `daily_limit` points per UTC day. Calling it again for an
+    /// existing operator updates the limit.
+    pub fn set_points_operator(
+        ctx: Context<SetPointsOperator>,
+        operator: Pubkey,
+        daily_limit: u64,
+    ) -> Result<()> {
+        trace_ix!(
+            "set_points_operator",
+            points_program = ctx.accounts.points_program.key(),
+            admin = ctx.accounts.admin.key(),
+            operator = operator,
+            daily_limit = daily_limit,
+        );
+        let entry = &mut ctx.accounts.operator_entry;
+        entry.points_program = ctx.accounts.points_program.key();
+        entry.operator = operator;
+        entry.daily_limit = daily_limit;
+        entry.bump = ctx.bumps.operator_entry;
+        Ok(())
+    }
+
+    /// Mint `amount` soulbound points to a holder's points token account, within the operator's
+    /// daily issuance limit.
+    pub fn award_points(ctx: Context<AwardPoints>, amount: u64) -> Result<()> {
+        trace_ix!(
+            "award_points",
+            points_mint = ctx.accounts.points_mint.key(),
+            operator = ctx.accounts.operator.key(),
+            destination = ctx.accounts.destination.key(),
+            amount = amount,
+        );
+        let today = Clock::get()?.unix_timestamp / SECONDS_PER_DAY;
+        let entry = &mut ctx.accounts.operator_entry;
+        if entry.day != today {
+            entry.day = today;
+            entry.issued_today = 0;
+        }
+        entry.issued_today = entry.issued_today.checked_add(amount).ok_or(MyError::MathOverflow)?;
+        if entry.issued_today > entry.daily_limit {
+            return Err(MyError::DailyLimitExceeded.into());
+        }
+
+        let points = &ctx.accounts.points_program;
+        let seeds: &[&[u8]] = &[POINTS_SEED, points.launch_mint.as_ref(), &[points.bump]];
+        let (decimals, _) = mint_decimals_and_fee(&ctx.accounts.points_mint, amount)?;
+        let mint_ix = token_instruction::mint_to_checked(
+            ctx.accounts.token_program.key,
+            ctx.accounts.points_mint.key,
+            ctx.accounts.destination.key,
+            &points.key(),
+            &[],
+            amount,
+            decimals,
+        )?;
+        invoke_signed(
+            &mint_ix,
+            &[
+                ctx.accounts.points_mint.to_account_info(),
+                ctx.accounts.destination.to_account_info(),
+                points.to_account_info(),
+                ctx.accounts.token_program.to_account_info(),
+            ],
+            &[seeds],
+        )?;
+
+        let points = &mut ctx.accounts.points_program;
+        points.total_awarded = points.total_awarded.checked_add(amount).ok_or(MyError::MathOverflow)?;
+
+        emit!(PointsAwarded {
+            version: EVENT_VERSION,
+            points_program: points.key(),
+            operator: ctx.accounts.operator.key(),
+            destination: ctx.accounts.destination.key(),
+            amount,
+        });
+
+        Ok(())
+    }
+
+    /// Burn `amount` of the holder's points. Off-chain rewards key off the PointsRedeemed event.
+    pub fn redeem_points(ctx: Context<RedeemPoints>, amount: u64) -> Result<()> {
+        trace_ix!(
+            "redeem_points",
+            points_mint = ctx.accounts.points_mint.key(),
+            holder = ctx.accounts.holder.key(),
+            amount = amount,
+        );
+        let (decimals, _) = mint_decimals_and_fee(&ctx.accounts.points_mint, amount)?;
+        let burn_ix = token_instruction::burn_checked(
+            ctx.accounts.token_program.key,
+            ctx.accounts.source.key,
+            ctx.accounts.points_mint.key,
+            ctx.accounts.holder.key,
+            &[],
+            amount,
+            decimals,
+        )?;
+        invoke(
+            &burn_ix,
+            &[
+                ctx.accounts.source.to_account_info(),
+                ctx.accounts.points_mint.to_account_info(),
+                ctx.accounts.holder.to_account_info(),
+                ctx.accounts.token_program.to_account_info(),
+            ],
+        )?;
+
+        let points = &mut ctx.accounts.points_program;
+        points.total_redeemed = points.total_redeemed.checked_add(amount).ok_or(MyError::MathOverflow)?;
+
+        emit!(PointsRedeemed {
+            version: EVENT_VERSION,
+            points_program: points.key(),
+            holder: ctx.accounts.holder.key(),
+            amount,
+        });
+
+        Ok(())
+    }
+
+    /// Attach a quest to a launch. `attestor` signs off completions, each of which mints one
+    /// badge of the quest's soulbound badge mint.
+    pub fn create_quest(ctx: Context<CreateQuest>, quest_id: u64, attestor: Pubkey) -> Result<()> {
+        trace_ix!(
+            "create_quest",
+            quest = ctx.accounts.quest.key(),
+            creator = ctx.accounts.creator.key(),
+            quest_id = quest_id,
+            attestor = attestor,
+        );
+        let quest_key = ctx.accounts.quest.key();
+        let quest = &mut ctx.accounts.quest;
+        quest.launch_mint = ctx.accounts.launch_config.mint;
+        quest.quest_id = quest_id;
+        quest.admin = ctx.accounts.creator.key();
+        quest.attestor = attestor;
+        quest.badge_mint = ctx.accounts.badge_mint.key();
+        quest.completions = 0;
+        quest.bump = ctx.bumps.quest;
+
+        let mint_bump = ctx.bumps.badge_mint;
+        let mint_seeds: &[&[u8]] = &[QUEST_BADGE_SEED, quest_key.as_ref(), &[mint_bump]];
+        create_token_mint(
+            &ctx.accounts.creator,
+            &ctx.accounts.badge_mint,
+            &ctx.accounts.token_program,
+            &ctx.accounts.system_program,
+            &quest_key,
+            &quest_key,
+            None,
+            0,
+            TokenKind::Soulbound,
+            false,
+            &[mint_seeds],
+        )
+    }
+
+    /// Record that `wallet` finished a quest and mint its badge. Each wallet completes a quest
+    /// once; its per-launch progress counts the quests completed.
+    pub fn complete_quest(ctx: Context<CompleteQuest>, wallet: Pubkey) -> Result<()> {
+        trace_ix!(
+            "complete_quest",
+            quest = ctx.accounts.quest.key(),
+            attestor = ctx.accounts.attestor.key(),
+            wallet = wallet,
+        );
+        if token_account_owner(&ctx.accounts.badge_account)? != wallet {
+            return Err(MyError::InvalidTokenAccount.into());
+        }
+
+        let quest = &ctx.accounts.quest;
+        let quest_id_bytes = quest.quest_id.to_le_bytes();
+        let seeds: &[&[u8]] = &[QUEST_SEED, quest.launch_mint.as_ref(), &quest_id_bytes, &[quest.bump]];
+        let mint_ix = token_instruction::mint_to_checked(
+            ctx.accounts.token_program.key,
+            ctx.accounts.badge_mint.key,
+            ctx.accounts.badge_account.key,
+            &quest.key(),
+            &[],
+            1,
+            0,
+        )?;
+        invoke_signed(
+            &mint_ix,
+            &[
+                ctx.accounts.badge_mint.to_account_info(),
+                ctx.accounts.badge_account.to_account_info(),
+                quest.to_account_info(),
+                ctx.accounts.token_program.to_account_info(),
+            ],
+            &[seeds],
+        )?;
+
+        let now = Clock::get()?.unix_timestamp;
+        let completion = &mut ctx.accounts.completion;
+        completion.quest = quest.key();
+        completion.wallet = wallet;
+        completion.completed_at = now;
+        completion.bump = ctx.bumps.completion;
+
+        let progress = &mut ctx.accounts.progress;
+        progress.launch_mint = quest.launch_mint;
+        progress.wallet = wallet;
+        progress.quests_completed = progress.quests_completed.checked_add(1).ok_or(MyError::MathOverflow)?;
+        progress.last_completed_at = now;
+        progress.bump = ctx.bumps.progress;
+
+        let quest = &mut ctx.accounts.quest;
+        quest.completions = quest.completions.checked_add(1).ok_or(MyError::MathOverflow)?;
+
+        emit!(QuestCompleted {
+            version: EVENT_VERSION,
+            quest: quest.key(),
+            wallet,
+            attestor: ctx.accounts.attestor.key(),
+            quests_completed: ctx.accounts.progress.quests_completed,
+        });
+
+        Ok(())
+    }
+
+    /// Open a series of attendance events (a conference, a meetup series...). The organizer
+    /// signs the check-in vouchers of every event in it.
+    pub fn create_event_series(ctx: Context<CreateEventSeries>, series_id: u64, name: String) -> Result<()> {
+        trace_ix!(
+            "create_event_series",
+            series = ctx.accounts.series.key(),
+            organizer = ctx.accounts.organizer.key(),
+            series_id = series_id,
+        );
+        if name.len() > MAX_NAME_LEN {
+            return Err(MyError::NameTooLong.into());
+        }
+        let series = &mut ctx.accounts.series;
+        series.organizer = ctx.accounts.organizer.key();
+        series.series_id = series_id;
+        series.name = name;
+        series.events_created = 0;
+        series.bump = ctx.bumps.series;
+        Ok(())
+    }
+
+    /// Add an event to a series, with its own soulbound badge mint, check-in window and cap.
+    pub fn create_attendance_event(
+        ctx: Context<CreateAttendanceEvent>,
+        starts_at: i64,
+        ends_at: i64,
+        max_attendees: u32,
+    ) -> Result<()> {
+        trace_ix!(
+            "create_attendance_event",
+            series = ctx.accounts.series.key(),
+            organizer = ctx.accounts.organizer.key(),
+            starts_at = starts_at,
+            ends_at = ends_at,
+            max_attendees = max_attendees,
+        );
+        if ends_at <= starts_at || max_attendees == 0 {
+            return Err(MyError::InvalidAttendanceEvent.into());
+        }
+
+        let event_key = ctx.accounts.event.key();
+        let series = &mut ctx.accounts.series;
+        let event = &mut ctx.accounts.event;
+        event.series = series.key();
+        event.event_id = series.events_created;
+        event.badge_mint = ctx.accounts.badge_mint.key();
+        event.starts_at = starts_at;
+        event.ends_at = ends_at;
+        event.max_attendees = max_attendees;
+        event.attendees = 0;
+        event.bump = ctx.bumps.event;
+        series.events_created = series.events_created.checked_add(1).ok_or(MyError::MathOverflow)?;
+
+        let mint_seeds: &[&[u8]] = &[ATTENDANCE_BADGE_SEED, event_key.as_ref(), &[ctx.bumps.badge_mint]];
+        create_token_mint(
+            &ctx.accounts.organizer,
+            &ctx.accounts.badge_mint,
+            &ctx.accounts.token_program,
+            &ctx.accounts.system_program,
+            &event_key,
+            &event_key,
+            None,
+            0,
+            TokenKind::Soulbound,
+            false,
+            &[mint_seeds],
+        )
+    }
+
+    /// Mint the event's badge to the attendee, on a voucher from the organizer: an ed25519
+    /// instruction right before this one, verifying the organizer's signature over
+    /// `check_in_message(event, attendee)`. One badge per wallet, within the window and cap.
+    pub fn check_in(ctx: Context<CheckIn>) -> Result<()> {
+        trace_ix!(
+            "check_in",
+            event = ctx.accounts.event.key(),
+            attendee = ctx.accounts.attendee.key(),
+        );
+        let event_key = ctx.accounts.event.key();
+        let attendee = ctx.accounts.attendee.key();
+        voucher::verify(
+            &ctx.accounts.instructions,
+            &ctx.accounts.series.organizer,
+            &check_in_message(&event_key, &attendee),
+        )?;
+        if token_account_owner(&ctx.accounts.badge_account)? != attendee {
+            return Err(MyError::InvalidTokenAccount.into());
+        }
+
+        let now = Clock::get()?.unix_timestamp;
+        let event = &mut ctx.accounts.event;
+        if now < event.starts_at || now > event.ends_at {
+            return Err(MyError::CheckInClosed.into());
+        }
+        if event.attendees >= event.max_attendees {
+            return Err(MyError::EventFull.into());
+        }
+        event.attendees += 1;
+
+        let event_id_bytes = event.event_id.to_le_bytes();
+        let seeds: &[&[u8]] = &[ATTENDANCE_EVENT_SEED, event.series.as_ref(), &event_id_bytes, &[event.bump]];
+        let mint_ix = token_instruction::mint_to_checked(
+            ctx.accounts.token_program.key,
+            ctx.accounts.badge_mint.key,
+            ctx.accounts.badge_account.key,
+            &event_key,
+            &[],
+            1,
+            0,
+        )?;
+        invoke_signed(
+            &mint_ix,
+            &[
+                ctx.accounts.badge_mint.to_account_info(),
+                ctx.accounts.badge_account.to_account_info(),
+                event.to_account_info(),
+                ctx.accounts.token_program.to_account_info(),
+            ],
+            &[seeds],
+        )?;
+
+        let attendance = &mut ctx.accounts.attendance;
+        attendance.event = event_key;
+        attendance.wallet = attendee;
+        attendance.checked_in_at = now;
+        attendance.bump = ctx.bumps.attendance;
+
+        emit!(CheckedIn {
+            version: EVENT_VERSION,
+            event: event_key,
+            wallet: attendee,
+            attendees: event.attendees,
+        });
+
+        Ok(())
+    }
+
+    /// Publish the merkle root of `mint` holder balances at `slot`. Leaves are
+    /// `snapshot_leaf(wallet, balance)`; reflections, governance and airdrops verify against it.
+    pub fn post_snapshot(
+        ctx: Context<PostSnapshot>,
+        slot: u64,
+        merkle_root: [u8; 32],
+        total_balance: u64,
+        holders: u32,
+    ) -> Result<()> {
+        trace_ix!(
+            "post_snapshot",
+            mint = ctx.accounts.mint.key(),
+            snapshotter = ctx.accounts.snapshotter.key(),
+            slot = slot,
+            total_balance = total_balance,
+            holders = holders,
+        );
+        if slot > Clock::get()?.slot {
+            return Err(MyError::InvalidSnapshot.into());
+        }
+
+        let snapshot = &mut ctx.accounts.snapshot;
+        snapshot.mint = ctx.accounts.mint.key();
+        snapshot.slot = slot;
+        snapshot.merkle_root = merkle_root;
+        snapshot.total_balance = total_balance;
+        snapshot.holders = holders;
+        snapshot.posted_at = Clock::get()?.unix_timestamp;
+        snapshot.bump = ctx.bumps.snapshot;
+
+        emit!(SnapshotPosted {
+            version: EVENT_VERSION,
+            snapshot: snapshot.key(),
+            mint: snapshot.mint,
+            slot,
+            merkle_root,
+        });
+
+        Ok(())
+    }
+
+    /// Open a token-holder vote weighted by a posted snapshot. The proposer proves a non-zero
+    /// balance in that snapshot and sets the quorum, at least MIN_PROPOSAL_QUORUM_BPS of the
+    /// snapshot's total balance.
+    ///
+    /// Proposals are advisory: passing one changes nothing on-chain by itself. A fee change or
+    /// treasury spend it calls for is still made by the mint's admin, through the timelock.
+    pub fn create_proposal(
+        ctx: Context<CreateProposal>,
+        proposal_id: u64,
+        description_uri: String,
+        voting_seconds: i64,
+        quorum_bps: u16,
+        balance: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        trace_ix!(
+            "create_proposal",
+            snapshot = ctx.accounts.snapshot.key(),
+            proposer = ctx.accounts.proposer.key(),
+            proposal_id = proposal_id,
+            voting_seconds = voting_seconds,
+            quorum_bps = quorum_bps,
+            balance = balance,
+        );
+        if description_uri.len() > MAX_URI_LEN {
+            return Err(MyError::UriTooLong.into());
+        }
+        if voting_seconds <= 0 || !(MIN_PROPOSAL_QUORUM_BPS..=10_000).contains(&quorum_bps) {
+            return Err(MyError::InvalidProposal.into());
+        }
+        let proposer = ctx.accounts.proposer.key();
+        if balance == 0 || !ctx.accounts.snapshot.verify(&proposer, balance, &proof) {
+            return Err(MyError::InvalidSnapshotProof.into());
+        }
+
+        let now = Clock::get()?.unix_timestamp;
+        let proposal = &mut ctx.accounts.proposal;
+        proposal.snapshot = ctx.accounts.snapshot.key();
+        proposal.mint = ctx.accounts.snapshot.mint;
+        proposal.proposal_id = proposal_id;
+        proposal.proposer = proposer;
+        proposal.description_uri = description_uri;
+        proposal.created_at = now;
+        proposal.voting_ends_at = now.checked_add(voting_seconds).ok_or(MyError::MathOverflow)?;
+        proposal.quorum_weight = math::bps_share(ctx.accounts.snapshot.total_balance, quorum_bps);
+        proposal.yes_weight = 0;
+        proposal.no_weight = 0;
+        proposal.status = ProposalStatus::Voting;
+        proposal.bump = ctx.bumps.proposal;
+
+        emit!(ProposalCreated {
+            version: EVENT_VERSION,
+            proposal: proposal.key(),
+            snapshot: proposal.snapshot,
+            proposer,
+            voting_ends_at: proposal.voting_ends_at,
+        });
+
+        Ok(())
+    }
+
+    /// Vote with the balance `proof` shows in the proposal's snapshot plus the weight the voter's
+    /// vote lock on the mint had when the proposal opened. Only a lock made before the snapshot
+    /// slot counts: tokens locked later are still in the snapshot balance. A receipt PDA per
+    /// voter stops double voting.
+    pub fn cast_vote(
+        ctx: Context<CastVote>,
+        support: bool,
+        balance: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        trace_ix!(
+            "cast_vote",
+            proposal = ctx.accounts.proposal.key(),
+            voter = ctx.accounts.voter.key(),
+            support = support,
+            balance = balance,
+        );
+        let proposal = &mut ctx.accounts.proposal;
+        if proposal.status != ProposalStatus::Voting
+            || Clock::get()?.unix_timestamp >= proposal.voting_ends_at
+        {
+            return Err(MyError::VotingClosed.into());
+        }
+        let voter = ctx.accounts.voter.key();
+        if balance > 0 && !ctx.accounts.snapshot.verify(&voter, balance, &proof) {
+            return Err(MyError::InvalidSnapshotProof.into());
+        }
+        let locked_weight = match &ctx.accounts.vote_lock {
+            Some(lock) if lock.locked_slot < ctx.accounts.snapshot.slot => lock.voting_weight(proposal.created_at),
+            _ => 0,
+        };
+        let weight = balance.checked_add(locked_weight).ok_or(MyError::MathOverflow)?;
+        if weight == 0 {
+            return Err(MyError::InvalidSnapshotProof.into());
+        }
+
+        if support {
+            proposal.yes_weight = proposal.yes_weight.checked_add(weight).ok_or(MyError::MathOverflow)?;
+        } else {
+            proposal.no_weight = proposal.no_weight.checked_add(weight).ok_or(MyError::MathOverflow)?;
+        }
+
+        let receipt = &mut ctx.accounts.receipt;
+        receipt.proposal = proposal.key();
+        receipt.voter = voter;
+        receipt.support = support;
+        receipt.weight = weight;
+        receipt.bump = ctx.bumps.receipt;
+
+        emit!(VoteCast {
+            version: EVENT_VERSION,
+            proposal: proposal.key(),
+            voter,
+            support,
+            weight,
+        });
+
+        Ok(())
+    }
+
+    /// Close voting once the period is over and record the outcome: passed when the votes
+    /// cast reach the quorum and more of them are for than against. Callable by anyone.
+    pub fn finalize_proposal(ctx: Context<FinalizeProposal>) -> Result<()> {
+        trace_ix!(
+            "finalize_proposal",
+            proposal = ctx.accounts.proposal.key(),
+        );
+        let proposal = &mut ctx.accounts.proposal;
+        if proposal.status != ProposalStatus::Voting {
+            return Err(MyError::VotingClosed.into());
+        }
+        if Clock::get()?.unix_timestamp < proposal.voting_ends_at {
+            return Err(MyError::VotingOpen.into());
+        }
+
+        proposal.status = proposal.outcome();
+
+        emit!(ProposalFinalized {
+            version: EVENT_VERSION,
+            proposal: proposal.key(),
+            passed: proposal.status == ProposalStatus::Passed,
+            yes_weight: proposal.yes_weight,
+            no_weight: proposal.no_weight,
+        });
+
+        Ok(())
+    }
+
+    /// Lock `amount` tokens for `duration` seconds in return for a voting weight that decays
+    /// linearly to zero at unlock. Weight is full when locked for MAX_VOTE_LOCK_SECONDS.
+    pub fn lock_for_voting(ctx: Context<LockForVoting>, amount: u64, duration: i64) -> Result<()> {
+        trace_ix!(
+            "lock_for_voting",
+            mint = ctx.accounts.mint.key(),
+            owner = ctx.accounts.owner.key(),
+            amount = amount,
+            duration = duration,
+        );
+        ensure_not_paused(&ctx.accounts.mint_pause)?;
+        if amount == 0 || duration <= 0 || duration > MAX_VOTE_LOCK_SECONDS {
+            return Err(MyError::InvalidVoteLock.into());
+        }
+        let vault = token_account(&ctx.accounts.vault)?;
+        if vault.mint != ctx.accounts.mint.key() || vault.owner != ctx.accounts.vote_lock.key() {
+            return Err(MyError::InvalidTokenAccount.into());
+        }
+
+        let fee = transfer_tokens(
+            &ctx.accounts.token_program,
+            &ctx.accounts.owner_token_account,
+            &ctx.accounts.mint,
+            &ctx.accounts.vault,
+            &ctx.accounts.owner,
+            &[],
+            amount,
+        )?;
+
+        let clock = Clock::get()?;
+        let now = clock.unix_timestamp;
+        let lock = &mut ctx.accounts.vote_lock;
+        lock.owner = ctx.accounts.owner.key();
+        lock.mint = ctx.accounts.mint.key();
+        lock.vault = ctx.accounts.vault.key();
+        lock.amount = amount - fee;
+        lock.locked_at = now;
+        lock.locked_slot = clock.slot;
+        lock.unlock_at = now + duration;
+        lock.bump = ctx.bumps.vote_lock;
+
+        emit!(VoteLockCreated {
+            version: EVENT_VERSION,
+            vote_lock: lock.key(),
+            owner: lock.owner,
+            mint: lock.mint,
+            amount: lock.amount,
+            unlock_at: lock.unlock_at,
+        });
+
+        Ok(())
+    }
+
+    /// Return the locked tokens once the lock has expired, and close the lock and its vault.
+    pub fn unlock_voting_lock(ctx: Context<UnlockVotingLock>) -> Result<()> {
+        trace_ix!(
+            "unlock_voting_lock",
+            vote_lock = ctx.accounts.vote_lock.key(),
+            mint = ctx.accounts.mint.key(),
+            owner = ctx.accounts.owner.key(),
+        );
+        let lock = &ctx.accounts.vote_lock;
+        if Clock::get()?.unix_timestamp < lock.unlock_at {
+            return Err(MyError::VoteLockActive.into());
+        }
+
+        let seeds: &[&[u8]] = &[VOTE_LOCK_SEED, lock.mint.as_ref(), lock.owner.as_ref(), &[lock.bump]];
+        let balance = token_account(&ctx.accounts.vault)?.amount;
+        if balance > 0 {
+            transfer_tokens(
+                &ctx.accounts.token_program,
+                &ctx.accounts.vault,
+                &ctx.accounts.mint,
+                &ctx.accounts.owner_token_account,
+                &lock.to_account_info(),
+                &[seeds],
+                balance,
+            )?;
+        }
+        close_vault(
+            &ctx.accounts.token_program,
+            &ctx.accounts.mint,
+            &ctx.accounts.vault,
+            &lock.to_account_info(),
+            &ctx.accounts.owner,
+            &[seeds],
+        )?;
+
+        Ok(())
+    }
+
+    /// Put the caller's platform-token vote lock weight behind `mint` for the current epoch.
+    /// Each lock votes once per epoch; the epoch keeps its MAX_FEATURED_LAUNCHES heaviest mints.
+    pub fn vote_gauge(ctx: Context<VoteGauge>, mint: Pubkey) -> Result<()> {
+        trace_ix!(
+            "vote_gauge",
+            voter = ctx.accounts.voter.key(),
+            mint = mint,
+            gauge_epoch = ctx.accounts.gauge_epoch.key(),
+        );
+        let clock = Clock::get()?;
+        let weight = ctx.accounts.vote_lock.voting_weight(clock.unix_timestamp);
+        if weight == 0 {
+            return Err(MyError::VoteLockExpired.into());
+        }
+
+        let launch_gauge = &mut ctx.accounts.launch_gauge;
+        launch_gauge.epoch = clock.epoch;
+        launch_gauge.mint = mint;
+        launch_gauge.weight = launch_gauge.weight.checked_add(weight).ok_or(MyError::MathOverflow)?;
+        launch_gauge.bump = ctx.bumps.launch_gauge;
+        let launch_weight = launch_gauge.weight;
+
+        let gauge = &mut ctx.accounts.gauge_epoch;
+        gauge.epoch = clock.epoch;
+        gauge.total_weight = gauge.total_weight.checked_add(weight).ok_or(MyError::MathOverflow)?;
+        gauge.bump = ctx.bumps.gauge_epoch;
+        gauge.record(mint, launch_weight);
+
+        let receipt = &mut ctx.accounts.receipt;
+        receipt.epoch = clock.epoch;
+        receipt.voter = ctx.accounts.voter.key();
+        receipt.mint = mint;
+        receipt.weight = weight;
+        receipt.bump = ctx.bumps.receipt;
+
+        emit!(GaugeVoteCast {
+            version: EVENT_VERSION,
+            epoch: clock.epoch,
+            voter: receipt.voter,
+            mint,
+            weight,
+        });
+
+        Ok(())
+    }
+
+    /// Flag an existing launch as featured once an epoch it topped has ended. Callable by anyone.
+    pub fn mark_featured(ctx: Context<MarkFeatured>) -> Result<()> {
+        trace_ix!(
+            "mark_featured",
+            launch_config = ctx.accounts.launch_config.key(),
+            gauge_epoch = ctx.accounts.gauge_epoch.key(),
+        );
+        let gauge = &ctx.accounts.gauge_epoch;
+        let launch_config = &mut ctx.accounts.launch_config;
+        if gauge.epoch >= Clock::get()?.epoch || !gauge.is_featured(&launch_config.mint) {
+            return Err(MyError::NotFeatured.into());
+        }
+        launch_config.featured = true;
+        launch_config.featured_epoch = launch_config.featured_epoch.max(gauge.epoch);
+
+        emit!(LaunchFeatured {
+            version: EVENT_VERSION,
+            mint: launch_config.mint,
+            epoch: gauge.epoch,
+        });
+
+        Ok(())
+    }
+
+    /// Create the project treasury of a launch. Tokens sent to a token account owned by the
+    /// treasury PDA can only leave through `treasury_transfer`, within the spending cap.
+    pub fn init_project_treasury(ctx: Context<InitProjectTreasury>, limits: TreasuryLimits) -> Result<()> {
+        trace_ix!(
+            "init_project_treasury",
+            treasury = ctx.accounts.treasury.key(),
+            creator = ctx.accounts.creator.key(),
+        );
+        limits.validate()?;
+        let treasury = &mut ctx.accounts.treasury;
+        treasury.mint = ctx.accounts.launch_config.mint;
+        treasury.admin = ctx.accounts.creator.key();
+        treasury.spenders = limits.spenders;
+        treasury.spending = PeriodCap::new(limits.period_seconds, limits.period_cap, Clock::get()?.unix_timestamp);
+        treasury.disbursements = 0;
+        treasury.bump = ctx.bumps.treasury;
+        Ok(())
+    }
+
+    /// Replace the treasury's spenders and spending cap. The current period's spend carries over.
+    pub fn update_treasury_limits(ctx: Context<UpdateTreasuryLimits>, limits: TreasuryLimits) -> Result<()> {
+        trace_ix!(
+            "update_treasury_limits",
+            treasury = ctx.accounts.treasury.key(),
+            admin = ctx.accounts.admin.key(),
+        );
+        limits.validate()?;
+        let treasury = &mut ctx.accounts.treasury;
+        treasury.spenders = limits.spenders;
+        treasury.spending.set_limits(limits.period_seconds, limits.period_cap);
+        Ok(())
+    }
+
+    /// Pay `amount` of the project mint out of the treasury and log it in a Disbursement PDA.
+    /// Spenders share one cap per period; the amount counts against it before transfer fees.
+    pub fn treasury_transfer(ctx: Context<TreasuryTransfer>, amount: u64, memo: String) -> Result<()> {
+        trace_ix!(
+            "treasury_transfer",
+            treasury = ctx.accounts.treasury.key(),
+            mint = ctx.accounts.mint.key(),
+            spender = ctx.accounts.spender.key(),
+            destination = ctx.accounts.destination.key(),
+            amount = amount,
+        );
+        ensure_not_paused(&ctx.accounts.mint_pause)?;
+        if memo.len() > MAX_URI_LEN {
+            return Err(MyError::UriTooLong.into());
+        }
+        let spender = ctx.accounts.spender.key();
+        let now = Clock::get()?.unix_timestamp;
+        let treasury = &mut ctx.accounts.treasury;
+        if !treasury.spenders.contains(&spender) {
+            return Err(MyError::Unauthorized.into());
+        }
+        treasury.spending.charge(amount, now)?;
+
+        let fee = {
+            let treasury = &ctx.accounts.treasury;
+            let seeds: &[&[u8]] = &[PROJECT_TREASURY_SEED, treasury.mint.as_ref(), &[treasury.bump]];
+            transfer_tokens(
+                &ctx.accounts.token_program,
+                &ctx.accounts.vault,
+                &ctx.accounts.mint,
+                &ctx.accounts.destination,
+                &treasury.to_account_info(),
+                &[seeds],
+                amount,
+            )?
+        };
+
+        let treasury = &mut ctx.accounts.treasury;
+        let disbursement = &mut ctx.accounts.disbursement;
+        disbursement.treasury = treasury.key();
+        disbursement.index = treasury.disbursements;
+        disbursement.spender = spender;
+        disbursement.destination = ctx.accounts.destination.key();
+        disbursement.amount = amount;
+        disbursement.fee = fee;
+        disbursement.memo = memo;
+        disbursement.paid_at = now;
+        disbursement.bump = ctx.bumps.disbursement;
+        treasury.disbursements += 1;
+
+        emit!(TreasuryDisbursed {
+            version: EVENT_VERSION,
+            treasury: treasury.key(),
+            index: disbursement.index,
+            spender,
+            destination: disbursement.destination,
+            amount,
+            fee,
+        });
+
+        Ok(())
+    }
+
+    /// Schedule `amount` of the project mint to be paid from the treasury to `destination`
+    /// every `interval_seconds`, starting at `first_due`, which can't be in the past. Only the
+    /// treasury admin can schedule.
+    pub fn create_recurring_payment(
+        ctx: Context<CreateRecurringPayment>,
+        payment_id: u64,
+        destination: Pubkey,
+        amount: u64,
+        interval_seconds: i64,
+        first_due: i64,
+    ) -> Result<()> {
+        trace_ix!(
+            "create_recurring_payment",
+            treasury = ctx.accounts.treasury.key(),
+            admin = ctx.accounts.admin.key(),
+            payment_id = payment_id,
+            destination = destination,
+            amount = amount,
+            interval_seconds = interval_seconds,
+        );
+        if amount == 0 || interval_seconds <= 0 || first_due < Clock::get()?.unix_timestamp {
+            return Err(MyError::InvalidRecurringPayment.into());
+        }
+        let payment = &mut ctx.accounts.payment;
+        payment.treasury = ctx.accounts.treasury.key();
+        payment.payment_id = payment_id;
+        payment.destination = destination;
+        payment.amount = amount;
+        payment.interval_seconds = interval_seconds;
+        payment.next_due = first_due;
+        payment.payments_made = 0;
+        payment.bump = ctx.bumps.payment;
+        Ok(())
+    }
+
+    /// Make one due payment. Permissionless so a crank can run it; the payment still counts
+    /// against the treasury's spending cap. Intervals missed while nobody cranked are skipped,
+    /// not paid in arrears: the next payment falls due at the first interval after now.
+    pub fn execute_recurring_payment(ctx: Context<ExecuteRecurringPayment>) -> Result<()> {
+        trace_ix!(
+            "execute_recurring_payment",
+            payment = ctx.accounts.payment.key(),
+            mint = ctx.accounts.mint.key(),
+            destination = ctx.accounts.destination.key(),
+        );
+        ensure_not_paused(&ctx.accounts.mint_pause)?;
+        let now = Clock::get()?.unix_timestamp;
+        let payment = &mut ctx.accounts.payment;
+        if now < payment.next_due {
+            return Err(MyError::PaymentNotDue.into());
+        }
+        let amount = payment.amount;
+        ctx.accounts.treasury.spending.charge(amount, now)?;
+
+        let treasury = &ctx.accounts.treasury;
+        let seeds: &[&[u8]] = &[PROJECT_TREASURY_SEED, treasury.mint.as_ref(), &[treasury.bump]];
+        let fee = transfer_tokens(
+            &ctx.accounts.token_program,
+            &ctx.accounts.vault,
+            &ctx.accounts.mint,
+            &ctx.accounts.destination,
+            &treasury.to_account_info(),
+            &[seeds],
+            amount,
+        )?;
+
+        let intervals = (now - payment.next_due) / payment.interval_seconds + 1;
+        payment.next_due = intervals
+            .checked_mul(payment.interval_seconds)
+            .and_then(|elapsed| payment.next_due.checked_add(elapsed))
+            .ok_or(MyError::MathOverflow)?;
+        payment.payments_made = payment.payments_made.checked_add(1).ok_or(MyError::MathOverflow)?;
+
+        emit!(RecurringPaymentExecuted {
+            version: EVENT_VERSION,
+            payment: payment.key(),
+            destination: payment.destination,
+            amount,
+            fee,
+            next_due: payment.next_due,
+        });
+
+        Ok(())
+    }
+
+    /// Stop a recurring payment and close its account.
+    pub fn cancel_recurring_payment(_ctx: Context<CancelRecurringPayment>) -> Result<()> {
+        trace_ix!(
+            "cancel_recurring_payment",
+            payment = _ctx.accounts.payment.key(),
+            admin = _ctx.accounts.admin.key(),
+        );
+        Ok(())
+    }
+
+    /// Register a launched mint with a Wormhole NTT manager so it can be bridged. In burning
+    /// mode the mint authority moves to the manager's `token_authority` PDA, which is final.
+    pub fn configure_ntt(ctx: Context<ConfigureNtt>, ntt_manager: Pubkey, mode: NttMode) -> Result<()> {
+        trace_ix!(
+            "configure_ntt",
+            mint = ctx.accounts.mint.key(),
+            creator = ctx.accounts.creator.key(),
+            ntt_manager = ntt_manager,
+        );
+        let launch_config = &mut ctx.accounts.launch_config;
+        if launch_config.ntt_manager.is_some() {
+            return Err(MyError::NttAlreadyConfigured.into());
+        }
+
+        if mode == NttMode::Burning {
+            let (token_authority, _) = Pubkey::find_program_address(&[NTT_TOKEN_AUTHORITY_SEED], &ntt_manager);
+            let set_authority_ix = token_instruction::set_authority(
+                ctx.accounts.token_program.key,
+                ctx.accounts.mint.key,
+                Some(&token_authority),
+                token_instruction::AuthorityType::MintTokens,
+                ctx.accounts.mint_authority.key,
+                &[],
+            )?;
+            invoke(
+                &set_authority_ix,
+                &[
+                    ctx.accounts.mint.to_account_info(),
+                    ctx.accounts.mint_authority.to_account_info(),
+                    ctx.accounts.token_program.to_account_info(),
+                ],
+            )?;
+        }
+
+        launch_config.ntt_manager = Some(ntt_manager);
+        launch_config.ntt_mode = Some(mode);
+
+        emit!(NttConfigured {
+            version: EVENT_VERSION,
+            mint: launch_config.mint,
+            ntt_manager,
+            mode,
+        });
+
+        Ok(())
+    }
+
+    /// Open the current epoch's leaderboard and freeze the previous one, if passed. Permissionless.
+    pub fn rollover_epoch(ctx: Context<RolloverEpoch>) -> Result<()> {
+        trace_ix!(
+            "rollover_epoch",
+            leaderboard = ctx.accounts.leaderboard.key(),
+        );
+        let epoch = Clock::get()?.epoch;
+        let leaderboard = &mut ctx.accounts.leaderboard;
+        leaderboard.epoch = epoch;
+        leaderboard.entries = Vec::new();
+        leaderboard.finalized = false;
+        leaderboard.bump = ctx.bumps.leaderboard;
+
+        if let Some(previous) = ctx.accounts.previous_leaderboard.as_mut() {
+            if previous.epoch >= epoch {
+                return Err(MyError::InvalidLeaderboardEpoch.into());
+            }
+            previous.finalized = true;
+        }
+
+        emit!(EpochRolledOver { version: EVENT_VERSION, epoch });
+
+        Ok(())
+    }
+
+    /// Push a mint's volume and fees for the current epoch into the leaderboard, which keeps
+    /// the MAX_LEADERBOARD_ENTRIES highest-volume mints. Permissionless crank. The figures come
+    /// from the mint's MintStats, so they rank self-reported volume only: a mint whose traders
+    /// transfer through Token-2022 directly places low however busy it is.
+    pub fn update_leaderboard(ctx: Context<UpdateLeaderboard>) -> Result<()> {
+        trace_ix!(
+            "update_leaderboard",
+            leaderboard = ctx.accounts.leaderboard.key(),
+            mint_stats = ctx.accounts.mint_stats.key(),
+        );
+        let stats = &ctx.accounts.mint_stats;
+        let leaderboard = &mut ctx.accounts.leaderboard;
+        if leaderboard.finalized || stats.epoch != leaderboard.epoch {
+            return Err(MyError::InvalidLeaderboardEpoch.into());
+        }
+        leaderboard.record(LeaderboardEntry {
+            mint: stats.mint,
+            volume: stats.epoch_volume,
+            fees: stats.epoch_fees,
+        });
+        Ok(())
+    }
+
+    /// Pause the program-mediated actions of a mint for up to MAX_MINT_PAUSE_SECONDS: streams
+    /// (create, withdraw, cancel), OTC offers and fills, gifts, wrapping, airdrops and voucher
+    /// claims, vote locks, allocation releases, revocations and beneficiary changes, treasury
+    /// transfers and recurring payments, emissions, faucet claims, staking and fee rebates.
+    /// Refunds of an escrow to whoever funded it (cancelling an OTC offer or gift, closing a
+    /// stream or airdrop, sweeping unclaimed tokens, unlocking an expired vote lock) and plain
+    /// token transfers are unaffected. Callable by the launch creator or the platform admin;
+    /// the pause lapses on its own, and the next one can't start until as long after it ended
+    /// as it lasted.
+    pub fn pause_mint(ctx: Context<PauseMint>, duration_seconds: i64) -> Result<()> {
+        trace_ix!(
+            "pause_mint",
+            launch_config = ctx.accounts.launch_config.key(),
+            authority = ctx.accounts.authority.key(),
+            duration_seconds = duration_seconds,
+        );
+        let authority = ctx.accounts.authority.key();
+        if authority != ctx.accounts.launch_config.creator && authority != ctx.accounts.platform_config.admin {
+            return Err(MyError::Unauthorized.into());
+        }
+
+        let pause = &mut ctx.accounts.mint_pause;
+        pause.start(Clock::get()?.unix_timestamp, duration_seconds)?;
+        pause.mint = ctx.accounts.launch_config.mint;
+        pause.paused_by = authority;
+        pause.bump = ctx.bumps.mint_pause;
+
+        emit!(MintPaused {
+            version: EVENT_VERSION,
+            mint: pause.mint,
+            paused_by: authority,
+            paused_until: pause.paused_until,
+        });
+
+        Ok(())
+    }
+
+    /// Lift a mint pause early.
+    pub fn unpause_mint(ctx: Context<UnpauseMint>) -> Result<()> {
+        trace_ix!(
+            "unpause_mint",
+            launch_config = ctx.accounts.launch_config.key(),
+            authority = ctx.accounts.authority.key(),
+        );
+        let authority = ctx.accounts.authority.key();
+        if authority != ctx.accounts.launch_config.creator && authority != ctx.accounts.platform_config.admin {
+            return Err(MyError::Unauthorized.into());
+        }
+        ctx.accounts.mint_pause.paused_until = 0;
+
+        emit!(MintUnpaused {
+            version: EVENT_VERSION,
+            mint: ctx.accounts.mint_pause.mint,
+            unpaused_by: authority,
+        });
+
+        Ok(())
+    }
+
+    /// Announce when a mint opens. Program-mediated actions on the mint stay closed until
+    /// `opens_at`, which an unpause does not lift, and the launch is listed on the calendar page
+    /// of its UTC day. A creator can hold at most `MAX_CALENDAR_LAUNCHES_PER_CREATOR` of a page's
+    /// slots, so one creator can't fill a day. Creator only, once per mint.
+    pub fn schedule_launch(ctx: Context<ScheduleLaunch>, opens_at: i64) -> Result<()> {
+        trace_ix!(
+            "schedule_launch",
+            mint = ctx.accounts.launch_config.mint,
+            creator = ctx.accounts.creator.key(),
+            opens_at = opens_at,
+        );
+        let now = Clock::get()?.unix_timestamp;
+        if opens_at <= now || opens_at - now > MAX_LAUNCH_SCHEDULE_SECONDS {
+            return Err(MyError::InvalidLaunchSchedule.into());
+        }
+        let pause = &mut ctx.accounts.mint_pause;
+        if pause.opens_at != 0 {
+            return Err(MyError::LaunchAlreadyScheduled.into());
+        }
+        let mint = ctx.accounts.launch_config.mint;
+        if pause.mint == Pubkey::default() {
+            pause.mint = mint;
+            pause.paused_by = ctx.accounts.creator.key();
+            pause.bump = ctx.bumps.mint_pause;
+        }
+        pause.opens_at = opens_at;
+
+        let day = opens_at / SECONDS_PER_DAY;
+        let calendar = &mut ctx.accounts.calendar;
+        calendar.day = day;
+        calendar.bump = ctx.bumps.calendar;
+        calendar.schedule(ScheduledLaunch {
+            mint,
+            creator: ctx.accounts.creator.key(),
+            opens_at,
+        })?;
+
+        emit!(LaunchScheduled {
+            version: EVENT_VERSION,
+            mint,
+            creator: ctx.accounts.creator.key(),
+            opens_at,
+            day,
+        });
+
+        Ok(())
+    }
+
+    /// Register an AMM pool's token vault of a mint. Transfers out of a registered vault count
+    /// as buys and transfers into one as sells, but only those made through `transfer_with_fee`
+    /// with the stats passed. An AMM's own swaps move tokens through Token-2022 directly, so the
+    /// split is partial and mostly misses swap flow. Creator or platform verifier only.
+    pub fn register_pool(ctx: Context<UpdatePoolRegistry>, pool_vault: Pubkey) -> Result<()> {
+        trace_ix!(
+            "register_pool",
+            pool_registry = ctx.accounts.pool_registry.key(),
+            authority = ctx.accounts.authority.key(),
+            pool_vault = pool_vault,
+        );
+        ctx.accounts.check_authority()?;
+        let registry = &mut ctx.accounts.pool_registry;
+        if registry.pools.contains(&pool_vault) {
+            return Err(MyError::PoolAlreadyRegistered.into());
+        }
+        if registry.pools.len() >= PoolRegistry::capacity(registry.to_account_info().data_len()) {
+            return Err(MyError::PoolRegistryFull.into());
+        }
+        registry.mint = ctx.accounts.launch_config.mint;
+        registry.bump = ctx.bumps.pool_registry;
+        registry.pools.push(pool_vault);
+
+        emit!(PoolRegistered {
+            version: EVENT_VERSION,
+            mint: registry.mint,
+            pool_vault,
+        });
+
+        Ok(())
+    }
+
+    /// Remove a pool vault from a mint's registry.
+    pub fn deregister_pool(ctx: Context<UpdatePoolRegistry>, pool_vault: Pubkey) -> Result<()> {
+        trace_ix!(
+            "deregister_pool",
+            pool_registry = ctx.accounts.pool_registry.key(),
+            authority = ctx.accounts.authority.key(),
+            pool_vault = pool_vault,
+        );
+        ctx.accounts.check_authority()?;
+        let registry = &mut ctx.accounts.pool_registry;
+        let index = registry
+            .pools
+            .iter()
+            .position(|pool| *pool == pool_vault)
+            .ok_or(MyError::PoolNotRegistered)?;
+        registry.pools.swap_remove(index);
+
+        emit!(PoolDeregistered {
+            version: EVENT_VERSION,
+            mint: registry.mint,
+            pool_vault,
+        });
+
+        Ok(())
+    }
+
+    /// Put a mint's supply on a program-enforced emission schedule. The mint authority moves to
+    /// the schedule PDA, so from then on tokens only appear through `mint_emission`.
+    pub fn init_emission_schedule(
+        ctx: Context<InitEmissionSchedule>,
+        params: EmissionParams,
+    ) -> Result<()> {
+        trace_ix!(
+            "init_emission_schedule",
+            mint = ctx.accounts.mint.key(),
+            creator = ctx.accounts.creator.key(),
+            start_time = params.start_time,
+            period_seconds = params.period_seconds,
+            initial_per_period = params.initial_per_period,
+        );
+        params.validate()?;
+        if token_account(&ctx.accounts.vault)?.mint != ctx.accounts.mint.key() {
+            return Err(MyError::InvalidTokenAccount.into());
+        }
+
+        let schedule_key = ctx.accounts.schedule.key();
+        let set_authority_ix = token_instruction::set_authority(
+            ctx.accounts.token_program.key,
+            ctx.accounts.mint.key,
+            Some(&schedule_key),
+            token_instruction::AuthorityType::MintTokens,
+            ctx.accounts.mint_authority.key,
+            &[],
+        )?;
+        invoke(
+            &set_authority_ix,
+            &[
+                ctx.accounts.mint.to_account_info(),
+                ctx.accounts.mint_authority.to_account_info(),
+                ctx.accounts.token_program.to_account_info(),
+            ],
+        )?;
+
+        let schedule = &mut ctx.accounts.schedule;
+        schedule.mint = ctx.accounts.mint.key();
+        schedule.vault = ctx.accounts.vault.key();
+        schedule.start_time = params.start_time;
+        schedule.period_seconds = params.period_seconds;
+        schedule.initial_per_period = params.initial_per_period;
+        schedule.curve = params.curve;
+        schedule.minted = 0;
+        schedule.bump = ctx.bumps.schedule;
+
+        Ok(())
+    }
+
+    /// Mint everything the schedule has released so far into its vault. Permissionless crank.
+    pub fn mint_emission(ctx: Context<MintEmission>) -> Result<()> {
+        trace_ix!(
+            "mint_emission",
+            schedule = ctx.accounts.schedule.key(),
+            mint = ctx.accounts.mint.key(),
+        );
+        ensure_not_paused(&ctx.accounts.mint_pause)?;
+        let schedule = &ctx.accounts.schedule;
+        let released = schedule.released(Clock::get()?.unix_timestamp)?;
+        let amount = released.saturating_sub(schedule.minted);
+        if amount == 0 {
+            return Err(MyError::NothingToClaim.into());
+        }
+
+        let seeds: &[&[u8]] = &[EMISSION_SEED, schedule.mint.as_ref(), &[schedule.bump]];
+        let (decimals, _) = mint_decimals_and_fee(&ctx.accounts.mint, amount)?;
+        let mint_ix = token_instruction::mint_to_checked(
+            ctx.accounts.token_program.key,
+            ctx.accounts.mint.key,
+            ctx.accounts.vault.key,
+            &schedule.key(),
+            &[],
+            amount,
+            decimals,
+        )?;
+        invoke_signed(
+            &mint_ix,
+            &[
+                ctx.accounts.mint.to_account_info(),
+                ctx.accounts.vault.to_account_info(),
+                schedule.to_account_info(),
+                ctx.accounts.token_program.to_account_info(),
+            ],
+            &[seeds],
+        )?;
+
+        let schedule = &mut ctx.accounts.schedule;
+        schedule.minted = released;
+
+        emit!(EmissionMinted {
+            version: EVENT_VERSION,
+            mint: schedule.mint,
+            amount,
+            total_minted: released,
+        });
+
+        Ok(())
+    }
+
+    /// Mint a fresh mint's whole supply according to a declared allocation plan, then revoke the
+    /// mint authority so the plan can't be exceeded. Remaining accounts are the token accounts
+    /// minted into, one per bucket: plan-owned lockers for team and liquidity buckets, the
+    /// beneficiary itself for sale and treasury buckets.
+    pub fn init_allocation_plan<'info>(
+        ctx: Context<'_, '_, 'info, 'info, InitAllocationPlan<'info>>,
+        params: AllocationParams,
+    ) -> Result<()> {
+        trace_ix!(
+            "init_allocation_plan",
+            mint = ctx.accounts.mint.key(),
+            creator = ctx.accounts.creator.key(),
+            total_supply = params.total_supply,
+            buckets = params.buckets.len(),
+        );
+        params.validate()?;
+        let vaults = ctx.remaining_accounts;
+        params.check_vaults(&vaults.iter().map(|vault| vault.key()).collect::<Vec<_>>())?;
+        {
+            let mint_data = ctx.accounts.mint.try_borrow_data()?;
+            if StateWithExtensions::<Token2022Mint>::unpack(&mint_data)?.base.supply != 0 {
+                return Err(MyError::InvalidAllocationPlan.into());
+            }
+        }
+
+        let mint_key = ctx.accounts.mint.key();
+        let plan_key = ctx.accounts.plan.key();
+        let treasury_key = Pubkey::find_program_address(&[PROJECT_TREASURY_SEED, mint_key.as_ref()], &crate::ID).0;
+        let mut buckets = Vec::with_capacity(params.buckets.len());
+        let bps: Vec<u16> = params.buckets.iter().map(|bucket| bucket.bps).collect();
+        let amounts = math::allocate_by_bps(params.total_supply, &bps).ok_or(MyError::InvalidAllocationPlan)?;
+        for ((bucket, vault), amount) in params.buckets.iter().zip(vaults).zip(amounts) {
+            let vault_account = token_account(vault)?;
+            let expected_owner = match bucket.kind {
+                AllocationKind::Team | AllocationKind::Liquidity => Some(plan_key),
+                AllocationKind::Treasury => Some(treasury_key),
+                AllocationKind::Sale => None,
+            };
+            let locked = bucket.kind.is_locked();
+            if vault_account.mint != mint_key
+                || expected_owner.is_some_and(|owner| vault_account.owner != owner)
+                || (!locked && vault.key() != bucket.beneficiary)
+            {
+                return Err(MyError::InvalidTokenAccount.into());
+            }
+            buckets.push(AllocationBucket {
+                kind: bucket.kind,
+                bps: bucket.bps,
+                amount,
+                vault: vault.key(),
+                beneficiary: bucket.beneficiary,
+                vesting_start: bucket.vesting_start,
+                vesting_end: bucket.vesting_end,
+                released: if locked { 0 } else { amount },
+                revocable: bucket.revocable,
+                pending_beneficiary: None,
+            });
+        }
+
+        let set_authority = |new_authority: Option<&Pubkey>, current: &Pubkey| {
+            token_instruction::set_authority(
+                ctx.accounts.token_program.key,
+                &mint_key,
+                new_authority,
+                token_instruction::AuthorityType::MintTokens,
+                current,
+                &[],
+            )
+        };
+        invoke(
+            &set_authority(Some(&plan_key), ctx.accounts.mint_authority.key)?,
+            &[
+                ctx.accounts.mint.to_account_info(),
+                ctx.accounts.mint_authority.to_account_info(),
+                ctx.accounts.token_program.to_account_info(),
+            ],
+        )?;
+
+        let seeds: &[&[u8]] = &[ALLOCATION_PLAN_SEED, mint_key.as_ref(), &[ctx.bumps.plan]];
+        let (decimals, _) = mint_decimals_and_fee(&ctx.accounts.mint, 0)?;
+        for (bucket, vault) in buckets.iter().zip(vaults) {
+            if bucket.amount == 0 {
+                continue;
+            }
+            let mint_ix = token_instruction::mint_to_checked(
+                ctx.accounts.token_program.key,
+                &mint_key,
+                vault.key,
+                &plan_key,
+                &[],
+                bucket.amount,
+                decimals,
+            )?;
+            invoke_signed(
+                &mint_ix,
+                &[
+                    ctx.accounts.mint.to_account_info(),
+                    vault.clone(),
+                    ctx.accounts.plan.to_account_info(),
+                    ctx.accounts.token_program.to_account_info(),
+                ],
+                &[seeds],
+            )?;
+        }
+        invoke_signed(
+            &set_authority(None, &plan_key)?,
+            &[
+                ctx.accounts.mint.to_account_info(),
+                ctx.accounts.plan.to_account_info(),
+                ctx.accounts.token_program.to_account_info(),
+            ],
+            &[seeds],
+        )?;
+
+        let plan = &mut ctx.accounts.plan;
+        plan.mint = mint_key;
+        plan.creator = ctx.accounts.creator.key();
+        plan.total_supply = params.total_supply;
+        plan.created_at = Clock::get()?.unix_timestamp;
+        plan.buckets = buckets;
+        plan.bump = ctx.bumps.plan;
+
+        emit!(AllocationPlanCreated {
+            version: EVENT_VERSION,
+            mint: mint_key,
+            creator: plan.creator,
+            total_supply: plan.total_supply,
+            buckets: plan.buckets.len() as u8,
+        });
+
+        Ok(())
+    }
+
+    /// Send whatever a locked allocation bucket has unlocked so far to its beneficiary. Anything
+    /// else its vault holds stays there until `close_allocation_plan`. Permissionless crank.
+    pub fn release_allocation(ctx: Context<ReleaseAllocation>, index: u8) -> Result<()> {
+        trace_ix!(
+            "release_allocation",
+            plan = ctx.accounts.plan.key(),
+            index = index,
+        );
+        ensure_not_paused(&ctx.accounts.mint_pause)?;
+        let plan = &ctx.accounts.plan;
+        let bucket = plan.buckets.get(index as usize).ok_or(MyError::InvalidAllocationPlan)?;
+        if bucket.vault != ctx.accounts.vault.key() || bucket.beneficiary != ctx.accounts.beneficiary.key() {
+            return Err(MyError::InvalidTokenAccount.into());
+        }
+        let unlocked = bucket.unlocked(Clock::get()?.unix_timestamp);
+        let payout = unlocked
+            .saturating_sub(bucket.released)
+            .min(token_account(&ctx.accounts.vault)?.amount);
+        if payout == 0 {
+            return Err(MyError::NothingToClaim.into());
+        }
+
+        let seeds: &[&[u8]] = &[ALLOCATION_PLAN_SEED, plan.mint.as_ref(), &[plan.bump]];
+        let fee = transfer_tokens(
+            &ctx.accounts.token_program,
+            &ctx.accounts.vault,
+            &ctx.accounts.mint,
+            &ctx.accounts.beneficiary,
+            &plan.to_account_info(),
+            &[seeds],
+            payout,
+        )?;
+
+        let plan = &mut ctx.accounts.plan;
+        plan.buckets[index as usize].released += payout;
+        invariant!(
+            "allocation vault covers the bucket's locked remainder",
+            vault_covers(&ctx.accounts.vault, plan.buckets[index as usize].amount - plan.buckets[index as usize].released)
+        );
+
+        emit!(AllocationReleased {
+            version: EVENT_VERSION,
+            mint: plan.mint,
+            index,
+            amount: payout,
+            fee,
+        });
+
+        Ok(())
+    }
+
+    /// Close a plan once every locked bucket has been released in full. Whatever a bucket's vault
+    /// still holds, such as tokens sent to it since, goes to that bucket's beneficiary, then
+    /// the vaults and the plan are closed to the creator, who paid their rent. This is the close
+    /// for finished vesting buckets, as `close_stream` is for streams. Remaining accounts are each
+    /// locked bucket's vault and beneficiary, in bucket order. Creator only.
+    pub fn close_allocation_plan<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CloseAllocationPlan<'info>>,
+    ) -> Result<()> {
+        trace_ix!(
+            "close_allocation_plan",
+            plan = ctx.accounts.plan.key(),
+            creator = ctx.accounts.creator.key(),
+        );
+        let plan = &ctx.accounts.plan;
+        let locked: Vec<&AllocationBucket> = plan.buckets.iter().filter(|bucket| bucket.kind.is_locked()).collect();
+        if ctx.remaining_accounts.len() != locked.len() * 2 {
+            return Err(MyError::InvalidTokenAccount.into());
+        }
+        if locked.iter().any(|bucket| bucket.released < bucket.amount) {
+            return Err(MyError::AllocationNotReleased.into());
+        }
+
+        let seeds: &[&[u8]] = &[ALLOCATION_PLAN_SEED, plan.mint.as_ref(), &[plan.bump]];
+        let mut swept = 0u64;
+        for (bucket, accounts) in locked.iter().zip(ctx.remaining_accounts.chunks(2)) {
+            let (vault, beneficiary) = (&accounts[0], &accounts[1]);
+            if vault.key() != bucket.vault || beneficiary.key() != bucket.beneficiary {
+                return Err(MyError::InvalidTokenAccount.into());
+            }
+            let balance = token_account(vault)?.amount;
+            if balance > 0 {
+                transfer_tokens(
+                    &ctx.accounts.token_program,
+                    vault,
+                    &ctx.accounts.mint,
+                    beneficiary,
+                    &plan.to_account_info(),
+                    &[seeds],
+                    balance,
+                )?;
+                swept = swept.checked_add(balance).ok_or(MyError::MathOverflow)?;
+            }
+            close_vault(
+                &ctx.accounts.token_program,
+                &ctx.accounts.mint,
+                vault,
+                &plan.to_account_info(),
+                &ctx.accounts.creator,
+                &[seeds],
+            )?;
+        }
+
+        emit!(AllocationPlanClosed {
+            version: EVENT_VERSION,
+            mint: plan.mint,
+            swept,
+        });
+
+        Ok(())
+    }
+
+    /// Stop a revocable team bucket vesting any further and send its unvested part to
+    /// `destination`, a token account of the mint chosen by the creator. What had vested stays
+    /// releasable to the beneficiary. Creator only, and only for buckets made revocable in the
+    /// plan. Streams need no equivalent: their sender can `cancel_stream` at any time.
+    pub fn revoke_allocation(ctx: Context<RevokeAllocation>, index: u8) -> Result<()> {
+        trace_ix!(
+            "revoke_allocation",
+            plan = ctx.accounts.plan.key(),
+            index = index,
+            destination = ctx.accounts.destination.key(),
+        );
+        ensure_not_paused(&ctx.accounts.mint_pause)?;
+        let now = Clock::get()?.unix_timestamp;
+        let plan = &ctx.accounts.plan;
+        let bucket = plan.buckets.get(index as usize).ok_or(MyError::InvalidAllocationPlan)?;
+        if !bucket.revocable {
+            return Err(MyError::AllocationNotRevocable.into());
+        }
+        if bucket.vault != ctx.accounts.vault.key() {
+            return Err(MyError::InvalidTokenAccount.into());
+        }
+        let vested = bucket.unlocked(now);
+        let unvested = bucket.amount - vested;
+        if unvested == 0 {
+            return Err(MyError::NothingToClaim.into());
+        }
+
+        let seeds: &[&[u8]] = &[ALLOCATION_PLAN_SEED, plan.mint.as_ref(), &[plan.bump]];
+        let fee = transfer_tokens(
+            &ctx.accounts.token_program,
+            &ctx.accounts.vault,
+            &ctx.accounts.mint,
+            &ctx.accounts.destination,
+            &plan.to_account_info(),
+            &[seeds],
+            unvested,
+        )?;
+
+        // Shrink the bucket to what had vested and end its vesting now, so `unlocked` stays
+        // at exactly that amount from here on
+        let plan = &mut ctx.accounts.plan;
+        let bucket = &mut plan.buckets[index as usize];
+        bucket.amount = vested;
+        bucket.vesting_start = bucket.vesting_start.min(now);
+        bucket.vesting_end = now;
+        bucket.revocable = false;
+        invariant!(
+            "allocation vault covers the bucket's locked remainder",
+            vault_covers(&ctx.accounts.vault, bucket.amount - bucket.released)
+        );
+
+        emit!(AllocationRevoked {
+            version: EVENT_VERSION,
+            mint: plan.mint,
+            index,
+            returned: unvested,
+            fee,
+        });
+
+        Ok(())
+    }
+
+    /// Propose moving a locked bucket's future releases to `new_beneficiary`, a token account of
+    /// the mint. Signed by the owner of the bucket's current beneficiary account; takes effect
+    /// once the creator accepts it. Replaces any proposal already pending.
+    pub fn propose_allocation_beneficiary(ctx: Context<ProposeAllocationBeneficiary>, index: u8) -> Result<()> {
+        trace_ix!(
+            "propose_allocation_beneficiary",
+            plan = ctx.accounts.plan.key(),
+            index = index,
+            new_beneficiary = ctx.accounts.new_beneficiary.key(),
+        );
+        let plan = &mut ctx.accounts.plan;
+        let mint = plan.mint;
+        let bucket = plan.buckets.get_mut(index as usize).ok_or(MyError::InvalidAllocationPlan)?;
+        if !bucket.kind.is_locked() || bucket.beneficiary != ctx.accounts.beneficiary.key() {
+            return Err(MyError::InvalidTokenAccount.into());
+        }
+        if token_account(&ctx.accounts.beneficiary)?.owner != ctx.accounts.owner.key() {
+            return Err(MyError::Unauthorized.into());
+        }
+        let new_beneficiary = ctx.accounts.new_beneficiary.key();
+        if token_account(&ctx.accounts.new_beneficiary)?.mint != mint || new_beneficiary == bucket.beneficiary {
+            return Err(MyError::InvalidTokenAccount.into());
+        }
+        bucket.pending_beneficiary = Some(new_beneficiary);
+
+        emit!(AllocationBeneficiaryProposed {
+            version: EVENT_VERSION,
+            mint,
+            index,
+            beneficiary: new_beneficiary,
+        });
+
+        Ok(())
+    }
+
+    /// Accept a bucket's proposed beneficiary. What had vested by now is first released to the
+    /// current beneficiary, so the switch only moves future releases. Creator only.
+    pub fn accept_allocation_beneficiary(ctx: Context<AcceptAllocationBeneficiary>, index: u8) -> Result<()> {
+        trace_ix!(
+            "accept_allocation_beneficiary",
+            plan = ctx.accounts.plan.key(),
+            index = index,
+            creator = ctx.accounts.creator.key(),
+        );
+        ensure_not_paused(&ctx.accounts.mint_pause)?;
+        let plan = &ctx.accounts.plan;
+        let bucket = plan.buckets.get(index as usize).ok_or(MyError::InvalidAllocationPlan)?;
+        let new_beneficiary = bucket.pending_beneficiary.ok_or(MyError::NoPendingBeneficiary)?;
+        if bucket.vault != ctx.accounts.vault.key() || bucket.beneficiary != ctx.accounts.beneficiary.key() {
+            return Err(MyError::InvalidTokenAccount.into());
+        }
+        let payout = bucket
+            .unlocked(Clock::get()?.unix_timestamp)
+            .saturating_sub(bucket.released)
+            .min(token_account(&ctx.accounts.vault)?.amount);
+
+        if payout > 0 {
+            let seeds: &[&[u8]] = &[ALLOCATION_PLAN_SEED, plan.mint.as_ref(), &[plan.bump]];
+            transfer_tokens(
+                &ctx.accounts.token_program,
+                &ctx.accounts.vault,
+                &ctx.accounts.mint,
+                &ctx.accounts.beneficiary,
+                &plan.to_account_info(),
+                &[seeds],
+                payout,
+            )?;
+        }
+
+        let plan = &mut ctx.accounts.plan;
+        let bucket = &mut plan.buckets[index as usize];
+        let previous = std::mem::replace(&mut bucket.beneficiary, new_beneficiary);
+        bucket.pending_beneficiary = None;
+        bucket.released += payout;
+        invariant!(
+            "allocation vault covers the bucket's locked remainder",
+            vault_covers(&ctx.accounts.vault, bucket.amount - bucket.released)
+        );
+
+        emit!(AllocationBeneficiaryChanged {
+            version: EVENT_VERSION,
+            mint: plan.mint,
+            index,
+            previous,
+            beneficiary: new_beneficiary,
+            released: payout,
+        });
+
+        Ok(())
+    }
+
+    /// Point an index token's UI multiplier at a Pyth feed. The feed's current price becomes
+    /// the base the multiplier is measured against, so the multiplier starts at 1. Launch
+    /// creator only, once per mint.
+    pub fn init_index_feed(ctx: Context<InitIndexFeed>, params: IndexFeedParams) -> Result<()> {
+        trace_ix!(
+            "init_index_feed",
+            mint = ctx.accounts.launch_config.mint,
+            creator = ctx.accounts.creator.key(),
+            max_deviation_bps = params.max_deviation_bps,
+            min_update_interval = params.min_update_interval_seconds,
+        );
+        if ctx.accounts.launch_config.kind != TokenKind::Indexed {
+            return Err(MyError::NotIndexed.into());
+        }
+        params.validate()?;
+        let price = fresh_pyth_price(
+            &ctx.accounts.price_update,
+            &params.feed_id,
+            params.max_price_age_seconds,
+            params.max_confidence_bps,
+        )?;
+
+        let feed = &mut ctx.accounts.index_feed;
+        feed.mint = ctx.accounts.launch_config.mint;
+        feed.feed_id = params.feed_id;
+        feed.base_price = price.price;
+        feed.exponent = price.exponent;
+        feed.multiplier = math::MULTIPLIER_ONE;
+        feed.max_deviation_bps = params.max_deviation_bps;
+        feed.min_update_interval_seconds = params.min_update_interval_seconds;
+        feed.max_price_age_seconds = params.max_price_age_seconds;
+        feed.max_confidence_bps = params.max_confidence_bps;
+        feed.last_publish_time = price.publish_time;
+        feed.updated_at = Clock::get()?.unix_timestamp;
+        feed.bump = ctx.bumps.index_feed;
+
+        emit!(IndexFeedInitialized {
+            version: EVENT_VERSION,
+            mint: feed.mint,
+            feed_id: feed.feed_id,
+            base_price: feed.base_price,
+            exponent: feed.exponent,
+            max_deviation_bps: feed.max_deviation_bps,
+            min_update_interval_seconds: feed.min_update_interval_seconds,
+        });
+
+        Ok(())
+    }
+
+    /// Move an index token's UI multiplier towards the feed's latest price, by at most the
+    /// feed's deviation limit per update and no more often than its update interval.
+    /// Permissionless crank.
+    pub fn update_index_multiplier(ctx: Context<UpdateIndexMultiplier>) -> Result<()> {
+        trace_ix!(
+            "update_index_multiplier",
+            mint = ctx.accounts.mint.key(),
+            price_update = ctx.accounts.price_update.key(),
+        );
+        let now = Clock::get()?.unix_timestamp;
+        let feed = &ctx.accounts.index_feed;
+        if now - feed.updated_at < feed.min_update_interval_seconds {
+            return Err(MyError::IndexUpdateTooSoon.into());
+        }
+        let price = fresh_pyth_price(
+            &ctx.accounts.price_update,
+            &feed.feed_id,
+            feed.max_price_age_seconds,
+            feed.max_confidence_bps,
+        )?;
+        if price.exponent != feed.exponent {
+            return Err(MyError::InvalidPriceUpdate.into());
+        }
+        if price.publish_time <= feed.last_publish_time {
+            return Err(MyError::StalePrice.into());
+        }
+        let target = math::index_multiplier(feed.base_price, price.price).ok_or(MyError::MathOverflow)?;
+        let multiplier = math::clamp_deviation(feed.multiplier, target, feed.max_deviation_bps);
+
+        let mint_key = ctx.accounts.mint.key();
+        let seeds: &[&[u8]] = &[INDEX_FEED_SEED, mint_key.as_ref(), &[feed.bump]];
+        let update_ix = scaled_ui_amount_instruction::update_multiplier(
+            ctx.accounts.token_program.key,
+            &mint_key,
+            &feed.key(),
+            &[],
+            multiplier as f64 / math::MULTIPLIER_ONE as f64,
+            now,
+        )?;
+        invoke_signed(
+            &update_ix,
+            &[
+                ctx.accounts.mint.to_account_info(),
+                feed.to_account_info(),
+                ctx.accounts.token_program.to_account_info(),
+            ],
+            &[seeds],
+        )?;
+
+        let feed = &mut ctx.accounts.index_feed;
+        feed.multiplier = multiplier;
+        feed.last_publish_time = price.publish_time;
+        feed.updated_at = now;
+
+        emit!(IndexMultiplierUpdated {
+            version: EVENT_VERSION,
+            mint: mint_key,
+            price: price.price,
+            target_multiplier: target,
+            multiplier,
+        });
+
+        Ok(())
+    }
+
+    /// Put an interest-bearing mint's rate under bounds. Main use is to stop the rate authority
+    /// from displaying an absurd yield: the creator may then only move the rate within the
+    /// bounds and once per interval, anything else goes through the mint's timelock. Launch
+    /// creator only, once per mint.
+    pub fn init_interest_rate_governor(ctx: Context<InitInterestRateGovernor>, bounds: InterestRateBounds) -> Result<()> {
+        trace_ix!(
+            "init_interest_rate_governor",
+            mint = ctx.accounts.launch_config.mint,
+            creator = ctx.accounts.creator.key(),
+            min_rate_bps = bounds.min_rate_bps,
+            max_rate_bps = bounds.max_rate_bps,
+            min_update_interval = bounds.min_update_interval_seconds,
+        );
+        let TokenKind::InterestBearing { rate_bps } = ctx.accounts.launch_config.kind else {
+            return Err(MyError::NotInterestBearing.into());
+        };
+        bounds.validate()?;
+        if !bounds.admits(rate_bps) {
+            return Err(MyError::InterestRateOutOfBounds.into());
+        }
+
+        let governor = &mut ctx.accounts.interest_rate_governor;
+        governor.mint = ctx.accounts.launch_config.mint;
+        governor.admin = ctx.accounts.creator.key();
+        governor.bounds = bounds;
+        governor.rate_bps = rate_bps;
+        governor.updated_at = Clock::get()?.unix_timestamp;
+        governor.bump = ctx.bumps.interest_rate_governor;
+
+        emit!(InterestRateGovernorInitialized {
+            version: EVENT_VERSION,
+            mint: governor.mint,
+            admin: governor.admin,
+            rate_bps,
+            min_rate_bps: bounds.min_rate_bps,
+            max_rate_bps: bounds.max_rate_bps,
+            min_update_interval_seconds: bounds.min_update_interval_seconds,
+        });
+
+        Ok(())
+    }
+
+    /// Change an interest-bearing mint's rate within its bounds, no more often than their
+    /// update interval. Governor admin only.
+    pub fn set_interest_rate(ctx: Context<SetInterestRate>, rate_bps: i16) -> Result<()> {
+        trace_ix!(
+            "set_interest_rate",
+            mint = ctx.accounts.mint.key(),
+            admin = ctx.accounts.admin.key(),
+            rate_bps = rate_bps,
+        );
+        let now = Clock::get()?.unix_timestamp;
+        let governor = &ctx.accounts.interest_rate_governor;
+        if !governor.bounds.admits(rate_bps) {
+            return Err(MyError::InterestRateOutOfBounds.into());
+        }
+        if now - governor.updated_at < governor.bounds.min_update_interval_seconds {
+            return Err(MyError::InterestRateUpdateTooSoon.into());
+        }
+        update_interest_rate(&ctx.accounts.token_program, &ctx.accounts.mint, governor, rate_bps)?;
+
+        let governor = &mut ctx.accounts.interest_rate_governor;
+        governor.rate_bps = rate_bps;
+        governor.updated_at = now;
+
+        emit!(InterestRateUpdated {
+            version: EVENT_VERSION,
+            mint: governor.mint,
+            rate_bps,
+            min_rate_bps: governor.bounds.min_rate_bps,
+            max_rate_bps: governor.bounds.max_rate_bps,
+            governed: false,
+        });
+
+        Ok(())
+    }
+
+    /// Set an interest-bearing mint's rate past its bounds or its interval, optionally
+    /// replacing the bounds. Only the mint's timelock can sign this, so such changes wait out
+    /// its delay in public.
+    pub fn govern_interest_rate(
+        ctx: Context<GovernInterestRate>,
+        rate_bps: i16,
+        bounds: Option<InterestRateBounds>,
+    ) -> Result<()> {
+        trace_ix!(
+            "govern_interest_rate",
+            mint = ctx.accounts.mint.key(),
+            timelock = ctx.accounts.timelock.key(),
+            rate_bps = rate_bps,
+        );
+        if let Some(bounds) = &bounds {
+            bounds.validate()?;
+        }
+        update_interest_rate(
+            &ctx.accounts.token_program,
+            &ctx.accounts.mint,
+            &ctx.accounts.interest_rate_governor,
+            rate_bps,
+        )?;
+
+        let governor = &mut ctx.accounts.interest_rate_governor;
+        if let Some(bounds) = bounds {
+            governor.bounds = bounds;
+        }
+        governor.rate_bps = rate_bps;
+        governor.updated_at = Clock::get()?.unix_timestamp;
+
+        emit!(InterestRateUpdated {
+            version: EVENT_VERSION,
+            mint: governor.mint,
+            rate_bps,
+            min_rate_bps: governor.bounds.min_rate_bps,
+            max_rate_bps: governor.bounds.max_rate_bps,
+            governed: true,
+        });
+
+        Ok(())
+    }
+
+    /// Change how much a wallet can take from a mint's devnet faucet per day.
+    pub fn set_faucet_claim_amount(ctx: Context<SetFaucetClaimAmount>, claim_amount: u64) -> Result<()> {
+        trace_ix!(
+            "set_faucet_claim_amount",
+            faucet = ctx.accounts.faucet.key(),
+            creator = ctx.accounts.creator.key(),
+            claim_amount = claim_amount,
+        );
+        ctx.accounts.faucet.claim_amount = claim_amount;
+        Ok(())
+    }
+
+    /// Take the daily faucet amount from the faucet vault, a token account owned by the faucet
+    /// PDA that the creator keeps funded. Once per wallet per UTC day.
+    pub fn claim_from_faucet(ctx: Context<ClaimFromFaucet>) -> Result<()> {
+        trace_ix!(
+            "claim_from_faucet",
+            mint = ctx.accounts.mint.key(),
+            wallet = ctx.accounts.wallet.key(),
+            destination = ctx.accounts.destination.key(),
+        );
+        ensure_not_paused(&ctx.accounts.mint_pause)?;
+        let today = Clock::get()?.unix_timestamp / SECONDS_PER_DAY;
+        let claim = &mut ctx.accounts.claim;
+        if claim.wallet != Pubkey::default() && claim.last_claim_day == today {
+            return Err(MyError::FaucetAlreadyClaimed.into());
+        }
+        claim.wallet = ctx.accounts.wallet.key();
+        claim.last_claim_day = today;
+        claim.bump = ctx.bumps.claim;
+
+        let faucet = &ctx.accounts.faucet;
+        let vault = token_account(&ctx.accounts.vault)?;
+        if vault.mint != faucet.mint || vault.owner != faucet.key() {
+            return Err(MyError::InvalidTokenAccount.into());
+        }
+        let seeds: &[&[u8]] = &[FAUCET_SEED, faucet.mint.as_ref(), &[faucet.bump]];
+        transfer_tokens(
+            &ctx.accounts.token_program,
+            &ctx.accounts.vault,
+            &ctx.accounts.mint,
+            &ctx.accounts.destination,
+            &faucet.to_account_info(),
+            &[seeds],
+            faucet.claim_amount,
+        )?;
+
+        Ok(())
+    }
+
+    /// Reclaim a fully withdrawn stream: anything left in the vault, such as tokens sent to it
+    /// directly, goes to the sender, then the vault and the stream account are closed, returning
+    /// their rent to the sender. Permissionless.
+    pub fn close_stream(ctx: Context<CloseStream>) -> Result<()> {
+        trace_ix!(
+            "close_stream",
+            stream = ctx.accounts.stream.key(),
+            mint = ctx.accounts.mint.key(),
+            sender = ctx.accounts.sender.key(),
+        );
+        let stream = &ctx.accounts.stream;
+        if stream.withdrawn < stream.deposited {
+            return Err(MyError::StreamNotFinished.into());
+        }
+
+        let remaining = token_account(&ctx.accounts.vault)?.amount;
+        let stream_id_bytes = stream.stream_id.to_le_bytes();
+        let seeds: &[&[u8]] = &[
+            STREAM_SEED,
+            stream.sender.as_ref(),
+            stream.mint.as_ref(),
+            &stream_id_bytes,
+            &[stream.bump],
+        ];
+        if remaining > 0 {
+            let destination = ctx
+                .accounts
+                .sender_token_account
+                .as_ref()
+                .ok_or(MyError::InvalidTokenAccount)?;
+            transfer_tokens(
+                &ctx.accounts.token_program,
+                &ctx.accounts.vault,
+                &ctx.accounts.mint,
+                destination,
+                &stream.to_account_info(),
+                &[seeds],
+                remaining,
+            )?;
+        }
+        close_vault(
+            &ctx.accounts.token_program,
+            &ctx.accounts.mint,
+            &ctx.accounts.vault,
+            &stream.to_account_info(),
+            &ctx.accounts.sender,
+            &[seeds],
+        )?;
+
+        emit!(StreamClosed { version: EVENT_VERSION, stream: stream.key() });
+
+        Ok(())
+    }
+
+    /// Reclaim an airdrop whose list has been paid out in full: any remainder goes back to the
+    /// creator, then the vault and campaign are closed to the creator. Skipped entries must be
+    /// claimed first, and a campaign with a voucher signer must also have expired, as vouchers
+    /// can be claimed until then.
+    pub fn close_airdrop(ctx: Context<CloseAirdrop>) -> Result<()> {
+        trace_ix!(
+            "close_airdrop",
+            campaign = ctx.accounts.campaign.key(),
+            mint = ctx.accounts.mint.key(),
+            creator = ctx.accounts.creator.key(),
+        );
+        let campaign = &ctx.accounts.campaign;
+        if campaign.recipients.is_empty() || (campaign.cursor as usize) < campaign.recipients.len() {
+            return Err(MyError::AirdropNotExhausted.into());
+        }
+        if !campaign.skipped.is_empty() {
+            return Err(MyError::AirdropSkippedUnclaimed.into());
+        }
+        if campaign.voucher_signer.is_some() && Clock::get()?.unix_timestamp < campaign.expires_at {
+            return Err(MyError::AirdropNotExpired.into());
+        }
+
+        let remaining = token_account(&ctx.accounts.vault)?.amount;
+        let campaign_id_bytes = campaign.campaign_id.to_le_bytes();
+        let seeds: &[&[u8]] = &[
+            AIRDROP_SEED,
+            campaign.creator.as_ref(),
+            campaign.mint.as_ref(),
+            &campaign_id_bytes,
+            &[campaign.bump],
+        ];
+        if remaining > 0 {
+            let destination = ctx
+                .accounts
+                .creator_token_account
+                .as_ref()
+                .ok_or(MyError::InvalidTokenAccount)?;
+            transfer_tokens(
+                &ctx.accounts.token_program,
+                &ctx.accounts.vault,
+                &ctx.accounts.mint,
+                destination,
+                &campaign.to_account_info(),
+                &[seeds],
+                remaining,
+            )?;
+        }
+        close_vault(
+            &ctx.accounts.token_program,
+            &ctx.accounts.mint,
+            &ctx.accounts.vault,
+            &campaign.to_account_info(),
+            &ctx.accounts.creator,
+            &[seeds],
+        )?;
+
+        emit!(AirdropClosed {
+            version: EVENT_VERSION,
+            campaign: campaign.key(),
+            returned: remaining,
+        });
+
+        Ok(())
+    }
+
+    /// Close the voter's ballot on a finalized proposal, returning its rent to the voter.
+    pub fn close_vote_receipt(ctx: Context<CloseVoteReceipt>) -> Result<()> {
+        trace_ix!(
+            "close_vote_receipt",
+            proposal = ctx.accounts.proposal.key(),
+            voter = ctx.accounts.voter.key(),
+        );
+        if ctx.accounts.proposal.status == ProposalStatus::Voting {
+            return Err(MyError::VotingOpen.into());
+        }
+        Ok(())
+    }
+
+    /// Close a gauge vote from a past epoch, returning its rent to the voter.
+    pub fn close_gauge_vote(ctx: Context<CloseGaugeVote>) -> Result<()> {
+        trace_ix!(
+            "close_gauge_vote",
+            receipt = ctx.accounts.receipt.key(),
+            voter = ctx.accounts.voter.key(),
+        );
+        if ctx.accounts.receipt.epoch >= Clock::get()?.epoch {
+            return Err(MyError::GaugeEpochActive.into());
+        }
+        Ok(())
+    }
+
+    /// Close a faucet claim record from an earlier day, returning its rent to the wallet. The
+    /// next claim recreates it.
+    pub fn close_faucet_claim(ctx: Context<CloseFaucetClaim>) -> Result<()> {
+        trace_ix!(
+            "close_faucet_claim",
+            mint = ctx.accounts.mint.key(),
+            wallet = ctx.accounts.wallet.key(),
+        );
+        let today = Clock::get()?.unix_timestamp / SECONDS_PER_DAY;
+        if ctx.accounts.claim.last_claim_day >= today {
+            return Err(MyError::FaucetClaimActive.into());
+        }
+        Ok(())
+    }
+
+    /// Grow a mint's pool registry by `additional` entries, paid by the authority.
+    pub fn expand_pool_registry(ctx: Context<ExpandPoolRegistry>, additional: u16) -> Result<()> {
+        trace_ix!(
+            "expand_pool_registry",
+            pool_registry = ctx.accounts.pool_registry.key(),
+            authority = ctx.accounts.authority.key(),
+            additional = additional,
+        );
+        let authority = ctx.accounts.authority.key();
+        if authority != ctx.accounts.launch_config.creator && authority != ctx.accounts.platform_config.verifier {
+            return Err(MyError::Unauthorized.into());
+        }
+        let registry = ctx.accounts.pool_registry.to_account_info();
+        emit_expanded(&registry, additional, PoolRegistry::capacity(registry.data_len()))
+    }
+
+    /// Grow a mint's fee exemption list by `additional` entries, paid by the list authority.
+    pub fn expand_fee_exemption_list(ctx: Context<ExpandFeeExemptionList>, additional: u16) -> Result<()> {
+        trace_ix!(
+            "expand_fee_exemption_list",
+            exemption_list = ctx.accounts.exemption_list.key(),
+            authority = ctx.accounts.authority.key(),
+            additional = additional,
+        );
+        let exemption_list = ctx.accounts.exemption_list.to_account_info();
+        emit_expanded(&exemption_list, additional, FeeExemptionList::capacity(exemption_list.data_len()))
+    }
+
+    /// Grow an airdrop list by `additional` recipients, paid by the creator.
+    pub fn expand_airdrop(ctx: Context<ExpandAirdrop>, additional: u16) -> Result<()> {
+        trace_ix!(
+            "expand_airdrop",
+            campaign = ctx.accounts.campaign.key(),
+            creator = ctx.accounts.creator.key(),
+            additional = additional,
+        );
+        let campaign = ctx.accounts.campaign.to_account_info();
+        emit_expanded(&campaign, additional, AirdropCampaign::capacity(campaign.data_len()))
+    }
+
+    /// Return a launch's record as versioned return data, for CPI callers and simulations.
+    pub fn get_launch_config(ctx: Context<GetLaunchConfig>) -> Result<LaunchConfigView> {
+        let config = &ctx.accounts.launch_config;
+        Ok(LaunchConfigView {
+            version: VIEW_VERSION,
+            mint: config.mint,
+            creator: config.creator,
+            created_at: config.created_at,
+            decimals: config.decimals,
+            kind: config.kind,
+            verified: config.verified,
+            featured: config.featured,
+            featured_epoch: config.featured_epoch,
+            ntt_manager: config.ntt_manager,
+            platform_reviewed: config.platform_reviewed,
+            partner: config.partner,
+            compliance: config.compliance,
+            compliance_officer: config.compliance_officer,
+            metadata_locked: config.metadata_locked,
+            unbonded_active: config.unbonded_active,
+        })
+    }
+
+    /// Return a stream's balances as of now as versioned return data.
+    pub fn get_stream_state(ctx: Context<GetStreamState>) -> Result<StreamView> {
+        let stream = &ctx.accounts.stream;
+        let vested = stream.vested_amount(Clock::get()?.unix_timestamp);
+        Ok(StreamView {
+            version: VIEW_VERSION,
+            sender: stream.sender,
+            recipient: stream.recipient,
+            mint: stream.mint,
+            deposited: stream.deposited,
+            withdrawn: stream.withdrawn,
+            vested,
+            withdrawable: vested.saturating_sub(stream.withdrawn),
+            start_time: stream.start_time,
+            end_time: stream.end_time,
+        })
+    }
+
+    /// Return an emission schedule's progress as of now as versioned return data.
+    pub fn get_emission_state(ctx: Context<GetEmissionState>) -> Result<EmissionView> {
+        let schedule = &ctx.accounts.schedule;
+        let released = schedule.released(Clock::get()?.unix_timestamp)?;
+        Ok(EmissionView {
+            version: VIEW_VERSION,
+            mint: schedule.mint,
+            start_time: schedule.start_time,
+            period_seconds: schedule.period_seconds,
+            curve: schedule.curve,
+            released,
+            minted: schedule.minted,
+            mintable: released.saturating_sub(schedule.minted),
+        })
+    }
+
+    /// Return an allocation plan's buckets as of now as versioned return data: how much each
+    /// holds, has released, and could release right now.
+    pub fn get_vesting_state(ctx: Context<GetVestingState>) -> Result<AllocationPlanView> {
+        let plan = &ctx.accounts.plan;
+        let now = Clock::get()?.unix_timestamp;
+        Ok(AllocationPlanView {
+            version: VIEW_VERSION,
+            mint: plan.mint,
+            creator: plan.creator,
+            total_supply: plan.total_supply,
+            buckets: plan
+                .buckets
+                .iter()
+                .map(|bucket| {
+                    let unlocked = bucket.unlocked(now);
+                    AllocationBucketView {
+                        kind: bucket.kind,
+                        beneficiary: bucket.beneficiary,
+                        vault: bucket.vault,
+                        amount: bucket.amount,
+                        vesting_start: bucket.vesting_start,
+                        vesting_end: bucket.vesting_end,
+                        unlocked,
+                        released: bucket.released,
+                        releasable: unlocked.saturating_sub(bucket.released),
+                        revocable: bucket.revocable,
+                    }
+                })
+                .collect(),
+        })
+    }
+
+    /// Rug-check readout for any Token-2022 mint: who holds each authority (renounced, one of
+    /// this program's PDAs, or a plain key), whether the transfer fee can still change, and any
+    /// transfer hook or permanent delegate the mint carries.
+    pub fn get_mint_health(ctx: Context<GetMintHealth>) -> Result<MintHealthView> {
+        let mint_key = ctx.accounts.mint.key();
+        let program_pdas = [
+            (EMISSION_SEED, AuthorityHolder::EmissionSchedule),
+            (TAX_HOLIDAY_SEED, AuthorityHolder::TaxHolidaySchedule),
+            (FEE_DELEGATE_SEED, AuthorityHolder::FeeDelegate),
+            (TIMELOCK_SEED, AuthorityHolder::Timelock),
+        ]
+        .map(|(seed, holder)| (Pubkey::find_program_address(&[seed, mint_key.as_ref()], &crate::ID).0, holder));
+        let holder = |authority: Option<Pubkey>| match authority {
+            None => AuthorityHolder::Renounced,
+            Some(key) => program_pdas
+                .iter()
+                .find(|(pda, _)| *pda == key)
+                .map_or(AuthorityHolder::Key(key), |(_, holder)| holder.clone()),
+        };
+
+        let mint_data = ctx.accounts.mint.try_borrow_data()?;
+        let mint = StateWithExtensions::<Token2022Mint>::unpack(&mint_data)?;
+        let epoch = Clock::get()?.epoch;
+        let fee_config = mint.get_extension::<TransferFeeConfig>().ok();
+        let transfer_fee_config_authority =
+            fee_config.map(|config| holder(Option::<Pubkey>::from(config.transfer_fee_config_authority)));
+        // A fee set within the last two epochs hasn't taken effect yet
+        let fee_pending = fee_config.is_some_and(|config| u64::from(config.newer_transfer_fee.epoch) > epoch);
+
+        Ok(MintHealthView {
+            version: VIEW_VERSION,
+            mint: mint_key,
+            mint_authority: holder(mint.base.mint_authority.into()),
+            freeze_authority: holder(mint.base.freeze_authority.into()),
+            fee_mutable: fee_pending
+                || transfer_fee_config_authority
+                    .as_ref()
+                    .is_some_and(|authority| *authority != AuthorityHolder::Renounced),
+            transfer_fee_config_authority,
+            withdraw_withheld_authority: fee_config
+                .map(|config| holder(Option::<Pubkey>::from(config.withdraw_withheld_authority))),
+            transfer_fee_basis_points: fee_config
+                .map(|config| u16::from(config.get_epoch_fee(epoch).transfer_fee_basis_points)),
+            transfer_hook_program: mint
+                .get_extension::<TransferHook>()
+                .ok()
+                .and_then(|hook| Option::<Pubkey>::from(hook.program_id)),
+            permanent_delegate: mint
+                .get_extension::<PermanentDelegate>()
+                .ok()
+                .and_then(|delegate| Option::<Pubkey>::from(delegate.delegate)),
+        })
+    }
+
+    /// Fee terms of a taxed mint as an exchange's listing review needs them: the fee in effect,
+    /// any scheduled change, whether it can still change, and how many wallets are rebated.
+    pub fn get_declared_fees(ctx: Context<GetDeclaredFees>) -> Result<DeclaredFeesView> {
+        let fee_config = transfer_fee_config(&ctx.accounts.mint)?;
+        let epoch = Clock::get()?.epoch;
+        let current = fee_config.get_epoch_fee(epoch);
+        let newer = fee_config.newer_transfer_fee;
+        let scheduled_fee = (u64::from(newer.epoch) > epoch).then(|| DeclaredFee {
+            transfer_fee_basis_points: u16::from(newer.transfer_fee_basis_points),
+            maximum_fee: u64::from(newer.maximum_fee),
+            epoch: u64::from(newer.epoch),
+        });
+        Ok(DeclaredFeesView {
+            version: VIEW_VERSION,
+            mint: ctx.accounts.mint.key(),
+            transfer_fee_basis_points: u16::from(current.transfer_fee_basis_points),
+            maximum_fee: u64::from(current.maximum_fee),
+            scheduled_fee,
+            transfer_fee_config_authority: fee_config.transfer_fee_config_authority.into(),
+            exempt_wallets: ctx.accounts.exemption_list.as_ref().map(|list| list.exemptions.len() as u32),
+        })
+    }
+
+    /// Add the fees a launch generated since the last sync to its creator's profile.
+    /// Permissionless crank.
+    pub fn sync_creator_fees(ctx: Context<SyncCreatorFees>) -> Result<()> {
+        trace_ix!(
+            "sync_creator_fees",
+            mint_stats = ctx.accounts.mint_stats.key(),
+            creator_reputation = ctx.accounts.creator_reputation.key(),
+        );
+        let stats = &mut ctx.accounts.mint_stats;
+        let new_fees = stats.fees.saturating_sub(stats.fees_synced);
+        let reputation = &mut ctx.accounts.creator_reputation;
+        reputation.total_fees = reputation.total_fees.checked_add(new_fees).ok_or(MyError::MathOverflow)?;
+        stats.fees_synced = stats.fees;
+        Ok(())
+    }
+
+    /// Hand the mint's withheld-fee withdraw authority to a FeeDelegate PDA, so a bot key can
+    /// withdraw fees within `limits` without the root authority key being kept hot. The signer
+    /// stays the delegate's authority and can withdraw without limits or take the role back.
+    /// A non-empty `allowed_destinations` locks every withdrawal to those token accounts; the
+    /// list then only changes `allowlist_delay_seconds` after the change is queued.
+    pub fn init_fee_delegate(
+        ctx: Context<InitFeeDelegate>,
+        limits: FeeDelegateLimits,
+        allowed_destinations: Vec<Pubkey>,
+        allowlist_delay_seconds: i64,
+    ) -> Result<()> {
+        trace_ix!(
+            "init_fee_delegate",
+            mint = ctx.accounts.mint.key(),
+            authority = ctx.accounts.authority.key(),
+            delegate = limits.delegate,
+            destination = limits.destination,
+            allowed_destinations = allowed_destinations.len(),
+        );
+        limits.validate()?;
+        if allowed_destinations.len() > MAX_FEE_DESTINATIONS || allowlist_delay_seconds < 0 {
+            return Err(MyError::InvalidFeeDelegateLimits.into());
+        }
+        let fee_config = transfer_fee_config(&ctx.accounts.mint)?;
+        let withdraw_authority = Option::<Pubkey>::from(fee_config.withdraw_withheld_authority);
+        if withdraw_authority != Some(ctx.accounts.authority.key()) {
+            return Err(MyError::Unauthorized.into());
+        }
+
+        let fee_delegate = &mut ctx.accounts.fee_delegate;
+        fee_delegate.mint = ctx.accounts.mint.key();
+        fee_delegate.authority = ctx.accounts.authority.key();
+        fee_delegate.delegate = limits.delegate;
+        fee_delegate.destination = limits.destination;
+        fee_delegate.withdrawals = PeriodCap::new(limits.period_seconds, limits.period_cap, Clock::get()?.unix_timestamp);
+        fee_delegate.allowed_destinations = allowed_destinations;
+        fee_delegate.allowlist_delay_seconds = allowlist_delay_seconds;
+        fee_delegate.pending_destinations = Vec::new();
+        fee_delegate.pending_at = 0;
+        fee_delegate.bump = ctx.bumps.fee_delegate;
+        if !fee_delegate.allows(&fee_delegate.destination) {
+            return Err(MyError::InvalidFeeDestination.into());
+        }
+
+        let set_authority_ix = token_instruction::set_authority(
+            &ctx.accounts.token_program.key(),
+            &ctx.accounts.mint.key(),
+            Some(&ctx.accounts.fee_delegate.key()),
+            token_instruction::AuthorityType::WithheldWithdraw,
+            &ctx.accounts.authority.key(),
+            &[],
+        )?;
+        invoke(
+            &set_authority_ix,
+            &[
+                ctx.accounts.mint.to_account_info(),
+                ctx.accounts.authority.to_account_info(),
+                ctx.accounts.token_program.to_account_info(),
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    /// Replace the delegate key, destination or cap. The current period's withdrawals still count.
+    pub fn update_fee_delegate(ctx: Context<UpdateFeeDelegate>, limits: FeeDelegateLimits) -> Result<()> {
+        trace_ix!(
+            "update_fee_delegate",
+            fee_delegate = ctx.accounts.fee_delegate.key(),
+            authority = ctx.accounts.authority.key(),
+            delegate = limits.delegate,
+        );
+        limits.validate()?;
+        let fee_delegate = &mut ctx.accounts.fee_delegate;
+        if !fee_delegate.allows(&limits.destination) {
+            return Err(MyError::InvalidFeeDestination.into());
+        }
+        fee_delegate.delegate = limits.delegate;
+        fee_delegate.destination = limits.destination;
+        fee_delegate.withdrawals.set_limits(limits.period_seconds, limits.period_cap);
+        Ok(())
+    }
+
+    /// Hand the delegate's authority role to a new key, e.g. the mint's timelock.
+    pub fn set_fee_delegate_authority(ctx: Context<UpdateFeeDelegate>, new_authority: Pubkey) -> Result<()> {
+        trace_ix!(
+            "set_fee_delegate_authority",
+            fee_delegate = ctx.accounts.fee_delegate.key(),
+            authority = ctx.accounts.authority.key(),
+            new_authority = new_authority,
+        );
+        ctx.accounts.fee_delegate.authority = new_authority;
+        Ok(())
+    }
+
+    /// Withdraw everything withheld on the mint. The delegate may only pay the fixed destination
+    /// and only while the amount fits in the period's cap; harvesting often keeps it small. The
+    /// authority withdraws uncapped to any allowed destination. Either may always withdraw to a
+    /// token account of the mint's project treasury, uncapped, since the treasury caps its own
+    /// spending.
+    pub fn withdraw_delegated_fees(ctx: Context<WithdrawDelegatedFees>) -> Result<()> {
+        trace_ix!(
+            "withdraw_delegated_fees",
+            fee_delegate = ctx.accounts.fee_delegate.key(),
+            signer = ctx.accounts.signer.key(),
+            destination = ctx.accounts.destination.key(),
+        );
+        let amount = u64::from(transfer_fee_config(&ctx.accounts.mint)?.withheld_amount);
+        if amount == 0 {
+            return Err(MyError::NothingToClaim.into());
+        }
+
+        let signer = ctx.accounts.signer.key();
+        let fee_delegate = &mut ctx.accounts.fee_delegate;
+        let treasury = Pubkey::find_program_address(&[PROJECT_TREASURY_SEED, fee_delegate.mint.as_ref()], &crate::ID).0;
+        let to_treasury = token_account_owner(&ctx.accounts.destination)? == treasury;
+        if signer != fee_delegate.delegate && signer != fee_delegate.authority {
+            return Err(MyError::Unauthorized.into());
+        }
+        if !to_treasury {
+            if signer == fee_delegate.delegate {
+                if ctx.accounts.destination.key() != fee_delegate.destination {
+                    return Err(MyError::InvalidFeeDestination.into());
+                }
+                fee_delegate.withdrawals.charge(amount, Clock::get()?.unix_timestamp)?;
+            } else if !fee_delegate.allows(&ctx.accounts.destination.key()) {
+                return Err(MyError::InvalidFeeDestination.into());
+            }
+        }
+
+        let withdraw_ix = withdraw_withheld_tokens_from_mint(
+            &ctx.accounts.token_program.key(),
+            &ctx.accounts.mint.key(),
+            &ctx.accounts.destination.key(),
+            &fee_delegate.key(),
+            &[],
+        )?;
+        let seeds: &[&[u8]] = &[FEE_DELEGATE_SEED, fee_delegate.mint.as_ref(), &[fee_delegate.bump]];
+        invoke_signed(
+            &withdraw_ix,
+            &[
+                ctx.accounts.mint.to_account_info(),
+                ctx.accounts.destination.to_account_info(),
+                fee_delegate.to_account_info(),
+                ctx.accounts.token_program.to_account_info(),
+            ],
+            &[seeds],
+        )?;
+
+        emit!(DelegatedFeesWithdrawn {
+            version: EVENT_VERSION,
+            mint: fee_delegate.mint,
+            signer,
+            destination: ctx.accounts.destination.key(),
+            amount,
+        });
+
+        Ok(())
+    }
+
+    /// Queue a new destination allowlist, applicable once the delegate's delay has passed.
+    /// Replaces any change already queued.
+    pub fn queue_fee_destinations(ctx: Context<UpdateFeeDelegate>, destinations: Vec<Pubkey>) -> Result<()> {
+        trace_ix!(
+            "queue_fee_destinations",
+            fee_delegate = ctx.accounts.fee_delegate.key(),
+            authority = ctx.accounts.authority.key(),
+            destinations = destinations.len(),
+        );
+        if destinations.len() > MAX_FEE_DESTINATIONS {
+            return Err(MyError::InvalidFeeDelegateLimits.into());
+        }
+        let fee_delegate = &mut ctx.accounts.fee_delegate;
+        let applies_at = Clock::get()?
+            .unix_timestamp
+            .checked_add(fee_delegate.allowlist_delay_seconds)
+            .ok_or(MyError::MathOverflow)?;
+        fee_delegate.pending_destinations = destinations.clone();
+        fee_delegate.pending_at = applies_at;
+
+        emit!(FeeDestinationsQueued {
+            version: EVENT_VERSION,
+            mint: fee_delegate.mint,
+            destinations,
+            applies_at,
+        });
+
+        Ok(())
+    }
+
+    /// Drop a queued allowlist change.
+    pub fn cancel_fee_destinations(ctx: Context<UpdateFeeDelegate>) -> Result<()> {
+        trace_ix!(
+            "cancel_fee_destinations",
+            fee_delegate = ctx.accounts.fee_delegate.key(),
+            authority = ctx.accounts.authority.key(),
+        );
+        let fee_delegate = &mut ctx.accounts.fee_delegate;
+        if fee_delegate.pending_at == 0 {
+            return Err(MyError::NoPendingFeeDestinations.into());
+        }
+        fee_delegate.pending_destinations = Vec::new();
+        fee_delegate.pending_at = 0;
+        Ok(())
+    }
+
+    /// Apply the queued allowlist once its delay has passed. It must still allow the delegate's
+    /// destination.
+    pub fn apply_fee_destinations(ctx: Context<UpdateFeeDelegate>) -> Result<()> {
+        trace_ix!(
+            "apply_fee_destinations",
+            fee_delegate = ctx.accounts.fee_delegate.key(),
+            authority = ctx.accounts.authority.key(),
+        );
+        let fee_delegate = &mut ctx.accounts.fee_delegate;
+        if fee_delegate.pending_at == 0 {
+            return Err(MyError::NoPendingFeeDestinations.into());
+        }
+        if Clock::get()?.unix_timestamp < fee_delegate.pending_at {
+            return Err(MyError::FeeDestinationsTimelocked.into());
+        }
+        fee_delegate.allowed_destinations = std::mem::take(&mut fee_delegate.pending_destinations);
+        fee_delegate.pending_at = 0;
+        if !fee_delegate.allows(&fee_delegate.destination) {
+            return Err(MyError::InvalidFeeDestination.into());
+        }
+        Ok(())
+    }
+
+    /// Give the withdraw authority back to the delegate's authority and close the delegate.
+    /// An allowlist must be emptied first, through its timelock, so it can't be bypassed.
+    pub fn close_fee_delegate(ctx: Context<CloseFeeDelegate>) -> Result<()> {
+        trace_ix!(
+            "close_fee_delegate",
+            fee_delegate = ctx.accounts.fee_delegate.key(),
+            authority = ctx.accounts.authority.key(),
+        );
+        let fee_delegate = &ctx.accounts.fee_delegate;
+        if !fee_delegate.allowed_destinations.is_empty() {
+            return Err(MyError::FeeDestinationsTimelocked.into());
+        }
+        let set_authority_ix = token_instruction::set_authority(
+            &ctx.accounts.token_program.key(),
+            &ctx.accounts.mint.key(),
+            Some(&ctx.accounts.authority.key()),
+            token_instruction::AuthorityType::WithheldWithdraw,
+            &fee_delegate.key(),
+            &[],
+        )?;
+        let seeds: &[&[u8]] = &[FEE_DELEGATE_SEED, fee_delegate.mint.as_ref(), &[fee_delegate.bump]];
+        invoke_signed(
+            &set_authority_ix,
+            &[
+                ctx.accounts.mint.to_account_info(),
+                fee_delegate.to_account_info(),
+                ctx.accounts.token_program.to_account_info(),
+            ],
+            &[seeds],
+        )?;
+
+        Ok(())
+    }
+
+    /// Create a mint's timelock. Any authority handed to the timelock PDA (exemption list, tax
+    /// holiday admin, FeeDelegate authority, or the mint's own Token-2022 authorities) can then
+    /// only act through `queue_op`, and only `delay_seconds` later through `execute_op`.
+    /// Launch creator only; the creator becomes the timelock admin, who queues and cancels ops.
+    pub fn init_timelock(ctx: Context<InitTimelock>, delay_seconds: i64) -> Result<()> {
+        trace_ix!(
+            "init_timelock",
+            launch_config = ctx.accounts.launch_config.key(),
+            admin = ctx.accounts.admin.key(),
+            delay_seconds = delay_seconds,
+        );
+        if ctx.accounts.admin.key() != ctx.accounts.launch_config.creator {
+            return Err(MyError::Unauthorized.into());
+        }
+        validate_timelock_delay(delay_seconds)?;
+
+        let timelock = &mut ctx.accounts.timelock;
+        timelock.mint = ctx.accounts.launch_config.mint;
+        timelock.admin = ctx.accounts.admin.key();
+        timelock.delay_seconds = delay_seconds;
+        timelock.next_op_id = 0;
+        timelock.bump = ctx.bumps.timelock;
+
+        Ok(())
+    }
+
+    /// Queue an instruction for this program or Token-2022, signed by the timelock PDA when
+    /// executed. The timelock PDA is the only signer an op may ask for.
+    pub fn queue_op(
+        ctx: Context<QueueOp>,
+        program_id: Pubkey,
+        accounts: Vec<TimelockAccountMeta>,
+        data: Vec<u8>,
+    ) -> Result<()> {
+        trace_ix!(
+            "queue_op",
+            timelock = ctx.accounts.timelock.key(),
+            admin = ctx.accounts.admin.key(),
+            program_id = program_id,
+            accounts = accounts.len(),
+            data = data.len(),
+        );
+        if program_id != crate::ID && program_id != spl_token_2022::id() {
+            return Err(MyError::InvalidTimelockOp.into());
+        }
+        if accounts.len() > MAX_OP_ACCOUNTS || data.len() > MAX_OP_DATA {
+            return Err(MyError::InvalidTimelockOp.into());
+        }
+        let timelock_key = ctx.accounts.timelock.key();
+        if accounts.iter().any(|meta| meta.is_signer && meta.pubkey != timelock_key) {
+            return Err(MyError::InvalidTimelockOp.into());
+        }
+
+        let timelock = &mut ctx.accounts.timelock;
+        let op_id = timelock.next_op_id;
+        let eta = Clock::get()?
+            .unix_timestamp
+            .checked_add(timelock.delay_seconds)
+            .ok_or(MyError::MathOverflow)?;
+        timelock.next_op_id = op_id.checked_add(1).ok_or(MyError::MathOverflow)?;
+
+        let op = &mut ctx.accounts.queued_op;
+        op.timelock = timelock_key;
+        op.id = op_id;
+        op.program_id = program_id;
+        op.accounts = accounts.clone();
+        op.data = data.clone();
+        op.eta = eta;
+        op.bump = ctx.bumps.queued_op;
+
+        emit!(OpQueued {
+            version: EVENT_VERSION,
+            mint: timelock.mint,
+            op_id,
+            program_id,
+            accounts,
+            data,
+            eta,
+        });
+
+        Ok(())
+    }
+
+    /// Drop a queued op and refund its rent to the admin.
+    pub fn cancel_op(ctx: Context<CancelOp>) -> Result<()> {
+        trace_ix!(
+            "cancel_op",
+            queued_op = ctx.accounts.queued_op.key(),
+            admin = ctx.accounts.admin.key(),
+        );
+        emit!(OpCancelled {
+            version: EVENT_VERSION,
+            mint: ctx.accounts.timelock.mint,
+            op_id: ctx.accounts.queued_op.id,
+        });
+
+        Ok(())
+    }
+
+    /// Permissionless: run a queued op once its delay has passed. The op's accounts are passed
+    /// as remaining accounts, in the order they were queued.
+    pub fn execute_op<'info>(ctx: Context<'_, '_, 'info, 'info, ExecuteOp<'info>>) -> Result<()> {
+        trace_ix!(
+            "execute_op",
+            queued_op = ctx.accounts.queued_op.key(),
+            timelock = ctx.accounts.timelock.key(),
+        );
+        let op = &ctx.accounts.queued_op;
+        if Clock::get()?.unix_timestamp < op.eta {
+            return Err(MyError::OpTimelocked.into());
+        }
+        let remaining = ctx.remaining_accounts;
+        if remaining.len() != op.accounts.len()
+            || remaining.iter().zip(&op.accounts).any(|(info, meta)| *info.key != meta.pubkey)
+        {
+            return Err(MyError::InvalidTimelockOp.into());
+        }
+
+        let ix = Instruction {
+            program_id: op.program_id,
+            accounts: op
+                .accounts
+                .iter()
+                .map(|meta| AccountMeta {
+                    pubkey: meta.pubkey,
+                    is_signer: meta.is_signer,
+                    is_writable: meta.is_writable,
+                })
+                .collect(),
+            data: op.data.clone(),
+        };
+        let mut infos = remaining.to_vec();
+        infos.push(ctx.accounts.target_program.to_account_info());
+        let timelock = &ctx.accounts.timelock;
+        let seeds: &[&[u8]] = &[TIMELOCK_SEED, timelock.mint.as_ref(), &[timelock.bump]];
+        invoke_signed(&ix, &infos, &[seeds])?;
+
+        emit!(OpExecuted {
+            version: EVENT_VERSION,
+            mint: timelock.mint,
+            op_id: op.id,
+        });
+
+        Ok(())
+    }
+
+    /// Change the timelock's admin or delay. Only the timelock itself can sign this, so the
+    /// change has to be queued like any other op.
+    pub fn update_timelock(ctx: Context<UpdateTimelock>, admin: Pubkey, delay_seconds: i64) -> Result<()> {
+        trace_ix!(
+            "update_timelock",
+            timelock = ctx.accounts.timelock.key(),
+            admin = admin,
+            delay_seconds = delay_seconds,
+        );
+        validate_timelock_delay(delay_seconds)?;
+        let timelock = &mut ctx.accounts.timelock;
+        timelock.admin = admin;
+        timelock.delay_seconds = delay_seconds;
+        Ok(())
+    }
+
+    /// Transfer using TransferCheckedWithFee CPI — useful for tests and clients to use.
+    /// `fee` is what the caller expects to be withheld; it must match the fee the mint's
+    /// TransferFeeConfig charges, which is what rebates and stats record. Only transfers made
+    /// here earn exempt wallets a rebate; a direct Token-2022 transfer is never seen. Routing
+    /// a transfer through here, and passing the stats account, is up to the sender, so
+    /// MintStats only ever holds a self-reported share of a mint's transfers.
+    pub fn transfer_with_fee(
+        ctx: Context<TransferWithFee>,
+        amount: u64,
+        decimals: u8,
+        fee: u64,
+    ) -> Result<()> {
+        trace_ix!(
+            "transfer_with_fee",
+            mint = ctx.accounts.mint.key(),
+            source = ctx.accounts.source.key(),
+            destination = ctx.accounts.destination.key(),
+            authority = ctx.accounts.authority.key(),
+            amount = amount,
+            fee = fee,
+        );
+        ensure_no_transfer_hook(&ctx.accounts.mint)?;
+        if mint_decimals_and_fee(&ctx.accounts.mint, amount)?.1 != Some(fee) {
+            return Err(MyError::FeeMismatch.into());
+        }
+        let ix = spl_token_2022::extension::transfer_fee::instruction::transfer_checked_with_fee(
+            &ctx.accounts.token_program.key(),
+            &ctx.accounts.source.key(),
+            &ctx.accounts.mint.key(),
+            &ctx.accounts.destination.key(),
+            &ctx.accounts.authority.key(),
+            &[],
+            amount,
+            decimals,
+            fee,
+        )?;
+
+        invoke(
+            &ix,
+            &[
+                ctx.accounts.token_program.to_account_info(),
+                ctx.accounts.source.to_account_info(),
+                ctx.accounts.mint.to_account_info(),
+                ctx.accounts.destination.to_account_info(),
+                ctx.accounts.authority.to_account_info(),
+            ],
+        )?;
+
+        // Withholding can't be skipped, so fees paid by or to an exempt wallet are recorded
+        // against that wallet for a later rebate. The recipient's entry wins when both are exempt.
+        let exemption_list_info = ctx.accounts.exemption_list.to_account_info();
+        if fee > 0 && !exemption_list_info.data_is_empty() {
+            let mut exemption_list = FeeExemptionList::try_deserialize(&mut &exemption_list_info.try_borrow_data()?[..])?;
+            let source_owner = token_account_owner(&ctx.accounts.source)?;
+            let destination_owner = token_account_owner(&ctx.accounts.destination)?;
+            let position = exemption_list
+                .position(&destination_owner)
+                .or_else(|| exemption_list.position(&source_owner));
+            if let Some(index) = position {
+                let entry = &mut exemption_list.exemptions[index];
+                entry.rebate_owed = entry.rebate_owed.checked_add(fee).ok_or(MyError::MathOverflow)?;
+                emit!(ExemptTransferRecorded {
+                    version: EVENT_VERSION,
+                    mint: ctx.accounts.mint.key(),
+                    wallet: entry.wallet,
+                    fee,
+                    rebate_owed: entry.rebate_owed,
+                });
+                exemption_list.try_serialize(&mut &mut exemption_list_info.try_borrow_mut_data()?[..])?;
+            }
+        }
+
+        if let Some(stats) = ctx.accounts.mint_stats.as_mut() {
+            let clock = Clock::get()?;
+            stats.record_transfer(amount, fee, clock.slot, clock.epoch)?;
+            if let Some(registry) = &ctx.accounts.pool_registry {
+                if registry.is_pool(&ctx.accounts.source.key()) {
+                    stats.buy_volume = stats.buy_volume.checked_add(amount as u128).ok_or(MyError::MathOverflow)?;
+                } else if registry.is_pool(&ctx.accounts.destination.key()) {
+                    stats.sell_volume = stats.sell_volume.checked_add(amount as u128).ok_or(MyError::MathOverflow)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Set or clear the compliance rules of a launch. Creator only.
+    pub fn set_compliance_flags(ctx: Context<SetComplianceFlags>, flags: Option<ComplianceFlags>) -> Result<()> {
+        trace_ix!(
+            "set_compliance_flags",
+            mint = ctx.accounts.launch_config.mint,
+            creator = ctx.accounts.creator.key(),
+            enabled = flags.is_some(),
+        );
+        let launch_config = &mut ctx.accounts.launch_config;
+        launch_config.compliance = flags;
+
+        emit!(ComplianceFlagsUpdated {
+            version: EVENT_VERSION,
+            mint: launch_config.mint,
+            flags,
+        });
+
+        Ok(())
+    }
+
+    /// Record a compliance attestor's check of `wallet` for a mint: the restricted-jurisdiction
+    /// list it was screened against and whether it is accredited. Overwrites any earlier
+    /// attestation; an `expires_at` in the past revokes it.
+    pub fn attest_wallet(
+        ctx: Context<AttestWallet>,
+        wallet: Pubkey,
+        jurisdictions_hash: [u8; 32],
+        accredited: bool,
+        expires_at: i64,
+    ) -> Result<()> {
+        trace_ix!(
+            "attest_wallet",
+            mint = ctx.accounts.launch_config.mint,
+            attestor = ctx.accounts.attestor.key(),
+            wallet = wallet,
+            accredited = accredited,
+            expires_at = expires_at,
+        );
+        let flags = ctx.accounts.launch_config.compliance.ok_or(MyError::ComplianceNotConfigured)?;
+        if flags.attestor != ctx.accounts.attestor.key() {
+            return Err(MyError::Unauthorized.into());
+        }
+
+        let attestation = &mut ctx.accounts.attestation;
+        attestation.mint = ctx.accounts.launch_config.mint;
+        attestation.wallet = wallet;
+        attestation.attestor = flags.attestor;
+        attestation.jurisdictions_hash = jurisdictions_hash;
+        attestation.accredited = accredited;
+        attestation.expires_at = expires_at;
+        attestation.bump = ctx.bumps.attestation;
+
+        emit!(WalletAttested {
+            version: EVENT_VERSION,
+            mint: attestation.mint,
+            wallet,
+            accredited,
+            expires_at,
+        });
+
+        Ok(())
+    }
+
+    /// Freeze a token account whose owner doesn't meet the launch's compliance rules. The mint's
+    /// freeze authority must be the compliance PDA. Creator or compliance attestor only.
+    pub fn freeze_noncompliant(ctx: Context<ComplianceFreeze>) -> Result<()> {
+        trace_ix!(
+            "freeze_noncompliant",
+            mint = ctx.accounts.mint.key(),
+            token_account = ctx.accounts.token_account.key(),
+            authority = ctx.accounts.authority.key(),
+        );
+        let flags = ctx.accounts.flags()?;
+        let authority = ctx.accounts.authority.key();
+        if authority != ctx.accounts.launch_config.creator && authority != flags.attestor {
+            return Err(MyError::Unauthorized.into());
+        }
+        if flags.admits(ctx.accounts.attestation()?.as_ref(), Clock::get()?.unix_timestamp) {
+            return Err(MyError::AttestationValid.into());
+        }
+        ctx.accounts.set_frozen(true, ctx.bumps.compliance_authority)?;
+
+        emit!(ComplianceFrozen {
+            version: EVENT_VERSION,
+            mint: ctx.accounts.mint.key(),
+            token_account: ctx.accounts.token_account.key(),
+            wallet: ctx.accounts.wallet.key(),
+        });
+
+        Ok(())
+    }
+
+    /// Thaw a token account whose owner holds a valid attestation for the launch's compliance
+    /// rules. Permissionless.
+    pub fn thaw_compliant(ctx: Context<ComplianceFreeze>) -> Result<()> {
+        trace_ix!(
+            "thaw_compliant",
+            mint = ctx.accounts.mint.key(),
+            token_account = ctx.accounts.token_account.key(),
+        );
+        let flags = ctx.accounts.flags()?;
+        if !flags.admits(ctx.accounts.attestation()?.as_ref(), Clock::get()?.unix_timestamp) {
+            return Err(MyError::NotCompliant.into());
+        }
+        if !ctx.accounts.freeze_order.data_is_empty() {
+            let order = FreezeOrder::try_deserialize(&mut &ctx.accounts.freeze_order.try_borrow_data()?[..])?;
+            if order.is_active() {
+                return Err(MyError::FreezeOrderActive.into());
+            }
+        }
+        ctx.accounts.set_frozen(false, ctx.bumps.compliance_authority)?;
+
+        emit!(ComplianceThawed {
+            version: EVENT_VERSION,
+            mint: ctx.accounts.mint.key(),
+            token_account: ctx.accounts.token_account.key(),
+            wallet: ctx.accounts.wallet.key(),
+        });
+
+        Ok(())
+    }
+
+    /// Appoint or remove the compliance officer of a launch, the only key that can issue freeze
+    /// orders. Creator only.
+    pub fn set_compliance_officer(ctx: Context<SetComplianceFlags>, officer: Option<Pubkey>) -> Result<()> {
+        trace_ix!(
+            "set_compliance_officer",
+            mint = ctx.accounts.launch_config.mint,
+            creator = ctx.accounts.creator.key(),
+            officer = officer.unwrap_or_default(),
+        );
+        let launch_config = &mut ctx.accounts.launch_config;
+        launch_config.compliance_officer = officer;
+
+        emit!(ComplianceOfficerSet {
+            version: EVENT_VERSION,
+            mint: launch_config.mint,
+            officer,
+        });
+
+        Ok(())
+    }
+
+    /// Freeze a token account by order of the compliance officer. The order is kept on-chain
+    /// with the URI and hash of its public justification, and blocks `thaw_compliant` until
+    /// lifted. The mint's freeze authority must be the compliance PDA.
+    pub fn issue_freeze_order(
+        ctx: Context<IssueFreezeOrder>,
+        justification_uri: String,
+        justification_hash: [u8; 32],
+    ) -> Result<()> {
+        trace_ix!(
+            "issue_freeze_order",
+            mint = ctx.accounts.mint.key(),
+            token_account = ctx.accounts.token_account.key(),
+            officer = ctx.accounts.officer.key(),
+        );
+        check_justification(&justification_uri, &justification_hash)?;
+        if ctx.accounts.launch_config.compliance_officer != Some(ctx.accounts.officer.key()) {
+            return Err(MyError::Unauthorized.into());
+        }
+        if token_account(&ctx.accounts.token_account)?.mint != ctx.accounts.mint.key() {
+            return Err(MyError::InvalidTokenAccount.into());
+        }
+        if ctx.accounts.freeze_order.is_active() {
+            return Err(MyError::FreezeOrderActive.into());
+        }
+        check_compliance_authority(&ctx.accounts.mint, &ctx.accounts.compliance_authority.key())?;
+        set_compliance_frozen(
+            &ctx.accounts.token_program,
+            &ctx.accounts.token_account,
+            &ctx.accounts.mint,
+            &ctx.accounts.compliance_authority,
+            ctx.bumps.compliance_authority,
+            true,
+        )?;
+
+        let order = &mut ctx.accounts.freeze_order;
+        order.mint = ctx.accounts.mint.key();
+        order.token_account = ctx.accounts.token_account.key();
+        order.officer = ctx.accounts.officer.key();
+        order.justification_uri = justification_uri.clone();
+        order.justification_hash = justification_hash;
+        order.issued_at = Clock::get()?.unix_timestamp;
+        order.lifted_at = 0;
+        order.bump = ctx.bumps.freeze_order;
+
+        emit!(FreezeOrderIssued {
+            version: EVENT_VERSION,
+            mint: order.mint,
+            token_account: order.token_account,
+            officer: order.officer,
+            justification_uri,
+            justification_hash,
+        });
+
+        Ok(())
+    }
+
+    /// Lift a freeze order and thaw the account, again with a public justification. Compliance
+    /// officer only.
+    pub fn lift_freeze_order(
+        ctx: Context<LiftFreezeOrder>,
+        justification_uri: String,
+        justification_hash: [u8; 32],
+    ) -> Result<()> {
+        trace_ix!(
+            "lift_freeze_order",
+            freeze_order = ctx.accounts.freeze_order.key(),
+            officer = ctx.accounts.officer.key(),
+        );
+        check_justification(&justification_uri, &justification_hash)?;
+        if ctx.accounts.launch_config.compliance_officer != Some(ctx.accounts.officer.key()) {
+            return Err(MyError::Unauthorized.into());
+        }
+        if !ctx.accounts.freeze_order.is_active() {
+            return Err(MyError::FreezeOrderInactive.into());
+        }
+        check_compliance_authority(&ctx.accounts.mint, &ctx.accounts.compliance_authority.key())?;
+        set_compliance_frozen(
+            &ctx.accounts.token_program,
+            &ctx.accounts.token_account,
+            &ctx.accounts.mint,
+            &ctx.accounts.compliance_authority,
+            ctx.bumps.compliance_authority,
+            false,
+        )?;
+
+        let order = &mut ctx.accounts.freeze_order;
+        order.lifted_at = Clock::get()?.unix_timestamp;
+
+        emit!(FreezeOrderLifted {
+            version: EVENT_VERSION,
+            mint: order.mint,
+            token_account: order.token_account,
+            officer: ctx.accounts.officer.key(),
+            justification_uri,
+            justification_hash,
+        });
+
+        Ok(())
+    }
+
+    /// Start opt-in transfer stats for a mint. Anyone can pay for it; from then on transfers
+    /// through `transfer_with_fee` that pass the stats account are counted. Plain Token-2022
+    /// transfers never are, so the stats cover only what senders chose to report.
+    pub fn init_mint_stats(ctx: Context<InitMintStats>) -> Result<()> {
+        trace_ix!(
+            "init_mint_stats",
+            mint = ctx.accounts.mint.key(),
+            mint_stats = ctx.accounts.mint_stats.key(),
+        );
+        let stats = &mut ctx.accounts.mint_stats;
+        stats.mint = ctx.accounts.mint.key();
+        stats.bump = ctx.bumps.mint_stats;
+        Ok(())
+    }
+
+    /// Create the fee exemption list for a taxed mint. Only the mint's withheld-fee withdraw
+    /// authority can do this, since it is the party that funds rebates.
+    pub fn init_fee_exemption_list(ctx: Context<InitFeeExemptionList>) -> Result<()> {
+        trace_ix!(
+            "init_fee_exemption_list",
+            mint = ctx.accounts.mint.key(),
+            authority = ctx.accounts.authority.key(),
+        );
+        let fee_config = transfer_fee_config(&ctx.accounts.mint)?;
+        let withdraw_authority = Option::<Pubkey>::from(fee_config.withdraw_withheld_authority);
+        if withdraw_authority != Some(ctx.accounts.authority.key()) {
+            return Err(MyError::Unauthorized.into());
+        }
+
+        let exemption_list = &mut ctx.accounts.exemption_list;
+        exemption_list.mint = ctx.accounts.mint.key();
+        exemption_list.authority = ctx.accounts.authority.key();
+        exemption_list.exemptions = Vec::new();
+        exemption_list.bump = ctx.bumps.exemption_list;
+
+        Ok(())
+    }
+
+    /// Add a wallet (treasury, DEX router, CEX deposit address...) to the exemption list.
+    pub fn add_fee_exemption(ctx: Context<UpdateFeeExemptionList>, wallet: Pubkey) -> Result<()> {
+        trace_ix!(
+            "add_fee_exemption",
+            exemption_list = ctx.accounts.exemption_list.key(),
+            authority = ctx.accounts.authority.key(),
+            wallet = wallet,
+        );
+        let exemption_list = &mut ctx.accounts.exemption_list;
+        if exemption_list.position(&wallet).is_some() {
+            return Err(MyError::AlreadyExempt.into());
+        }
+        if exemption_list.exemptions.len() >= FeeExemptionList::capacity(exemption_list.to_account_info().data_len()) {
+            return Err(MyError::ExemptionListFull.into());
+        }
+        exemption_list.exemptions.push(FeeExemption { wallet, rebate_owed: 0 });
+
+        Ok(())
+    }
+
+    /// Remove a wallet from the exemption list. Any rebate it has not claimed is forfeited.
+    pub fn remove_fee_exemption(ctx: Context<UpdateFeeExemptionList>, wallet: Pubkey) -> Result<()> {
+        trace_ix!(
+            "remove_fee_exemption",
+            exemption_list = ctx.accounts.exemption_list.key(),
+            authority = ctx.accounts.authority.key(),
+            wallet = wallet,
+        );
+        let exemption_list = &mut ctx.accounts.exemption_list;
+        let index = exemption_list.position(&wallet).ok_or(MyError::NotExempt)?;
+        exemption_list.exemptions.swap_remove(index);
+
+        Ok(())
+    }
+
+    /// Hand the exemption list to a new authority, e.g. the mint's timelock.
+    pub fn set_fee_exemption_authority(ctx: Context<UpdateFeeExemptionList>, new_authority: Pubkey) -> Result<()> {
+        trace_ix!(
+            "set_fee_exemption_authority",
+            exemption_list = ctx.accounts.exemption_list.key(),
+            authority = ctx.accounts.authority.key(),
+            new_authority = new_authority,
+        );
+        ctx.accounts.exemption_list.authority = new_authority;
+        Ok(())
+    }
+
+    /// Ask for an exchange's deposit and withdrawal wallets to be exempted from `mint`'s fee.
+    /// Signed by the exchange, whose key identifies the listing; nothing is exempt until the
+    /// exemption list authority approves it.
+    pub fn register_exchange(ctx: Context<RegisterExchange>, name: String, wallets: Vec<Pubkey>) -> Result<()> {
+        trace_ix!(
+            "register_exchange",
+            mint = ctx.accounts.exemption_list.mint,
+            exchange = ctx.accounts.exchange.key(),
+            wallets = wallets.len(),
+        );
+        if name.len() > MAX_NAME_LEN {
+            return Err(MyError::NameTooLong.into());
+        }
+        if wallets.is_empty()
+            || wallets.len() > MAX_EXCHANGE_WALLETS
+            || wallets.iter().enumerate().any(|(i, wallet)| wallets[..i].contains(wallet))
+        {
+            return Err(MyError::InvalidExchangeWallets.into());
+        }
+
+        let listing = &mut ctx.accounts.listing;
+        listing.mint = ctx.accounts.exemption_list.mint;
+        listing.exchange = ctx.accounts.exchange.key();
+        listing.name = name;
+        listing.wallets = wallets;
+        listing.approved = false;
+        listing.bump = ctx.bumps.listing;
+
+        emit!(ExchangeRegistered {
+            version: EVENT_VERSION,
+            listing: listing.key(),
+            mint: listing.mint,
+            exchange: listing.exchange,
+            wallets: listing.wallets.len() as u8,
+        });
+
+        Ok(())
+    }
+
+    /// Approve a pending exchange listing, adding all its wallets to the exemption list.
+    pub fn approve_exchange(ctx: Context<ApproveExchange>) -> Result<()> {
+        trace_ix!(
+            "approve_exchange",
+            listing = ctx.accounts.listing.key(),
+            authority = ctx.accounts.authority.key(),
+        );
+        let listing = &mut ctx.accounts.listing;
+        if listing.approved {
+            return Err(MyError::AlreadyExempt.into());
+        }
+        let exemption_list = &mut ctx.accounts.exemption_list;
+        let capacity = FeeExemptionList::capacity(exemption_list.to_account_info().data_len());
+        if exemption_list.exemptions.len() + listing.wallets.len() > capacity {
+            return Err(MyError::ExemptionListFull.into());
+        }
+        for wallet in &listing.wallets {
+            if exemption_list.position(wallet).is_some() {
+                return Err(MyError::AlreadyExempt.into());
+            }
+            exemption_list.exemptions.push(FeeExemption { wallet: *wallet, rebate_owed: 0 });
+        }
+        listing.approved = true;
+
+        emit!(ExchangeApproved {
+            version: EVENT_VERSION,
+            listing: listing.key(),
+            mint: listing.mint,
+            exchange: listing.exchange,
+        });
+
+        Ok(())
+    }
+
+    /// Close an exchange listing, signed by the exchange or the exemption list authority. The
+    /// listing's wallets leave the exemption list and forfeit any unclaimed rebate, as with
+    /// `remove_fee_exemption`.
+    pub fn revoke_exchange(ctx: Context<RevokeExchange>) -> Result<()> {
+        trace_ix!(
+            "revoke_exchange",
+            listing = ctx.accounts.listing.key(),
+            signer = ctx.accounts.signer.key(),
+        );
+        let signer = ctx.accounts.signer.key();
+        let listing = &ctx.accounts.listing;
+        let exemption_list = &mut ctx.accounts.exemption_list;
+        if signer != listing.exchange && signer != exemption_list.authority {
+            return Err(MyError::Unauthorized.into());
+        }
+        // Wallets the authority already removed by hand are skipped
+        if listing.approved {
+            for wallet in &listing.wallets {
+                if let Some(index) = exemption_list.position(wallet) {
+                    exemption_list.exemptions.swap_remove(index);
+                }
+            }
+        }
+
+        emit!(ExchangeRevoked {
+            version: EVENT_VERSION,
+            listing: listing.key(),
+            mint: listing.mint,
+            exchange: listing.exchange,
+        });
+
+        Ok(())
+    }
+
+    /// Create the platform config. Only the program's upgrade authority can do this; it becomes
+    /// the platform admin.
+    pub fn initialize_platform(ctx: Context<InitializePlatform>, params: PlatformParams) -> Result<()> {
+        trace_ix!(
+            "initialize_platform",
+            admin = ctx.accounts.admin.key(),
+            treasury = params.treasury,
+            launch_fee_lamports = params.launch_fee_lamports,
+        );
+        let platform_config = &mut ctx.accounts.platform_config;
+        platform_config.admin = ctx.accounts.admin.key();
+        platform_config.apply(params)?;
+        platform_config.bump = ctx.bumps.platform_config;
+
+        Ok(())
+    }
+
+    /// Replace the platform settings. Bonds already posted keep their own terms.
+    pub fn update_platform_config(ctx: Context<UpdatePlatformConfig>, params: PlatformParams) -> Result<()> {
+        trace_ix!(
+            "update_platform_config",
+            admin = ctx.accounts.admin.key(),
+            treasury = params.treasury,
+            launch_fee_lamports = params.launch_fee_lamports,
+        );
+        ctx.accounts.platform_config.apply(params)
+    }
+
+    /// Return a creator bond once its lock period has passed and no dispute against the launch
+    /// is open or upheld, closing the bond account.
+    pub fn refund_creator_bond(ctx: Context<RefundCreatorBond>) -> Result<()> {
+        trace_ix!(
+            "refund_creator_bond",
+            creator_bond = ctx.accounts.creator_bond.key(),
+            creator = ctx.accounts.creator.key(),
+        );
+        let bond = &ctx.accounts.creator_bond;
+        if bond.disputed || is_dispute_live(&ctx.accounts.dispute)? {
+            return Err(MyError::BondDisputed.into());
+        }
+        if Clock::get()?.unix_timestamp < bond.unlock_at {
+            return Err(MyError::BondLocked.into());
+        }
+
+        let reputation = &mut ctx.accounts.creator_reputation;
+        reputation.bonds_refunded = reputation.bonds_refunded.saturating_add(1);
+        reputation.active_launches = reputation.active_launches.saturating_sub(1);
+        reputation.recompute_score();
+
+        emit!(CreatorBondRefunded {
+            version: EVENT_VERSION,
+            mint: bond.mint,
+            creator: bond.creator,
+            lamports: bond.lamports,
+        });
+
+        Ok(())
+    }
+
+    /// Stop counting a launch made without a creator bond towards its creator's active
+    /// launches, once as long as a bond would have been locked has passed since its creation.
+    /// Bonded launches stop counting when their bond is refunded or slashed. Permissionless.
+    pub fn retire_launch(ctx: Context<RetireLaunch>) -> Result<()> {
+        trace_ix!(
+            "retire_launch",
+            launch_config = ctx.accounts.launch_config.key(),
+            creator_reputation = ctx.accounts.creator_reputation.key(),
+        );
+        let launch_config = &mut ctx.accounts.launch_config;
+        if !launch_config.unbonded_active {
+            return Err(MyError::LaunchNotActive.into());
+        }
+        let active_until = launch_config
+            .created_at
+            .checked_add(ctx.accounts.platform_config.bond_lock_seconds)
+            .ok_or(MyError::MathOverflow)?;
+        if Clock::get()?.unix_timestamp < active_until {
+            return Err(MyError::LaunchStillActive.into());
+        }
+        launch_config.unbonded_active = false;
+
+        let reputation = &mut ctx.accounts.creator_reputation;
+        reputation.active_launches = reputation.active_launches.saturating_sub(1);
+
+        emit!(LaunchRetired {
+            version: EVENT_VERSION,
+            mint: launch_config.mint,
+            creator: launch_config.creator,
+            active_launches: reputation.active_launches,
+        });
+
+        Ok(())
+    }
+
+    /// Create the insurance vault that receives slashed creator bonds.
+    pub fn initialize_insurance_vault(ctx: Context<InitializeInsuranceVault>) -> Result<()> {
+        trace_ix!(
+            "initialize_insurance_vault",
+            insurance_vault = ctx.accounts.insurance_vault.key(),
+            admin = ctx.accounts.admin.key(),
+        );
+        let insurance_vault = &mut ctx.accounts.insurance_vault;
+        insurance_vault.total_slashed = 0;
+        insurance_vault.total_fees = 0;
+        insurance_vault.total_paid = 0;
+        insurance_vault.bump = ctx.bumps.insurance_vault;
+
+        Ok(())
+    }
+
+    /// Open a dispute against a launch. Anyone can flag; while the dispute is open the
+    /// creator bond (if any) can't be refunded. A dismissed dispute can only be reopened
+    /// DISPUTE_REFLAG_COOLDOWN_SECONDS after its dismissal, so flags can't hold a bond forever.
+    pub fn flag_launch(ctx: Context<FlagLaunch>, mint: Pubkey, reason_uri: String) -> Result<()> {
+        trace_ix!(
+            "flag_launch",
+            mint = mint,
+            reporter = ctx.accounts.reporter.key(),
+        );
+        if reason_uri.len() > MAX_URI_LEN {
+            return Err(MyError::UriTooLong.into());
+        }
+
+        let opened_at = Clock::get()?.unix_timestamp;
+        let dispute = &mut ctx.accounts.dispute;
+        if dispute.mint != Pubkey::default() {
+            if dispute.status != DisputeStatus::Dismissed {
+                return Err(MyError::DisputeAlreadyOpen.into());
+            }
+            if opened_at < dispute.resolved_at.saturating_add(DISPUTE_REFLAG_COOLDOWN_SECONDS) {
+                return Err(MyError::ReflagTooSoon.into());
+            }
+        }
+        dispute.mint = mint;
+        dispute.reporter = ctx.accounts.reporter.key();
+        dispute.reason_uri = reason_uri;
+        dispute.opened_at = opened_at;
+        dispute.resolved_at = 0;
+        dispute.status = DisputeStatus::Open;
+        dispute.bump = ctx.bumps.dispute;
+
+        set_bond_disputed(&ctx.accounts.creator_bond, true)?;
+
+        let reputation = &mut ctx.accounts.creator_reputation;
+        reputation.disputes = reputation.disputes.saturating_add(1);
+        reputation.recompute_score();
+
+        emit!(LaunchFlagged {
+            version: EVENT_VERSION,
+            mint,
+            reporter: ctx.accounts.reporter.key(),
+            opened_at,
+        });
+
+        Ok(())
+    }
+
+    /// Verifier decision on an open dispute. A dismissed dispute frees the bond but stays
+    /// on-chain, with the reporter's rent, to hold off a new flag for the re-flag cooldown; an
+    /// upheld one stays so the bond can be slashed.
+    pub fn resolve_dispute(ctx: Context<ResolveDispute>, upheld: bool) -> Result<()> {
+        trace_ix!(
+            "resolve_dispute",
+            dispute = ctx.accounts.dispute.key(),
+            verifier = ctx.accounts.verifier.key(),
+            upheld = upheld,
+        );
+        let dispute = &mut ctx.accounts.dispute;
+        if dispute.status != DisputeStatus::Open {
+            return Err(MyError::DisputeNotOpen.into());
+        }
+
+        dispute.resolved_at = Clock::get()?.unix_timestamp;
+        if upheld {
+            dispute.status = DisputeStatus::Upheld;
+            let reputation = &mut ctx.accounts.creator_reputation;
+            reputation.disputes_upheld = reputation.disputes_upheld.saturating_add(1);
+            reputation.recompute_score();
+        } else {
+            dispute.status = DisputeStatus::Dismissed;
+            set_bond_disputed(&ctx.accounts.creator_bond, false)?;
+        }
+
+        emit!(DisputeResolved {
+            version: EVENT_VERSION,
+            mint: dispute.mint,
+            upheld,
+        });
+
+        Ok(())
+    }
+
+    /// Move the bond of a launch with an upheld dispute into the insurance vault, closing both
+    /// the bond and the dispute.
+    pub fn slash_bond(ctx: Context<SlashBond>) -> Result<()> {
+        trace_ix!(
+            "slash_bond",
+            dispute = ctx.accounts.dispute.key(),
+            creator_bond = ctx.accounts.creator_bond.key(),
+            verifier = ctx.accounts.verifier.key(),
+        );
+        if ctx.accounts.dispute.status != DisputeStatus::Upheld {
+            return Err(MyError::DisputeNotUpheld.into());
+        }
+
+        let lamports = ctx.accounts.creator_bond.to_account_info().lamports();
+        let insurance_vault = &mut ctx.accounts.insurance_vault;
+        insurance_vault.total_slashed = insurance_vault
+            .total_slashed
+            .checked_add(lamports)
+            .ok_or(MyError::MathOverflow)?;
+
+        let reputation = &mut ctx.accounts.creator_reputation;
+        reputation.slashes = reputation.slashes.saturating_add(1);
+        reputation.active_launches = reputation.active_launches.saturating_sub(1);
+        reputation.recompute_score();
+
+        emit!(BondSlashed {
+            version: EVENT_VERSION,
+            mint: ctx.accounts.creator_bond.mint,
+            creator: ctx.accounts.creator_bond.creator,
+            lamports,
+        });
+
+        Ok(())
+    }
+
+    /// Pay an insurance claim out of the vault. Admin-governed; the vault never drops below
+    /// its rent-exempt minimum.
+    pub fn pay_claim(ctx: Context<PayClaim>, lamports: u64, claim_uri: String) -> Result<()> {
+        trace_ix!(
+            "pay_claim",
+            recipient = ctx.accounts.recipient.key(),
+            admin = ctx.accounts.admin.key(),
+            lamports = lamports,
+        );
+        if claim_uri.len() > MAX_URI_LEN {
+            return Err(MyError::UriTooLong.into());
+        }
+
+        let vault_info = ctx.accounts.insurance_vault.to_account_info();
+        let rent_minimum = Rent::get()?.minimum_balance(vault_info.data_len());
+        let available = vault_info.lamports().saturating_sub(rent_minimum);
+        if lamports > available {
+            return Err(MyError::InsufficientInsuranceFunds.into());
+        }
+
+        **vault_info.try_borrow_mut_lamports()? -= lamports;
+        **ctx.accounts.recipient.try_borrow_mut_lamports()? += lamports;
+
+        let insurance_vault = &mut ctx.accounts.insurance_vault;
+        insurance_vault.total_paid = insurance_vault
+            .total_paid
+            .checked_add(lamports)
+            .ok_or(MyError::MathOverflow)?;
+
+        emit!(InsuranceClaimPaid {
+            version: EVENT_VERSION,
+            recipient: ctx.accounts.recipient.key(),
+            lamports,
+            claim_uri,
+        });
+
+        Ok(())
+    }
+
+    /// Register a white-label partner under `namespace`. Launches passing the partner are
+    /// tagged with it, and it keeps `fee_share_bps` of the platform's part of their launch fee.
+    /// The platform admin co-signs, approving the share.
+    pub fn register_partner(
+        ctx: Context<RegisterPartner>,
+        namespace: String,
+        fee_share_bps: u16,
+        branding_uri: String,
+    ) -> Result<()> {
+        trace_ix!(
+            "register_partner",
+            authority = ctx.accounts.authority.key(),
+            fee_share_bps = fee_share_bps,
+        );
+        if namespace.len() > MAX_NAME_LEN {
+            return Err(MyError::NameTooLong.into());
+        }
+        let partner = &mut ctx.accounts.partner;
+        partner.authority = ctx.accounts.authority.key();
+        partner.namespace = namespace;
+        partner.launches = 0;
+        partner.fees_earned = 0;
+        partner.bump = ctx.bumps.partner;
+        partner.set_fee_share(fee_share_bps)?;
+        partner.set_branding(branding_uri)?;
+
+        emit!(PartnerRegistered {
+            version: EVENT_VERSION,
+            partner: partner.key(),
+            authority: partner.authority,
+            namespace: partner.namespace.clone(),
+            fee_share_bps,
+        });
+
+        Ok(())
+    }
+
+    /// Change a partner's branding. Partner authority only.
+    pub fn update_partner(ctx: Context<UpdatePartner>, branding_uri: String) -> Result<()> {
+        trace_ix!(
+            "update_partner",
+            partner = ctx.accounts.partner.key(),
+            authority = ctx.accounts.authority.key(),
+        );
+        let partner = &mut ctx.accounts.partner;
+        partner.set_branding(branding_uri)?;
+
+        emit!(PartnerUpdated {
+            version: EVENT_VERSION,
+            partner: partner.key(),
+            fee_share_bps: partner.fee_share_bps,
+        });
+
+        Ok(())
+    }
+
+    /// Change a partner's fee share. Platform admin only, so partners can't raise their own
+    /// cut; applies to launches from then on.
+    pub fn set_partner_fee_share(ctx: Context<SetPartnerFeeShare>, fee_share_bps: u16) -> Result<()> {
+        trace_ix!(
+            "set_partner_fee_share",
+            partner = ctx.accounts.partner.key(),
+            admin = ctx.accounts.admin.key(),
+            fee_share_bps = fee_share_bps,
+        );
+        let partner = &mut ctx.accounts.partner;
+        partner.set_fee_share(fee_share_bps)?;
+
+        emit!(PartnerUpdated {
+            version: EVENT_VERSION,
+            partner: partner.key(),
+            fee_share_bps,
+        });
+
+        Ok(())
+    }
+
+    /// Pay out the launch fees a partner has collected, everything above its rent-exempt
+    /// minimum, to `recipient`.
+    pub fn withdraw_partner_fees(ctx: Context<WithdrawPartnerFees>) -> Result<()> {
+        trace_ix!(
+            "withdraw_partner_fees",
+            partner = ctx.accounts.partner.key(),
+            recipient = ctx.accounts.recipient.key(),
+        );
+        let partner_info = ctx.accounts.partner.to_account_info();
+        let rent_minimum = Rent::get()?.minimum_balance(partner_info.data_len());
+        let lamports = partner_info.lamports().saturating_sub(rent_minimum);
+        if lamports == 0 {
+            return Err(MyError::NothingToClaim.into());
+        }
+        **partner_info.try_borrow_mut_lamports()? -= lamports;
+        **ctx.accounts.recipient.try_borrow_mut_lamports()? += lamports;
+
+        emit!(PartnerFeesWithdrawn {
+            version: EVENT_VERSION,
+            partner: partner_info.key(),
+            recipient: ctx.accounts.recipient.key(),
+            lamports,
+        });
+
+        Ok(())
+    }
+
+    /// Create the platform staking pool. Platform-token stakers share `staking_fee_bps` of
+    /// every launch fee the platform keeps. Only the platform admin can do this; `vault` is a
+    /// platform-token account owned by the pool PDA.
+    pub fn init_platform_staking(ctx: Context<InitPlatformStaking>) -> Result<()> {
+        trace_ix!("init_platform_staking", admin = ctx.accounts.admin.key(), vault = ctx.accounts.vault.key());
+        let vault = token_account(&ctx.accounts.vault)?;
+        if vault.mint != ctx.accounts.platform_config.platform_mint || vault.owner != ctx.accounts.staking_pool.key() {
+            return Err(MyError::InvalidTokenAccount.into());
+        }
+
+        let staking_pool = &mut ctx.accounts.staking_pool;
+        staking_pool.vault = ctx.accounts.vault.key();
+        staking_pool.ledger = StakeLedger {
+            rolled_epoch: Clock::get()?.epoch,
+            ..StakeLedger::default()
+        };
+        staking_pool.bump = ctx.bumps.staking_pool;
+
+        Ok(())
+    }
+
+    /// Stake `amount` platform tokens. Rewards are shared out per epoch: launch fees collected
+    /// during an epoch go to the stake in the pool when the epoch is rolled over, which the
+    /// first staking instruction of the next epoch does. New stake warms up for the rest of the
+    /// epoch it arrives in and only shares in rollovers once activated after that, so stake
+    /// placed just before a rollover can't claim the epoch's fees.
+    pub fn stake_platform_tokens(ctx: Context<StakePlatformTokens>, amount: u64) -> Result<()> {
+        trace_ix!("stake_platform_tokens", owner = ctx.accounts.owner.key(), amount = amount);
+        ensure_not_paused(&ctx.accounts.mint_pause)?;
+        if amount == 0 {
+            return Err(MyError::InvalidStakeAmount.into());
+        }
+        roll_staking_epoch(&mut ctx.accounts.staking_pool)?;
+
+        let fee = transfer_tokens(
+            &ctx.accounts.token_program,
+            &ctx.accounts.owner_token_account,
+            &ctx.accounts.mint,
+            &ctx.accounts.vault,
+            &ctx.accounts.owner,
+            &[],
+            amount,
+        )?;
+        let staked = amount - fee;
+
+        let epoch = Clock::get()?.epoch;
+        let staking_pool = &mut ctx.accounts.staking_pool;
+        let position = &mut ctx.accounts.position;
+        position.owner = ctx.accounts.owner.key();
+        position.bump = ctx.bumps.position;
+        position.activate(&mut staking_pool.ledger, epoch)?;
+        position.warming = position.warming.checked_add(staked).ok_or(MyError::MathOverflow)?;
+        position.warming_epoch = epoch;
+        let ledger = &mut staking_pool.ledger;
+        ledger.total_staked = ledger.total_staked.checked_add(staked).ok_or(MyError::MathOverflow)?;
+        ledger.warming_stake = ledger.warming_stake.checked_add(staked).ok_or(MyError::MathOverflow)?;
+        invariant!("stake vault covers the total stake", vault_covers(&ctx.accounts.vault, staking_pool.ledger.total_staked));
+
+        emit!(PlatformTokensStaked {
+            version: EVENT_VERSION,
+            owner: position.owner,
+            amount: staked,
+            total_staked: staking_pool.ledger.total_staked,
+        });
+
+        Ok(())
+    }
+
+    /// Take `amount` staked tokens back out of the pool, stake still warming up first. Rewards
+    /// earned so far stay claimable.
+    pub fn unstake_platform_tokens(ctx: Context<UnstakePlatformTokens>, amount: u64) -> Result<()> {
+        trace_ix!("unstake_platform_tokens", owner = ctx.accounts.owner.key(), amount = amount);
+        let position = &ctx.accounts.position;
+        if amount == 0 || amount > position.amount.saturating_add(position.warming) {
+            return Err(MyError::InvalidStakeAmount.into());
+        }
+        roll_staking_epoch(&mut ctx.accounts.staking_pool)?;
+
+        let epoch = Clock::get()?.epoch;
+        let staking_pool = &mut ctx.accounts.staking_pool;
+        let position = &mut ctx.accounts.position;
+        position.activate(&mut staking_pool.ledger, epoch)?;
+        let from_warming = amount.min(position.warming);
+        position.warming -= from_warming;
+        let remaining = position.amount - (amount - from_warming);
+        position.set_amount(&mut staking_pool.ledger, remaining)?;
+        staking_pool.ledger.warming_stake -= from_warming;
+        staking_pool.ledger.total_staked -= amount;
+
+        let seeds: &[&[u8]] = &[PLATFORM_STAKING_SEED, &[staking_pool.bump]];
+        transfer_tokens(
+            &ctx.accounts.token_program,
+            &ctx.accounts.vault,
+            &ctx.accounts.mint,
+            &ctx.accounts.owner_token_account,
+            &staking_pool.to_account_info(),
+            &[seeds],
+            amount,
+        )?;
+        invariant!("stake vault covers the total stake", vault_covers(&ctx.accounts.vault, staking_pool.ledger.total_staked));
+
+        emit!(PlatformTokensUnstaked {
+            version: EVENT_VERSION,
+            owner: position.owner,
+            amount,
+            total_staked: staking_pool.ledger.total_staked,
+        });
+
+        Ok(())
+    }
+
+    /// Pay a staker the launch fees their stake has earned, in lamports.
+    pub fn claim_staking_rewards(ctx: Context<ClaimStakingRewards>) -> Result<()> {
+        trace_ix!("claim_staking_rewards", owner = ctx.accounts.owner.key());
+        roll_staking_epoch(&mut ctx.accounts.staking_pool)?;
+
+        let epoch = Clock::get()?.epoch;
+        let staking_pool = &mut ctx.accounts.staking_pool;
+        let position = &mut ctx.accounts.position;
+        position.activate(&mut staking_pool.ledger, epoch)?;
+        position.settle(staking_pool.ledger.reward_per_share)?;
+        let lamports = position.unclaimed;
+        if lamports == 0 {
+            return Err(MyError::NothingToClaim.into());
+        }
+        position.unclaimed = 0;
+
+        let pool_info = ctx.accounts.staking_pool.to_account_info();
+        **pool_info.try_borrow_mut_lamports()? -= lamports;
+        **ctx.accounts.owner.try_borrow_mut_lamports()? += lamports;
+        invariant!(
+            "staking pool covers its pending rewards",
+            lamports_cover(&pool_info, ctx.accounts.staking_pool.ledger.pending_rewards)
+        );
+
+        emit!(StakingRewardsClaimed {
+            version: EVENT_VERSION,
+            owner: position.owner,
+            lamports,
+        });
+
+        Ok(())
+    }
+
+    /// Start a position's warmed-up stake earning, for stakers who haven't touched the pool
+    /// since their warm-up epoch ended. Permissionless; a no-op while the stake is still warming.
+    pub fn activate_stake(ctx: Context<ActivateStake>) -> Result<()> {
+        trace_ix!("activate_stake", position = ctx.accounts.position.key());
+        roll_staking_epoch(&mut ctx.accounts.staking_pool)?;
+        ctx.accounts
+            .position
+            .activate(&mut ctx.accounts.staking_pool.ledger, Clock::get()?.epoch)
+    }
+
+    /// Create a dividend pool for a mint: holders stake its tokens and earn `reward_mint` tokens,
+    /// such as a Token-2022 stablecoin, that anyone pays in with `fund_dividends`. The program
+    /// doesn't convert transfer fees itself, so the fee withdraw authority swaps what it harvests
+    /// and funds the pool. Both vaults are token accounts owned by the pool PDA. Creator only.
+    pub fn init_dividend_pool(ctx: Context<InitDividendPool>) -> Result<()> {
+        trace_ix!(
+            "init_dividend_pool",
+            mint = ctx.accounts.launch_config.mint,
+            reward_mint = ctx.accounts.reward_mint.key(),
+        );
+        let pool_key = ctx.accounts.dividend_pool.key();
+        let mint = ctx.accounts.launch_config.mint;
+        let reward_mint = ctx.accounts.reward_mint.key();
+        let stake_vault = token_account(&ctx.accounts.stake_vault)?;
+        let reward_vault = token_account(&ctx.accounts.reward_vault)?;
+        if stake_vault.mint != mint
+            || stake_vault.owner != pool_key
+            || reward_vault.mint != reward_mint
+            || reward_vault.owner != pool_key
+            || ctx.accounts.stake_vault.key() == ctx.accounts.reward_vault.key()
+        {
+            return Err(MyError::InvalidTokenAccount.into());
+        }
+
+        let pool = &mut ctx.accounts.dividend_pool;
+        pool.mint = mint;
+        pool.reward_mint = reward_mint;
+        pool.stake_vault = ctx.accounts.stake_vault.key();
+        pool.reward_vault = ctx.accounts.reward_vault.key();
+        pool.ledger = StakeLedger {
+            rolled_epoch: Clock::get()?.epoch,
+            ..StakeLedger::default()
+        };
+        pool.bump = ctx.bumps.dividend_pool;
+
+        emit!(DividendPoolCreated {
+            version: EVENT_VERSION,
+            mint,
+            reward_mint,
+        });
+
+        Ok(())
+    }
+
+    /// Pay `amount` reward tokens into a dividend pool. They are shared out over the active stake
+    /// at the next epoch rollover, like launch fees in the platform pool. Permissionless.
+    pub fn fund_dividends(ctx: Context<FundDividends>, amount: u64) -> Result<()> {
+        trace_ix!(
+            "fund_dividends",
+            dividend_pool = ctx.accounts.dividend_pool.key(),
+            funder = ctx.accounts.funder.key(),
+            amount = amount,
+        );
+        if amount == 0 {
+            return Err(MyError::InvalidStakeAmount.into());
+        }
+        roll_dividend_epoch(&mut ctx.accounts.dividend_pool)?;
+
+        let fee = transfer_tokens(
+            &ctx.accounts.token_program,
+            &ctx.accounts.funder_token_account,
+            &ctx.accounts.reward_mint,
+            &ctx.accounts.reward_vault,
+            &ctx.accounts.funder,
+            &[],
+            amount,
+        )?;
+        let received = amount - fee;
+
+        let pool = &mut ctx.accounts.dividend_pool;
+        pool.ledger.pending_rewards = pool.ledger.pending_rewards.checked_add(received).ok_or(MyError::MathOverflow)?;
+        invariant!(
+            "reward vault covers the pending dividends",
+            vault_covers(&ctx.accounts.reward_vault, pool.ledger.pending_rewards)
+        );
+
+        emit!(DividendsFunded {
+            version: EVENT_VERSION,
+            mint: pool.mint,
+            funder: ctx.accounts.funder.key(),
+            amount: received,
+        });
+
+        Ok(())
+    }
+
+    /// Stake `amount` of the mint's tokens in its dividend pool. New stake warms up for the rest
+    /// of the epoch it arrives in, as in the platform pool, so stake placed just before a
+    /// rollover can't claim what was funded during the epoch.
+    pub fn stake_for_dividends(ctx: Context<StakeForDividends>, amount: u64) -> Result<()> {
+        trace_ix!(
+            "stake_for_dividends",
+            dividend_pool = ctx.accounts.dividend_pool.key(),
+            owner = ctx.accounts.owner.key(),
+            amount = amount,
+        );
+        ensure_not_paused(&ctx.accounts.mint_pause)?;
+        if amount == 0 {
+            return Err(MyError::InvalidStakeAmount.into());
+        }
+        roll_dividend_epoch(&mut ctx.accounts.dividend_pool)?;
+
+        let fee = transfer_tokens(
+            &ctx.accounts.token_program,
+            &ctx.accounts.owner_token_account,
+            &ctx.accounts.mint,
+            &ctx.accounts.stake_vault,
+            &ctx.accounts.owner,
+            &[],
+            amount,
+        )?;
+        let staked = amount - fee;
+
+        let epoch = Clock::get()?.epoch;
+        let pool = &mut ctx.accounts.dividend_pool;
+        let position = &mut ctx.accounts.position;
+        position.owner = ctx.accounts.owner.key();
+        position.bump = ctx.bumps.position;
+        position.activate(&mut pool.ledger, epoch)?;
+        position.warming = position.warming.checked_add(staked).ok_or(MyError::MathOverflow)?;
+        position.warming_epoch = epoch;
+        let ledger = &mut pool.ledger;
+        ledger.total_staked = ledger.total_staked.checked_add(staked).ok_or(MyError::MathOverflow)?;
+        ledger.warming_stake = ledger.warming_stake.checked_add(staked).ok_or(MyError::MathOverflow)?;
+        invariant!("stake vault covers the total stake", vault_covers(&ctx.accounts.stake_vault, ledger.total_staked));
+
+        emit!(DividendStaked {
+            version: EVENT_VERSION,
+            mint: pool.mint,
+            owner: position.owner,
+            amount: staked,
+            total_staked: pool.ledger.total_staked,
+        });
+
+        Ok(())
+    }
+
+    /// Take `amount` staked tokens back out of a dividend pool, stake still warming up first.
+    /// Dividends earned so far stay claimable.
+    pub fn unstake_from_dividends(ctx: Context<UnstakeFromDividends>, amount: u64) -> Result<()> {
+        trace_ix!(
+            "unstake_from_dividends",
+            dividend_pool = ctx.accounts.dividend_pool.key(),
+            owner = ctx.accounts.owner.key(),
+            amount = amount,
+        );
+        let position = &ctx.accounts.position;
+        if amount == 0 || amount > position.amount.saturating_add(position.warming) {
+            return Err(MyError::InvalidStakeAmount.into());
+        }
+        roll_dividend_epoch(&mut ctx.accounts.dividend_pool)?;
+
+        let epoch = Clock::get()?.epoch;
+        let pool = &mut ctx.accounts.dividend_pool;
+        let position = &mut ctx.accounts.position;
+        position.activate(&mut pool.ledger, epoch)?;
+        let from_warming = amount.min(position.warming);
+        position.warming -= from_warming;
+        let remaining = position.amount - (amount - from_warming);
+        position.set_amount(&mut pool.ledger, remaining)?;
+        pool.ledger.warming_stake -= from_warming;
+        pool.ledger.total_staked -= amount;
+
+        let seeds: &[&[u8]] = &[DIVIDEND_POOL_SEED, pool.mint.as_ref(), &[pool.bump]];
+        transfer_tokens(
+            &ctx.accounts.token_program,
+            &ctx.accounts.stake_vault,
+            &ctx.accounts.mint,
+            &ctx.accounts.owner_token_account,
+            &pool.to_account_info(),
+            &[seeds],
+            amount,
+        )?;
+        invariant!(
+            "stake vault covers the total stake",
+            vault_covers(&ctx.accounts.stake_vault, pool.ledger.total_staked)
+        );
+
+        emit!(DividendUnstaked {
+            version: EVENT_VERSION,
+            mint: pool.mint,
+            owner: position.owner,
+            amount,
+            total_staked: pool.ledger.total_staked,
+        });
+
+        Ok(())
+    }
+
+    /// Pay a staker the dividends their stake has earned, in the pool's reward token.
+    pub fn claim_dividends(ctx: Context<ClaimDividends>) -> Result<()> {
+        trace_ix!(
+            "claim_dividends",
+            dividend_pool = ctx.accounts.dividend_pool.key(),
+            owner = ctx.accounts.owner.key(),
+        );
+        roll_dividend_epoch(&mut ctx.accounts.dividend_pool)?;
+
+        let epoch = Clock::get()?.epoch;
+        let pool = &mut ctx.accounts.dividend_pool;
+        let position = &mut ctx.accounts.position;
+        position.activate(&mut pool.ledger, epoch)?;
+        position.settle(pool.ledger.reward_per_share)?;
+        let amount = position.unclaimed;
+        if amount == 0 {
+            return Err(MyError::NothingToClaim.into());
+        }
+        position.unclaimed = 0;
+
+        let seeds: &[&[u8]] = &[DIVIDEND_POOL_SEED, pool.mint.as_ref(), &[pool.bump]];
+        let fee = transfer_tokens(
+            &ctx.accounts.token_program,
+            &ctx.accounts.reward_vault,
+            &ctx.accounts.reward_mint,
+            &ctx.accounts.owner_reward_account,
+            &pool.to_account_info(),
+            &[seeds],
+            amount,
+        )?;
+        invariant!(
+            "reward vault covers the pending dividends",
+            vault_covers(&ctx.accounts.reward_vault, pool.ledger.pending_rewards)
+        );
+
+        emit!(DividendsClaimed {
+            version: EVENT_VERSION,
+            mint: pool.mint,
+            owner: position.owner,
+            amount,
+            fee,
+        });
+
+        Ok(())
+    }
+
+    /// Start a dividend position's warmed-up stake earning, for stakers who haven't touched the
+    /// pool since their warm-up epoch ended. Permissionless; a no-op while the stake is still
+    /// warming.
+    pub fn activate_dividend_stake(ctx: Context<ActivateDividendStake>) -> Result<()> {
+        trace_ix!("activate_dividend_stake", position = ctx.accounts.position.key());
+        roll_dividend_epoch(&mut ctx.accounts.dividend_pool)?;
+        ctx.accounts
+            .position
+            .activate(&mut ctx.accounts.dividend_pool.ledger, Clock::get()?.epoch)
+    }
+
+    /// Mark a launch as verified by the platform, pointing at the attestation behind the badge.
+    /// Callable by the platform verifier, which may be an M-of-N multisig.
+    pub fn verify_token(ctx: Context<SetVerification>, attestation_uri: String) -> Result<()> {
+        trace_ix!(
+            "verify_token",
+            launch_config = ctx.accounts.launch_config.key(),
+            verifier = ctx.accounts.verifier.key(),
+        );
+        if attestation_uri.len() > MAX_URI_LEN {
+            return Err(MyError::UriTooLong.into());
+        }
+
+        let launch_config = &mut ctx.accounts.launch_config;
+        if !launch_config.verified {
+            let reputation = &mut ctx.accounts.creator_reputation;
+            reputation.verified_launches = reputation.verified_launches.saturating_add(1);
+        }
+        launch_config.verified = true;
+        launch_config.verified_at = Clock::get()?.unix_timestamp;
+        launch_config.attestation_uri = attestation_uri.clone();
+
+        emit!(TokenVerified {
+            version: EVENT_VERSION,
+            mint: launch_config.mint,
+            verifier: ctx.accounts.verifier.key(),
+            attestation_uri,
+        });
+
+        Ok(())
+    }
+
+    /// Remove a launch's verified flag.
+    pub fn revoke_verification(ctx: Context<SetVerification>) -> Result<()> {
+        trace_ix!(
+            "revoke_verification",
+            launch_config = ctx.accounts.launch_config.key(),
+            verifier = ctx.accounts.verifier.key(),
+        );
+        let launch_config = &mut ctx.accounts.launch_config;
+        if !launch_config.verified {
+            return Err(MyError::NotVerified.into());
+        }
+        launch_config.verified = false;
+        launch_config.verified_at = 0;
+        launch_config.attestation_uri.clear();
+
+        let reputation = &mut ctx.accounts.creator_reputation;
+        reputation.verified_launches = reputation.verified_launches.saturating_sub(1);
+
+        emit!(VerificationRevoked {
+            version: EVENT_VERSION,
+            mint: launch_config.mint,
+            verifier: ctx.accounts.verifier.key(),
+        });
+
+        Ok(())
+    }
+
+    /// Register an auditor allowed to publish audit attestations. Platform admin only.
+    pub fn register_auditor(ctx: Context<RegisterAuditor>, auditor: Pubkey, name: String) -> Result<()> {
+        trace_ix!(
+            "register_auditor",
+            auditor = auditor,
+            admin = ctx.accounts.admin.key(),
+        );
+        if name.len() > MAX_NAME_LEN {
+            return Err(MyError::NameTooLong.into());
+        }
+
+        let registration = &mut ctx.accounts.auditor_registration;
+        registration.auditor = auditor;
+        registration.name = name;
+        registration.bump = ctx.bumps.auditor_registration;
+
+        Ok(())
+    }
+
+    /// Deregister an auditor. Attestations it already published stay on-chain.
+    pub fn remove_auditor(_ctx: Context<RemoveAuditor>) -> Result<()> {
+        trace_ix!(
+            "remove_auditor",
+            auditor_registration = _ctx.accounts.auditor_registration.key(),
+            admin = _ctx.accounts.admin.key(),
+        );
+        Ok(())
+    }
+
+    /// Publish (or revise) a registered auditor's report for a launch.
+    pub fn publish_audit(
+        ctx: Context<PublishAudit>,
+        report_hash: [u8; 32],
+        report_uri: String,
+        passed: bool,
+    ) -> Result<()> {
+        trace_ix!(
+            "publish_audit",
+            launch_config = ctx.accounts.launch_config.key(),
+            auditor = ctx.accounts.auditor.key(),
+            passed = passed,
+        );
+        if report_uri.len() > MAX_URI_LEN {
+            return Err(MyError::UriTooLong.into());
+        }
+
+        let published_at = Clock::get()?.unix_timestamp;
+        let attestation = &mut ctx.accounts.attestation;
+        attestation.mint = ctx.accounts.launch_config.mint;
+        attestation.auditor = ctx.accounts.auditor.key();
+        attestation.report_hash = report_hash;
+        attestation.report_uri = report_uri.clone();
+        attestation.passed = passed;
+        attestation.published_at = published_at;
+        attestation.revision = attestation.revision.saturating_add(1);
+        attestation.bump = ctx.bumps.attestation;
+
+        emit!(AuditPublished {
+            version: EVENT_VERSION,
+            mint: attestation.mint,
+            auditor: attestation.auditor,
+            report_hash,
+            report_uri,
+            passed,
+            revision: attestation.revision,
+        });
+
+        Ok(())
+    }
+
+    /// Create or overwrite the social/info record of a launch. Creator only.
+    pub fn update_launch_info(ctx: Context<UpdateLaunchInfo>, info: LaunchInfoParams) -> Result<()> {
+        trace_ix!(
+            "update_launch_info",
+            launch_config = ctx.accounts.launch_config.key(),
+            creator = ctx.accounts.creator.key(),
+        );
+        info.validate()?;
+
+        let launch_info = &mut ctx.accounts.launch_info;
+        launch_info.mint = ctx.accounts.launch_config.mint;
+        launch_info.website = info.website;
+        launch_info.twitter = info.twitter;
+        launch_info.telegram = info.telegram;
+        launch_info.logo_uri = info.logo_uri;
+        launch_info.description = info.description;
+        launch_info.updated_at = Clock::get()?.unix_timestamp;
+        launch_info.bump = ctx.bumps.launch_info;
+
+        Ok(())
+    }
+
+    /// Write the Token-2022 metadata (name, symbol, URI) into a launched mint, behind the
+    /// metadata pointer it was created with. The creator becomes the metadata's update authority
+    /// and pays for the mint's growth. The launch is added to its symbol's index, and the event
+    /// flags a symbol another launch already uses; reusing one is allowed. Needs the mint
+    /// authority, so it must come before that is handed to a program.
+    pub fn init_token_metadata(ctx: Context<InitTokenMetadata>, metadata: TokenMetadataParams) -> Result<()> {
+        trace_ix!(
+            "init_token_metadata",
+            launch_config = ctx.accounts.launch_config.key(),
+            creator = ctx.accounts.creator.key(),
+            mint_authority = ctx.accounts.mint_authority.key(),
+        );
+        metadata.validate()?;
+
+        let mint = ctx.accounts.mint.to_account_info();
+        let creator = ctx.accounts.creator.key();
+        let new_len = {
+            let data = mint.try_borrow_data()?;
+            let state = StateWithExtensions::<Token2022Mint>::unpack(&data)?;
+            state.try_get_new_account_len_for_variable_len_extension(&TokenMetadata {
+                update_authority: OptionalNonZeroPubkey(creator),
+                mint: mint.key(),
+                name: metadata.name.clone(),
+                symbol: metadata.symbol.clone(),
+                uri: metadata.uri.clone(),
+                additional_metadata: Vec::new(),
+            })?
+        };
+        // Token-2022 reallocates the mint but leaves topping up its rent to the caller
+        let rent_due = Rent::get()?.minimum_balance(new_len).saturating_sub(mint.lamports());
+        if rent_due > 0 {
+            invoke(
+                &system_instruction::transfer(&creator, mint.key, rent_due),
+                &[
+                    ctx.accounts.creator.to_account_info(),
+                    mint.clone(),
+                    ctx.accounts.system_program.to_account_info(),
+                ],
+            )?;
+        }
+
+        let ix = token_metadata_instruction::initialize(
+            ctx.accounts.token_program.key,
+            mint.key,
+            &creator,
+            mint.key,
+            ctx.accounts.mint_authority.key,
+            metadata.name.clone(),
+            metadata.symbol.clone(),
+            metadata.uri.clone(),
+        );
+        invoke(
+            &ix,
+            &[
+                mint.clone(),
+                ctx.accounts.creator.to_account_info(),
+                ctx.accounts.mint_authority.to_account_info(),
+                ctx.accounts.token_program.to_account_info(),
+            ],
+        )?;
+
+        let symbol_index = &mut ctx.accounts.symbol_index;
+        let duplicate_symbol = symbol_index.launches > 0;
+        if !duplicate_symbol {
+            symbol_index.symbol = metadata.symbol_key();
+            symbol_index.first_mint = mint.key();
+            symbol_index.bump = ctx.bumps.symbol_index;
+        }
+        symbol_index.launches = symbol_index.launches.saturating_add(1);
+
+        emit!(TokenMetadataInitialized {
+            version: EVENT_VERSION,
+            mint: mint.key(),
+            update_authority: creator,
+            name: metadata.name,
+            symbol: metadata.symbol,
+            uri: metadata.uri,
+            duplicate_symbol,
+        });
+
+        Ok(())
+    }
+
+    /// Opt-in copy of a launch's Token-2022 metadata into a Metaplex metadata account, for
+    /// wallets and aggregators that only read Metaplex. Name, symbol and URI are taken from the
+    /// mint, so `init_token_metadata` must run first, and the creator becomes the update
+    /// authority until `lock_metadata` makes the copy immutable; it starts out immutable if the
+    /// launch's metadata is already locked. Needs the mint authority, like `init_token_metadata`.
+    pub fn init_metaplex_metadata(ctx: Context<InitMetaplexMetadata>) -> Result<()> {
+        trace_ix!(
+            "init_metaplex_metadata",
+            launch_config = ctx.accounts.launch_config.key(),
+            creator = ctx.accounts.creator.key(),
+            mint_authority = ctx.accounts.mint_authority.key(),
+        );
+        let mint = ctx.accounts.mint.to_account_info();
+        if !has_token_metadata(&mint)? {
+            return Err(MyError::MetadataNotInitialized.into());
+        }
+        let metadata = {
+            let data = mint.try_borrow_data()?;
+            let state = StateWithExtensions::<Token2022Mint>::unpack(&data)?;
+            state.get_variable_len_extension::<TokenMetadata>()?
+        };
+
+        let creator = ctx.accounts.creator.key();
+        let ix = create_metaplex_metadata_ix(
+            ctx.accounts.metaplex_metadata.key,
+            mint.key,
+            ctx.accounts.mint_authority.key,
+            &creator,
+            &metadata,
+            !ctx.accounts.launch_config.metadata_locked,
+        )?;
+        invoke(
+            &ix,
+            &[
+                ctx.accounts.metaplex_metadata.to_account_info(),
+                mint.clone(),
+                ctx.accounts.mint_authority.to_account_info(),
+                ctx.accounts.creator.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+                ctx.accounts.metaplex_program.to_account_info(),
+            ],
+        )?;
+
+        emit!(MetaplexMetadataCreated {
+            version: EVENT_VERSION,
+            mint: mint.key(),
+            metadata: ctx.accounts.metaplex_metadata.key(),
+            update_authority: creator,
+            is_mutable: !ctx.accounts.launch_config.metadata_locked,
+        });
+
+        Ok(())
+    }
+
+    /// Give up the update authority of a launch's token metadata for good, so its name, symbol
+    /// and URI can never change, and record the commitment in the launch config. A Metaplex copy
+    /// made by `init_metaplex_metadata` is made immutable along with it. Creator only.
+    pub fn lock_metadata(ctx: Context<LockMetadata>) -> Result<()> {
+        trace_ix!(
+            "lock_metadata",
+            launch_config = ctx.accounts.launch_config.key(),
+            creator = ctx.accounts.creator.key(),
+        );
+        let launch_config = &mut ctx.accounts.launch_config;
+        if launch_config.metadata_locked {
+            return Err(MyError::MetadataLocked.into());
+        }
+        if !has_token_metadata(&ctx.accounts.mint)? {
+            return Err(MyError::MetadataNotInitialized.into());
+        }
+
+        // The zero key is "no update authority"
+        let ix = token_metadata_instruction::update_authority(
+            ctx.accounts.token_program.key,
+            ctx.accounts.mint.key,
+            ctx.accounts.creator.key,
+            OptionalNonZeroPubkey::default(),
+        );
+        invoke(
+            &ix,
+            &[
+                ctx.accounts.mint.to_account_info(),
+                ctx.accounts.creator.to_account_info(),
+                ctx.accounts.token_program.to_account_info(),
+            ],
+        )?;
+        if metaplex_metadata_is_mutable(&ctx.accounts.metaplex_metadata)? {
+            invoke(
+                &lock_metaplex_metadata_ix(ctx.accounts.metaplex_metadata.key, ctx.accounts.creator.key)?,
+                &[
+                    ctx.accounts.metaplex_metadata.to_account_info(),
+                    ctx.accounts.creator.to_account_info(),
+                    ctx.accounts.metaplex_program.to_account_info(),
+                ],
+            )?;
+        }
+        launch_config.metadata_locked = true;
+
+        emit!(MetadataLocked {
+            version: EVENT_VERSION,
+            mint: launch_config.mint,
+            creator: launch_config.creator,
+            locked_at: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Create a tax holiday schedule for a taxed mint and hand the mint's transfer fee config
+    /// authority to the schedule PDA, so `crank_tax_holiday` can switch the fee off and back on
+    /// without the admin being online. The fee currently configured on the mint becomes the base fee.
+    pub fn init_tax_holiday_schedule(
+        ctx: Context<InitTaxHolidaySchedule>,
+        windows: Vec<TaxHolidayWindow>,
+    ) -> Result<()> {
+        trace_ix!(
+            "init_tax_holiday_schedule",
+            mint = ctx.accounts.mint.key(),
+            admin = ctx.accounts.admin.key(),
+            windows = windows.len(),
+        );
+        validate_tax_holiday_windows(&windows)?;
+
+        // The latest configured fee is the one the schedule restores outside of a holiday
+        let fee_config = transfer_fee_config(&ctx.accounts.mint)?;
+        let transfer_fee_basis_points = u16::from(fee_config.newer_transfer_fee.transfer_fee_basis_points);
+        let maximum_fee = u64::from(fee_config.newer_transfer_fee.maximum_fee);
+
+        let schedule = &mut ctx.accounts.schedule;
+        schedule.mint = ctx.accounts.mint.key();
+        schedule.admin = ctx.accounts.admin.key();
+        schedule.transfer_fee_basis_points = transfer_fee_basis_points;
+        schedule.maximum_fee = maximum_fee;
+        schedule.windows = windows;
+        schedule.bump = ctx.bumps.schedule;
+
+        // Move the transfer fee config authority from the admin to the schedule PDA
+        let set_authority_ix = token_instruction::set_authority(
+            &ctx.accounts.token_program.key(),
+            &ctx.accounts.mint.key(),
+            Some(&ctx.accounts.schedule.key()),
+            token_instruction::AuthorityType::TransferFeeConfig,
+            &ctx.accounts.admin.key(),
+            &[],
+        )?;
+
+        invoke(
+            &set_authority_ix,
+            &[
+                ctx.accounts.mint.to_account_info(),
+                ctx.accounts.admin.to_account_info(),
+                ctx.accounts.token_program.to_account_info(),
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    /// Replace the base fee and holiday windows of an existing schedule. Changes reach the mint
+    /// on the next `crank_tax_holiday`.
+    pub fn update_tax_holiday_schedule(
+        ctx: Context<UpdateTaxHolidaySchedule>,
+        transfer_fee_basis_points: u16,
+        maximum_fee: u64,
+        windows: Vec<TaxHolidayWindow>,
+    ) -> Result<()> {
+        trace_ix!(
+            "update_tax_holiday_schedule",
+            schedule = ctx.accounts.schedule.key(),
+            admin = ctx.accounts.admin.key(),
+            fee_bps = transfer_fee_basis_points,
+            maximum_fee = maximum_fee,
+            windows = windows.len(),
+        );
+        if transfer_fee_basis_points as u32 > 10_000 {
+            return Err(MyError::InvalidFeeConfig.into());
+        }
+        validate_tax_holiday_windows(&windows)?;
+
+        let schedule = &mut ctx.accounts.schedule;
+        schedule.transfer_fee_basis_points = transfer_fee_basis_points;
+        schedule.maximum_fee = maximum_fee;
+        schedule.windows = windows;
+
+        Ok(())
+    }
+
+    /// Hand the schedule to a new admin, e.g. the mint's timelock.
+    pub fn set_tax_holiday_admin(ctx: Context<UpdateTaxHolidaySchedule>, new_admin: Pubkey) -> Result<()> {
+        trace_ix!(
+            "set_tax_holiday_admin",
+            schedule = ctx.accounts.schedule.key(),
+            admin = ctx.accounts.admin.key(),
+            new_admin = new_admin,
+        );
+        ctx.accounts.schedule.admin = new_admin;
+        Ok(())
+    }
+
+    /// Permissionless crank: queue the fee the schedule wants two epochs from now.
+    /// Token-2022 only applies `SetTransferFee` two epochs after it is sent and keeps a single
+    /// pending fee, so the crank is a no-op while a change for the next epoch is still queued.
+    pub fn crank_tax_holiday(ctx: Context<CrankTaxHoliday>) -> Result<()> {
+        trace_ix!(
+            "crank_tax_holiday",
+            mint = ctx.accounts.mint.key(),
+            schedule = ctx.accounts.schedule.key(),
+        );
+        let epoch = Clock::get()?.epoch;
+        let target_epoch = epoch.saturating_add(2);
+
+        let fee_config = transfer_fee_config(&ctx.accounts.mint)?;
+        let pending_epoch = u64::from(fee_config.newer_transfer_fee.epoch);
+        let queued = fee_config.get_epoch_fee(target_epoch);
+        let queued_bps = u16::from(queued.transfer_fee_basis_points);
+        let queued_maximum_fee = u64::from(queued.maximum_fee);
+
+        // Overwriting a fee queued for the next epoch would cancel it before it ever applied
+        if pending_epoch == epoch.saturating_add(1) {
+            msg!("fee change pending for epoch {}, nothing to crank", pending_epoch);
+            return Ok(());
+        }
+
+        let schedule = &ctx.accounts.schedule;
+        let (transfer_fee_basis_points, maximum_fee) = schedule.fee_for_epoch(target_epoch);
+        if transfer_fee_basis_points == queued_bps && maximum_fee == queued_maximum_fee {
+            msg!("fee for epoch {} already up to date", target_epoch);
+            return Ok(());
+        }
+
+        let set_fee_ix = set_transfer_fee(
+            &ctx.accounts.token_program.key(),
+            &ctx.accounts.mint.key(),
+            &schedule.key(),
+            &[],
+            transfer_fee_basis_points,
+            maximum_fee,
+        )?;
+
+        let mint_key = ctx.accounts.mint.key();
+        let seeds: &[&[u8]] = &[TAX_HOLIDAY_SEED, mint_key.as_ref(), &[schedule.bump]];
+        invoke_signed(
+            &set_fee_ix,
+            &[
+                ctx.accounts.mint.to_account_info(),
+                schedule.to_account_info(),
+                ctx.accounts.token_program.to_account_info(),
+            ],
+            &[seeds],
+        )?;
+
+        msg!(
+            "transfer fee for epoch {} set to {} bps (max {})",
+            target_epoch,
+            transfer_fee_basis_points,
+            maximum_fee
+        );
+
+        Ok(())
+    }
+
+    /// Close a tax holiday schedule and return the transfer fee config authority to the admin.
+    pub fn close_tax_holiday_schedule(ctx: Context<CloseTaxHolidaySchedule>) -> Result<()> {
+        trace_ix!(
+            "close_tax_holiday_schedule",
+            mint = ctx.accounts.mint.key(),
+            schedule = ctx.accounts.schedule.key(),
+            admin = ctx.accounts.admin.key(),
+        );
+        let schedule = &ctx.accounts.schedule;
+        let set_authority_ix = token_instruction::set_authority(
+            &ctx.accounts.token_program.key(),
+            &ctx.accounts.mint.key(),
+            Some(&ctx.accounts.admin.key()),
+            token_instruction::AuthorityType::TransferFeeConfig,
+            &schedule.key(),
+            &[],
+        )?;
+
+        let mint_key = ctx.accounts.mint.key();
+        let seeds: &[&[u8]] = &[TAX_HOLIDAY_SEED, mint_key.as_ref(), &[schedule.bump]];
+        invoke_signed(
+            &set_authority_ix,
+            &[
+                ctx.accounts.mint.to_account_info(),
+                schedule.to_account_info(),
+                ctx.accounts.token_program.to_account_info(),
+            ],
+            &[seeds],
+        )?;
+
+        Ok(())
+    }
+
+    /// Pay an exempt wallet the fees recorded against it out of the mint's fee vault, the
+    /// exemption list's associated token account. The payout is grossed up so the wallet
+    /// receives the full rebate after the vault transfer itself is taxed.
+    pub fn claim_fee_rebate(ctx: Context<ClaimFeeRebate>) -> Result<()> {
+        trace_ix!(
+            "claim_fee_rebate",
+            mint = ctx.accounts.mint.key(),
+            wallet = ctx.accounts.wallet.key(),
+            destination = ctx.accounts.destination.key(),
+        );
+        ensure_not_paused(&ctx.accounts.mint_pause)?;
+        let mint_key = ctx.accounts.exemption_list.mint;
+        let index = ctx
+            .accounts
+            .exemption_list
+            .position(&ctx.accounts.wallet.key())
+            .ok_or(MyError::NotExempt)?;
+        let rebate = ctx.accounts.exemption_list.exemptions[index].rebate_owed;
+        if rebate == 0 {
+            return Err(MyError::NothingToClaim.into());
+        }
+
+        let vault = token_account(&ctx.accounts.fee_vault)?;
+        if vault.mint != mint_key || vault.owner != ctx.accounts.exemption_list.key() {
+            return Err(MyError::InvalidTokenAccount.into());
+        }
+        let destination = token_account(&ctx.accounts.destination)?;
+        if destination.mint != mint_key || destination.owner != ctx.accounts.wallet.key() {
+            return Err(MyError::InvalidTokenAccount.into());
+        }
+
+        let fee_config = transfer_fee_config(&ctx.accounts.mint)?;
+        let (bps, maximum_fee) = epoch_fee_terms(&fee_config)?;
+        let gross_amount = math::pre_fee_amount(rebate, bps, maximum_fee).ok_or(MyError::MathOverflow)?;
+        if vault.amount < gross_amount {
+            return Err(MyError::InsufficientFeeVault.into());
+        }
+
+        let bump = ctx.accounts.exemption_list.bump;
+        let seeds: &[&[u8]] = &[FEE_EXEMPTION_SEED, mint_key.as_ref(), &[bump]];
+        transfer_tokens(
+            &ctx.accounts.token_program,
+            &ctx.accounts.fee_vault,
+            &ctx.accounts.mint,
+            &ctx.accounts.destination,
+            &ctx.accounts.exemption_list.to_account_info(),
+            &[seeds],
+            gross_amount,
+        )?;
+
+        ctx.accounts.exemption_list.exemptions[index].rebate_owed = 0;
+
+        emit!(FeeRebateClaimed {
+            version: EVENT_VERSION,
+            mint: mint_key,
+            wallet: ctx.accounts.wallet.key(),
+            rebate,
+            gross_amount,
+        });
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct CreateTaxedToken<'info> {
+    /// The payer who funds account creation and will be mint authority by default
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The mint account to be created (passed as system account)
+    /// CHECK: this is a Keypair signer for the new mint account
+    #[account(mut)]
+    pub mint: Signer<'info>,
+
+    /// Authority that will be set as the mint authority
+    /// CHECK: this account must be a signer (the mint authority)
+    pub mint_authority: Signer<'info>,
+
+    /// Authority that will be allowed to withdraw withheld fees
+    /// CHECK: this account must be a signer (fee withdraw authority)
+    pub fee_withdraw_authority: Signer<'info>,
+
+    /// Optional freeze authority
+    /// CHECK: any pubkey
+    pub freeze_authority: UncheckedAccount<'info>,
+
+    /// The token-2022 program
+    /// CHECK: must be the SPL Token-2022 program id
+    #[account(address = spl_token_2022::id())]
+    pub token_program: UncheckedAccount<'info>,
+
+    #[account(mut, seeds = [PLATFORM_CONFIG_SEED], bump = platform_config.bump)]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    /// Creator bond, required when the platform config asks for one
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + CreatorBond::INIT_SPACE,
+        seeds = [CREATOR_BOND_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub creator_bond: Option<Account<'info, CreatorBond>>,
+
+    /// Devnet faucet; passing it at creation is the only way to enable one for the mint
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Faucet::INIT_SPACE,
+        seeds = [FAUCET_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub devnet_faucet: Option<Account<'info, Faucet>>,
+
+    /// Receives the platform's share of the launch fee
+    /// CHECK: must match platform_config.treasury
+    #[account(mut, address = platform_config.treasury @ MyError::InvalidTreasury)]
+    pub treasury: UncheckedAccount<'info>,
+
+    /// Receives the insurance share of the launch fee
+    #[account(mut, seeds = [INSURANCE_VAULT_SEED], bump = insurance_vault.bump)]
+    pub insurance_vault: Account<'info, InsuranceVault>,
+
+    /// Previous epoch's gauge; launches in its featured list pay the discounted fee
+    pub featured_gauge: Option<Account<'info, GaugeEpoch>>,
+
+    /// Pyth SOL/USD price update; required while the launch fee is set in USD
+    /// CHECK: owner, feed, freshness and confidence are validated when the fee is charged
+    pub price_update: Option<UncheckedAccount<'info>>,
+
+    /// Platform attestor co-signing the launch; required while the platform config asks for it
+    pub attestor: Option<Signer<'info>>,
+
+    /// White-label partner the launch is made through; takes its share of the launch fee
+    #[account(mut, seeds = [PARTNER_SEED, partner.namespace.as_bytes()], bump = partner.bump)]
+    pub partner: Option<Account<'info, Partner>>,
+
+    /// Platform staking pool; required while the platform routes a share of launch fees to it
+    #[account(mut, seeds = [PLATFORM_STAKING_SEED], bump = staking_pool.bump)]
+    pub staking_pool: Option<Account<'info, PlatformStaking>>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + LaunchConfig::INIT_SPACE,
+        seeds = [LAUNCH_CONFIG_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub launch_config: Account<'info, LaunchConfig>,
+
+    /// Reputation of the creator (the payer), created on their first launch
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + CreatorReputation::INIT_SPACE,
+        seeds = [REPUTATION_SEED, payer.key().as_ref()],
+        bump,
+    )]
+    pub creator_reputation: Account<'info, CreatorReputation>,
+
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
 pub struct CreateSoulboundToken<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
 
-    /// The mint account to be created
-    /// CHECK: this is a Keypair signer for the new mint account
-    #[account(mut)]
-    pub mint: Signer<'info>,
+    /// The mint account to be created
+    /// CHECK: this is a Keypair signer for the new mint account
+    #[account(mut)]
+    pub mint: Signer<'info>,
+
+    /// Authority that will be set as mint authority
+    /// CHECK: this account must be a signer (the mint authority)
+    pub mint_authority: Signer<'info>,
+
+    /// Optional freeze authority
+    /// CHECK: any pubkey
+    pub freeze_authority: UncheckedAccount<'info>,
+
+    /// Token-2022 program
+    /// CHECK: must be the SPL Token-2022 program id
+    #[account(address = spl_token_2022::id())]
+    pub token_program: UncheckedAccount<'info>,
+
+    #[account(mut, seeds = [PLATFORM_CONFIG_SEED], bump = platform_config.bump)]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    /// Creator bond, required when the platform config asks for one
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + CreatorBond::INIT_SPACE,
+        seeds = [CREATOR_BOND_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub creator_bond: Option<Account<'info, CreatorBond>>,
+
+    /// Devnet faucet; passing it at creation is the only way to enable one for the mint
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Faucet::INIT_SPACE,
+        seeds = [FAUCET_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub devnet_faucet: Option<Account<'info, Faucet>>,
+
+    /// Receives the platform's share of the launch fee
+    /// CHECK: must match platform_config.treasury
+    #[account(mut, address = platform_config.treasury @ MyError::InvalidTreasury)]
+    pub treasury: UncheckedAccount<'info>,
+
+    /// Receives the insurance share of the launch fee
+    #[account(mut, seeds = [INSURANCE_VAULT_SEED], bump = insurance_vault.bump)]
+    pub insurance_vault: Account<'info, InsuranceVault>,
+
+    /// Previous epoch's gauge; launches in its featured list pay the discounted fee
+    pub featured_gauge: Option<Account<'info, GaugeEpoch>>,
+
+    /// Pyth SOL/USD price update; required while the launch fee is set in USD
+    /// CHECK: owner, feed, freshness and confidence are validated when the fee is charged
+    pub price_update: Option<UncheckedAccount<'info>>,
+
+    /// Platform attestor co-signing the launch; required while the platform config asks for it
+    pub attestor: Option<Signer<'info>>,
+
+    /// White-label partner the launch is made through; takes its share of the launch fee
+    #[account(mut, seeds = [PARTNER_SEED, partner.namespace.as_bytes()], bump = partner.bump)]
+    pub partner: Option<Account<'info, Partner>>,
+
+    /// Platform staking pool; required while the platform routes a share of launch fees to it
+    #[account(mut, seeds = [PLATFORM_STAKING_SEED], bump = staking_pool.bump)]
+    pub staking_pool: Option<Account<'info, PlatformStaking>>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + LaunchConfig::INIT_SPACE,
+        seeds = [LAUNCH_CONFIG_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub launch_config: Account<'info, LaunchConfig>,
+
+    /// Reputation of the creator (the payer), created on their first launch
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + CreatorReputation::INIT_SPACE,
+        seeds = [REPUTATION_SEED, payer.key().as_ref()],
+        bump,
+    )]
+    pub creator_reputation: Account<'info, CreatorReputation>,
+
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+#[instruction(name: String)]
+pub struct PublishTemplate<'info> {
+    #[account(mut)]
+    pub publisher: Signer<'info>,
+
+    #[account(seeds = [PLATFORM_CONFIG_SEED], bump = platform_config.bump)]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(
+        init,
+        payer = publisher,
+        space = 8 + Template::INIT_SPACE,
+        seeds = [TEMPLATE_SEED, publisher.key().as_ref(), name.as_bytes()],
+        bump,
+    )]
+    pub template: Account<'info, Template>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RetireTemplate<'info> {
+    #[account(mut)]
+    pub publisher: Signer<'info>,
+
+    #[account(
+        mut,
+        close = publisher,
+        has_one = publisher @ MyError::Unauthorized,
+        seeds = [TEMPLATE_SEED, publisher.key().as_ref(), template.name.as_bytes()],
+        bump = template.bump,
+    )]
+    pub template: Account<'info, Template>,
+}
+
+#[derive(Accounts)]
+pub struct CreateFromTemplate<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The mint account to be created
+    /// CHECK: this is a Keypair signer for the new mint account
+    #[account(mut)]
+    pub mint: Signer<'info>,
+
+    /// Authority that will be set as mint authority
+    pub mint_authority: Signer<'info>,
+
+    /// Withheld-fee withdraw authority, required for taxed templates
+    pub fee_withdraw_authority: Option<Signer<'info>>,
+
+    /// Optional freeze authority
+    /// CHECK: any pubkey
+    pub freeze_authority: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [TEMPLATE_SEED, template.publisher.as_ref(), template.name.as_bytes()],
+        bump = template.bump,
+    )]
+    pub template: Account<'info, Template>,
+
+    /// Token-2022 program
+    /// CHECK: must be the SPL Token-2022 program id
+    #[account(address = spl_token_2022::id())]
+    pub token_program: UncheckedAccount<'info>,
+
+    #[account(mut, seeds = [PLATFORM_CONFIG_SEED], bump = platform_config.bump)]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    /// Creator bond, required when the platform config asks for one
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + CreatorBond::INIT_SPACE,
+        seeds = [CREATOR_BOND_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub creator_bond: Option<Account<'info, CreatorBond>>,
+
+    /// Devnet faucet; passing it at creation is the only way to enable one for the mint
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Faucet::INIT_SPACE,
+        seeds = [FAUCET_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub devnet_faucet: Option<Account<'info, Faucet>>,
+
+    /// Receives the platform's share of the launch fee
+    /// CHECK: must match platform_config.treasury
+    #[account(mut, address = platform_config.treasury @ MyError::InvalidTreasury)]
+    pub treasury: UncheckedAccount<'info>,
+
+    /// Receives the insurance share of the launch fee
+    #[account(mut, seeds = [INSURANCE_VAULT_SEED], bump = insurance_vault.bump)]
+    pub insurance_vault: Account<'info, InsuranceVault>,
+
+    /// Previous epoch's gauge; launches in its featured list pay the discounted fee
+    pub featured_gauge: Option<Account<'info, GaugeEpoch>>,
+
+    /// Pyth SOL/USD price update; required while the launch fee is set in USD
+    /// CHECK: owner, feed, freshness and confidence are validated when the fee is charged
+    pub price_update: Option<UncheckedAccount<'info>>,
+
+    /// Platform attestor co-signing the launch; required while the platform config asks for it
+    pub attestor: Option<Signer<'info>>,
+
+    /// White-label partner the launch is made through; takes its share of the launch fee
+    #[account(mut, seeds = [PARTNER_SEED, partner.namespace.as_bytes()], bump = partner.bump)]
+    pub partner: Option<Account<'info, Partner>>,
+
+    /// Platform staking pool; required while the platform routes a share of launch fees to it
+    #[account(mut, seeds = [PLATFORM_STAKING_SEED], bump = staking_pool.bump)]
+    pub staking_pool: Option<Account<'info, PlatformStaking>>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + LaunchConfig::INIT_SPACE,
+        seeds = [LAUNCH_CONFIG_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub launch_config: Account<'info, LaunchConfig>,
+
+    /// Reputation of the creator (the payer), created on their first launch
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + CreatorReputation::INIT_SPACE,
+        seeds = [REPUTATION_SEED, payer.key().as_ref()],
+        bump,
+    )]
+    pub creator_reputation: Account<'info, CreatorReputation>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CloneTokenConfig<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The mint account to be created
+    /// CHECK: this is a Keypair signer for the new mint account
+    #[account(mut)]
+    pub mint: Signer<'info>,
+
+    /// Authority that will be set as mint authority
+    pub mint_authority: Signer<'info>,
+
+    /// Withheld-fee withdraw authority, required when cloning a taxed launch
+    pub fee_withdraw_authority: Option<Signer<'info>>,
+
+    /// Optional freeze authority
+    /// CHECK: any pubkey
+    pub freeze_authority: UncheckedAccount<'info>,
+
+    /// Launch whose settings are copied
+    #[account(
+        seeds = [LAUNCH_CONFIG_SEED, source_launch_config.mint.as_ref()],
+        bump = source_launch_config.bump,
+    )]
+    pub source_launch_config: Account<'info, LaunchConfig>,
+
+    /// Token-2022 program
+    /// CHECK: must be the SPL Token-2022 program id
+    #[account(address = spl_token_2022::id())]
+    pub token_program: UncheckedAccount<'info>,
+
+    #[account(mut, seeds = [PLATFORM_CONFIG_SEED], bump = platform_config.bump)]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    /// Creator bond, required when the platform config asks for one
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + CreatorBond::INIT_SPACE,
+        seeds = [CREATOR_BOND_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub creator_bond: Option<Account<'info, CreatorBond>>,
+
+    /// Devnet faucet; passing it at creation is the only way to enable one for the mint
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Faucet::INIT_SPACE,
+        seeds = [FAUCET_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub devnet_faucet: Option<Account<'info, Faucet>>,
+
+    /// Receives the platform's share of the launch fee
+    /// CHECK: must match platform_config.treasury
+    #[account(mut, address = platform_config.treasury @ MyError::InvalidTreasury)]
+    pub treasury: UncheckedAccount<'info>,
+
+    /// Receives the insurance share of the launch fee
+    #[account(mut, seeds = [INSURANCE_VAULT_SEED], bump = insurance_vault.bump)]
+    pub insurance_vault: Account<'info, InsuranceVault>,
+
+    /// Previous epoch's gauge; launches in its featured list pay the discounted fee
+    pub featured_gauge: Option<Account<'info, GaugeEpoch>>,
+
+    /// Pyth SOL/USD price update; required while the launch fee is set in USD
+    /// CHECK: owner, feed, freshness and confidence are validated when the fee is charged
+    pub price_update: Option<UncheckedAccount<'info>>,
+
+    /// Platform attestor co-signing the launch; required while the platform config asks for it
+    pub attestor: Option<Signer<'info>>,
+
+    /// White-label partner the launch is made through; takes its share of the launch fee
+    #[account(mut, seeds = [PARTNER_SEED, partner.namespace.as_bytes()], bump = partner.bump)]
+    pub partner: Option<Account<'info, Partner>>,
+
+    /// Platform staking pool; required while the platform routes a share of launch fees to it
+    #[account(mut, seeds = [PLATFORM_STAKING_SEED], bump = staking_pool.bump)]
+    pub staking_pool: Option<Account<'info, PlatformStaking>>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + LaunchConfig::INIT_SPACE,
+        seeds = [LAUNCH_CONFIG_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub launch_config: Account<'info, LaunchConfig>,
+
+    /// Reputation of the creator (the payer), created on their first launch
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + CreatorReputation::INIT_SPACE,
+        seeds = [REPUTATION_SEED, payer.key().as_ref()],
+        bump,
+    )]
+    pub creator_reputation: Account<'info, CreatorReputation>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreateTokensBatch<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Authority that will be set as mint authority of every mint
+    pub mint_authority: Signer<'info>,
+
+    /// Withheld-fee withdraw authority, required for taxed batches
+    pub fee_withdraw_authority: Option<Signer<'info>>,
+
+    /// Optional freeze authority
+    /// CHECK: any pubkey
+    pub freeze_authority: UncheckedAccount<'info>,
+
+    /// Token-2022 program
+    /// CHECK: must be the SPL Token-2022 program id
+    #[account(address = spl_token_2022::id())]
+    pub token_program: UncheckedAccount<'info>,
+
+    #[account(mut, seeds = [PLATFORM_CONFIG_SEED], bump = platform_config.bump)]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    /// Receives the platform's share of the launch fees
+    /// CHECK: must match platform_config.treasury
+    #[account(mut, address = platform_config.treasury @ MyError::InvalidTreasury)]
+    pub treasury: UncheckedAccount<'info>,
+
+    /// Receives the insurance share of the launch fees
+    #[account(mut, seeds = [INSURANCE_VAULT_SEED], bump = insurance_vault.bump)]
+    pub insurance_vault: Account<'info, InsuranceVault>,
+
+    /// Pyth SOL/USD price update; required while the launch fee is set in USD
+    /// CHECK: owner, feed, freshness and confidence are validated when the fee is charged
+    pub price_update: Option<UncheckedAccount<'info>>,
+
+    /// Platform attestor co-signing the launch; required while the platform config asks for it
+    pub attestor: Option<Signer<'info>>,
+
+    /// White-label partner the launch is made through; takes its share of the launch fee
+    #[account(mut, seeds = [PARTNER_SEED, partner.namespace.as_bytes()], bump = partner.bump)]
+    pub partner: Option<Account<'info, Partner>>,
+
+    /// Platform staking pool; required while the platform routes a share of launch fees to it
+    #[account(mut, seeds = [PLATFORM_STAKING_SEED], bump = staking_pool.bump)]
+    pub staking_pool: Option<Account<'info, PlatformStaking>>,
+
+    /// Reputation of the creator (the payer), created on their first launch
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + CreatorReputation::INIT_SPACE,
+        seeds = [REPUTATION_SEED, payer.key().as_ref()],
+        bump,
+    )]
+    pub creator_reputation: Account<'info, CreatorReputation>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct TransferWithFee<'info> {
+    /// Source token account (writable)
+    /// CHECK: owned by Token-2022; the transfer checks it is an account of the mint
+    #[account(mut, owner = spl_token_2022::id())]
+    pub source: UncheckedAccount<'info>,
+
+    /// Mint
+    /// CHECK: owned by Token-2022, so its fee config can be trusted
+    #[account(owner = spl_token_2022::id())]
+    pub mint: UncheckedAccount<'info>,
+
+    /// Destination token account (writable)
+    /// CHECK: owned by Token-2022; the transfer checks it is an account of the mint
+    #[account(mut, owner = spl_token_2022::id())]
+    pub destination: UncheckedAccount<'info>,
+
+    /// Authority signing for source
+    pub authority: Signer<'info>,
+
+    /// Token-2022 program
+    /// CHECK: must be Token-2022 id
+    #[account(address = spl_token_2022::id())]
+    pub token_program: UncheckedAccount<'info>,
+
+    /// Exemption list of the mint, where exempt parties accrue the withheld fee as a rebate;
+    /// may not exist. Required rather than optional so a transfer touching an exempt wallet
+    /// can't leave its rebate unrecorded.
+    /// CHECK: PDA of the mint, read as a FeeExemptionList when it exists
+    #[account(mut, seeds = [FEE_EXEMPTION_SEED, mint.key().as_ref()], bump)]
+    pub exemption_list: UncheckedAccount<'info>,
+
+    /// Optional stats of the mint, updated with this transfer. Opt-in: leaving it out keeps
+    /// the transfer off the stats
+    #[account(
+        mut,
+        seeds = [MINT_STATS_SEED, mint.key().as_ref()],
+        bump = mint_stats.bump,
+    )]
+    pub mint_stats: Option<Account<'info, MintStats>>,
+
+    /// Optional pool registry of the mint, splitting this transfer's stats volume into buys
+    /// and sells
+    #[account(
+        seeds = [POOL_REGISTRY_SEED, mint.key().as_ref()],
+        bump = pool_registry.bump,
+    )]
+    pub pool_registry: Option<Account<'info, PoolRegistry>>,
+}
+
+#[derive(Accounts)]
+pub struct SetComplianceFlags<'info> {
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = creator @ MyError::Unauthorized,
+        seeds = [LAUNCH_CONFIG_SEED, launch_config.mint.as_ref()],
+        bump = launch_config.bump,
+    )]
+    pub launch_config: Account<'info, LaunchConfig>,
+}
+
+#[derive(Accounts)]
+#[instruction(wallet: Pubkey)]
+pub struct AttestWallet<'info> {
+    #[account(mut)]
+    pub attestor: Signer<'info>,
+
+    #[account(seeds = [LAUNCH_CONFIG_SEED, launch_config.mint.as_ref()], bump = launch_config.bump)]
+    pub launch_config: Account<'info, LaunchConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = attestor,
+        space = 8 + WalletAttestation::INIT_SPACE,
+        seeds = [WALLET_ATTESTATION_SEED, launch_config.mint.as_ref(), wallet.as_ref()],
+        bump,
+    )]
+    pub attestation: Account<'info, WalletAttestation>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ComplianceFreeze<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        has_one = mint,
+        seeds = [LAUNCH_CONFIG_SEED, mint.key().as_ref()],
+        bump = launch_config.bump,
+    )]
+    pub launch_config: Account<'info, LaunchConfig>,
+
+    /// CHECK: checked against launch_config.mint
+    pub mint: UncheckedAccount<'info>,
+
+    /// CHECK: mint and owner are validated in the handler
+    #[account(mut)]
+    pub token_account: UncheckedAccount<'info>,
+
+    /// Owner of the token account
+    /// CHECK: compared with the token account's owner in the handler
+    pub wallet: UncheckedAccount<'info>,
+
+    /// Attestation of the wallet; may not exist
+    /// CHECK: PDA of the mint and wallet, read by the compliance handlers
+    #[account(seeds = [WALLET_ATTESTATION_SEED, mint.key().as_ref(), wallet.key().as_ref()], bump)]
+    pub attestation: UncheckedAccount<'info>,
+
+    /// Freeze order against the token account; may not exist
+    /// CHECK: PDA of the mint and token account, read by thaw_compliant
+    #[account(seeds = [FREEZE_ORDER_SEED, mint.key().as_ref(), token_account.key().as_ref()], bump)]
+    pub freeze_order: UncheckedAccount<'info>,
+
+    /// Freeze authority of the mint
+    /// CHECK: PDA of the mint, signs the freeze and thaw
+    #[account(seeds = [COMPLIANCE_AUTHORITY_SEED, mint.key().as_ref()], bump)]
+    pub compliance_authority: UncheckedAccount<'info>,
+
+    /// Token-2022 program
+    /// CHECK: must be Token-2022 id
+    #[account(address = spl_token_2022::id())]
+    pub token_program: UncheckedAccount<'info>,
+}
+
+impl ComplianceFreeze<'_> {
+    /// Compliance rules of the launch, once the token account and freeze authority check out
+    fn flags(&self) -> Result<ComplianceFlags> {
+        let flags = self.launch_config.compliance.ok_or(MyError::ComplianceNotConfigured)?;
+        let account = token_account(&self.token_account)?;
+        if account.mint != self.mint.key() || account.owner != self.wallet.key() {
+            return Err(MyError::InvalidTokenAccount.into());
+        }
+        check_compliance_authority(&self.mint, &self.compliance_authority.key())?;
+        Ok(flags)
+    }
+
+    /// The wallet's attestation, if one has been recorded
+    fn attestation(&self) -> Result<Option<WalletAttestation>> {
+        if self.attestation.data_is_empty() {
+            return Ok(None);
+        }
+        let attestation =
+            WalletAttestation::try_deserialize(&mut &self.attestation.try_borrow_data()?[..])?;
+        Ok(Some(attestation))
+    }
+
+    fn set_frozen(&self, frozen: bool, bump: u8) -> Result<()> {
+        set_compliance_frozen(
+            &self.token_program,
+            &self.token_account,
+            &self.mint,
+            &self.compliance_authority,
+            bump,
+            frozen,
+        )
+    }
+}
+
+#[derive(Accounts)]
+pub struct IssueFreezeOrder<'info> {
+    #[account(mut)]
+    pub officer: Signer<'info>,
+
+    #[account(
+        has_one = mint,
+        seeds = [LAUNCH_CONFIG_SEED, mint.key().as_ref()],
+        bump = launch_config.bump,
+    )]
+    pub launch_config: Account<'info, LaunchConfig>,
+
+    /// CHECK: checked against launch_config.mint
+    pub mint: UncheckedAccount<'info>,
+
+    /// CHECK: mint is validated in the handler
+    #[account(mut)]
+    pub token_account: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = officer,
+        space = 8 + FreezeOrder::INIT_SPACE,
+        seeds = [FREEZE_ORDER_SEED, mint.key().as_ref(), token_account.key().as_ref()],
+        bump,
+    )]
+    pub freeze_order: Account<'info, FreezeOrder>,
+
+    /// Freeze authority of the mint
+    /// CHECK: PDA of the mint, signs the freeze
+    #[account(seeds = [COMPLIANCE_AUTHORITY_SEED, mint.key().as_ref()], bump)]
+    pub compliance_authority: UncheckedAccount<'info>,
+
+    /// Token-2022 program
+    /// CHECK: must be Token-2022 id
+    #[account(address = spl_token_2022::id())]
+    pub token_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct LiftFreezeOrder<'info> {
+    pub officer: Signer<'info>,
+
+    #[account(
+        has_one = mint,
+        seeds = [LAUNCH_CONFIG_SEED, mint.key().as_ref()],
+        bump = launch_config.bump,
+    )]
+    pub launch_config: Account<'info, LaunchConfig>,
+
+    /// CHECK: checked against launch_config.mint
+    pub mint: UncheckedAccount<'info>,
+
+    /// CHECK: checked against freeze_order.token_account
+    #[account(mut)]
+    pub token_account: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        has_one = token_account,
+        seeds = [FREEZE_ORDER_SEED, mint.key().as_ref(), token_account.key().as_ref()],
+        bump = freeze_order.bump,
+    )]
+    pub freeze_order: Account<'info, FreezeOrder>,
+
+    /// Freeze authority of the mint
+    /// CHECK: PDA of the mint, signs the thaw
+    #[account(seeds = [COMPLIANCE_AUTHORITY_SEED, mint.key().as_ref()], bump)]
+    pub compliance_authority: UncheckedAccount<'info>,
+
+    /// Token-2022 program
+    /// CHECK: must be Token-2022 id
+    #[account(address = spl_token_2022::id())]
+    pub token_program: UncheckedAccount<'info>,
+}
+
+pub const FREEZE_ORDER_SEED: &[u8] = b"freeze_order";
+
+/// Latest compliance-officer freeze of one token account, with its public justification.
+/// Kept after it is lifted; a new order overwrites it.
+#[account]
+#[derive(InitSpace)]
+pub struct FreezeOrder {
+    pub mint: Pubkey,
+    pub token_account: Pubkey,
+    pub officer: Pubkey,
+    #[max_len(MAX_URI_LEN)]
+    pub justification_uri: String,
+    /// Hash of the justification document behind the URI
+    pub justification_hash: [u8; 32],
+    pub issued_at: i64,
+    /// 0 while the order stands
+    pub lifted_at: i64,
+    pub bump: u8,
+}
+
+impl FreezeOrder {
+    pub fn is_active(&self) -> bool {
+        self.issued_at != 0 && self.lifted_at == 0
+    }
+}
+
+#[event(discriminator = [125, 217, 221, 134, 15, 95, 79, 229])]
+pub struct ComplianceOfficerSet {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub officer: Option<Pubkey>,
+}
+
+#[event(discriminator = [55, 170, 248, 91, 102, 107, 123, 159])]
+pub struct FreezeOrderIssued {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub token_account: Pubkey,
+    pub officer: Pubkey,
+    pub justification_uri: String,
+    pub justification_hash: [u8; 32],
+}
+
+#[event(discriminator = [145, 8, 147, 74, 54, 49, 218, 233])]
+pub struct FreezeOrderLifted {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub token_account: Pubkey,
+    pub officer: Pubkey,
+    pub justification_uri: String,
+    pub justification_hash: [u8; 32],
+}
+
+pub const WALLET_ATTESTATION_SEED: &[u8] = b"wallet_attestation";
+/// Freeze authority a compliance launch hands its mint's freezes to
+pub const COMPLIANCE_AUTHORITY_SEED: &[u8] = b"compliance_authority";
+
+/// Attestation rules of a compliance launch. Enforcement rests on the attestor: the program
+/// only checks that a holder's attestation matches these rules.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub struct ComplianceFlags {
+    /// Key allowed to attest wallets
+    pub attestor: Pubkey,
+    /// Hash of the restricted-jurisdiction list holders must have been screened against
+    pub restricted_jurisdictions_hash: [u8; 32],
+    pub accreditation_required: bool,
+}
+
+impl ComplianceFlags {
+    /// Whether `attestation` is current and meets these rules
+    pub fn admits(&self, attestation: Option<&WalletAttestation>, now: i64) -> bool {
+        attestation.is_some_and(|attestation| {
+            attestation.attestor == self.attestor
+                && attestation.expires_at > now
+                && attestation.jurisdictions_hash == self.restricted_jurisdictions_hash
+                && (attestation.accredited || !self.accreditation_required)
+        })
+    }
+}
+
+/// A compliance attestor's check of one wallet for one mint.
+#[account]
+#[derive(InitSpace)]
+pub struct WalletAttestation {
+    pub mint: Pubkey,
+    pub wallet: Pubkey,
+    pub attestor: Pubkey,
+    /// Restricted-jurisdiction list the wallet was screened against
+    pub jurisdictions_hash: [u8; 32],
+    pub accredited: bool,
+    pub expires_at: i64,
+    pub bump: u8,
+}
+
+#[event(discriminator = [170, 33, 31, 11, 82, 58, 250, 92])]
+pub struct ComplianceFlagsUpdated {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub flags: Option<ComplianceFlags>,
+}
+
+#[event(discriminator = [119, 224, 21, 16, 122, 86, 119, 167])]
+pub struct WalletAttested {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub wallet: Pubkey,
+    pub accredited: bool,
+    pub expires_at: i64,
+}
+
+#[event(discriminator = [232, 246, 124, 95, 227, 64, 69, 152])]
+pub struct ComplianceFrozen {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub token_account: Pubkey,
+    pub wallet: Pubkey,
+}
+
+#[event(discriminator = [188, 205, 139, 201, 77, 215, 97, 109])]
+pub struct ComplianceThawed {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub token_account: Pubkey,
+    pub wallet: Pubkey,
+}
+
+#[derive(Accounts)]
+pub struct InitMintStats<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: must be a Token-2022 mint
+    #[account(owner = spl_token_2022::id())]
+    pub mint: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + MintStats::INIT_SPACE,
+        seeds = [MINT_STATS_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub mint_stats: Account<'info, MintStats>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub const MINT_STATS_SEED: &[u8] = b"mint_stats";
+
+/// Self-reported transfer counters of a mint, kept by `transfer_with_fee` for transfers that
+/// opt in by passing this account. Transfers made directly through Token-2022 aren't seen, so
+/// every counter is a lower bound on the mint's activity, not its total, and no holder count is
+/// kept since one can't be derived from a subset of transfers.
+#[account]
+#[derive(InitSpace)]
+pub struct MintStats {
+    pub mint: Pubkey,
+    pub transfers: u64,
+    pub volume: u128,
+    pub fees: u128,
+    pub last_transfer_slot: u64,
+    /// Solana epoch the `epoch_*` counters cover
+    pub epoch: u64,
+    pub epoch_volume: u64,
+    pub epoch_fees: u64,
+    /// Reported volume out of and into registered pool vaults. Partial: swaps an AMM makes
+    /// itself never pass through `transfer_with_fee`
+    pub buy_volume: u128,
+    pub sell_volume: u128,
+    /// Part of `fees` already added to the creator's profile by `sync_creator_fees`
+    pub fees_synced: u128,
+    pub bump: u8,
+}
+
+impl MintStats {
+    pub fn record_transfer(&mut self, amount: u64, fee: u64, slot: u64, epoch: u64) -> Result<()> {
+        if epoch != self.epoch {
+            self.epoch = epoch;
+            self.epoch_volume = 0;
+            self.epoch_fees = 0;
+        }
+        self.epoch_volume = self.epoch_volume.saturating_add(amount);
+        self.epoch_fees = self.epoch_fees.saturating_add(fee);
+        self.transfers = self.transfers.saturating_add(1);
+        self.volume = self.volume.checked_add(amount as u128).ok_or(MyError::MathOverflow)?;
+        self.fees = self.fees.checked_add(fee as u128).ok_or(MyError::MathOverflow)?;
+        self.last_transfer_slot = slot;
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(stream_id: u64)]
+pub struct CreateStream<'info> {
+    #[account(mut)]
+    pub sender: Signer<'info>,
+
+    /// Mint being streamed
+    /// CHECK: must be a Token-2022 mint
+    #[account(owner = spl_token_2022::id())]
+    pub mint: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = sender,
+        space = 8 + Stream::INIT_SPACE,
+        seeds = [STREAM_SEED, sender.key().as_ref(), mint.key().as_ref(), &stream_id.to_le_bytes()],
+        bump,
+    )]
+    pub stream: Account<'info, Stream>,
+
+    /// Sender's token account funding the stream
+    /// CHECK: validated by the token program during the transfer
+    #[account(mut)]
+    pub sender_token_account: UncheckedAccount<'info>,
+
+    /// Escrow: token account of the mint owned by the stream PDA
+    /// CHECK: mint and owner are validated in the handler
+    #[account(mut)]
+    pub vault: UncheckedAccount<'info>,
+
+    /// Token-2022 program
+    /// CHECK: must be Token-2022 id
+    #[account(address = spl_token_2022::id())]
+    pub token_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Pause record of the mint; may not exist
+    /// CHECK: PDA of the mint, read by ensure_not_paused
+    #[account(seeds = [MINT_PAUSE_SEED, mint.key().as_ref()], bump)]
+    pub mint_pause: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawFromStream<'info> {
+    pub recipient: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = recipient @ MyError::Unauthorized,
+        has_one = mint,
+        has_one = vault,
+        seeds = [STREAM_SEED, stream.sender.as_ref(), mint.key().as_ref(), &stream.stream_id.to_le_bytes()],
+        bump = stream.bump,
+    )]
+    pub stream: Account<'info, Stream>,
+
+    /// CHECK: checked against stream.mint
+    pub mint: UncheckedAccount<'info>,
+
+    /// CHECK: checked against stream.vault
+    #[account(mut)]
+    pub vault: UncheckedAccount<'info>,
+
+    /// Recipient's token account
+    /// CHECK: validated by the token program during the transfer
+    #[account(mut)]
+    pub recipient_token_account: UncheckedAccount<'info>,
+
+    /// Token-2022 program
+    /// CHECK: must be Token-2022 id
+    #[account(address = spl_token_2022::id())]
+    pub token_program: UncheckedAccount<'info>,
+
+    /// Pause record of the mint; may not exist
+    /// CHECK: PDA of the mint, read by ensure_not_paused
+    #[account(seeds = [MINT_PAUSE_SEED, mint.key().as_ref()], bump)]
+    pub mint_pause: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelStream<'info> {
+    #[account(mut)]
+    pub sender: Signer<'info>,
+
+    #[account(
+        mut,
+        close = sender,
+        has_one = sender @ MyError::Unauthorized,
+        has_one = mint,
+        has_one = vault,
+        seeds = [STREAM_SEED, sender.key().as_ref(), mint.key().as_ref(), &stream.stream_id.to_le_bytes()],
+        bump = stream.bump,
+    )]
+    pub stream: Account<'info, Stream>,
+
+    /// CHECK: checked against stream.mint
+    #[account(mut)]
+    pub mint: UncheckedAccount<'info>,
+
+    /// CHECK: checked against stream.vault
+    #[account(mut)]
+    pub vault: UncheckedAccount<'info>,
+
+    /// Sender's token account receiving the unstreamed remainder
+    /// CHECK: validated by the token program during the transfer
+    #[account(mut)]
+    pub sender_token_account: UncheckedAccount<'info>,
+
+    /// Recipient's token account receiving what has accrued
+    /// CHECK: must belong to stream.recipient; validated in the handler
+    #[account(mut, constraint = token_account_owner(&recipient_token_account)? == stream.recipient @ MyError::InvalidTokenAccount)]
+    pub recipient_token_account: UncheckedAccount<'info>,
+
+    /// Token-2022 program
+    /// CHECK: must be Token-2022 id
+    #[account(address = spl_token_2022::id())]
+    pub token_program: UncheckedAccount<'info>,
+
+    /// Pause record of the mint; may not exist
+    /// CHECK: PDA of the mint, read by ensure_not_paused
+    #[account(seeds = [MINT_PAUSE_SEED, mint.key().as_ref()], bump)]
+    pub mint_pause: UncheckedAccount<'info>,
+}
+
+pub const STREAM_SEED: &[u8] = b"stream";
+
+/// Linear per-second token stream from `sender` to `recipient`, escrowed in `vault`.
+#[account]
+#[derive(InitSpace)]
+pub struct Stream {
+    pub sender: Pubkey,
+    pub recipient: Pubkey,
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+    pub stream_id: u64,
+    /// Tokens that arrived in the vault, after the deposit's transfer fee
+    pub deposited: u64,
+    /// Tokens taken out of the vault by the recipient so far
+    pub withdrawn: u64,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub bump: u8,
+}
+
+impl Stream {
+    /// Escrowed tokens accrued to the recipient by `now`
+    pub fn vested_amount(&self, now: i64) -> u64 {
+        math::linear_vested(self.deposited, self.start_time, self.end_time, now)
+    }
+}
+
+#[event(discriminator = [93, 150, 91, 15, 166, 8, 251, 166])]
+pub struct StreamCreated {
+    pub version: u8,
+    pub stream: Pubkey,
+    pub sender: Pubkey,
+    pub recipient: Pubkey,
+    pub mint: Pubkey,
+    pub deposited: u64,
+    pub start_time: i64,
+    pub end_time: i64,
+}
+
+#[event(discriminator = [229, 224, 216, 237, 68, 225, 122, 75])]
+pub struct StreamWithdrawn {
+    pub version: u8,
+    pub stream: Pubkey,
+    /// Tokens taken out of the vault
+    pub amount: u64,
+    /// Transfer fee withheld from the payout
+    pub fee: u64,
+}
+
+#[event(discriminator = [91, 215, 29, 237, 194, 6, 184, 92])]
+pub struct StreamCancelled {
+    pub version: u8,
+    pub stream: Pubkey,
+    pub paid_to_recipient: u64,
+    pub refunded: u64,
+}
+
+#[event(discriminator = [14, 206, 32, 10, 248, 82, 169, 234])]
+pub struct StreamClosed {
+    pub version: u8,
+    pub stream: Pubkey,
+}
+
+#[derive(Accounts)]
+#[instruction(offer_id: u64)]
+pub struct CreateOtcOffer<'info> {
+    #[account(mut)]
+    pub maker: Signer<'info>,
+
+    /// Mint the maker is selling
+    /// CHECK: must be a Token-2022 mint
+    #[account(owner = spl_token_2022::id())]
+    pub mint_a: UncheckedAccount<'info>,
+
+    /// Mint the maker wants in return
+    /// CHECK: must be a Token-2022 mint
+    #[account(owner = spl_token_2022::id())]
+    pub mint_b: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = maker,
+        space = 8 + OtcOffer::INIT_SPACE,
+        seeds = [OTC_OFFER_SEED, maker.key().as_ref(), &offer_id.to_le_bytes()],
+        bump,
+    )]
+    pub offer: Account<'info, OtcOffer>,
+
+    /// Maker's token account of mint A funding the escrow
+    /// CHECK: validated by the token program during the transfer
+    #[account(mut)]
+    pub maker_token_account_a: UncheckedAccount<'info>,
+
+    /// Escrow: token account of mint A owned by the offer PDA
+    /// CHECK: mint and owner are validated in the handler
+    #[account(mut)]
+    pub vault: UncheckedAccount<'info>,
+
+    /// Token-2022 program
+    /// CHECK: must be Token-2022 id
+    #[account(address = spl_token_2022::id())]
+    pub token_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Pause record of the mint; may not exist
+    /// CHECK: PDA of the mint, read by ensure_not_paused
+    #[account(seeds = [MINT_PAUSE_SEED, mint_a.key().as_ref()], bump)]
+    pub mint_pause: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct FillOtcOffer<'info> {
+    pub taker: Signer<'info>,
+
+    /// CHECK: receives the offer and vault accounts' rent
+    #[account(mut, address = offer.maker)]
+    pub maker: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        close = maker,
+        has_one = mint_a,
+        has_one = mint_b,
+        has_one = vault,
+        seeds = [OTC_OFFER_SEED, offer.maker.as_ref(), &offer.offer_id.to_le_bytes()],
+        bump = offer.bump,
+    )]
+    pub offer: Account<'info, OtcOffer>,
+
+    /// CHECK: checked against offer.mint_a
+    pub mint_a: UncheckedAccount<'info>,
+
+    /// CHECK: checked against offer.mint_b
+    pub mint_b: UncheckedAccount<'info>,
+
+    /// CHECK: checked against offer.vault
+    #[account(mut)]
+    pub vault: UncheckedAccount<'info>,
+
+    /// Taker's token account receiving mint A
+    /// CHECK: validated by the token program during the transfer
+    #[account(mut)]
+    pub taker_token_account_a: UncheckedAccount<'info>,
+
+    /// Taker's token account paying mint B
+    /// CHECK: validated by the token program during the transfer
+    #[account(mut)]
+    pub taker_token_account_b: UncheckedAccount<'info>,
+
+    /// Maker's token account receiving whatever mint A is left in the vault
+    /// CHECK: mint and owner are validated in the handler
+    #[account(mut)]
+    pub maker_token_account_a: UncheckedAccount<'info>,
+
+    /// Maker's token account receiving mint B
+    /// CHECK: mint and owner are validated in the handler
+    #[account(mut)]
+    pub maker_token_account_b: UncheckedAccount<'info>,
+
+    /// Token-2022 program
+    /// CHECK: must be Token-2022 id
+    #[account(address = spl_token_2022::id())]
+    pub token_program: UncheckedAccount<'info>,
+
+    /// Pause record of the mint; may not exist
+    /// CHECK: PDA of the mint, read by ensure_not_paused
+    #[account(seeds = [MINT_PAUSE_SEED, mint_a.key().as_ref()], bump)]
+    pub mint_pause: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelOtcOffer<'info> {
+    #[account(mut)]
+    pub maker: Signer<'info>,
+
+    #[account(
+        mut,
+        close = maker,
+        has_one = maker @ MyError::Unauthorized,
+        has_one = mint_a,
+        has_one = vault,
+        seeds = [OTC_OFFER_SEED, maker.key().as_ref(), &offer.offer_id.to_le_bytes()],
+        bump = offer.bump,
+    )]
+    pub offer: Account<'info, OtcOffer>,
+
+    /// CHECK: checked against offer.mint_a
+    pub mint_a: UncheckedAccount<'info>,
+
+    /// CHECK: checked against offer.vault
+    #[account(mut)]
+    pub vault: UncheckedAccount<'info>,
+
+    /// Maker's token account receiving the escrow back
+    /// CHECK: validated by the token program during the transfer
+    #[account(mut)]
+    pub maker_token_account_a: UncheckedAccount<'info>,
+
+    /// Token-2022 program
+    /// CHECK: must be Token-2022 id
+    #[account(address = spl_token_2022::id())]
+    pub token_program: UncheckedAccount<'info>,
+}
+
+pub const OTC_OFFER_SEED: &[u8] = b"otc_offer";
+
+/// Peer-to-peer swap offer: `amount_a` of `mint_a` sits in `vault` until a taker delivers
+/// `amount_b` of `mint_b` to the maker.
+#[account]
+#[derive(InitSpace)]
+pub struct OtcOffer {
+    pub maker: Pubkey,
+    pub offer_id: u64,
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    pub vault: Pubkey,
+    /// Tokens of mint A held in escrow, after the deposit's transfer fee
+    pub amount_a: u64,
+    /// Tokens of mint B the maker receives, net of transfer fees
+    pub amount_b: u64,
+    /// Only this wallet may fill the offer, when set
+    pub counterparty: Option<Pubkey>,
+    pub bump: u8,
+}
+
+#[event(discriminator = [251, 3, 25, 253, 65, 120, 192, 10])]
+pub struct OtcOfferCreated {
+    pub version: u8,
+    pub offer: Pubkey,
+    pub maker: Pubkey,
+    pub mint_a: Pubkey,
+    pub amount_a: u64,
+    pub mint_b: Pubkey,
+    pub amount_b: u64,
+    pub counterparty: Option<Pubkey>,
+}
+
+#[event(discriminator = [30, 5, 200, 165, 242, 217, 17, 166])]
+pub struct OtcOfferFilled {
+    pub version: u8,
+    pub offer: Pubkey,
+    pub taker: Pubkey,
+    /// Tokens of mint A released from escrow, and the fee withheld from them
+    pub amount_a: u64,
+    pub fee_a: u64,
+    /// Tokens of mint B paid by the taker, and the fee withheld from them
+    pub amount_b: u64,
+    pub fee_b: u64,
+}
+
+#[event(discriminator = [45, 101, 199, 241, 255, 137, 157, 151])]
+pub struct OtcOfferCancelled {
+    pub version: u8,
+    pub offer: Pubkey,
+}
+
+pub const GIFT_SEED: &[u8] = b"gift";
+
+/// Tokens escrowed for whoever holds the secret key of `claim_key`.
+#[account]
+#[derive(InitSpace)]
+pub struct Gift {
+    pub sender: Pubkey,
+    /// Public half of the keypair shared with the recipient
+    pub claim_key: Pubkey,
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+    /// Escrowed amount, after the deposit's transfer fee
+    pub amount: u64,
+    /// Claims fail from this time on; the sender can still cancel
+    pub expires_at: i64,
+    pub bump: u8,
+}
+
+#[derive(Accounts)]
+#[instruction(claim_key: Pubkey)]
+pub struct CreateGift<'info> {
+    #[account(mut)]
+    pub sender: Signer<'info>,
+
+    /// Mint being gifted
+    /// CHECK: must be a Token-2022 mint
+    #[account(owner = spl_token_2022::id())]
+    pub mint: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = sender,
+        space = 8 + Gift::INIT_SPACE,
+        seeds = [GIFT_SEED, claim_key.as_ref()],
+        bump,
+    )]
+    pub gift: Account<'info, Gift>,
+
+    /// Sender's token account funding the gift
+    /// CHECK: validated by the token program during the transfer
+    #[account(mut)]
+    pub sender_token_account: UncheckedAccount<'info>,
+
+    /// Escrow: token account of the mint owned by the gift PDA
+    /// CHECK: mint and owner are validated in the handler
+    #[account(mut)]
+    pub vault: UncheckedAccount<'info>,
+
+    /// Token-2022 program
+    /// CHECK: must be Token-2022 id
+    #[account(address = spl_token_2022::id())]
+    pub token_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Pause record of the mint; may not exist
+    /// CHECK: PDA of the mint, read by ensure_not_paused
+    #[account(seeds = [MINT_PAUSE_SEED, mint.key().as_ref()], bump)]
+    pub mint_pause: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimGift<'info> {
+    pub claim_key: Signer<'info>,
+
+    #[account(
+        mut,
+        close = sender,
+        has_one = claim_key @ MyError::Unauthorized,
+        has_one = sender,
+        has_one = mint,
+        has_one = vault,
+        seeds = [GIFT_SEED, claim_key.key().as_ref()],
+        bump = gift.bump,
+    )]
+    pub gift: Account<'info, Gift>,
+
+    /// Gift's sender, receiving the rent
+    /// CHECK: checked against gift.sender
+    #[account(mut)]
+    pub sender: UncheckedAccount<'info>,
+
+    /// CHECK: checked against gift.mint
+    #[account(mut)]
+    pub mint: UncheckedAccount<'info>,
+
+    /// CHECK: checked against gift.vault
+    #[account(mut)]
+    pub vault: UncheckedAccount<'info>,
+
+    /// Any token account of the mint
+    /// CHECK: validated by the token program during the transfer
+    #[account(mut)]
+    pub recipient_token_account: UncheckedAccount<'info>,
+
+    /// Token-2022 program
+    /// CHECK: must be Token-2022 id
+    #[account(address = spl_token_2022::id())]
+    pub token_program: UncheckedAccount<'info>,
+
+    /// Pause record of the mint; may not exist
+    /// CHECK: PDA of the mint, read by ensure_not_paused
+    #[account(seeds = [MINT_PAUSE_SEED, mint.key().as_ref()], bump)]
+    pub mint_pause: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelGift<'info> {
+    #[account(mut)]
+    pub sender: Signer<'info>,
+
+    #[account(
+        mut,
+        close = sender,
+        has_one = sender @ MyError::Unauthorized,
+        has_one = mint,
+        has_one = vault,
+        seeds = [GIFT_SEED, gift.claim_key.as_ref()],
+        bump = gift.bump,
+    )]
+    pub gift: Account<'info, Gift>,
+
+    /// CHECK: checked against gift.mint
+    #[account(mut)]
+    pub mint: UncheckedAccount<'info>,
+
+    /// CHECK: checked against gift.vault
+    #[account(mut)]
+    pub vault: UncheckedAccount<'info>,
+
+    /// Sender's token account receiving the refund
+    /// CHECK: validated by the token program during the transfer
+    #[account(mut)]
+    pub sender_token_account: UncheckedAccount<'info>,
+
+    /// Token-2022 program
+    /// CHECK: must be Token-2022 id
+    #[account(address = spl_token_2022::id())]
+    pub token_program: UncheckedAccount<'info>,
+}
+
+#[event(discriminator = [149, 59, 123, 115, 215, 127, 14, 194])]
+pub struct GiftCreated {
+    pub version: u8,
+    pub gift: Pubkey,
+    pub sender: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub expires_at: i64,
+}
+
+#[event(discriminator = [70, 176, 25, 29, 237, 141, 185, 163])]
+pub struct GiftClaimed {
+    pub version: u8,
+    pub gift: Pubkey,
+    pub recipient_token_account: Pubkey,
+    pub amount: u64,
+}
+
+#[event(discriminator = [223, 4, 128, 62, 95, 139, 48, 179])]
+pub struct GiftCancelled {
+    pub version: u8,
+    pub gift: Pubkey,
+    pub refunded: u64,
+}
+
+pub const WRAPPER_SEED: &[u8] = b"wrapper";
+pub const WRAPPED_MINT_SEED: &[u8] = b"wrapped_mint";
+
+/// Fee-free companion of a taxed mint. Every companion token is backed by one token of the
+/// mint in `vault`.
+#[account]
+#[derive(InitSpace)]
+pub struct Wrapper {
+    pub mint: Pubkey,
+    /// Plain Token-2022 mint at `[WRAPPED_MINT_SEED, mint]`, minted and burned by this PDA
+    pub wrapped_mint: Pubkey,
+    pub vault: Pubkey,
+    pub bump: u8,
+}
+
+#[derive(Accounts)]
+pub struct InitWrapper<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Taxed mint being wrapped
+    /// CHECK: must be a Token-2022 mint
+    #[account(owner = spl_token_2022::id())]
+    pub mint: UncheckedAccount<'info>,
+
+    #[account(seeds = [LAUNCH_CONFIG_SEED, mint.key().as_ref()], bump = launch_config.bump)]
+    pub launch_config: Account<'info, LaunchConfig>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Wrapper::INIT_SPACE,
+        seeds = [WRAPPER_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub wrapper: Account<'info, Wrapper>,
+
+    /// Companion mint, created by the handler
+    /// CHECK: PDA of the mint, created here
+    #[account(mut, seeds = [WRAPPED_MINT_SEED, mint.key().as_ref()], bump)]
+    pub wrapped_mint: UncheckedAccount<'info>,
+
+    /// Token account of the mint owned by the wrapper PDA
+    /// CHECK: mint and owner are validated in the handler
+    pub vault: UncheckedAccount<'info>,
+
+    /// Token-2022 program
+    /// CHECK: must be Token-2022 id
+    #[account(address = spl_token_2022::id())]
+    pub token_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WrapTokens<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        has_one = mint,
+        has_one = wrapped_mint,
+        has_one = vault,
+        seeds = [WRAPPER_SEED, mint.key().as_ref()],
+        bump = wrapper.bump,
+    )]
+    pub wrapper: Account<'info, Wrapper>,
+
+    /// CHECK: checked against wrapper.mint
+    pub mint: UncheckedAccount<'info>,
+
+    /// CHECK: checked against wrapper.wrapped_mint
+    #[account(mut)]
+    pub wrapped_mint: UncheckedAccount<'info>,
+
+    /// CHECK: checked against wrapper.vault
+    #[account(mut)]
+    pub vault: UncheckedAccount<'info>,
+
+    /// Owner's token account of the taxed mint
+    /// CHECK: validated by the token program during the transfer
+    #[account(mut)]
+    pub taxed_token_account: UncheckedAccount<'info>,
+
+    /// Owner's token account of the companion mint
+    /// CHECK: validated by the token program during the mint or burn
+    #[account(mut)]
+    pub wrapped_token_account: UncheckedAccount<'info>,
+
+    /// Token-2022 program
+    /// CHECK: must be Token-2022 id
+    #[account(address = spl_token_2022::id())]
+    pub token_program: UncheckedAccount<'info>,
+
+    /// Pause record of the mint; may not exist
+    /// CHECK: PDA of the mint, read by ensure_not_paused
+    #[account(seeds = [MINT_PAUSE_SEED, mint.key().as_ref()], bump)]
+    pub mint_pause: UncheckedAccount<'info>,
+}
+
+#[event(discriminator = [184, 253, 133, 68, 124, 86, 149, 255])]
+pub struct WrapperCreated {
+    pub version: u8,
+    pub wrapper: Pubkey,
+    pub mint: Pubkey,
+    pub wrapped_mint: Pubkey,
+}
+
+#[event(discriminator = [11, 127, 145, 31, 206, 134, 73, 130])]
+pub struct Wrapped {
+    pub version: u8,
+    pub wrapper: Pubkey,
+    pub owner: Pubkey,
+    /// Taxed tokens sent, before the transfer fee
+    pub deposited: u64,
+    pub minted: u64,
+}
+
+#[event(discriminator = [25, 86, 93, 80, 145, 113, 86, 93])]
+pub struct Unwrapped {
+    pub version: u8,
+    pub wrapper: Pubkey,
+    pub owner: Pubkey,
+    pub burned: u64,
+    /// Taxed tokens received, after the transfer fee
+    pub received: u64,
+}
+
+#[derive(Accounts)]
+#[instruction(campaign_id: u64)]
+pub struct InitAirdrop<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    /// Mint being airdropped
+    /// CHECK: must be a Token-2022 mint
+    #[account(owner = spl_token_2022::id())]
+    pub mint: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + AirdropCampaign::INIT_SPACE,
+        seeds = [AIRDROP_SEED, creator.key().as_ref(), mint.key().as_ref(), &campaign_id.to_le_bytes()],
+        bump,
+    )]
+    pub campaign: Account<'info, AirdropCampaign>,
+
+    /// Escrow: token account of the mint owned by the campaign PDA
+    /// CHECK: mint and owner are validated in the handler
+    pub vault: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AddAirdropRecipients<'info> {
+    pub creator: Signer<'info>,
+
+    #[account(mut, has_one = creator @ MyError::Unauthorized)]
+    pub campaign: Account<'info, AirdropCampaign>,
+}
+
+#[derive(Accounts)]
+pub struct AirdropBatch<'info> {
+    #[account(
+        mut,
+        has_one = mint,
+        has_one = vault,
+        seeds = [AIRDROP_SEED, campaign.creator.as_ref(), mint.key().as_ref(), &campaign.campaign_id.to_le_bytes()],
+        bump = campaign.bump,
+    )]
+    pub campaign: Account<'info, AirdropCampaign>,
+
+    /// CHECK: checked against campaign.mint
+    pub mint: UncheckedAccount<'info>,
+
+    /// CHECK: checked against campaign.vault
+    #[account(mut)]
+    pub vault: UncheckedAccount<'info>,
+
+    /// Token-2022 program
+    /// CHECK: must be Token-2022 id
+    #[account(address = spl_token_2022::id())]
+    pub token_program: UncheckedAccount<'info>,
+
+    /// Pause record of the mint; may not exist
+    /// CHECK: PDA of the mint, read by ensure_not_paused
+    #[account(seeds = [MINT_PAUSE_SEED, mint.key().as_ref()], bump)]
+    pub mint_pause: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimSkippedAirdrop<'info> {
+    #[account(
+        mut,
+        has_one = mint,
+        has_one = vault,
+        seeds = [AIRDROP_SEED, campaign.creator.as_ref(), mint.key().as_ref(), &campaign.campaign_id.to_le_bytes()],
+        bump = campaign.bump,
+    )]
+    pub campaign: Account<'info, AirdropCampaign>,
+
+    /// CHECK: checked against campaign.mint
+    pub mint: UncheckedAccount<'info>,
+
+    /// CHECK: checked against campaign.vault
+    #[account(mut)]
+    pub vault: UncheckedAccount<'info>,
+
+    /// Recipient's token account of the mint
+    /// CHECK: mint and owner are validated in the handler
+    #[account(mut)]
+    pub destination: UncheckedAccount<'info>,
+
+    /// Token-2022 program
+    /// CHECK: must be Token-2022 id
+    #[account(address = spl_token_2022::id())]
+    pub token_program: UncheckedAccount<'info>,
+
+    /// Pause record of the mint; may not exist
+    /// CHECK: PDA of the mint, read by ensure_not_paused
+    #[account(seeds = [MINT_PAUSE_SEED, mint.key().as_ref()], bump)]
+    pub mint_pause: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SweepUnclaimed<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        close = creator,
+        has_one = creator @ MyError::Unauthorized,
+        has_one = mint,
+        has_one = vault,
+        seeds = [AIRDROP_SEED, creator.key().as_ref(), mint.key().as_ref(), &campaign.campaign_id.to_le_bytes()],
+        bump = campaign.bump,
+    )]
+    pub campaign: Account<'info, AirdropCampaign>,
+
+    /// CHECK: checked against campaign.mint
+    #[account(mut)]
+    pub mint: UncheckedAccount<'info>,
+
+    /// CHECK: checked against campaign.vault
+    #[account(mut)]
+    pub vault: UncheckedAccount<'info>,
+
+    /// Creator's token account receiving the remainder; not needed when burning
+    /// CHECK: validated by the token program during the transfer
+    #[account(mut)]
+    pub creator_token_account: Option<UncheckedAccount<'info>>,
+
+    /// Token-2022 program
+    /// CHECK: must be Token-2022 id
+    #[account(address = spl_token_2022::id())]
+    pub token_program: UncheckedAccount<'info>,
+}
+
+pub const AIRDROP_SEED: &[u8] = b"airdrop";
+pub const MAX_AIRDROP_RECIPIENTS: usize = 200;
+pub const MAX_AIRDROP_BATCH: usize = 20;
+/// Skipped list entries a campaign holds unclaimed at once
+pub const MAX_AIRDROP_SKIPPED: usize = 32;
+pub const ASSOCIATED_TOKEN_PROGRAM_ID: Pubkey = pubkey!("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTm6ZyhdCbu");
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub struct AirdropRecipient {
+    pub wallet: Pubkey,
+    pub amount: u64,
+}
+
+/// Push-style airdrop list paid out from `vault` in order; `cursor` is the next unpaid entry.
+#[account]
+#[derive(InitSpace)]
+pub struct AirdropCampaign {
+    pub creator: Pubkey,
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+    pub campaign_id: u64,
+    #[max_len(MAX_AIRDROP_RECIPIENTS)]
+    pub recipients: Vec<AirdropRecipient>,
+    pub cursor: u32,
+    /// List entries skipped over a frozen account and not yet claimed
+    #[max_len(MAX_AIRDROP_SKIPPED)]
+    pub skipped: Vec<u32>,
+    /// No payouts from this time on; the remainder can be swept
+    pub expires_at: i64,
+    /// Key whose ed25519 vouchers `claim_voucher` pays out
+    pub voucher_signer: Option<Pubkey>,
+    pub bump: u8,
+}
+
+impl AirdropCampaign {
+    /// Recipients an account of `data_len` bytes can hold
+    pub fn capacity(data_len: usize) -> usize {
+        list_capacity(data_len, 8 + Self::INIT_SPACE, MAX_AIRDROP_RECIPIENTS, AirdropRecipient::INIT_SPACE)
+    }
+}
+
+pub const VOUCHER_NONCES_SEED: &[u8] = b"voucher_nonces";
+pub const NONCE_PAGE_BYTES: usize = 1024;
+/// Nonces tracked by one bitmap page; nonce `n` lives in page `n / NONCES_PER_PAGE`
+pub const NONCES_PER_PAGE: u64 = 8 * NONCE_PAGE_BYTES as u64;
+/// Domain separating claim vouchers from other messages the voucher signer signs
+pub const VOUCHER_CLAIM_DOMAIN: &[u8] = b"launchpad:voucher_claim";
+
+/// Message a campaign's voucher signer signs to let `wallet` claim `amount` once
+pub fn voucher_claim_message(campaign: &Pubkey, mint: &Pubkey, wallet: &Pubkey, amount: u64, nonce: u64) -> Vec<u8> {
+    [
+        VOUCHER_CLAIM_DOMAIN,
+        campaign.as_ref(),
+        mint.as_ref(),
+        wallet.as_ref(),
+        &amount.to_le_bytes(),
+        &nonce.to_le_bytes(),
+    ]
+    .concat()
+}
+
+/// One page of a campaign's spent voucher nonces, a bit per nonce.
+#[account]
+#[derive(InitSpace)]
+pub struct VoucherNoncePage {
+    pub campaign: Pubkey,
+    pub page: u64,
+    pub bits: [u8; NONCE_PAGE_BYTES],
+    pub bump: u8,
+}
+
+impl VoucherNoncePage {
+    fn bit(nonce: u64) -> (usize, u8) {
+        let index = (nonce % NONCES_PER_PAGE) as usize;
+        (index / 8, 1 << (index % 8))
+    }
+
+    pub fn is_used(&self, nonce: u64) -> bool {
+        let (byte, mask) = Self::bit(nonce);
+        self.bits[byte] & mask != 0
+    }
+
+    /// Mark `nonce` spent, failing if it already was
+    pub fn consume(&mut self, nonce: u64) -> Result<()> {
+        if self.is_used(nonce) {
+            return Err(MyError::VoucherAlreadyClaimed.into());
+        }
+        let (byte, mask) = Self::bit(nonce);
+        self.bits[byte] |= mask;
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct SetVoucherSigner<'info> {
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = creator @ MyError::Unauthorized,
+        seeds = [AIRDROP_SEED, creator.key().as_ref(), campaign.mint.as_ref(), &campaign.campaign_id.to_le_bytes()],
+        bump = campaign.bump,
+    )]
+    pub campaign: Account<'info, AirdropCampaign>,
+}
+
+#[derive(Accounts)]
+#[instruction(wallet: Pubkey, amount: u64, nonce: u64)]
+pub struct ClaimVoucher<'info> {
+    /// Pays the rent of a new nonce page; need not be the wallet
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        has_one = mint,
+        has_one = vault,
+        seeds = [AIRDROP_SEED, campaign.creator.as_ref(), mint.key().as_ref(), &campaign.campaign_id.to_le_bytes()],
+        bump = campaign.bump,
+    )]
+    pub campaign: Account<'info, AirdropCampaign>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + VoucherNoncePage::INIT_SPACE,
+        seeds = [VOUCHER_NONCES_SEED, campaign.key().as_ref(), &(nonce / NONCES_PER_PAGE).to_le_bytes()],
+        bump,
+    )]
+    pub nonces: Box<Account<'info, VoucherNoncePage>>,
+
+    /// CHECK: checked against campaign.mint
+    pub mint: UncheckedAccount<'info>,
+
+    /// CHECK: checked against campaign.vault
+    #[account(mut)]
+    pub vault: UncheckedAccount<'info>,
+
+    /// Wallet's token account of the mint
+    /// CHECK: mint and owner are validated in the handler
+    #[account(mut)]
+    pub destination: UncheckedAccount<'info>,
+
+    /// Instructions sysvar, to find the voucher
+    /// CHECK: address is the instructions sysvar
+    #[account(address = solana_sdk_ids::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    /// Token-2022 program
+    /// CHECK: must be Token-2022 id
+    #[account(address = spl_token_2022::id())]
+    pub token_program: UncheckedAccount<'info>,
+
+    /// Pause record of the mint; may not exist
+    /// CHECK: PDA of the mint, read by ensure_not_paused
+    #[account(seeds = [MINT_PAUSE_SEED, mint.key().as_ref()], bump)]
+    pub mint_pause: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event(discriminator = [45, 117, 206, 58, 70, 216, 79, 135])]
+pub struct VoucherClaimed {
+    pub version: u8,
+    pub campaign: Pubkey,
+    pub wallet: Pubkey,
+    pub amount: u64,
+    pub nonce: u64,
+}
+
+#[event(discriminator = [81, 241, 178, 115, 116, 170, 66, 101])]
+pub struct AirdropBatchSent {
+    pub version: u8,
+    pub campaign: Pubkey,
+    /// List entries the cursor moved past, skipped ones included
+    pub recipients: u32,
+    pub amount: u64,
+    pub cursor: u32,
+}
+
+/// List entry `airdrop_batch` passed over unpaid, its associated token account frozen
+#[event(discriminator = [41, 2, 28, 208, 187, 187, 239, 187])]
+pub struct AirdropRecipientSkipped {
+    pub version: u8,
+    pub campaign: Pubkey,
+    pub index: u32,
+    pub wallet: Pubkey,
+    pub amount: u64,
+}
+
+#[event(discriminator = [141, 230, 126, 217, 232, 203, 226, 6])]
+pub struct AirdropSkippedClaimed {
+    pub version: u8,
+    pub campaign: Pubkey,
+    pub index: u32,
+    pub wallet: Pubkey,
+    pub amount: u64,
+}
+
+#[event(discriminator = [95, 83, 208, 221, 184, 59, 198, 101])]
+pub struct AirdropSwept {
+    pub version: u8,
+    pub campaign: Pubkey,
+    pub amount: u64,
+    pub burned: bool,
+}
+
+#[event(discriminator = [196, 85, 30, 72, 165, 151, 163, 126])]
+pub struct AirdropClosed {
+    pub version: u8,
+    pub campaign: Pubkey,
+    /// Remainder sent back to the creator
+    pub returned: u64,
+}
+
+#[derive(Accounts)]
+pub struct InitPointsProgram<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        seeds = [LAUNCH_CONFIG_SEED, launch_config.mint.as_ref()],
+        bump = launch_config.bump,
+        has_one = creator @ MyError::Unauthorized,
+    )]
+    pub launch_config: Account<'info, LaunchConfig>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + PointsProgram::INIT_SPACE,
+        seeds = [POINTS_SEED, launch_config.mint.as_ref()],
+        bump,
+    )]
+    pub points_program: Account<'info, PointsProgram>,
+
+    /// Soulbound points mint, created by the handler
+    /// CHECK: PDA of the points program
+    #[account(mut, seeds = [POINTS_MINT_SEED, points_program.key().as_ref()], bump)]
+    pub points_mint: UncheckedAccount<'info>,
+
+    /// Token-2022 program
+    /// CHECK: must be Token-2022 id
+    #[account(address = spl_token_2022::id())]
+    pub token_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(operator: Pubkey)]
+pub struct SetPointsOperator<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(has_one = admin @ MyError::Unauthorized)]
+    pub points_program: Account<'info, PointsProgram>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + PointsOperator::INIT_SPACE,
+        seeds = [POINTS_OPERATOR_SEED, points_program.key().as_ref(), operator.as_ref()],
+        bump,
+    )]
+    pub operator_entry: Account<'info, PointsOperator>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AwardPoints<'info> {
+    pub operator: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = points_mint,
+        seeds = [POINTS_SEED, points_program.launch_mint.as_ref()],
+        bump = points_program.bump,
+    )]
+    pub points_program: Account<'info, PointsProgram>,
+
+    #[account(
+        mut,
+        has_one = operator @ MyError::Unauthorized,
+        has_one = points_program,
+        seeds = [POINTS_OPERATOR_SEED, points_program.key().as_ref(), operator.key().as_ref()],
+        bump = operator_entry.bump,
+    )]
+    pub operator_entry: Account<'info, PointsOperator>,
+
+    /// CHECK: checked against points_program.points_mint
+    #[account(mut)]
+    pub points_mint: UncheckedAccount<'info>,
+
+    /// Holder's points token account
+    /// CHECK: validated by the token program during the mint
+    #[account(mut)]
+    pub destination: UncheckedAccount<'info>,
+
+    /// Token-2022 program
+    /// CHECK: must be Token-2022 id
+    #[account(address = spl_token_2022::id())]
+    pub token_program: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RedeemPoints<'info> {
+    pub holder: Signer<'info>,
+
+    #[account(mut, has_one = points_mint)]
+    pub points_program: Account<'info, PointsProgram>,
+
+    /// CHECK: checked against points_program.points_mint
+    #[account(mut)]
+    pub points_mint: UncheckedAccount<'info>,
+
+    /// Holder's points token account
+    /// CHECK: validated by the token program during the burn
+    #[account(mut)]
+    pub source: UncheckedAccount<'info>,
+
+    /// Token-2022 program
+    /// CHECK: must be Token-2022 id
+    #[account(address = spl_token_2022::id())]
+    pub token_program: UncheckedAccount<'info>,
+}
+
+pub const POINTS_SEED: &[u8] = b"points";
+pub const POINTS_MINT_SEED: &[u8] = b"points_mint";
+pub const POINTS_OPERATOR_SEED: &[u8] = b"points_operator";
+pub const SECONDS_PER_DAY: i64 = 86_400;
+
+/// Loyalty points of a launch, issued as a soulbound mint controlled by this PDA.
+#[account]
+#[derive(InitSpace)]
+pub struct PointsProgram {
+    pub launch_mint: Pubkey,
+    pub admin: Pubkey,
+    pub points_mint: Pubkey,
+    pub total_awarded: u64,
+    pub total_redeemed: u64,
+    pub bump: u8,
+}
+
+/// Wallet allowed to award points, with its daily issuance limit.
+#[account]
+#[derive(InitSpace)]
+pub struct PointsOperator {
+    pub points_program: Pubkey,
+    pub operator: Pubkey,
+    pub daily_limit: u64,
+    /// UTC day (unix time / 86400) that `issued_today` counts
+    pub day: i64,
+    pub issued_today: u64,
+    pub bump: u8,
+}
+
+#[event(discriminator = [201, 95, 152, 50, 215, 83, 188, 38])]
+pub struct PointsAwarded {
+    pub version: u8,
+    pub points_program: Pubkey,
+    pub operator: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+}
+
+#[event(discriminator = [57, 161, 97, 67, 102, 120, 85, 147])]
+pub struct PointsRedeemed {
+    pub version: u8,
+    pub points_program: Pubkey,
+    pub holder: Pubkey,
+    pub amount: u64,
+}
+
+#[derive(Accounts)]
+#[instruction(quest_id: u64)]
+pub struct CreateQuest<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        seeds = [LAUNCH_CONFIG_SEED, launch_config.mint.as_ref()],
+        bump = launch_config.bump,
+        has_one = creator @ MyError::Unauthorized,
+    )]
+    pub launch_config: Account<'info, LaunchConfig>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + Quest::INIT_SPACE,
+        seeds = [QUEST_SEED, launch_config.mint.as_ref(), &quest_id.to_le_bytes()],
+        bump,
+    )]
+    pub quest: Account<'info, Quest>,
+
+    /// Soulbound badge mint, created by the handler
+    /// CHECK: PDA of the quest
+    #[account(mut, seeds = [QUEST_BADGE_SEED, quest.key().as_ref()], bump)]
+    pub badge_mint: UncheckedAccount<'info>,
+
+    /// Token-2022 program
+    /// CHECK: must be Token-2022 id
+    #[account(address = spl_token_2022::id())]
+    pub token_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(wallet: Pubkey)]
+pub struct CompleteQuest<'info> {
+    #[account(mut)]
+    pub attestor: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = attestor @ MyError::Unauthorized,
+        has_one = badge_mint,
+        seeds = [QUEST_SEED, quest.launch_mint.as_ref(), &quest.quest_id.to_le_bytes()],
+        bump = quest.bump,
+    )]
+    pub quest: Account<'info, Quest>,
+
+    #[account(
+        init,
+        payer = attestor,
+        space = 8 + QuestCompletion::INIT_SPACE,
+        seeds = [QUEST_COMPLETION_SEED, quest.key().as_ref(), wallet.as_ref()],
+        bump,
+    )]
+    pub completion: Account<'info, QuestCompletion>,
+
+    #[account(
+        init_if_needed,
+        payer = attestor,
+        space = 8 + QuestProgress::INIT_SPACE,
+        seeds = [QUEST_PROGRESS_SEED, quest.launch_mint.as_ref(), wallet.as_ref()],
+        bump,
+    )]
+    pub progress: Account<'info, QuestProgress>,
+
+    /// CHECK: checked against quest.badge_mint
+    #[account(mut)]
+    pub badge_mint: UncheckedAccount<'info>,
+
+    /// Wallet's badge token account
+    /// CHECK: owner is validated in the handler
+    #[account(mut)]
+    pub badge_account: UncheckedAccount<'info>,
+
+    /// Token-2022 program
+    /// CHECK: must be Token-2022 id
+    #[account(address = spl_token_2022::id())]
+    pub token_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub const QUEST_SEED: &[u8] = b"quest";
+pub const QUEST_BADGE_SEED: &[u8] = b"quest_badge";
+pub const QUEST_COMPLETION_SEED: &[u8] = b"quest_completion";
+pub const QUEST_PROGRESS_SEED: &[u8] = b"quest_progress";
+
+/// Quest attached to a launch, completed on the word of `attestor`.
+#[account]
+#[derive(InitSpace)]
+pub struct Quest {
+    pub launch_mint: Pubkey,
+    pub quest_id: u64,
+    pub admin: Pubkey,
+    pub attestor: Pubkey,
+    pub badge_mint: Pubkey,
+    pub completions: u64,
+    pub bump: u8,
+}
+
+/// One wallet's completion of one quest; its existence blocks a second badge.
+#[account]
+#[derive(InitSpace)]
+pub struct QuestCompletion {
+    pub quest: Pubkey,
+    pub wallet: Pubkey,
+    pub completed_at: i64,
+    pub bump: u8,
+}
+
+/// A wallet's quest progress across one launch.
+#[account]
+#[derive(InitSpace)]
+pub struct QuestProgress {
+    pub launch_mint: Pubkey,
+    pub wallet: Pubkey,
+    pub quests_completed: u32,
+    pub last_completed_at: i64,
+    pub bump: u8,
+}
+
+pub const EVENT_SERIES_SEED: &[u8] = b"event_series";
+pub const ATTENDANCE_EVENT_SEED: &[u8] = b"attendance_event";
+pub const ATTENDANCE_BADGE_SEED: &[u8] = b"attendance_badge";
+pub const ATTENDANCE_SEED: &[u8] = b"attendance";
+/// Domain separating check-in vouchers from other messages the organizer key signs
+pub const CHECK_IN_VOUCHER_DOMAIN: &[u8] = b"launchpad:check_in";
+
+/// Message an organizer signs to let `wallet` check in to `event`
+pub fn check_in_message(event: &Pubkey, wallet: &Pubkey) -> Vec<u8> {
+    [CHECK_IN_VOUCHER_DOMAIN, event.as_ref(), wallet.as_ref()].concat()
+}
+
+/// Series of attendance events run by one organizer.
+#[account]
+#[derive(InitSpace)]
+pub struct EventSeries {
+    pub organizer: Pubkey,
+    pub series_id: u64,
+    #[max_len(MAX_NAME_LEN)]
+    pub name: String,
+    /// Also the id of the next event
+    pub events_created: u32,
+    pub bump: u8,
+}
+
+/// One event of a series, minting a soulbound badge per checked-in wallet.
+#[account]
+#[derive(InitSpace)]
+pub struct AttendanceEvent {
+    pub series: Pubkey,
+    pub event_id: u32,
+    pub badge_mint: Pubkey,
+    /// Check-in window, inclusive
+    pub starts_at: i64,
+    pub ends_at: i64,
+    pub max_attendees: u32,
+    pub attendees: u32,
+    pub bump: u8,
+}
+
+/// One wallet's check-in to one event; its existence blocks a second badge.
+#[account]
+#[derive(InitSpace)]
+pub struct Attendance {
+    pub event: Pubkey,
+    pub wallet: Pubkey,
+    pub checked_in_at: i64,
+    pub bump: u8,
+}
+
+#[derive(Accounts)]
+#[instruction(series_id: u64)]
+pub struct CreateEventSeries<'info> {
+    #[account(mut)]
+    pub organizer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = organizer,
+        space = 8 + EventSeries::INIT_SPACE,
+        seeds = [EVENT_SERIES_SEED, organizer.key().as_ref(), &series_id.to_le_bytes()],
+        bump,
+    )]
+    pub series: Account<'info, EventSeries>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreateAttendanceEvent<'info> {
+    #[account(mut)]
+    pub organizer: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = organizer @ MyError::Unauthorized,
+        seeds = [EVENT_SERIES_SEED, organizer.key().as_ref(), &series.series_id.to_le_bytes()],
+        bump = series.bump,
+    )]
+    pub series: Account<'info, EventSeries>,
+
+    #[account(
+        init,
+        payer = organizer,
+        space = 8 + AttendanceEvent::INIT_SPACE,
+        seeds = [ATTENDANCE_EVENT_SEED, series.key().as_ref(), &series.events_created.to_le_bytes()],
+        bump,
+    )]
+    pub event: Account<'info, AttendanceEvent>,
+
+    /// Soulbound badge mint, created by the handler
+    /// CHECK: PDA of the event
+    #[account(mut, seeds = [ATTENDANCE_BADGE_SEED, event.key().as_ref()], bump)]
+    pub badge_mint: UncheckedAccount<'info>,
+
+    /// Token-2022 program
+    /// CHECK: must be Token-2022 id
+    #[account(address = spl_token_2022::id())]
+    pub token_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CheckIn<'info> {
+    #[account(mut)]
+    pub attendee: Signer<'info>,
+
+    #[account(
+        seeds = [EVENT_SERIES_SEED, series.organizer.as_ref(), &series.series_id.to_le_bytes()],
+        bump = series.bump,
+    )]
+    pub series: Account<'info, EventSeries>,
+
+    #[account(
+        mut,
+        has_one = series,
+        has_one = badge_mint,
+        seeds = [ATTENDANCE_EVENT_SEED, series.key().as_ref(), &event.event_id.to_le_bytes()],
+        bump = event.bump,
+    )]
+    pub event: Account<'info, AttendanceEvent>,
+
+    #[account(
+        init,
+        payer = attendee,
+        space = 8 + Attendance::INIT_SPACE,
+        seeds = [ATTENDANCE_SEED, event.key().as_ref(), attendee.key().as_ref()],
+        bump,
+    )]
+    pub attendance: Account<'info, Attendance>,
+
+    /// CHECK: checked against event.badge_mint
+    #[account(mut)]
+    pub badge_mint: UncheckedAccount<'info>,
+
+    /// Attendee's badge token account
+    /// CHECK: owner is validated in the handler
+    #[account(mut)]
+    pub badge_account: UncheckedAccount<'info>,
+
+    /// Instructions sysvar, to find the organizer's voucher
+    /// CHECK: address is the instructions sysvar
+    #[account(address = solana_sdk_ids::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    /// Token-2022 program
+    /// CHECK: must be Token-2022 id
+    #[account(address = spl_token_2022::id())]
+    pub token_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event(discriminator = [211, 80, 198, 244, 196, 84, 212, 150])]
+pub struct CheckedIn {
+    pub version: u8,
+    pub event: Pubkey,
+    pub wallet: Pubkey,
+    /// Attendees so far, including this one
+    pub attendees: u32,
+}
+
+#[event(discriminator = [185, 225, 220, 84, 209, 183, 217, 238])]
+pub struct QuestCompleted {
+    pub version: u8,
+    pub quest: Pubkey,
+    pub wallet: Pubkey,
+    pub attestor: Pubkey,
+    pub quests_completed: u32,
+}
+
+#[derive(Accounts)]
+#[instruction(slot: u64)]
+pub struct PostSnapshot<'info> {
+    #[account(mut)]
+    pub snapshotter: Signer<'info>,
+
+    #[account(
+        seeds = [PLATFORM_CONFIG_SEED],
+        bump = platform_config.bump,
+        has_one = snapshotter @ MyError::Unauthorized,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    /// CHECK: must be a Token-2022 mint
+    #[account(owner = spl_token_2022::id())]
+    pub mint: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = snapshotter,
+        space = 8 + Snapshot::INIT_SPACE,
+        seeds = [SNAPSHOT_SEED, mint.key().as_ref(), &slot.to_le_bytes()],
+        bump,
+    )]
+    pub snapshot: Account<'info, Snapshot>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub const SNAPSHOT_SEED: &[u8] = b"snapshot";
+
+/// Merkle root of a mint's holder balances at `slot`.
+#[account]
+#[derive(InitSpace)]
+pub struct Snapshot {
+    pub mint: Pubkey,
+    pub slot: u64,
+    pub merkle_root: [u8; 32],
+    /// Sum of all leaf balances
+    pub total_balance: u64,
+    pub holders: u32,
+    pub posted_at: i64,
+    pub bump: u8,
+}
+
+impl Snapshot {
+    /// Whether `proof` shows that `wallet` held `balance` in this snapshot. Sibling pairs are
+    /// hashed in sorted order, so proofs carry no left/right flags.
+    pub fn verify(&self, wallet: &Pubkey, balance: u64, proof: &[[u8; 32]]) -> bool {
+        let mut node = snapshot_leaf(wallet, balance);
+        for sibling in proof {
+            node = if node <= *sibling {
+                hashv(&[&node, sibling]).to_bytes()
+            } else {
+                hashv(&[sibling, &node]).to_bytes()
+            };
+        }
+        node == self.merkle_root
+    }
+}
+
+/// Snapshot leaf: sha256 of the wallet followed by its little-endian balance.
+pub fn snapshot_leaf(wallet: &Pubkey, balance: u64) -> [u8; 32] {
+    hashv(&[wallet.as_ref(), &balance.to_le_bytes()]).to_bytes()
+}
+
+#[event(discriminator = [224, 99, 100, 104, 153, 13, 20, 15])]
+pub struct SnapshotPosted {
+    pub version: u8,
+    pub snapshot: Pubkey,
+    pub mint: Pubkey,
+    pub slot: u64,
+    pub merkle_root: [u8; 32],
+}
+
+#[derive(Accounts)]
+#[instruction(proposal_id: u64)]
+pub struct CreateProposal<'info> {
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    #[account(seeds = [SNAPSHOT_SEED, snapshot.mint.as_ref(), &snapshot.slot.to_le_bytes()], bump = snapshot.bump)]
+    pub snapshot: Account<'info, Snapshot>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = 8 + Proposal::INIT_SPACE,
+        seeds = [PROPOSAL_SEED, snapshot.mint.as_ref(), &proposal_id.to_le_bytes()],
+        bump,
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CastVote<'info> {
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    #[account(mut, has_one = snapshot)]
+    pub proposal: Account<'info, Proposal>,
+
+    pub snapshot: Account<'info, Snapshot>,
+
+    /// Voter's vote lock on the proposal's mint, adding its decayed weight
+    #[account(
+        seeds = [VOTE_LOCK_SEED, proposal.mint.as_ref(), voter.key().as_ref()],
+        bump = vote_lock.bump,
+    )]
+    pub vote_lock: Option<Account<'info, VoteLock>>,
+
+    #[account(
+        init,
+        payer = voter,
+        space = 8 + VoteReceipt::INIT_SPACE,
+        seeds = [VOTE_RECEIPT_SEED, proposal.key().as_ref(), voter.key().as_ref()],
+        bump,
+    )]
+    pub receipt: Account<'info, VoteReceipt>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeProposal<'info> {
+    #[account(mut)]
+    pub proposal: Account<'info, Proposal>,
+}
+
+pub const PROPOSAL_SEED: &[u8] = b"proposal";
+/// Lowest quorum a proposal may set, as a share of its snapshot's total balance
+pub const MIN_PROPOSAL_QUORUM_BPS: u16 = 1_000;
+pub const VOTE_RECEIPT_SEED: &[u8] = b"vote_receipt";
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum ProposalStatus {
+    Voting,
+    Passed,
+    Rejected,
+}
+
+/// Token-holder proposal of a mint, voted with balances from `snapshot`.
+#[account]
+#[derive(InitSpace)]
+pub struct Proposal {
+    pub snapshot: Pubkey,
+    pub mint: Pubkey,
+    pub proposal_id: u64,
+    pub proposer: Pubkey,
+    #[max_len(MAX_URI_LEN)]
+    pub description_uri: String,
+    pub created_at: i64,
+    pub voting_ends_at: i64,
+    /// Weight the yes and no votes together must reach for the proposal to pass
+    pub quorum_weight: u64,
+    pub yes_weight: u64,
+    pub no_weight: u64,
+    pub status: ProposalStatus,
+    pub bump: u8,
+}
+
+impl Proposal {
+    /// Outcome of the votes cast so far.
+    pub fn outcome(&self) -> ProposalStatus {
+        let turnout = self.yes_weight as u128 + self.no_weight as u128;
+        if turnout >= self.quorum_weight as u128 && self.yes_weight > self.no_weight {
+            ProposalStatus::Passed
+        } else {
+            ProposalStatus::Rejected
+        }
+    }
+}
+
+/// A voter's ballot on one proposal.
+#[account]
+#[derive(InitSpace)]
+pub struct VoteReceipt {
+    pub proposal: Pubkey,
+    pub voter: Pubkey,
+    pub support: bool,
+    pub weight: u64,
+    pub bump: u8,
+}
+
+#[event(discriminator = [186, 8, 160, 108, 81, 13, 51, 206])]
+pub struct ProposalCreated {
+    pub version: u8,
+    pub proposal: Pubkey,
+    pub snapshot: Pubkey,
+    pub proposer: Pubkey,
+    pub voting_ends_at: i64,
+}
+
+#[event(discriminator = [39, 53, 195, 104, 188, 17, 225, 213])]
+pub struct VoteCast {
+    pub version: u8,
+    pub proposal: Pubkey,
+    pub voter: Pubkey,
+    pub support: bool,
+    pub weight: u64,
+}
+
+#[event(discriminator = [159, 104, 210, 220, 86, 209, 61, 51])]
+pub struct ProposalFinalized {
+    pub version: u8,
+    pub proposal: Pubkey,
+    pub passed: bool,
+    pub yes_weight: u64,
+    pub no_weight: u64,
+}
+
+#[derive(Accounts)]
+pub struct LockForVoting<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// Mint being locked
+    /// CHECK: must be a Token-2022 mint
+    #[account(owner = spl_token_2022::id())]
+    pub mint: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + VoteLock::INIT_SPACE,
+        seeds = [VOTE_LOCK_SEED, mint.key().as_ref(), owner.key().as_ref()],
+        bump,
+    )]
+    pub vote_lock: Account<'info, VoteLock>,
+
+    /// Owner's token account funding the lock
+    /// CHECK: validated by the token program during the transfer
+    #[account(mut)]
+    pub owner_token_account: UncheckedAccount<'info>,
+
+    /// Escrow: token account of the mint owned by the vote lock PDA
+    /// CHECK: mint and owner are validated in the handler
+    #[account(mut)]
+    pub vault: UncheckedAccount<'info>,
+
+    /// Token-2022 program
+    /// CHECK: must be Token-2022 id
+    #[account(address = spl_token_2022::id())]
+    pub token_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Pause record of the mint; may not exist
+    /// CHECK: PDA of the mint, read by ensure_not_paused
+    #[account(seeds = [MINT_PAUSE_SEED, mint.key().as_ref()], bump)]
+    pub mint_pause: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UnlockVotingLock<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        close = owner,
+        has_one = owner @ MyError::Unauthorized,
+        has_one = mint,
+        has_one = vault,
+        seeds = [VOTE_LOCK_SEED, mint.key().as_ref(), owner.key().as_ref()],
+        bump = vote_lock.bump,
+    )]
+    pub vote_lock: Account<'info, VoteLock>,
+
+    /// CHECK: checked against vote_lock.mint
+    #[account(mut)]
+    pub mint: UncheckedAccount<'info>,
+
+    /// CHECK: checked against vote_lock.vault
+    #[account(mut)]
+    pub vault: UncheckedAccount<'info>,
+
+    /// Owner's token account receiving the tokens back
+    /// CHECK: validated by the token program during the transfer
+    #[account(mut)]
+    pub owner_token_account: UncheckedAccount<'info>,
+
+    /// Token-2022 program
+    /// CHECK: must be Token-2022 id
+    #[account(address = spl_token_2022::id())]
+    pub token_program: UncheckedAccount<'info>,
+}
+
+pub const VOTE_LOCK_SEED: &[u8] = b"ve_lock";
+/// Longest vote lock, four years; a lock this long votes with its full amount
+pub const MAX_VOTE_LOCK_SECONDS: i64 = 4 * 365 * SECONDS_PER_DAY;
+
+/// Tokens escrowed for voting power until `unlock_at`.
+#[account]
+#[derive(InitSpace)]
+pub struct VoteLock {
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+    /// Tokens held in the vault, after the deposit's transfer fee
+    pub amount: u64,
+    pub locked_at: i64,
+    /// Slot the tokens left the owner's balance; only snapshots after it leave them out
+    pub locked_slot: u64,
+    pub unlock_at: i64,
+    pub bump: u8,
+}
+
+impl VoteLock {
+    /// Voting weight at `now`: amount scaled by the remaining lock time over the maximum lock
+    pub fn voting_weight(&self, now: i64) -> u64 {
+        math::decayed_weight(self.amount, self.unlock_at - now, MAX_VOTE_LOCK_SECONDS)
+    }
+}
+
+#[event(discriminator = [231, 17, 170, 98, 123, 221, 153, 211])]
+pub struct VoteLockCreated {
+    pub version: u8,
+    pub vote_lock: Pubkey,
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub unlock_at: i64,
+}
+
+#[derive(Accounts)]
+#[instruction(mint: Pubkey)]
+pub struct VoteGauge<'info> {
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    #[account(seeds = [PLATFORM_CONFIG_SEED], bump = platform_config.bump)]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    /// Voter's lock of the platform token
+    #[account(
+        seeds = [VOTE_LOCK_SEED, platform_config.platform_mint.as_ref(), voter.key().as_ref()],
+        bump = vote_lock.bump,
+    )]
+    pub vote_lock: Account<'info, VoteLock>,
+
+    #[account(
+        init_if_needed,
+        payer = voter,
+        space = 8 + GaugeEpoch::INIT_SPACE,
+        seeds = [GAUGE_EPOCH_SEED, &Clock::get()?.epoch.to_le_bytes()],
+        bump,
+    )]
+    pub gauge_epoch: Account<'info, GaugeEpoch>,
+
+    #[account(
+        init_if_needed,
+        payer = voter,
+        space = 8 + LaunchGauge::INIT_SPACE,
+        seeds = [LAUNCH_GAUGE_SEED, &Clock::get()?.epoch.to_le_bytes(), mint.as_ref()],
+        bump,
+    )]
+    pub launch_gauge: Account<'info, LaunchGauge>,
+
+    #[account(
+        init,
+        payer = voter,
+        space = 8 + GaugeVote::INIT_SPACE,
+        seeds = [GAUGE_VOTE_SEED, &Clock::get()?.epoch.to_le_bytes(), voter.key().as_ref()],
+        bump,
+    )]
+    pub receipt: Account<'info, GaugeVote>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MarkFeatured<'info> {
+    pub gauge_epoch: Account<'info, GaugeEpoch>,
+
+    #[account(
+        mut,
+        seeds = [LAUNCH_CONFIG_SEED, launch_config.mint.as_ref()],
+        bump = launch_config.bump,
+    )]
+    pub launch_config: Account<'info, LaunchConfig>,
+}
+
+pub const GAUGE_EPOCH_SEED: &[u8] = b"gauge_epoch";
+pub const LAUNCH_GAUGE_SEED: &[u8] = b"launch_gauge";
+pub const GAUGE_VOTE_SEED: &[u8] = b"gauge_vote";
+pub const MAX_FEATURED_LAUNCHES: usize = 5;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub struct GaugeEntry {
+    pub mint: Pubkey,
+    pub weight: u64,
+}
+
+/// Gauge votes of one Solana epoch; `featured` holds the heaviest mints, heaviest first.
+#[account]
+#[derive(InitSpace)]
+pub struct GaugeEpoch {
+    pub epoch: u64,
+    pub total_weight: u64,
+    #[max_len(MAX_FEATURED_LAUNCHES)]
+    pub featured: Vec<GaugeEntry>,
+    pub bump: u8,
+}
+
+impl GaugeEpoch {
+    /// Update `mint`'s running weight in the featured list, keeping only the heaviest mints
+    pub fn record(&mut self, mint: Pubkey, weight: u64) {
+        if let Some(index) = self.featured.iter().position(|entry| entry.mint == mint) {
+            self.featured[index].weight = weight;
+        } else if self.featured.len() < MAX_FEATURED_LAUNCHES {
+            self.featured.push(GaugeEntry { mint, weight });
+        } else {
+            let lightest = self.featured.len() - 1;
+            if weight <= self.featured[lightest].weight {
+                return;
+            }
+            self.featured[lightest] = GaugeEntry { mint, weight };
+        }
+        self.featured.sort_by(|a, b| b.weight.cmp(&a.weight));
+    }
+
+    pub fn is_featured(&self, mint: &Pubkey) -> bool {
+        self.featured.iter().any(|entry| entry.mint == *mint)
+    }
+}
+
+/// Weight voted onto one mint in one epoch. Mints need not exist yet.
+#[account]
+#[derive(InitSpace)]
+pub struct LaunchGauge {
+    pub epoch: u64,
+    pub mint: Pubkey,
+    pub weight: u64,
+    pub bump: u8,
+}
+
+/// A vote lock's gauge vote for one epoch.
+#[account]
+#[derive(InitSpace)]
+pub struct GaugeVote {
+    pub epoch: u64,
+    pub voter: Pubkey,
+    pub mint: Pubkey,
+    pub weight: u64,
+    pub bump: u8,
+}
+
+#[event(discriminator = [210, 66, 171, 101, 180, 152, 105, 57])]
+pub struct GaugeVoteCast {
+    pub version: u8,
+    pub epoch: u64,
+    pub voter: Pubkey,
+    pub mint: Pubkey,
+    pub weight: u64,
+}
+
+#[event(discriminator = [181, 122, 3, 14, 211, 214, 52, 132])]
+pub struct LaunchFeatured {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub epoch: u64,
+}
+
+#[derive(Accounts)]
+pub struct InitProjectTreasury<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        seeds = [LAUNCH_CONFIG_SEED, launch_config.mint.as_ref()],
+        bump = launch_config.bump,
+        has_one = creator @ MyError::Unauthorized,
+    )]
+    pub launch_config: Account<'info, LaunchConfig>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + ProjectTreasury::INIT_SPACE,
+        seeds = [PROJECT_TREASURY_SEED, launch_config.mint.as_ref()],
+        bump,
+    )]
+    pub treasury: Account<'info, ProjectTreasury>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateTreasuryLimits<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(mut, has_one = admin @ MyError::Unauthorized)]
+    pub treasury: Account<'info, ProjectTreasury>,
+}
+
+#[derive(Accounts)]
+pub struct TreasuryTransfer<'info> {
+    #[account(mut)]
+    pub spender: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = mint,
+        seeds = [PROJECT_TREASURY_SEED, mint.key().as_ref()],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info, ProjectTreasury>,
+
+    #[account(
+        init,
+        payer = spender,
+        space = 8 + Disbursement::INIT_SPACE,
+        seeds = [DISBURSEMENT_SEED, treasury.key().as_ref(), &treasury.disbursements.to_le_bytes()],
+        bump,
+    )]
+    pub disbursement: Account<'info, Disbursement>,
+
+    /// CHECK: checked against treasury.mint
+    pub mint: UncheckedAccount<'info>,
+
+    /// Treasury token account of the mint; the token program checks its owner is the treasury
+    /// CHECK: validated by the token program during the transfer
+    #[account(mut)]
+    pub vault: UncheckedAccount<'info>,
+
+    /// CHECK: validated by the token program during the transfer
+    #[account(mut)]
+    pub destination: UncheckedAccount<'info>,
+
+    /// Token-2022 program
+    /// CHECK: must be Token-2022 id
+    #[account(address = spl_token_2022::id())]
+    pub token_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Pause record of the mint; may not exist
+    /// CHECK: PDA of the mint, read by ensure_not_paused
+    #[account(seeds = [MINT_PAUSE_SEED, mint.key().as_ref()], bump)]
+    pub mint_pause: UncheckedAccount<'info>,
+}
+
+pub const PROJECT_TREASURY_SEED: &[u8] = b"project_treasury";
+pub const DISBURSEMENT_SEED: &[u8] = b"disbursement";
+pub const MAX_TREASURY_SPENDERS: usize = 5;
+
+/// Spenders and spending cap of a project treasury
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct TreasuryLimits {
+    pub spenders: Vec<Pubkey>,
+    pub period_seconds: i64,
+    /// Most the spenders may pay out per period, in raw units of the project mint
+    pub period_cap: u64,
+}
+
+impl TreasuryLimits {
+    pub fn validate(&self) -> Result<()> {
+        if self.spenders.len() > MAX_TREASURY_SPENDERS || self.period_seconds <= 0 {
+            return Err(MyError::InvalidTreasuryLimits.into());
+        }
+        Ok(())
+    }
+}
+
+/// Most that may leave an account per fixed-length period. Shared by project treasuries and fee
+/// delegates.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub struct PeriodCap {
+    pub period_seconds: i64,
+    /// In raw units of the mint
+    pub cap: u64,
+    pub period_start: i64,
+    /// Charged against the cap since `period_start`
+    pub spent: u64,
+}
+
+impl PeriodCap {
+    /// A cap whose first period starts at `now`
+    pub fn new(period_seconds: i64, cap: u64, now: i64) -> Self {
+        Self { period_seconds, cap, period_start: now, spent: 0 }
+    }
+
+    /// Change the period length and cap; what the current period has spent still counts.
+    pub fn set_limits(&mut self, period_seconds: i64, cap: u64) {
+        self.period_seconds = period_seconds;
+        self.cap = cap;
+    }
+
+    /// Count `amount` against the current period's cap, rolling the period forward if needed
+    pub fn charge(&mut self, amount: u64, now: i64) -> Result<()> {
+        if now >= self.period_start + self.period_seconds {
+            let elapsed_periods = (now - self.period_start) / self.period_seconds;
+            self.period_start += elapsed_periods * self.period_seconds;
+            self.spent = 0;
+        }
+        let spent = self.spent.checked_add(amount).ok_or(MyError::MathOverflow)?;
+        if spent > self.cap {
+            return Err(MyError::SpendingCapExceeded.into());
+        }
+        self.spent = spent;
+        Ok(())
+    }
+}
+
+/// Project-mint treasury of a launch, spent by role holders within a per-period cap.
+#[account]
+#[derive(InitSpace)]
+pub struct ProjectTreasury {
+    pub mint: Pubkey,
+    pub admin: Pubkey,
+    #[max_len(MAX_TREASURY_SPENDERS)]
+    pub spenders: Vec<Pubkey>,
+    /// Shared by all spenders
+    pub spending: PeriodCap,
+    /// Number of Disbursement records written; also the next record's index
+    pub disbursements: u64,
+    pub bump: u8,
+}
+
+/// One payout from a project treasury.
+#[account]
+#[derive(InitSpace)]
+pub struct Disbursement {
+    pub treasury: Pubkey,
+    pub index: u64,
+    pub spender: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    /// Transfer fee withheld from the payout
+    pub fee: u64,
+    #[max_len(MAX_URI_LEN)]
+    pub memo: String,
+    pub paid_at: i64,
+    pub bump: u8,
+}
+
+#[event(discriminator = [181, 163, 45, 130, 26, 197, 100, 210])]
+pub struct TreasuryDisbursed {
+    pub version: u8,
+    pub treasury: Pubkey,
+    pub index: u64,
+    pub spender: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub fee: u64,
+}
+
+#[derive(Accounts)]
+#[instruction(payment_id: u64)]
+pub struct CreateRecurringPayment<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(has_one = admin @ MyError::Unauthorized)]
+    pub treasury: Account<'info, ProjectTreasury>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + RecurringPayment::INIT_SPACE,
+        seeds = [RECURRING_PAYMENT_SEED, treasury.key().as_ref(), &payment_id.to_le_bytes()],
+        bump,
+    )]
+    pub payment: Account<'info, RecurringPayment>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteRecurringPayment<'info> {
+    #[account(
+        mut,
+        has_one = mint,
+        seeds = [PROJECT_TREASURY_SEED, mint.key().as_ref()],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info, ProjectTreasury>,
+
+    #[account(
+        mut,
+        has_one = treasury,
+        has_one = destination,
+        seeds = [RECURRING_PAYMENT_SEED, treasury.key().as_ref(), &payment.payment_id.to_le_bytes()],
+        bump = payment.bump,
+    )]
+    pub payment: Account<'info, RecurringPayment>,
+
+    /// CHECK: checked against treasury.mint
+    pub mint: UncheckedAccount<'info>,
+
+    /// Treasury token account of the mint
+    /// CHECK: validated by the token program during the transfer
+    #[account(mut)]
+    pub vault: UncheckedAccount<'info>,
+
+    /// CHECK: checked against payment.destination
+    #[account(mut)]
+    pub destination: UncheckedAccount<'info>,
+
+    /// Token-2022 program
+    /// CHECK: must be Token-2022 id
+    #[account(address = spl_token_2022::id())]
+    pub token_program: UncheckedAccount<'info>,
+
+    /// Pause record of the mint; may not exist
+    /// CHECK: PDA of the mint, read by ensure_not_paused
+    #[account(seeds = [MINT_PAUSE_SEED, mint.key().as_ref()], bump)]
+    pub mint_pause: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelRecurringPayment<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(has_one = admin @ MyError::Unauthorized)]
+    pub treasury: Account<'info, ProjectTreasury>,
+
+    #[account(mut, close = admin, has_one = treasury)]
+    pub payment: Account<'info, RecurringPayment>,
+}
+
+pub const RECURRING_PAYMENT_SEED: &[u8] = b"recurring_payment";
+
+/// Standing order paying `amount` from a project treasury to `destination` every interval.
+#[account]
+#[derive(InitSpace)]
+pub struct RecurringPayment {
+    pub treasury: Pubkey,
+    pub payment_id: u64,
+    /// Token account receiving the payments
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub interval_seconds: i64,
+    pub next_due: i64,
+    pub payments_made: u64,
+    pub bump: u8,
+}
+
+#[event(discriminator = [167, 174, 91, 30, 102, 117, 232, 180])]
+pub struct RecurringPaymentExecuted {
+    pub version: u8,
+    pub payment: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub fee: u64,
+    pub next_due: i64,
+}
+
+#[derive(Accounts)]
+pub struct ConfigureNtt<'info> {
+    pub creator: Signer<'info>,
+
+    /// Current mint authority of the mint
+    pub mint_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [LAUNCH_CONFIG_SEED, mint.key().as_ref()],
+        bump = launch_config.bump,
+        has_one = creator @ MyError::Unauthorized,
+        has_one = mint,
+    )]
+    pub launch_config: Account<'info, LaunchConfig>,
+
+    /// CHECK: checked against launch_config.mint
+    #[account(mut)]
+    pub mint: UncheckedAccount<'info>,
+
+    /// Token-2022 program
+    /// CHECK: must be Token-2022 id
+    #[account(address = spl_token_2022::id())]
+    pub token_program: UncheckedAccount<'info>,
+}
+
+/// Seed of the NTT manager PDA that holds mint authority in burning mode
+pub const NTT_TOKEN_AUTHORITY_SEED: &[u8] = b"token_authority";
+
+#[event(discriminator = [52, 30, 161, 16, 41, 46, 28, 100])]
+pub struct NttConfigured {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub ntt_manager: Pubkey,
+    pub mode: NttMode,
+}
+
+#[derive(Accounts)]
+pub struct RolloverEpoch<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Leaderboard::INIT_SPACE,
+        seeds = [LEADERBOARD_SEED, &Clock::get()?.epoch.to_le_bytes()],
+        bump,
+    )]
+    pub leaderboard: Account<'info, Leaderboard>,
+
+    #[account(mut)]
+    pub previous_leaderboard: Option<Account<'info, Leaderboard>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateLeaderboard<'info> {
+    #[account(seeds = [MINT_STATS_SEED, mint_stats.mint.as_ref()], bump = mint_stats.bump)]
+    pub mint_stats: Account<'info, MintStats>,
+
+    #[account(mut, seeds = [LEADERBOARD_SEED, &leaderboard.epoch.to_le_bytes()], bump = leaderboard.bump)]
+    pub leaderboard: Account<'info, Leaderboard>,
+}
+
+pub const LEADERBOARD_SEED: &[u8] = b"leaderboard";
+pub const MAX_LEADERBOARD_ENTRIES: usize = 10;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub struct LeaderboardEntry {
+    pub mint: Pubkey,
+    /// Epoch volume and fees reported through `transfer_with_fee`
+    pub volume: u64,
+    pub fees: u64,
+}
+
+/// Highest self-reported-volume mints of one Solana epoch, highest first; see `MintStats`
+/// for what the volume covers.
+#[account]
+#[derive(InitSpace)]
+pub struct Leaderboard {
+    pub epoch: u64,
+    #[max_len(MAX_LEADERBOARD_ENTRIES)]
+    pub entries: Vec<LeaderboardEntry>,
+    /// Set once the next epoch has rolled over; no more updates
+    pub finalized: bool,
+    pub bump: u8,
+}
+
+impl Leaderboard {
+    /// Insert or refresh a mint's entry, keeping only the highest-volume mints
+    pub fn record(&mut self, entry: LeaderboardEntry) {
+        if let Some(index) = self.entries.iter().position(|e| e.mint == entry.mint) {
+            self.entries[index] = entry;
+        } else if self.entries.len() < MAX_LEADERBOARD_ENTRIES {
+            self.entries.push(entry);
+        } else {
+            let lowest = self.entries.len() - 1;
+            if entry.volume <= self.entries[lowest].volume {
+                return;
+            }
+            self.entries[lowest] = entry;
+        }
+        self.entries.sort_by(|a, b| b.volume.cmp(&a.volume));
+    }
+}
+
+#[event(discriminator = [248, 151, 208, 212, 152, 58, 23, 231])]
+pub struct EpochRolledOver {
+    pub version: u8,
+    pub epoch: u64,
+}
+
+#[derive(Accounts)]
+pub struct PauseMint<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(seeds = [PLATFORM_CONFIG_SEED], bump = platform_config.bump)]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(seeds = [LAUNCH_CONFIG_SEED, launch_config.mint.as_ref()], bump = launch_config.bump)]
+    pub launch_config: Account<'info, LaunchConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + MintPause::INIT_SPACE,
+        seeds = [MINT_PAUSE_SEED, launch_config.mint.as_ref()],
+        bump,
+    )]
+    pub mint_pause: Account<'info, MintPause>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UnpauseMint<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(seeds = [PLATFORM_CONFIG_SEED], bump = platform_config.bump)]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(seeds = [LAUNCH_CONFIG_SEED, launch_config.mint.as_ref()], bump = launch_config.bump)]
+    pub launch_config: Account<'info, LaunchConfig>,
+
+    #[account(mut, seeds = [MINT_PAUSE_SEED, launch_config.mint.as_ref()], bump = mint_pause.bump)]
+    pub mint_pause: Account<'info, MintPause>,
+}
+
+pub const MINT_PAUSE_SEED: &[u8] = b"mint_pause";
+/// Longest single pause, so a pause can't turn into a permanent freeze
+pub const MAX_MINT_PAUSE_SECONDS: i64 = 7 * SECONDS_PER_DAY;
+
+/// Temporary pause of a mint's program-mediated actions.
+#[account]
+#[derive(InitSpace)]
+pub struct MintPause {
+    pub mint: Pubkey,
+    pub paused_by: Pubkey,
+    /// Actions are paused while the clock is before this time
+    pub paused_until: i64,
+    /// Scheduled launch time; actions stay closed before it even when unpaused
+    pub opens_at: i64,
+    /// No new pause may start before this time
+    pub next_pause_at: i64,
+    pub bump: u8,
+}
+
+impl MintPause {
+    /// Pause for `duration_seconds` from `now`. Refused while a pause is in force or cooling
+    /// down, so back-to-back pauses can't add up to a permanent freeze.
+    pub fn start(&mut self, now: i64, duration_seconds: i64) -> Result<()> {
+        if duration_seconds <= 0 || duration_seconds > MAX_MINT_PAUSE_SECONDS {
+            return Err(MyError::InvalidPauseDuration.into());
+        }
+        if now < self.next_pause_at {
+            return Err(MyError::PauseCooldownActive.into());
+        }
+        self.paused_until = now + duration_seconds;
+        self.next_pause_at = self.paused_until + duration_seconds;
+        Ok(())
+    }
+}
+
+#[event(discriminator = [190, 101, 215, 127, 210, 131, 124, 246])]
+pub struct MintPaused {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub paused_by: Pubkey,
+    pub paused_until: i64,
+}
+
+#[event(discriminator = [71, 38, 143, 177, 243, 7, 181, 14])]
+pub struct MintUnpaused {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub unpaused_by: Pubkey,
+}
+
+#[derive(Accounts)]
+#[instruction(opens_at: i64)]
+pub struct ScheduleLaunch<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        has_one = creator @ MyError::Unauthorized,
+        seeds = [LAUNCH_CONFIG_SEED, launch_config.mint.as_ref()],
+        bump = launch_config.bump,
+    )]
+    pub launch_config: Account<'info, LaunchConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = 8 + MintPause::INIT_SPACE,
+        seeds = [MINT_PAUSE_SEED, launch_config.mint.as_ref()],
+        bump,
+    )]
+    pub mint_pause: Account<'info, MintPause>,
+
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = 8 + LaunchCalendar::INIT_SPACE,
+        seeds = [LAUNCH_CALENDAR_SEED, &(opens_at / SECONDS_PER_DAY).to_le_bytes()],
+        bump,
+    )]
+    pub calendar: Account<'info, LaunchCalendar>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub const LAUNCH_CALENDAR_SEED: &[u8] = b"launch_calendar";
+pub const MAX_CALENDAR_LAUNCHES: usize = 32;
+/// Slots of one calendar page a single creator can hold
+pub const MAX_CALENDAR_LAUNCHES_PER_CREATOR: usize = 2;
+/// Furthest ahead a launch can be scheduled, so a schedule can't turn into a long freeze
+pub const MAX_LAUNCH_SCHEDULE_SECONDS: i64 = 30 * SECONDS_PER_DAY;
+
+/// Launches opening on one UTC day, `day` days after the Unix epoch, ordered by opening time.
+#[account]
+#[derive(InitSpace)]
+pub struct LaunchCalendar {
+    pub day: i64,
+    #[max_len(MAX_CALENDAR_LAUNCHES)]
+    pub launches: Vec<ScheduledLaunch>,
+    pub bump: u8,
+}
+
+impl LaunchCalendar {
+    /// List a launch in opening-time order, within the page's and its creator's limits
+    pub fn schedule(&mut self, launch: ScheduledLaunch) -> Result<()> {
+        if self.launches.len() >= MAX_CALENDAR_LAUNCHES {
+            return Err(MyError::LaunchCalendarFull.into());
+        }
+        let held = self.launches.iter().filter(|scheduled| scheduled.creator == launch.creator).count();
+        if held >= MAX_CALENDAR_LAUNCHES_PER_CREATOR {
+            return Err(MyError::CalendarCreatorLimit.into());
+        }
+        let index = self.launches.partition_point(|scheduled| scheduled.opens_at <= launch.opens_at);
+        self.launches.insert(index, launch);
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub struct ScheduledLaunch {
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub opens_at: i64,
+}
+
+#[event(discriminator = [238, 26, 84, 92, 241, 165, 254, 231])]
+pub struct LaunchScheduled {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub opens_at: i64,
+    pub day: i64,
+}
+
+#[derive(Accounts)]
+pub struct UpdatePoolRegistry<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(seeds = [PLATFORM_CONFIG_SEED], bump = platform_config.bump)]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(seeds = [LAUNCH_CONFIG_SEED, launch_config.mint.as_ref()], bump = launch_config.bump)]
+    pub launch_config: Account<'info, LaunchConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        // an expanded registry keeps its size
+        space = (8 + PoolRegistry::INIT_SPACE).max(pool_registry.data_len()),
+        seeds = [POOL_REGISTRY_SEED, launch_config.mint.as_ref()],
+        bump,
+    )]
+    pub pool_registry: Account<'info, PoolRegistry>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl UpdatePoolRegistry<'_> {
+    fn check_authority(&self) -> Result<()> {
+        let authority = self.authority.key();
+        if authority != self.launch_config.creator && authority != self.platform_config.verifier {
+            return Err(MyError::Unauthorized.into());
+        }
+        Ok(())
+    }
+}
+
+pub const POOL_REGISTRY_SEED: &[u8] = b"pool_registry";
+pub const MAX_REGISTERED_POOLS: usize = 8;
+
+/// Known AMM pool token vaults of a mint, used to tell buys and sells apart in the
+/// self-reported MintStats volume.
+#[account]
+#[derive(InitSpace)]
+pub struct PoolRegistry {
+    pub mint: Pubkey,
+    #[max_len(MAX_REGISTERED_POOLS)]
+    pub pools: Vec<Pubkey>,
+    pub bump: u8,
+}
+
+impl PoolRegistry {
+    /// Pools an account of `data_len` bytes can hold
+    pub fn capacity(data_len: usize) -> usize {
+        list_capacity(data_len, 8 + Self::INIT_SPACE, MAX_REGISTERED_POOLS, 32)
+    }
+
+    pub fn is_pool(&self, token_account: &Pubkey) -> bool {
+        self.pools.contains(token_account)
+    }
+}
+
+#[event(discriminator = [76, 6, 6, 201, 199, 67, 177, 147])]
+pub struct AccountExpanded {
+    pub version: u8,
+    pub account: Pubkey,
+    /// Entries the account can now hold
+    pub capacity: u32,
+}
+
+#[event(discriminator = [77, 114, 165, 230, 33, 230, 135, 215])]
+pub struct PoolRegistered {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub pool_vault: Pubkey,
+}
+
+#[event(discriminator = [199, 101, 13, 77, 137, 141, 22, 17])]
+pub struct PoolDeregistered {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub pool_vault: Pubkey,
+}
+
+#[derive(Accounts)]
+pub struct InitEmissionSchedule<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    /// Current mint authority, handing over to the schedule
+    pub mint_authority: Signer<'info>,
+
+    #[account(
+        seeds = [LAUNCH_CONFIG_SEED, mint.key().as_ref()],
+        bump = launch_config.bump,
+        has_one = creator @ MyError::Unauthorized,
+        has_one = mint,
+    )]
+    pub launch_config: Account<'info, LaunchConfig>,
+
+    /// CHECK: checked against launch_config.mint
+    #[account(mut)]
+    pub mint: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + EmissionSchedule::INIT_SPACE,
+        seeds = [EMISSION_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub schedule: Account<'info, EmissionSchedule>,
+
+    /// Token account of the mint receiving emissions (rewards, ecosystem fund...)
+    /// CHECK: mint is validated in the handler
+    pub vault: UncheckedAccount<'info>,
+
+    /// Token-2022 program
+    /// CHECK: must be Token-2022 id
+    #[account(address = spl_token_2022::id())]
+    pub token_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MintEmission<'info> {
+    #[account(
+        mut,
+        has_one = mint,
+        has_one = vault,
+        seeds = [EMISSION_SEED, mint.key().as_ref()],
+        bump = schedule.bump,
+    )]
+    pub schedule: Account<'info, EmissionSchedule>,
+
+    /// CHECK: checked against schedule.mint
+    #[account(mut)]
+    pub mint: UncheckedAccount<'info>,
+
+    /// CHECK: checked against schedule.vault
+    #[account(mut)]
+    pub vault: UncheckedAccount<'info>,
+
+    /// Token-2022 program
+    /// CHECK: must be Token-2022 id
+    #[account(address = spl_token_2022::id())]
+    pub token_program: UncheckedAccount<'info>,
+
+    /// Pause record of the mint; may not exist
+    /// CHECK: PDA of the mint, read by ensure_not_paused
+    #[account(seeds = [MINT_PAUSE_SEED, mint.key().as_ref()], bump)]
+    pub mint_pause: UncheckedAccount<'info>,
+}
+
+pub const EMISSION_SEED: &[u8] = b"emission";
+
+/// Shape of an emission schedule over its periods
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum EmissionCurve {
+    /// Per-period emission halves every `periods_per_halving` periods
+    Halving { periods_per_halving: u32 },
+    /// Per-period emission falls linearly to zero over `total_periods`
+    LinearDecay { total_periods: u32 },
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct EmissionParams {
+    pub start_time: i64,
+    pub period_seconds: i64,
+    pub initial_per_period: u64,
+    pub curve: EmissionCurve,
+}
+
+impl EmissionParams {
+    pub fn validate(&self) -> Result<()> {
+        let curve_ok = match self.curve {
+            EmissionCurve::Halving { periods_per_halving } => periods_per_halving > 0,
+            EmissionCurve::LinearDecay { total_periods } => total_periods > 0,
+        };
+        if !curve_ok || self.period_seconds <= 0 || self.initial_per_period == 0 {
+            return Err(MyError::InvalidEmissionSchedule.into());
+        }
+        Ok(())
+    }
+}
+
+/// Program-held mint authority releasing supply on a fixed curve.
+#[account]
+#[derive(InitSpace)]
+pub struct EmissionSchedule {
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+    pub start_time: i64,
+    pub period_seconds: i64,
+    pub initial_per_period: u64,
+    pub curve: EmissionCurve,
+    /// Total minted by the schedule so far
+    pub minted: u64,
+    pub bump: u8,
+}
+
+impl EmissionSchedule {
+    /// Total the schedule has released by `now`, counting whole periods only
+    pub fn released(&self, now: i64) -> Result<u64> {
+        if now < self.start_time {
+            return Ok(0);
+        }
+        let periods = ((now - self.start_time) / self.period_seconds) as u64;
+        let total = match self.curve {
+            EmissionCurve::Halving { periods_per_halving } => {
+                math::halving_released(self.initial_per_period, periods_per_halving, periods)
+            }
+            EmissionCurve::LinearDecay { total_periods } => {
+                math::linear_decay_released(self.initial_per_period, total_periods, periods)
+            }
+        };
+        u64::try_from(total).map_err(|_| MyError::MathOverflow.into())
+    }
+}
+
+#[event(discriminator = [127, 4, 53, 212, 212, 89, 247, 244])]
+pub struct EmissionMinted {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub total_minted: u64,
+}
+
+#[derive(Accounts)]
+pub struct InitAllocationPlan<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    /// Current mint authority, handing over to the plan
+    pub mint_authority: Signer<'info>,
+
+    #[account(
+        seeds = [LAUNCH_CONFIG_SEED, mint.key().as_ref()],
+        bump = launch_config.bump,
+        has_one = creator @ MyError::Unauthorized,
+        has_one = mint,
+    )]
+    pub launch_config: Account<'info, LaunchConfig>,
+
+    /// CHECK: checked against launch_config.mint
+    #[account(mut)]
+    pub mint: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + AllocationPlan::INIT_SPACE,
+        seeds = [ALLOCATION_PLAN_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub plan: Account<'info, AllocationPlan>,
+
+    /// Token-2022 program
+    /// CHECK: must be Token-2022 id
+    #[account(address = spl_token_2022::id())]
+    pub token_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ReleaseAllocation<'info> {
+    #[account(
+        mut,
+        has_one = mint,
+        seeds = [ALLOCATION_PLAN_SEED, mint.key().as_ref()],
+        bump = plan.bump,
+    )]
+    pub plan: Account<'info, AllocationPlan>,
+
+    /// CHECK: checked against plan.mint
+    pub mint: UncheckedAccount<'info>,
+
+    /// Plan-owned locker of the bucket
+    /// CHECK: checked against the bucket's vault
+    #[account(mut)]
+    pub vault: UncheckedAccount<'info>,
+
+    /// CHECK: checked against the bucket's beneficiary
+    #[account(mut)]
+    pub beneficiary: UncheckedAccount<'info>,
+
+    /// Token-2022 program
+    /// CHECK: must be Token-2022 id
+    #[account(address = spl_token_2022::id())]
+    pub token_program: UncheckedAccount<'info>,
+
+    /// Pause record of the mint; may not exist
+    /// CHECK: PDA of the mint, read by ensure_not_paused
+    #[account(seeds = [MINT_PAUSE_SEED, mint.key().as_ref()], bump)]
+    pub mint_pause: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseAllocationPlan<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        close = creator,
+        has_one = creator @ MyError::Unauthorized,
+        has_one = mint,
+        seeds = [ALLOCATION_PLAN_SEED, mint.key().as_ref()],
+        bump = plan.bump,
+    )]
+    pub plan: Account<'info, AllocationPlan>,
+
+    /// CHECK: checked against plan.mint
+    #[account(mut)]
+    pub mint: UncheckedAccount<'info>,
+
+    /// Token-2022 program
+    /// CHECK: must be Token-2022 id
+    #[account(address = spl_token_2022::id())]
+    pub token_program: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeAllocation<'info> {
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = creator @ MyError::Unauthorized,
+        has_one = mint,
+        seeds = [ALLOCATION_PLAN_SEED, mint.key().as_ref()],
+        bump = plan.bump,
+    )]
+    pub plan: Account<'info, AllocationPlan>,
+
+    /// CHECK: checked against plan.mint
+    pub mint: UncheckedAccount<'info>,
+
+    /// Plan-owned locker of the bucket
+    /// CHECK: checked against the bucket's vault
+    #[account(mut)]
+    pub vault: UncheckedAccount<'info>,
+
+    /// Receives the unvested tokens
+    /// CHECK: the transfer checks it is an account of the mint
+    #[account(mut)]
+    pub destination: UncheckedAccount<'info>,
+
+    /// Token-2022 program
+    /// CHECK: must be Token-2022 id
+    #[account(address = spl_token_2022::id())]
+    pub token_program: UncheckedAccount<'info>,
+
+    /// Pause record of the mint; may not exist
+    /// CHECK: PDA of the mint, read by ensure_not_paused
+    #[account(seeds = [MINT_PAUSE_SEED, mint.key().as_ref()], bump)]
+    pub mint_pause: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeAllocationBeneficiary<'info> {
+    /// Owner of the bucket's current beneficiary account
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [ALLOCATION_PLAN_SEED, plan.mint.as_ref()],
+        bump = plan.bump,
+    )]
+    pub plan: Account<'info, AllocationPlan>,
+
+    /// CHECK: checked against the bucket's beneficiary
+    pub beneficiary: UncheckedAccount<'info>,
+
+    /// CHECK: must be a token account of plan.mint
+    pub new_beneficiary: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAllocationBeneficiary<'info> {
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = creator @ MyError::Unauthorized,
+        has_one = mint,
+        seeds = [ALLOCATION_PLAN_SEED, mint.key().as_ref()],
+        bump = plan.bump,
+    )]
+    pub plan: Account<'info, AllocationPlan>,
+
+    /// CHECK: checked against plan.mint
+    pub mint: UncheckedAccount<'info>,
+
+    /// Plan-owned locker of the bucket
+    /// CHECK: checked against the bucket's vault
+    #[account(mut)]
+    pub vault: UncheckedAccount<'info>,
+
+    /// Current beneficiary; receives what has vested so far
+    /// CHECK: checked against the bucket's beneficiary
+    #[account(mut)]
+    pub beneficiary: UncheckedAccount<'info>,
+
+    /// Token-2022 program
+    /// CHECK: must be Token-2022 id
+    #[account(address = spl_token_2022::id())]
+    pub token_program: UncheckedAccount<'info>,
+
+    /// Pause record of the mint; may not exist
+    /// CHECK: PDA of the mint, read by ensure_not_paused
+    #[account(seeds = [MINT_PAUSE_SEED, mint.key().as_ref()], bump)]
+    pub mint_pause: UncheckedAccount<'info>,
+}
+
+pub const ALLOCATION_PLAN_SEED: &[u8] = b"allocation_plan";
+pub const MAX_ALLOCATION_BUCKETS: usize = 6;
+
+/// What an allocation bucket is for, which decides how it is held
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum AllocationKind {
+    /// Locked, vesting linearly between the bucket's vesting times
+    Team,
+    /// Locked until the bucket's vesting end
+    Liquidity,
+    /// Minted straight to the sale vault
+    Sale,
+    /// Minted straight to an account of the project treasury
+    Treasury,
+}
+
+impl AllocationKind {
+    /// Whether the bucket is held by the plan and released over time
+    pub fn is_locked(self) -> bool {
+        matches!(self, AllocationKind::Team | AllocationKind::Liquidity)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct AllocationBucketParams {
+    pub kind: AllocationKind,
+    /// Share of the total supply
+    pub bps: u16,
+    /// Token account the bucket ends up in
+    pub beneficiary: Pubkey,
+    pub vesting_start: i64,
+    pub vesting_end: i64,
+    /// Lets the creator take back what hasn't vested yet; team buckets only
+    pub revocable: bool,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct AllocationParams {
+    pub total_supply: u64,
+    pub buckets: Vec<AllocationBucketParams>,
+}
+
+impl AllocationParams {
+    pub fn validate(&self) -> Result<()> {
+        let total_bps: u32 = self.buckets.iter().map(|bucket| bucket.bps as u32).sum();
+        let vesting_ok = self
+            .buckets
+            .iter()
+            .all(|bucket| !bucket.kind.is_locked() || bucket.vesting_end >= bucket.vesting_start);
+        let revocable_ok = self
+            .buckets
+            .iter()
+            .all(|bucket| !bucket.revocable || bucket.kind == AllocationKind::Team);
+        if self.total_supply == 0
+            || self.buckets.is_empty()
+            || self.buckets.len() > MAX_ALLOCATION_BUCKETS
+            || total_bps != 10_000
+            || !vesting_ok
+            || !revocable_ok
+        {
+            return Err(MyError::InvalidAllocationPlan.into());
+        }
+        Ok(())
+    }
+
+    /// Check there is one vault per bucket and that no locked bucket shares its vault with
+    /// another bucket, which could then be paid out of it.
+    pub fn check_vaults(&self, vaults: &[Pubkey]) -> Result<()> {
+        if vaults.len() != self.buckets.len() {
+            return Err(MyError::InvalidAllocationPlan.into());
+        }
+        for (bucket, vault) in self.buckets.iter().zip(vaults) {
+            if bucket.kind.is_locked() && vaults.iter().filter(|other| *other == vault).count() > 1 {
+                return Err(MyError::SharedAllocationVault.into());
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A mint's supply as split at creation. The plan is the only account that ever held the
+/// mint authority after the launch, and gave it up once the buckets were minted.
+#[account]
+#[derive(InitSpace)]
+pub struct AllocationPlan {
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub total_supply: u64,
+    pub created_at: i64,
+    #[max_len(MAX_ALLOCATION_BUCKETS)]
+    pub buckets: Vec<AllocationBucket>,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq, InitSpace)]
+pub struct AllocationBucket {
+    pub kind: AllocationKind,
+    pub bps: u16,
+    pub amount: u64,
+    /// Account the bucket was minted into
+    pub vault: Pubkey,
+    pub beneficiary: Pubkey,
+    pub vesting_start: i64,
+    pub vesting_end: i64,
+    /// Sent to the beneficiary so far; the whole amount for unlocked kinds
+    pub released: u64,
+    /// Whether the creator may still `revoke_allocation` the unvested part
+    pub revocable: bool,
+    /// Beneficiary proposed by the current one, awaiting the creator's acceptance
+    pub pending_beneficiary: Option<Pubkey>,
+}
+
+impl AllocationBucket {
+    /// Part of the bucket its beneficiary may have by `now`
+    pub fn unlocked(&self, now: i64) -> u64 {
+        match self.kind {
+            AllocationKind::Team => math::linear_vested(self.amount, self.vesting_start, self.vesting_end, now),
+            AllocationKind::Liquidity if now < self.vesting_end => 0,
+            _ => self.amount,
+        }
+    }
+}
+
+#[event(discriminator = [215, 233, 9, 142, 98, 154, 162, 152])]
+pub struct AllocationPlanCreated {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub total_supply: u64,
+    pub buckets: u8,
+}
+
+#[event(discriminator = [13, 30, 122, 132, 167, 177, 23, 108])]
+pub struct AllocationReleased {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub index: u8,
+    pub amount: u64,
+    pub fee: u64,
+}
+
+#[event(discriminator = [120, 165, 246, 33, 28, 181, 211, 64])]
+pub struct AllocationPlanClosed {
+    pub version: u8,
+    pub mint: Pubkey,
+    /// Left in the locked buckets' vaults and sent to their beneficiaries
+    pub swept: u64,
+}
+
+#[event(discriminator = [40, 66, 35, 103, 104, 167, 148, 159])]
+pub struct AllocationRevoked {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub index: u8,
+    /// Unvested tokens taken out of the bucket
+    pub returned: u64,
+    pub fee: u64,
+}
+
+#[event(discriminator = [185, 117, 184, 67, 10, 45, 164, 251])]
+pub struct AllocationBeneficiaryProposed {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub index: u8,
+    pub beneficiary: Pubkey,
+}
+
+#[event(discriminator = [46, 136, 183, 244, 120, 90, 104, 197])]
+pub struct AllocationBeneficiaryChanged {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub index: u8,
+    pub previous: Pubkey,
+    pub beneficiary: Pubkey,
+    /// Vested tokens released to the previous beneficiary before the switch
+    pub released: u64,
+}
+
+#[derive(Accounts)]
+pub struct InitIndexFeed<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        seeds = [LAUNCH_CONFIG_SEED, launch_config.mint.as_ref()],
+        bump = launch_config.bump,
+        has_one = creator @ MyError::Unauthorized,
+    )]
+    pub launch_config: Account<'info, LaunchConfig>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + IndexFeed::INIT_SPACE,
+        seeds = [INDEX_FEED_SEED, launch_config.mint.as_ref()],
+        bump,
+    )]
+    pub index_feed: Account<'info, IndexFeed>,
+
+    /// Pyth price update of the index feed
+    /// CHECK: owner, feed, freshness and confidence are validated by fresh_pyth_price
+    pub price_update: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateIndexMultiplier<'info> {
+    #[account(
+        mut,
+        has_one = mint,
+        seeds = [INDEX_FEED_SEED, mint.key().as_ref()],
+        bump = index_feed.bump,
+    )]
+    pub index_feed: Account<'info, IndexFeed>,
+
+    /// CHECK: checked against index_feed.mint
+    #[account(mut)]
+    pub mint: UncheckedAccount<'info>,
+
+    /// Pyth price update of the index feed
+    /// CHECK: owner, feed, freshness and confidence are validated by fresh_pyth_price
+    pub price_update: UncheckedAccount<'info>,
+
+    /// Token-2022 program
+    /// CHECK: must be Token-2022 id
+    #[account(address = spl_token_2022::id())]
+    pub token_program: UncheckedAccount<'info>,
+}
+
+pub const INDEX_FEED_SEED: &[u8] = b"index_feed";
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct IndexFeedParams {
+    /// Pyth feed id of the tracked index
+    pub feed_id: [u8; 32],
+    /// Furthest one update may move the multiplier, as a share of the current multiplier
+    pub max_deviation_bps: u16,
+    pub min_update_interval_seconds: i64,
+    pub max_price_age_seconds: i64,
+    pub max_confidence_bps: u16,
+}
+
+impl IndexFeedParams {
+    pub fn validate(&self) -> Result<()> {
+        if self.max_deviation_bps == 0
+            || self.max_deviation_bps >= 10_000
+            || self.min_update_interval_seconds < 0
+            || self.max_price_age_seconds <= 0
+            || self.max_confidence_bps > 10_000
+        {
+            return Err(MyError::InvalidIndexFeed.into());
+        }
+        Ok(())
+    }
+}
+
+/// Pyth feed an index token's UI multiplier tracks. The PDA is the mint's ScaledUiAmount
+/// multiplier authority, so the multiplier only moves through `update_index_multiplier`.
+#[account]
+#[derive(InitSpace)]
+pub struct IndexFeed {
+    pub mint: Pubkey,
+    pub feed_id: [u8; 32],
+    /// Feed price at which the multiplier is 1
+    pub base_price: i64,
+    pub exponent: i32,
+    /// Multiplier in effect, scaled by `math::MULTIPLIER_ONE`
+    pub multiplier: u64,
+    pub max_deviation_bps: u16,
+    pub min_update_interval_seconds: i64,
+    pub max_price_age_seconds: i64,
+    pub max_confidence_bps: u16,
+    /// Publish time of the last price applied; older updates are refused
+    pub last_publish_time: i64,
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+#[event(discriminator = [238, 172, 33, 33, 94, 244, 104, 102])]
+pub struct IndexFeedInitialized {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub feed_id: [u8; 32],
+    pub base_price: i64,
+    pub exponent: i32,
+    pub max_deviation_bps: u16,
+    pub min_update_interval_seconds: i64,
+}
+
+#[event(discriminator = [136, 125, 59, 235, 153, 141, 179, 174])]
+pub struct IndexMultiplierUpdated {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub price: i64,
+    /// Multiplier the price called for, before the deviation limit
+    pub target_multiplier: u64,
+    pub multiplier: u64,
+}
+
+#[derive(Accounts)]
+pub struct InitInterestRateGovernor<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        seeds = [LAUNCH_CONFIG_SEED, launch_config.mint.as_ref()],
+        bump = launch_config.bump,
+        has_one = creator @ MyError::Unauthorized,
+    )]
+    pub launch_config: Account<'info, LaunchConfig>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + InterestRateGovernor::INIT_SPACE,
+        seeds = [INTEREST_RATE_GOVERNOR_SEED, launch_config.mint.as_ref()],
+        bump,
+    )]
+    pub interest_rate_governor: Account<'info, InterestRateGovernor>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetInterestRate<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = admin @ MyError::Unauthorized,
+        has_one = mint,
+        seeds = [INTEREST_RATE_GOVERNOR_SEED, mint.key().as_ref()],
+        bump = interest_rate_governor.bump,
+    )]
+    pub interest_rate_governor: Account<'info, InterestRateGovernor>,
+
+    /// CHECK: checked against interest_rate_governor.mint
+    #[account(mut)]
+    pub mint: UncheckedAccount<'info>,
+
+    /// Token-2022 program
+    /// CHECK: must be Token-2022 id
+    #[account(address = spl_token_2022::id())]
+    pub token_program: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct GovernInterestRate<'info> {
+    /// Signed only through `execute_op`
+    #[account(signer, seeds = [TIMELOCK_SEED, mint.key().as_ref()], bump = timelock.bump)]
+    pub timelock: Account<'info, Timelock>,
+
+    #[account(
+        mut,
+        has_one = mint,
+        seeds = [INTEREST_RATE_GOVERNOR_SEED, mint.key().as_ref()],
+        bump = interest_rate_governor.bump,
+    )]
+    pub interest_rate_governor: Account<'info, InterestRateGovernor>,
+
+    /// CHECK: checked against interest_rate_governor.mint
+    #[account(mut)]
+    pub mint: UncheckedAccount<'info>,
+
+    /// Token-2022 program
+    /// CHECK: must be Token-2022 id
+    #[account(address = spl_token_2022::id())]
+    pub token_program: UncheckedAccount<'info>,
+}
+
+pub const INTEREST_RATE_GOVERNOR_SEED: &[u8] = b"interest_rate_governor";
+
+/// Rates, in basis points a year, an interest-bearing mint's admin may set unaided
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub struct InterestRateBounds {
+    pub min_rate_bps: i16,
+    pub max_rate_bps: i16,
+    pub min_update_interval_seconds: i64,
+}
+
+impl InterestRateBounds {
+    pub fn validate(&self) -> Result<()> {
+        if self.min_rate_bps > self.max_rate_bps || self.min_update_interval_seconds < 0 {
+            return Err(MyError::InvalidInterestRateBounds.into());
+        }
+        Ok(())
+    }
+
+    pub fn admits(&self, rate_bps: i16) -> bool {
+        (self.min_rate_bps..=self.max_rate_bps).contains(&rate_bps)
+    }
+}
+
+/// Bounds on an interest-bearing mint's rate. The PDA is the mint's InterestBearingConfig rate
+/// authority, so the rate only moves through `set_interest_rate` and `govern_interest_rate`.
+#[account]
+#[derive(InitSpace)]
+pub struct InterestRateGovernor {
+    pub mint: Pubkey,
+    /// Sets rates within the bounds
+    pub admin: Pubkey,
+    pub bounds: InterestRateBounds,
+    /// Rate in effect, in basis points a year
+    pub rate_bps: i16,
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+#[event(discriminator = [49, 156, 152, 218, 192, 158, 113, 98])]
+pub struct InterestRateGovernorInitialized {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub admin: Pubkey,
+    pub rate_bps: i16,
+    pub min_rate_bps: i16,
+    pub max_rate_bps: i16,
+    pub min_update_interval_seconds: i64,
+}
+
+#[event(discriminator = [175, 35, 117, 108, 182, 73, 172, 114])]
+pub struct InterestRateUpdated {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub rate_bps: i16,
+    pub min_rate_bps: i16,
+    pub max_rate_bps: i16,
+    /// Set through the timelock rather than by the admin
+    pub governed: bool,
+}
+
+#[derive(Accounts)]
+pub struct SetFaucetClaimAmount<'info> {
+    pub creator: Signer<'info>,
+
+    #[account(
+        seeds = [LAUNCH_CONFIG_SEED, faucet.mint.as_ref()],
+        bump = launch_config.bump,
+        has_one = creator @ MyError::Unauthorized,
+    )]
+    pub launch_config: Account<'info, LaunchConfig>,
+
+    #[account(mut, seeds = [FAUCET_SEED, faucet.mint.as_ref()], bump = faucet.bump)]
+    pub faucet: Account<'info, Faucet>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimFromFaucet<'info> {
+    #[account(mut)]
+    pub wallet: Signer<'info>,
+
+    #[account(
+        has_one = mint,
+        seeds = [FAUCET_SEED, mint.key().as_ref()],
+        bump = faucet.bump,
+    )]
+    pub faucet: Account<'info, Faucet>,
+
+    #[account(
+        init_if_needed,
+        payer = wallet,
+        space = 8 + FaucetClaim::INIT_SPACE,
+        seeds = [FAUCET_CLAIM_SEED, mint.key().as_ref(), wallet.key().as_ref()],
+        bump,
+    )]
+    pub claim: Account<'info, FaucetClaim>,
+
+    /// CHECK: checked against faucet.mint
+    pub mint: UncheckedAccount<'info>,
+
+    /// Faucet vault: token account of the mint owned by the faucet PDA
+    /// CHECK: mint and owner are validated in the handler
+    #[account(mut)]
+    pub vault: UncheckedAccount<'info>,
+
+    /// CHECK: validated by the token program during the transfer
+    #[account(mut)]
+    pub destination: UncheckedAccount<'info>,
+
+    /// Token-2022 program
+    /// CHECK: must be Token-2022 id
+    #[account(address = spl_token_2022::id())]
+    pub token_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Pause record of the mint; may not exist
+    /// CHECK: PDA of the mint, read by ensure_not_paused
+    #[account(seeds = [MINT_PAUSE_SEED, mint.key().as_ref()], bump)]
+    pub mint_pause: UncheckedAccount<'info>,
+}
+
+pub const FAUCET_SEED: &[u8] = b"faucet";
+pub const FAUCET_CLAIM_SEED: &[u8] = b"faucet_claim";
+/// Whole tokens a wallet can claim per day from a new faucet
+pub const FAUCET_DEFAULT_CLAIM_TOKENS: u64 = 1_000;
+
+/// Daily-capped faucet of a test launch, enabled at creation.
+#[account]
+#[derive(InitSpace)]
+pub struct Faucet {
+    pub mint: Pubkey,
+    /// Raw amount paid out per claim
+    pub claim_amount: u64,
+    pub bump: u8,
+}
+
+/// A wallet's last faucet claim.
+#[account]
+#[derive(InitSpace)]
+pub struct FaucetClaim {
+    pub wallet: Pubkey,
+    pub last_claim_day: i64,
+    pub bump: u8,
+}
+
+#[derive(Accounts)]
+pub struct CloseStream<'info> {
+    /// Original sender, receiving the rent
+    /// CHECK: checked against stream.sender
+    #[account(mut)]
+    pub sender: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        close = sender,
+        has_one = sender,
+        has_one = mint,
+        has_one = vault,
+        seeds = [STREAM_SEED, sender.key().as_ref(), mint.key().as_ref(), &stream.stream_id.to_le_bytes()],
+        bump = stream.bump,
+    )]
+    pub stream: Account<'info, Stream>,
+
+    /// CHECK: checked against stream.mint
+    #[account(mut)]
+    pub mint: UncheckedAccount<'info>,
+
+    /// CHECK: checked against stream.vault
+    #[account(mut)]
+    pub vault: UncheckedAccount<'info>,
+
+    /// Sender's token account receiving any remainder; only needed when the vault isn't empty
+    /// CHECK: must belong to stream.sender, since anyone can close the stream
+    #[account(mut, constraint = token_account_owner(sender_token_account)? == stream.sender @ MyError::InvalidTokenAccount)]
+    pub sender_token_account: Option<UncheckedAccount<'info>>,
+
+    /// Token-2022 program
+    /// CHECK: must be Token-2022 id
+    #[account(address = spl_token_2022::id())]
+    pub token_program: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseAirdrop<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        close = creator,
+        has_one = creator @ MyError::Unauthorized,
+        has_one = mint,
+        has_one = vault,
+        seeds = [AIRDROP_SEED, creator.key().as_ref(), mint.key().as_ref(), &campaign.campaign_id.to_le_bytes()],
+        bump = campaign.bump,
+    )]
+    pub campaign: Account<'info, AirdropCampaign>,
+
+    /// CHECK: checked against campaign.mint
+    #[account(mut)]
+    pub mint: UncheckedAccount<'info>,
+
+    /// CHECK: checked against campaign.vault
+    #[account(mut)]
+    pub vault: UncheckedAccount<'info>,
+
+    /// Creator's token account receiving any remainder; only needed when the vault isn't empty
+    /// CHECK: validated by the token program during the transfer
+    #[account(mut)]
+    pub creator_token_account: Option<UncheckedAccount<'info>>,
+
+    /// Token-2022 program
+    /// CHECK: must be Token-2022 id
+    #[account(address = spl_token_2022::id())]
+    pub token_program: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseVoteReceipt<'info> {
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        mut,
+        close = voter,
+        has_one = proposal,
+        has_one = voter @ MyError::Unauthorized,
+        seeds = [VOTE_RECEIPT_SEED, proposal.key().as_ref(), voter.key().as_ref()],
+        bump = receipt.bump,
+    )]
+    pub receipt: Account<'info, VoteReceipt>,
+}
+
+#[derive(Accounts)]
+pub struct CloseGaugeVote<'info> {
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    #[account(
+        mut,
+        close = voter,
+        has_one = voter @ MyError::Unauthorized,
+        seeds = [GAUGE_VOTE_SEED, &receipt.epoch.to_le_bytes(), voter.key().as_ref()],
+        bump = receipt.bump,
+    )]
+    pub receipt: Account<'info, GaugeVote>,
+}
+
+#[derive(Accounts)]
+pub struct CloseFaucetClaim<'info> {
+    #[account(mut)]
+    pub wallet: Signer<'info>,
+
+    /// CHECK: only used to derive the claim address
+    pub mint: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        close = wallet,
+        has_one = wallet @ MyError::Unauthorized,
+        seeds = [FAUCET_CLAIM_SEED, mint.key().as_ref(), wallet.key().as_ref()],
+        bump = claim.bump,
+    )]
+    pub claim: Account<'info, FaucetClaim>,
+}
+
+#[derive(Accounts)]
+#[instruction(additional: u16)]
+pub struct ExpandPoolRegistry<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(seeds = [PLATFORM_CONFIG_SEED], bump = platform_config.bump)]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(seeds = [LAUNCH_CONFIG_SEED, launch_config.mint.as_ref()], bump = launch_config.bump)]
+    pub launch_config: Account<'info, LaunchConfig>,
+
+    #[account(
+        mut,
+        seeds = [POOL_REGISTRY_SEED, launch_config.mint.as_ref()],
+        bump = pool_registry.bump,
+        realloc = pool_registry.to_account_info().data_len() + additional as usize * 32,
+        realloc::payer = authority,
+        realloc::zero = false,
+    )]
+    pub pool_registry: Account<'info, PoolRegistry>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(additional: u16)]
+pub struct ExpandFeeExemptionList<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = authority @ MyError::Unauthorized,
+        seeds = [FEE_EXEMPTION_SEED, exemption_list.mint.as_ref()],
+        bump = exemption_list.bump,
+        realloc = exemption_list.to_account_info().data_len() + additional as usize * FeeExemption::INIT_SPACE,
+        realloc::payer = authority,
+        realloc::zero = false,
+    )]
+    pub exemption_list: Account<'info, FeeExemptionList>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(additional: u16)]
+pub struct ExpandAirdrop<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = creator @ MyError::Unauthorized,
+        seeds = [AIRDROP_SEED, creator.key().as_ref(), campaign.mint.as_ref(), &campaign.campaign_id.to_le_bytes()],
+        bump = campaign.bump,
+        realloc = campaign.to_account_info().data_len() + additional as usize * AirdropRecipient::INIT_SPACE,
+        realloc::payer = creator,
+        realloc::zero = false,
+    )]
+    pub campaign: Account<'info, AirdropCampaign>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct GetLaunchConfig<'info> {
+    #[account(seeds = [LAUNCH_CONFIG_SEED, launch_config.mint.as_ref()], bump = launch_config.bump)]
+    pub launch_config: Account<'info, LaunchConfig>,
+}
+
+#[derive(Accounts)]
+pub struct GetStreamState<'info> {
+    pub stream: Account<'info, Stream>,
+}
+
+#[derive(Accounts)]
+pub struct GetEmissionState<'info> {
+    #[account(seeds = [EMISSION_SEED, schedule.mint.as_ref()], bump = schedule.bump)]
+    pub schedule: Account<'info, EmissionSchedule>,
+}
+
+#[derive(Accounts)]
+pub struct GetVestingState<'info> {
+    #[account(seeds = [ALLOCATION_PLAN_SEED, plan.mint.as_ref()], bump = plan.bump)]
+    pub plan: Account<'info, AllocationPlan>,
+}
+
+#[derive(Accounts)]
+pub struct GetMintHealth<'info> {
+    /// Mint to report on
+    /// CHECK: must be a Token-2022 mint
+    #[account(owner = spl_token_2022::id())]
+    pub mint: UncheckedAccount<'info>,
+}
+
+/// Layout version leading every `get_*` return value. Fields are only ever appended, so a reader
+/// decodes the fields it knows and ignores any trailing bytes from a newer version.
+pub const VIEW_VERSION: u8 = 1;
+
+/// Layout version leading every event, after its discriminator. Discriminators are pinned so
+/// renaming an event doesn't break indexers, and fields are only ever appended, as for views.
+pub const EVENT_VERSION: u8 = 1;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct LaunchConfigView {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub created_at: i64,
+    pub decimals: u8,
+    pub kind: TokenKind,
+    pub verified: bool,
+    pub featured: bool,
+    pub featured_epoch: u64,
+    pub ntt_manager: Option<Pubkey>,
+    pub platform_reviewed: bool,
+    pub partner: Option<Pubkey>,
+    pub compliance: Option<ComplianceFlags>,
+    pub compliance_officer: Option<Pubkey>,
+    pub metadata_locked: bool,
+    pub unbonded_active: bool,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct StreamView {
+    pub version: u8,
+    pub sender: Pubkey,
+    pub recipient: Pubkey,
+    pub mint: Pubkey,
+    pub deposited: u64,
+    pub withdrawn: u64,
+    pub vested: u64,
+    pub withdrawable: u64,
+    pub start_time: i64,
+    pub end_time: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct EmissionView {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub start_time: i64,
+    pub period_seconds: i64,
+    pub curve: EmissionCurve,
+    pub released: u64,
+    pub minted: u64,
+    pub mintable: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct AllocationBucketView {
+    pub kind: AllocationKind,
+    pub beneficiary: Pubkey,
+    pub vault: Pubkey,
+    pub amount: u64,
+    pub vesting_start: i64,
+    pub vesting_end: i64,
+    /// Part of `amount` the beneficiary may have by now
+    pub unlocked: u64,
+    pub released: u64,
+    /// What `release_allocation` would pay out now, before transfer fees
+    pub releasable: u64,
+    pub revocable: bool,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct AllocationPlanView {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub total_supply: u64,
+    pub buckets: Vec<AllocationBucketView>,
+}
+
+/// Holder of one of a mint's authorities
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub enum AuthorityHolder {
+    Renounced,
+    /// Mints only what the emission schedule has released
+    EmissionSchedule,
+    /// Switches the fee only along the published holiday windows
+    TaxHolidaySchedule,
+    /// Withdraws only within the delegate's caps and allowlist
+    FeeDelegate,
+    /// Acts only after the mint's timelock delay
+    Timelock,
+    /// Any other key, free to use the authority at will
+    Key(Pubkey),
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct MintHealthView {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub mint_authority: AuthorityHolder,
+    pub freeze_authority: AuthorityHolder,
+    /// Whether the transfer fee can still change, through a live authority or a pending fee
+    pub fee_mutable: bool,
+    /// None for mints without the TransferFee extension, as with the two fields below
+    pub transfer_fee_config_authority: Option<AuthorityHolder>,
+    pub withdraw_withheld_authority: Option<AuthorityHolder>,
+    /// Fee in effect this epoch
+    pub transfer_fee_basis_points: Option<u16>,
+    pub transfer_hook_program: Option<Pubkey>,
+    pub permanent_delegate: Option<Pubkey>,
+}
+
+#[derive(Accounts)]
+pub struct GetDeclaredFees<'info> {
+    /// Taxed mint to report on
+    /// CHECK: must be a Token-2022 mint with the TransferFee extension
+    #[account(owner = spl_token_2022::id())]
+    pub mint: UncheckedAccount<'info>,
+
+    /// Exemption list of the mint, if it has one
+    #[account(seeds = [FEE_EXEMPTION_SEED, mint.key().as_ref()], bump = exemption_list.bump)]
+    pub exemption_list: Option<Account<'info, FeeExemptionList>>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct DeclaredFee {
+    pub transfer_fee_basis_points: u16,
+    pub maximum_fee: u64,
+    /// First epoch the fee applies in
+    pub epoch: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct DeclaredFeesView {
+    pub version: u8,
+    pub mint: Pubkey,
+    /// Fee in effect this epoch
+    pub transfer_fee_basis_points: u16,
+    pub maximum_fee: u64,
+    /// Fee already set to take over in a later epoch
+    pub scheduled_fee: Option<DeclaredFee>,
+    /// Key that can set a new fee; None once renounced
+    pub transfer_fee_config_authority: Option<Pubkey>,
+    /// Wallets on the exemption list; None if the mint has no list
+    pub exempt_wallets: Option<u32>,
+}
+
+#[derive(Accounts)]
+pub struct SyncCreatorFees<'info> {
+    #[account(mut, seeds = [MINT_STATS_SEED, mint_stats.mint.as_ref()], bump = mint_stats.bump)]
+    pub mint_stats: Account<'info, MintStats>,
+
+    #[account(seeds = [LAUNCH_CONFIG_SEED, mint_stats.mint.as_ref()], bump = launch_config.bump)]
+    pub launch_config: Account<'info, LaunchConfig>,
+
+    #[account(
+        mut,
+        seeds = [REPUTATION_SEED, launch_config.creator.as_ref()],
+        bump = creator_reputation.bump,
+    )]
+    pub creator_reputation: Account<'info, CreatorReputation>,
+}
+
+#[derive(Accounts)]
+pub struct InitTaxHolidaySchedule<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Current transfer fee config authority of the mint; becomes the schedule admin
+    pub admin: Signer<'info>,
+
+    /// Taxed mint whose fee the schedule will control
+    /// CHECK: must be a Token-2022 mint with the TransferFee extension
+    #[account(mut, owner = spl_token_2022::id())]
+    pub mint: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + TaxHolidaySchedule::INIT_SPACE,
+        seeds = [TAX_HOLIDAY_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub schedule: Account<'info, TaxHolidaySchedule>,
+
+    /// Token-2022 program
+    /// CHECK: must be Token-2022 id
+    #[account(address = spl_token_2022::id())]
+    pub token_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateTaxHolidaySchedule<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = admin @ MyError::Unauthorized,
+        seeds = [TAX_HOLIDAY_SEED, schedule.mint.as_ref()],
+        bump = schedule.bump,
+    )]
+    pub schedule: Account<'info, TaxHolidaySchedule>,
+}
+
+#[derive(Accounts)]
+pub struct CrankTaxHoliday<'info> {
+    /// Taxed mint controlled by the schedule
+    /// CHECK: must be a Token-2022 mint with the TransferFee extension
+    #[account(mut, owner = spl_token_2022::id())]
+    pub mint: UncheckedAccount<'info>,
+
+    #[account(
+        has_one = mint,
+        seeds = [TAX_HOLIDAY_SEED, mint.key().as_ref()],
+        bump = schedule.bump,
+    )]
+    pub schedule: Account<'info, TaxHolidaySchedule>,
+
+    /// Token-2022 program
+    /// CHECK: must be Token-2022 id
+    #[account(address = spl_token_2022::id())]
+    pub token_program: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseTaxHolidaySchedule<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// Taxed mint controlled by the schedule
+    /// CHECK: must be a Token-2022 mint with the TransferFee extension
+    #[account(mut, owner = spl_token_2022::id())]
+    pub mint: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        close = admin,
+        has_one = admin @ MyError::Unauthorized,
+        has_one = mint,
+        seeds = [TAX_HOLIDAY_SEED, mint.key().as_ref()],
+        bump = schedule.bump,
+    )]
+    pub schedule: Account<'info, TaxHolidaySchedule>,
+
+    /// Token-2022 program
+    /// CHECK: must be Token-2022 id
+    #[account(address = spl_token_2022::id())]
+    pub token_program: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializePlatform<'info> {
+    /// Upgrade authority of this program; becomes the platform admin
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + PlatformConfig::INIT_SPACE,
+        seeds = [PLATFORM_CONFIG_SEED],
+        bump,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()) @ MyError::Unauthorized)]
+    pub program: Program<'info, crate::program::TaxedTokenLaunchpad>,
+
+    #[account(constraint = program_data.upgrade_authority_address == Some(admin.key()) @ MyError::Unauthorized)]
+    pub program_data: Account<'info, ProgramData>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdatePlatformConfig<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = admin @ MyError::Unauthorized,
+        seeds = [PLATFORM_CONFIG_SEED],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+}
+
+#[derive(Accounts)]
+pub struct RefundCreatorBond<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        close = creator,
+        has_one = creator @ MyError::Unauthorized,
+        seeds = [CREATOR_BOND_SEED, creator_bond.mint.as_ref()],
+        bump = creator_bond.bump,
+    )]
+    pub creator_bond: Account<'info, CreatorBond>,
+
+    #[account(
+        mut,
+        has_one = creator,
+        seeds = [REPUTATION_SEED, creator.key().as_ref()],
+        bump = creator_reputation.bump,
+    )]
+    pub creator_reputation: Account<'info, CreatorReputation>,
+
+    /// Dispute against the launch; may not exist
+    /// CHECK: PDA of the bond's mint, read by is_dispute_live
+    #[account(seeds = [DISPUTE_SEED, creator_bond.mint.as_ref()], bump)]
+    pub dispute: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RetireLaunch<'info> {
+    #[account(seeds = [PLATFORM_CONFIG_SEED], bump = platform_config.bump)]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(
+        mut,
+        seeds = [LAUNCH_CONFIG_SEED, launch_config.mint.as_ref()],
+        bump = launch_config.bump,
+    )]
+    pub launch_config: Account<'info, LaunchConfig>,
+
+    #[account(
+        mut,
+        seeds = [REPUTATION_SEED, launch_config.creator.as_ref()],
+        bump = creator_reputation.bump,
+    )]
+    pub creator_reputation: Account<'info, CreatorReputation>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeInsuranceVault<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        has_one = admin @ MyError::Unauthorized,
+        seeds = [PLATFORM_CONFIG_SEED],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + InsuranceVault::INIT_SPACE,
+        seeds = [INSURANCE_VAULT_SEED],
+        bump,
+    )]
+    pub insurance_vault: Account<'info, InsuranceVault>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(mint: Pubkey)]
+pub struct FlagLaunch<'info> {
+    #[account(mut)]
+    pub reporter: Signer<'info>,
+
+    /// New, or a dismissed dispute being reopened
+    #[account(
+        init_if_needed,
+        payer = reporter,
+        space = 8 + Dispute::INIT_SPACE,
+        seeds = [DISPUTE_SEED, mint.as_ref()],
+        bump,
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    #[account(seeds = [LAUNCH_CONFIG_SEED, mint.as_ref()], bump = launch_config.bump)]
+    pub launch_config: Account<'info, LaunchConfig>,
+
+    #[account(
+        mut,
+        seeds = [REPUTATION_SEED, launch_config.creator.as_ref()],
+        bump = creator_reputation.bump,
+    )]
+    pub creator_reputation: Account<'info, CreatorReputation>,
+
+    /// Bond of the flagged launch; may not exist
+    /// CHECK: PDA of the mint, written by set_bond_disputed
+    #[account(mut, seeds = [CREATOR_BOND_SEED, mint.as_ref()], bump)]
+    pub creator_bond: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveDispute<'info> {
+    pub verifier: Signer<'info>,
+
+    #[account(
+        has_one = verifier @ MyError::Unauthorized,
+        seeds = [PLATFORM_CONFIG_SEED],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(
+        mut,
+        seeds = [DISPUTE_SEED, dispute.mint.as_ref()],
+        bump = dispute.bump,
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    /// Bond of the disputed launch; may not exist
+    /// CHECK: PDA of the mint, written by set_bond_disputed
+    #[account(mut, seeds = [CREATOR_BOND_SEED, dispute.mint.as_ref()], bump)]
+    pub creator_bond: UncheckedAccount<'info>,
+
+    #[account(seeds = [LAUNCH_CONFIG_SEED, dispute.mint.as_ref()], bump = launch_config.bump)]
+    pub launch_config: Account<'info, LaunchConfig>,
+
+    #[account(
+        mut,
+        seeds = [REPUTATION_SEED, launch_config.creator.as_ref()],
+        bump = creator_reputation.bump,
+    )]
+    pub creator_reputation: Account<'info, CreatorReputation>,
+}
+
+#[derive(Accounts)]
+pub struct SlashBond<'info> {
+    pub verifier: Signer<'info>,
+
+    #[account(
+        has_one = verifier @ MyError::Unauthorized,
+        seeds = [PLATFORM_CONFIG_SEED],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(
+        mut,
+        close = reporter,
+        has_one = reporter,
+        seeds = [DISPUTE_SEED, dispute.mint.as_ref()],
+        bump = dispute.bump,
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    /// Receives the dispute rent back
+    /// CHECK: checked against dispute.reporter
+    #[account(mut)]
+    pub reporter: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        close = insurance_vault,
+        seeds = [CREATOR_BOND_SEED, dispute.mint.as_ref()],
+        bump = creator_bond.bump,
+    )]
+    pub creator_bond: Account<'info, CreatorBond>,
+
+    #[account(mut, seeds = [INSURANCE_VAULT_SEED], bump = insurance_vault.bump)]
+    pub insurance_vault: Account<'info, InsuranceVault>,
+
+    #[account(
+        mut,
+        seeds = [REPUTATION_SEED, creator_bond.creator.as_ref()],
+        bump = creator_reputation.bump,
+    )]
+    pub creator_reputation: Account<'info, CreatorReputation>,
+}
+
+#[derive(Accounts)]
+pub struct PayClaim<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        has_one = admin @ MyError::Unauthorized,
+        seeds = [PLATFORM_CONFIG_SEED],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(mut, seeds = [INSURANCE_VAULT_SEED], bump = insurance_vault.bump)]
+    pub insurance_vault: Account<'info, InsuranceVault>,
+
+    /// CHECK: any account receiving the payout
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
+}
+
+pub const PARTNER_SEED: &[u8] = b"partner";
+/// Most of the platform's part of a launch fee a partner can keep
+pub const MAX_PARTNER_FEE_SHARE_BPS: u16 = 5_000;
+
+/// White-label frontend registered under its own namespace. Its share of launch fees
+/// accumulates as lamports on this account until withdrawn.
+#[account]
+#[derive(InitSpace)]
+pub struct Partner {
+    pub authority: Pubkey,
+    #[max_len(MAX_NAME_LEN)]
+    pub namespace: String,
+    /// Share of the launch fee, after the insurance share, paid to the partner
+    pub fee_share_bps: u16,
+    #[max_len(MAX_URI_LEN)]
+    pub branding_uri: String,
+    pub launches: u64,
+    /// Lamports received over the partner's lifetime
+    pub fees_earned: u64,
+    pub bump: u8,
+}
+
+impl Partner {
+    fn set_fee_share(&mut self, fee_share_bps: u16) -> Result<()> {
+        if fee_share_bps > MAX_PARTNER_FEE_SHARE_BPS {
+            return Err(MyError::InvalidFeeConfig.into());
+        }
+        self.fee_share_bps = fee_share_bps;
+        Ok(())
+    }
+
+    fn set_branding(&mut self, branding_uri: String) -> Result<()> {
+        if branding_uri.len() > MAX_URI_LEN {
+            return Err(MyError::UriTooLong.into());
+        }
+        self.branding_uri = branding_uri;
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(namespace: String)]
+pub struct RegisterPartner<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Platform admin approving the partner and its fee share
+    pub admin: Signer<'info>,
+
+    #[account(
+        has_one = admin @ MyError::Unauthorized,
+        seeds = [PLATFORM_CONFIG_SEED],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Partner::INIT_SPACE,
+        seeds = [PARTNER_SEED, namespace.as_bytes()],
+        bump,
+    )]
+    pub partner: Account<'info, Partner>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdatePartner<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = authority @ MyError::Unauthorized,
+        seeds = [PARTNER_SEED, partner.namespace.as_bytes()],
+        bump = partner.bump,
+    )]
+    pub partner: Account<'info, Partner>,
+}
+
+#[derive(Accounts)]
+pub struct SetPartnerFeeShare<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        has_one = admin @ MyError::Unauthorized,
+        seeds = [PLATFORM_CONFIG_SEED],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(
+        mut,
+        seeds = [PARTNER_SEED, partner.namespace.as_bytes()],
+        bump = partner.bump,
+    )]
+    pub partner: Account<'info, Partner>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawPartnerFees<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = authority @ MyError::Unauthorized,
+        seeds = [PARTNER_SEED, partner.namespace.as_bytes()],
+        bump = partner.bump,
+    )]
+    pub partner: Account<'info, Partner>,
+
+    /// CHECK: any account receiving the payout
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
+}
+
+#[event(discriminator = [17, 156, 161, 97, 14, 245, 207, 212])]
+pub struct PartnerRegistered {
+    pub version: u8,
+    pub partner: Pubkey,
+    pub authority: Pubkey,
+    pub namespace: String,
+    pub fee_share_bps: u16,
+}
+
+#[event(discriminator = [252, 75, 196, 136, 205, 179, 24, 155])]
+pub struct PartnerUpdated {
+    pub version: u8,
+    pub partner: Pubkey,
+    pub fee_share_bps: u16,
+}
+
+#[event(discriminator = [255, 247, 184, 82, 31, 124, 81, 163])]
+pub struct PartnerFeesWithdrawn {
+    pub version: u8,
+    pub partner: Pubkey,
+    pub recipient: Pubkey,
+    pub lamports: u64,
+}
+
+pub const PLATFORM_STAKING_SEED: &[u8] = b"platform_staking";
+pub const STAKE_POSITION_SEED: &[u8] = b"stake_position";
+
+/// Platform-token staking pool earning a share of launch fees. The rewards are lamports on
+/// this account on top of its rent.
+#[account]
+#[derive(InitSpace)]
+pub struct PlatformStaking {
+    /// Platform-token account owned by this PDA holding the stake
+    pub vault: Pubkey,
+    pub ledger: StakeLedger,
+    pub bump: u8,
+}
+
+/// Stake and reward accounting of a staking pool, shared by the platform pool and per-mint
+/// dividend pools. Rewards are in the pool's reward unit: lamports for the platform pool, the
+/// reward token's base units for a dividend pool.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq, InitSpace)]
+pub struct StakeLedger {
+    pub total_staked: u64,
+    /// Part of `total_staked` still warming up, which rollovers don't share rewards over
+    pub warming_stake: u64,
+    /// Rewards earned per staked token over the pool's life, scaled by `math::REWARD_PRECISION`
+    pub reward_per_share: u128,
+    /// Rewards collected since the last epoch rollover, not yet shared out
+    pub pending_rewards: u64,
+    /// What rounding has left unshared, in accumulator units: the remainder of earlier share-outs
+    /// and the fractions of a unit positions gave back. Carried into the next share-out.
+    pub reward_dust: u128,
+    pub rolled_epoch: u64,
+    /// Rewards shared out over the pool's life
+    pub total_rewards: u64,
+}
+
+impl StakeLedger {
+    /// Share out the rewards collected in earlier epochs over the active stake, leaving out stake
+    /// still warming up. With nothing active they wait for the first activated staker; the dust
+    /// the share-out's rounding leaves is carried into the next epoch's. Returns what was shared,
+    /// or `None` if the pool was already rolled for `epoch` or had no active stake.
+    fn roll(&mut self, epoch: u64) -> Result<Option<u64>> {
+        let active_stake = self
+            .total_staked
+            .checked_sub(self.warming_stake)
+            .ok_or(MyError::MathOverflow)?;
+        if epoch <= self.rolled_epoch || active_stake == 0 {
+            return Ok(None);
+        }
+        let rewards = self.pending_rewards;
+        let (per_share, dust) =
+            math::distribute_rewards(rewards, self.reward_dust, active_stake).ok_or(MyError::MathOverflow)?;
+        self.reward_per_share = self.reward_per_share.checked_add(per_share).ok_or(MyError::MathOverflow)?;
+        self.total_rewards = self.total_rewards.saturating_add(rewards);
+        self.pending_rewards = 0;
+        self.reward_dust = dust;
+        self.rolled_epoch = epoch;
+        Ok(Some(rewards))
+    }
+}
+
+/// One wallet's stake in the platform staking pool or in a mint's dividend pool.
+#[account]
+#[derive(InitSpace)]
+pub struct StakePosition {
+    pub owner: Pubkey,
+    /// Tokens staked and earning, after the deposit's transfer fee
+    pub amount: u64,
+    /// Tokens staked in `warming_epoch`, earning nothing until activated after it
+    pub warming: u64,
+    pub warming_epoch: u64,
+    /// Rewards `amount` had accrued at the pool's accumulator when last settled, in accumulator
+    /// units, less the fraction of a lamport that settlement carried forward
+    pub reward_debt: u128,
+    /// Settled rewards not yet claimed
+    pub unclaimed: u64,
+    pub bump: u8,
+}
+
+impl StakePosition {
+    /// Move stake whose warm-up epoch has ended into `amount`, so it shares in rollovers from
+    /// here on. The pool must already be rolled for `epoch`.
+    fn activate(&mut self, ledger: &mut StakeLedger, epoch: u64) -> Result<()> {
+        if self.warming == 0 || self.warming_epoch >= epoch {
+            return Ok(());
+        }
+        let amount = self.amount.checked_add(self.warming).ok_or(MyError::MathOverflow)?;
+        self.set_amount(ledger, amount)?;
+        ledger.warming_stake -= self.warming;
+        self.warming = 0;
+        Ok(())
+    }
+
+    /// Move the rewards accrued since the last settlement into `unclaimed`.
+    fn settle(&mut self, reward_per_share: u128) -> Result<()> {
+        let (accrued, reward_debt) =
+            math::settle_rewards(self.amount, reward_per_share, self.reward_debt).ok_or(MyError::MathOverflow)?;
+        self.unclaimed = self.unclaimed.checked_add(accrued).ok_or(MyError::MathOverflow)?;
+        self.reward_debt = reward_debt;
+        Ok(())
+    }
+
+    /// Settle, then change the earning stake to `amount`. The fraction of a lamport the
+    /// settlement carried goes back to the pool's dust rather than riding on the new amount.
+    fn set_amount(&mut self, ledger: &mut StakeLedger, amount: u64) -> Result<()> {
+        let reward_per_share = ledger.reward_per_share;
+        self.settle(reward_per_share)?;
+        let earned = math::scaled_rewards(self.amount, reward_per_share).ok_or(MyError::MathOverflow)?;
+        ledger.reward_dust = ledger
+            .reward_dust
+            .checked_add(earned - self.reward_debt)
+            .ok_or(MyError::MathOverflow)?;
+        self.amount = amount;
+        self.reward_debt = math::scaled_rewards(amount, reward_per_share).ok_or(MyError::MathOverflow)?;
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct InitPlatformStaking<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        has_one = admin @ MyError::Unauthorized,
+        seeds = [PLATFORM_CONFIG_SEED],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + PlatformStaking::INIT_SPACE,
+        seeds = [PLATFORM_STAKING_SEED],
+        bump,
+    )]
+    pub staking_pool: Account<'info, PlatformStaking>,
+
+    /// Platform-token account owned by the pool PDA
+    /// CHECK: mint and owner are validated in the handler
+    pub vault: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct StakePlatformTokens<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(seeds = [PLATFORM_CONFIG_SEED], bump = platform_config.bump)]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(mut, has_one = vault, seeds = [PLATFORM_STAKING_SEED], bump = staking_pool.bump)]
+    pub staking_pool: Account<'info, PlatformStaking>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + StakePosition::INIT_SPACE,
+        seeds = [STAKE_POSITION_SEED, owner.key().as_ref()],
+        bump,
+    )]
+    pub position: Account<'info, StakePosition>,
+
+    /// Platform mint
+    /// CHECK: checked against platform_config.platform_mint
+    #[account(address = platform_config.platform_mint)]
+    pub mint: UncheckedAccount<'info>,
+
+    /// CHECK: checked against staking_pool.vault
+    #[account(mut)]
+    pub vault: UncheckedAccount<'info>,
+
+    /// Owner's platform-token account funding the stake
+    /// CHECK: validated by the token program during the transfer
+    #[account(mut)]
+    pub owner_token_account: UncheckedAccount<'info>,
+
+    /// Token-2022 program
+    /// CHECK: must be Token-2022 id
+    #[account(address = spl_token_2022::id())]
+    pub token_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Pause record of the platform mint; may not exist
+    /// CHECK: PDA of the mint, read by ensure_not_paused
+    #[account(seeds = [MINT_PAUSE_SEED, mint.key().as_ref()], bump)]
+    pub mint_pause: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UnstakePlatformTokens<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(seeds = [PLATFORM_CONFIG_SEED], bump = platform_config.bump)]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(mut, has_one = vault, seeds = [PLATFORM_STAKING_SEED], bump = staking_pool.bump)]
+    pub staking_pool: Account<'info, PlatformStaking>,
+
+    #[account(
+        mut,
+        has_one = owner @ MyError::Unauthorized,
+        seeds = [STAKE_POSITION_SEED, owner.key().as_ref()],
+        bump = position.bump,
+    )]
+    pub position: Account<'info, StakePosition>,
+
+    /// Platform mint
+    /// CHECK: checked against platform_config.platform_mint
+    #[account(address = platform_config.platform_mint)]
+    pub mint: UncheckedAccount<'info>,
+
+    /// CHECK: checked against staking_pool.vault
+    #[account(mut)]
+    pub vault: UncheckedAccount<'info>,
+
+    /// Owner's platform-token account receiving the tokens
+    /// CHECK: validated by the token program during the transfer
+    #[account(mut)]
+    pub owner_token_account: UncheckedAccount<'info>,
+
+    /// Token-2022 program
+    /// CHECK: must be Token-2022 id
+    #[account(address = spl_token_2022::id())]
+    pub token_program: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimStakingRewards<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(mut, seeds = [PLATFORM_STAKING_SEED], bump = staking_pool.bump)]
+    pub staking_pool: Account<'info, PlatformStaking>,
+
+    #[account(
+        mut,
+        has_one = owner @ MyError::Unauthorized,
+        seeds = [STAKE_POSITION_SEED, owner.key().as_ref()],
+        bump = position.bump,
+    )]
+    pub position: Account<'info, StakePosition>,
+}
+
+#[derive(Accounts)]
+pub struct ActivateStake<'info> {
+    #[account(mut, seeds = [PLATFORM_STAKING_SEED], bump = staking_pool.bump)]
+    pub staking_pool: Account<'info, PlatformStaking>,
+
+    #[account(mut, seeds = [STAKE_POSITION_SEED, position.owner.as_ref()], bump = position.bump)]
+    pub position: Account<'info, StakePosition>,
+}
+
+#[event(discriminator = [204, 252, 79, 160, 182, 108, 69, 222])]
+pub struct PlatformTokensStaked {
+    pub version: u8,
+    pub owner: Pubkey,
+    /// Tokens added to the stake, after the transfer fee
+    pub amount: u64,
+    pub total_staked: u64,
+}
+
+#[event(discriminator = [213, 37, 206, 202, 175, 172, 76, 7])]
+pub struct PlatformTokensUnstaked {
+    pub version: u8,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub total_staked: u64,
+}
+
+#[event(discriminator = [21, 111, 78, 12, 59, 48, 127, 209])]
+pub struct StakingRewardsDistributed {
+    pub version: u8,
+    pub epoch: u64,
+    pub rewards: u64,
+    pub reward_per_share: u128,
+}
+
+#[event(discriminator = [251, 169, 50, 96, 7, 92, 141, 137])]
+pub struct StakingRewardsClaimed {
+    pub version: u8,
+    pub owner: Pubkey,
+    pub lamports: u64,
+}
+
+pub const DIVIDEND_POOL_SEED: &[u8] = b"dividend_pool";
+pub const DIVIDEND_POSITION_SEED: &[u8] = b"dividend_position";
+
+/// Pool where holders of a mint stake it to earn a reward token. Positions are `StakePosition`s
+/// seeded by the mint and the owner.
+#[account]
+#[derive(InitSpace)]
+pub struct DividendPool {
+    pub mint: Pubkey,
+    pub reward_mint: Pubkey,
+    /// Token account of the mint owned by this PDA holding the stake
+    pub stake_vault: Pubkey,
+    /// Token account of the reward mint owned by this PDA holding the dividends
+    pub reward_vault: Pubkey,
+    pub ledger: StakeLedger,
+    pub bump: u8,
+}
+
+#[derive(Accounts)]
+pub struct InitDividendPool<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        has_one = creator @ MyError::Unauthorized,
+        seeds = [LAUNCH_CONFIG_SEED, launch_config.mint.as_ref()],
+        bump = launch_config.bump,
+    )]
+    pub launch_config: Account<'info, LaunchConfig>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + DividendPool::INIT_SPACE,
+        seeds = [DIVIDEND_POOL_SEED, launch_config.mint.as_ref()],
+        bump,
+    )]
+    pub dividend_pool: Account<'info, DividendPool>,
+
+    /// Mint the dividends are paid in
+    /// CHECK: must be a Token-2022 mint
+    #[account(owner = spl_token_2022::id())]
+    pub reward_mint: UncheckedAccount<'info>,
+
+    /// Token account of the mint owned by the pool PDA
+    /// CHECK: mint and owner are validated in the handler
+    pub stake_vault: UncheckedAccount<'info>,
+
+    /// Token account of the reward mint owned by the pool PDA
+    /// CHECK: mint and owner are validated in the handler
+    pub reward_vault: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FundDividends<'info> {
+    pub funder: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = reward_mint,
+        has_one = reward_vault,
+        seeds = [DIVIDEND_POOL_SEED, dividend_pool.mint.as_ref()],
+        bump = dividend_pool.bump,
+    )]
+    pub dividend_pool: Account<'info, DividendPool>,
+
+    /// CHECK: checked against dividend_pool.reward_mint
+    pub reward_mint: UncheckedAccount<'info>,
+
+    /// CHECK: checked against dividend_pool.reward_vault
+    #[account(mut)]
+    pub reward_vault: UncheckedAccount<'info>,
+
+    /// Funder's reward-token account paying in
+    /// CHECK: validated by the token program during the transfer
+    #[account(mut)]
+    pub funder_token_account: UncheckedAccount<'info>,
+
+    /// Token-2022 program
+    /// CHECK: must be Token-2022 id
+    #[account(address = spl_token_2022::id())]
+    pub token_program: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct StakeForDividends<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = mint,
+        has_one = stake_vault,
+        seeds = [DIVIDEND_POOL_SEED, mint.key().as_ref()],
+        bump = dividend_pool.bump,
+    )]
+    pub dividend_pool: Account<'info, DividendPool>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + StakePosition::INIT_SPACE,
+        seeds = [DIVIDEND_POSITION_SEED, mint.key().as_ref(), owner.key().as_ref()],
+        bump,
+    )]
+    pub position: Account<'info, StakePosition>,
+
+    /// CHECK: checked against dividend_pool.mint
+    pub mint: UncheckedAccount<'info>,
+
+    /// CHECK: checked against dividend_pool.stake_vault
+    #[account(mut)]
+    pub stake_vault: UncheckedAccount<'info>,
+
+    /// Owner's token account funding the stake
+    /// CHECK: validated by the token program during the transfer
+    #[account(mut)]
+    pub owner_token_account: UncheckedAccount<'info>,
+
+    /// Token-2022 program
+    /// CHECK: must be Token-2022 id
+    #[account(address = spl_token_2022::id())]
+    pub token_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Pause record of the mint; may not exist
+    /// CHECK: PDA of the mint, read by ensure_not_paused
+    #[account(seeds = [MINT_PAUSE_SEED, mint.key().as_ref()], bump)]
+    pub mint_pause: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UnstakeFromDividends<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = mint,
+        has_one = stake_vault,
+        seeds = [DIVIDEND_POOL_SEED, mint.key().as_ref()],
+        bump = dividend_pool.bump,
+    )]
+    pub dividend_pool: Account<'info, DividendPool>,
+
+    #[account(
+        mut,
+        has_one = owner @ MyError::Unauthorized,
+        seeds = [DIVIDEND_POSITION_SEED, mint.key().as_ref(), owner.key().as_ref()],
+        bump = position.bump,
+    )]
+    pub position: Account<'info, StakePosition>,
+
+    /// CHECK: checked against dividend_pool.mint
+    pub mint: UncheckedAccount<'info>,
+
+    /// CHECK: checked against dividend_pool.stake_vault
+    #[account(mut)]
+    pub stake_vault: UncheckedAccount<'info>,
+
+    /// Owner's token account receiving the tokens
+    /// CHECK: validated by the token program during the transfer
+    #[account(mut)]
+    pub owner_token_account: UncheckedAccount<'info>,
+
+    /// Token-2022 program
+    /// CHECK: must be Token-2022 id
+    #[account(address = spl_token_2022::id())]
+    pub token_program: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimDividends<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = reward_mint,
+        has_one = reward_vault,
+        seeds = [DIVIDEND_POOL_SEED, dividend_pool.mint.as_ref()],
+        bump = dividend_pool.bump,
+    )]
+    pub dividend_pool: Account<'info, DividendPool>,
+
+    #[account(
+        mut,
+        has_one = owner @ MyError::Unauthorized,
+        seeds = [DIVIDEND_POSITION_SEED, dividend_pool.mint.as_ref(), owner.key().as_ref()],
+        bump = position.bump,
+    )]
+    pub position: Account<'info, StakePosition>,
+
+    /// CHECK: checked against dividend_pool.reward_mint
+    pub reward_mint: UncheckedAccount<'info>,
+
+    /// CHECK: checked against dividend_pool.reward_vault
+    #[account(mut)]
+    pub reward_vault: UncheckedAccount<'info>,
+
+    /// Owner's reward-token account receiving the dividends
+    /// CHECK: validated by the token program during the transfer
+    #[account(mut)]
+    pub owner_reward_account: UncheckedAccount<'info>,
+
+    /// Token-2022 program
+    /// CHECK: must be Token-2022 id
+    #[account(address = spl_token_2022::id())]
+    pub token_program: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ActivateDividendStake<'info> {
+    #[account(mut, seeds = [DIVIDEND_POOL_SEED, dividend_pool.mint.as_ref()], bump = dividend_pool.bump)]
+    pub dividend_pool: Account<'info, DividendPool>,
+
+    #[account(
+        mut,
+        seeds = [DIVIDEND_POSITION_SEED, dividend_pool.mint.as_ref(), position.owner.as_ref()],
+        bump = position.bump,
+    )]
+    pub position: Account<'info, StakePosition>,
+}
+
+#[event(discriminator = [79, 251, 246, 255, 170, 123, 89, 18])]
+pub struct DividendPoolCreated {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub reward_mint: Pubkey,
+}
+
+#[event(discriminator = [197, 53, 221, 159, 242, 255, 49, 243])]
+pub struct DividendsFunded {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub funder: Pubkey,
+    /// Reward tokens added, after the transfer fee
+    pub amount: u64,
+}
+
+#[event(discriminator = [215, 152, 195, 19, 126, 43, 244, 135])]
+pub struct DividendStaked {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    /// Tokens added to the stake, after the transfer fee
+    pub amount: u64,
+    pub total_staked: u64,
+}
+
+#[event(discriminator = [1, 221, 224, 182, 232, 37, 144, 120])]
+pub struct DividendUnstaked {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub total_staked: u64,
+}
+
+#[event(discriminator = [117, 213, 170, 55, 19, 80, 211, 101])]
+pub struct DividendsDistributed {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub epoch: u64,
+    pub rewards: u64,
+    pub reward_per_share: u128,
+}
+
+#[event(discriminator = [47, 49, 16, 88, 139, 132, 245, 16])]
+pub struct DividendsClaimed {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub fee: u64,
+}
+
+#[derive(Accounts)]
+pub struct SetVerification<'info> {
+    pub verifier: Signer<'info>,
+
+    #[account(
+        has_one = verifier @ MyError::Unauthorized,
+        seeds = [PLATFORM_CONFIG_SEED],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(
+        mut,
+        seeds = [LAUNCH_CONFIG_SEED, launch_config.mint.as_ref()],
+        bump = launch_config.bump,
+    )]
+    pub launch_config: Account<'info, LaunchConfig>,
+
+    #[account(
+        mut,
+        seeds = [REPUTATION_SEED, launch_config.creator.as_ref()],
+        bump = creator_reputation.bump,
+    )]
+    pub creator_reputation: Account<'info, CreatorReputation>,
+}
+
+#[derive(Accounts)]
+#[instruction(auditor: Pubkey)]
+pub struct RegisterAuditor<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        has_one = admin @ MyError::Unauthorized,
+        seeds = [PLATFORM_CONFIG_SEED],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + AuditorRegistration::INIT_SPACE,
+        seeds = [AUDITOR_SEED, auditor.as_ref()],
+        bump,
+    )]
+    pub auditor_registration: Account<'info, AuditorRegistration>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveAuditor<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        has_one = admin @ MyError::Unauthorized,
+        seeds = [PLATFORM_CONFIG_SEED],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(
+        mut,
+        close = admin,
+        seeds = [AUDITOR_SEED, auditor_registration.auditor.as_ref()],
+        bump = auditor_registration.bump,
+    )]
+    pub auditor_registration: Account<'info, AuditorRegistration>,
+}
+
+#[derive(Accounts)]
+pub struct PublishAudit<'info> {
+    #[account(mut)]
+    pub auditor: Signer<'info>,
+
+    #[account(
+        has_one = auditor @ MyError::Unauthorized,
+        seeds = [AUDITOR_SEED, auditor.key().as_ref()],
+        bump = auditor_registration.bump,
+    )]
+    pub auditor_registration: Account<'info, AuditorRegistration>,
+
+    #[account(seeds = [LAUNCH_CONFIG_SEED, launch_config.mint.as_ref()], bump = launch_config.bump)]
+    pub launch_config: Account<'info, LaunchConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = auditor,
+        space = 8 + AuditAttestation::INIT_SPACE,
+        seeds = [AUDIT_SEED, launch_config.mint.as_ref(), auditor.key().as_ref()],
+        bump,
+    )]
+    pub attestation: Account<'info, AuditAttestation>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateLaunchInfo<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        has_one = creator @ MyError::Unauthorized,
+        seeds = [LAUNCH_CONFIG_SEED, launch_config.mint.as_ref()],
+        bump = launch_config.bump,
+    )]
+    pub launch_config: Account<'info, LaunchConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = 8 + LaunchInfo::INIT_SPACE,
+        seeds = [LAUNCH_INFO_SEED, launch_config.mint.as_ref()],
+        bump,
+    )]
+    pub launch_info: Account<'info, LaunchInfo>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub const PLATFORM_CONFIG_SEED: &[u8] = b"platform_config";
+pub const CREATOR_BOND_SEED: &[u8] = b"creator_bond";
+pub const DISPUTE_SEED: &[u8] = b"dispute";
+/// How long a dismissed dispute holds off a new flag, giving the creator a window to reclaim
+/// a bond that has unlocked
+pub const DISPUTE_REFLAG_COOLDOWN_SECONDS: i64 = 7 * SECONDS_PER_DAY;
+pub const INSURANCE_VAULT_SEED: &[u8] = b"insurance_vault";
+pub const MAX_URI_LEN: usize = 200;
+pub const LAUNCH_CONFIG_SEED: &[u8] = b"launch_config";
+pub const REPUTATION_SEED: &[u8] = b"reputation";
+pub const MAX_PROFILE_LAUNCHES: usize = 8;
+pub const AUDITOR_SEED: &[u8] = b"auditor";
+pub const AUDIT_SEED: &[u8] = b"audit";
+pub const MAX_NAME_LEN: usize = 32;
+pub const MAX_SYMBOL_LEN: usize = 10;
+pub const SYMBOL_INDEX_SEED: &[u8] = b"symbol_index";
+/// URI schemes accepted for token metadata
+pub const METADATA_URI_SCHEMES: [&str; 3] = ["https://", "ipfs://", "ar://"];
+/// Metaplex Token Metadata program, for wallets that don't read Token-2022 metadata
+pub const METAPLEX_METADATA_PROGRAM_ID: Pubkey = pubkey!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+pub const METAPLEX_METADATA_SEED: &[u8] = b"metadata";
+/// Instruction index of Metaplex's CreateMetadataAccountV3
+const CREATE_METADATA_ACCOUNT_V3: u8 = 33;
+/// Instruction index of Metaplex's UpdateMetadataAccountV2
+const UPDATE_METADATA_ACCOUNT_V2: u8 = 15;
+pub const LAUNCH_INFO_SEED: &[u8] = b"launch_info";
+pub const TEMPLATE_SEED: &[u8] = b"template";
+pub const BATCH_MINT_SEED: &[u8] = b"batch_mint";
+pub const MAX_BATCH_MINTS: usize = 8;
+pub const MAX_SOCIAL_LEN: usize = 64;
+pub const MAX_DESCRIPTION_LEN: usize = 500;
+
+/// Platform-wide settings, administered by `admin`.
+#[account]
+#[derive(InitSpace)]
+pub struct PlatformConfig {
+    pub admin: Pubkey,
+    /// Resolves disputes and slashes bonds; may be a multisig
+    pub verifier: Pubkey,
+    /// Receives launch fees, minus the insurance share
+    pub treasury: Pubkey,
+    /// SOL fee charged on every launch
+    pub launch_fee_lamports: u64,
+    /// Share of the launch fee routed to the insurance vault
+    pub insurance_fee_bps: u16,
+    /// SOL bond creators must lock at launch; 0 disables bonding
+    pub bond_lamports: u64,
+    /// How long a bond stays locked before the creator can reclaim it, and how long a launch
+    /// without one counts as active
+    pub bond_lock_seconds: i64,
+    /// Publishes holder balance snapshots
+    pub snapshotter: Pubkey,
+    /// Platform token; its vote locks carry weight in the featured-launch gauge
+    pub platform_mint: Pubkey,
+    /// Launch fee discount for launches featured by the previous epoch's gauge
+    pub featured_fee_discount_bps: u16,
+    /// Launch fee in micro-USD, converted to SOL with a Pyth price at launch; 0 keeps the
+    /// fixed `launch_fee_lamports`
+    pub launch_fee_usd: u64,
+    /// Pyth SOL/USD feed id the price update must carry
+    pub sol_usd_feed_id: [u8; 32],
+    pub max_price_age_seconds: i64,
+    /// Widest accepted confidence interval, relative to the price
+    pub max_price_confidence_bps: u16,
+    /// Most active launches a creator may have, bonded or not; 0 for no cap
+    pub max_active_launches: u32,
+    /// Minimum time between two launches of the same creator; 0 for none
+    pub launch_cooldown_seconds: i64,
+    /// Bond surcharge for every earlier launch of the creator, relative to `bond_lamports`
+    pub bond_escalation_bps: u16,
+    /// Reviewer key whose co-signature marks a launch `platform_reviewed`
+    pub launch_attestor: Option<Pubkey>,
+    /// Refuse launches the attestor hasn't co-signed
+    pub attestation_required: bool,
+    /// Share of the platform's part of each launch fee paid to platform-token stakers
+    pub staking_fee_bps: u16,
+    /// Launches per epoch charged the plain launch fee
+    pub demand_free_launches: u32,
+    /// Launch fee surcharge for each further launch in the epoch, relative to the plain fee
+    pub demand_fee_step_bps: u16,
+    /// Cap on the launch fee multiplier; 10_000 is the plain fee
+    pub max_demand_multiplier_bps: u32,
+    /// Epoch `epoch_launches` counts launches for
+    pub demand_epoch: u64,
+    pub epoch_launches: u32,
+    pub bump: u8,
+}
+
+impl PlatformConfig {
+    pub fn apply(&mut self, params: PlatformParams) -> Result<()> {
+        if params.bond_lock_seconds < 0 {
+            return Err(MyError::InvalidBondConfig.into());
+        }
+        if params.insurance_fee_bps as u32 > 10_000
+            || params.featured_fee_discount_bps as u32 > 10_000
+            || params.staking_fee_bps as u32 > 10_000
+        {
+            return Err(MyError::InvalidFeeConfig.into());
+        }
+        if params.demand_fee_step_bps > 0 && params.max_demand_multiplier_bps < 10_000 {
+            return Err(MyError::InvalidFeeConfig.into());
+        }
+        if params.launch_fee_usd > 0 && params.max_price_age_seconds <= 0 {
+            return Err(MyError::InvalidFeeConfig.into());
+        }
+        // A launch stays active for the bond lock period, so capping them needs one
+        let needs_lock = params.max_active_launches > 0 && params.bond_lock_seconds == 0;
+        let needs_bond = params.bond_escalation_bps > 0 && params.bond_lamports == 0;
+        if params.launch_cooldown_seconds < 0 || needs_lock || needs_bond {
+            return Err(MyError::InvalidLaunchLimits.into());
+        }
+        if params.attestation_required && params.launch_attestor.is_none() {
+            return Err(MyError::InvalidLaunchLimits.into());
+        }
+
+        self.verifier = params.verifier;
+        self.treasury = params.treasury;
+        self.launch_fee_lamports = params.launch_fee_lamports;
+        self.insurance_fee_bps = params.insurance_fee_bps;
+        self.bond_lamports = params.bond_lamports;
+        self.bond_lock_seconds = params.bond_lock_seconds;
+        self.snapshotter = params.snapshotter;
+        self.platform_mint = params.platform_mint;
+        self.featured_fee_discount_bps = params.featured_fee_discount_bps;
+        self.launch_fee_usd = params.launch_fee_usd;
+        self.sol_usd_feed_id = params.sol_usd_feed_id;
+        self.max_price_age_seconds = params.max_price_age_seconds;
+        self.max_price_confidence_bps = params.max_price_confidence_bps;
+        self.max_active_launches = params.max_active_launches;
+        self.launch_cooldown_seconds = params.launch_cooldown_seconds;
+        self.bond_escalation_bps = params.bond_escalation_bps;
+        self.launch_attestor = params.launch_attestor;
+        self.attestation_required = params.attestation_required;
+        self.staking_fee_bps = params.staking_fee_bps;
+        self.demand_free_launches = params.demand_free_launches;
+        self.demand_fee_step_bps = params.demand_fee_step_bps;
+        self.max_demand_multiplier_bps = params.max_demand_multiplier_bps;
+
+        Ok(())
+    }
+}
+
+/// Admin-settable fields of the platform config
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct PlatformParams {
+    pub verifier: Pubkey,
+    pub treasury: Pubkey,
+    pub launch_fee_lamports: u64,
+    pub insurance_fee_bps: u16,
+    pub bond_lamports: u64,
+    pub bond_lock_seconds: i64,
+    pub snapshotter: Pubkey,
+    pub platform_mint: Pubkey,
+    pub featured_fee_discount_bps: u16,
+    pub launch_fee_usd: u64,
+    pub sol_usd_feed_id: [u8; 32],
+    pub max_price_age_seconds: i64,
+    pub max_price_confidence_bps: u16,
+    pub max_active_launches: u32,
+    pub launch_cooldown_seconds: i64,
+    pub bond_escalation_bps: u16,
+    pub launch_attestor: Option<Pubkey>,
+    pub attestation_required: bool,
+    pub staking_fee_bps: u16,
+    pub demand_free_launches: u32,
+    pub demand_fee_step_bps: u16,
+    pub max_demand_multiplier_bps: u32,
+}
+
+/// SOL locked by a creator at launch. The lamports sit on this account on top of its rent.
+#[account]
+#[derive(InitSpace)]
+pub struct CreatorBond {
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub lamports: u64,
+    pub unlock_at: i64,
+    /// Set while a dispute against the launch is open or upheld
+    pub disputed: bool,
+    pub bump: u8,
+}
+
+/// Extension set of a launched mint
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum TokenKind {
+    /// TransferFee extension with the given fee
+    Taxed { transfer_fee_basis_points: u16, maximum_fee: u64 },
+    /// NonTransferable extension
+    Soulbound,
+    /// ScaledUiAmount extension, its multiplier driven by the mint's IndexFeed
+    Indexed,
+    /// InterestBearingConfig extension at the given yearly rate, its rate authority the mint's
+    /// InterestRateGovernor
+    InterestBearing { rate_bps: i16 },
+}
+
+impl TokenKind {
+    pub fn validate(&self) -> Result<()> {
+        if let TokenKind::Taxed { transfer_fee_basis_points, .. } = self {
+            if *transfer_fee_basis_points as u32 > 10_000 {
+                return Err(MyError::InvalidFeeConfig.into());
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Named launch preset ("meme", "utility", "credential"...) that `create_from_template` applies.
+#[account]
+#[derive(InitSpace)]
+pub struct Template {
+    pub publisher: Pubkey,
+    /// Published by the platform admin
+    pub official: bool,
+    #[max_len(MAX_NAME_LEN)]
+    pub name: String,
+    pub decimals: u8,
+    pub kind: TokenKind,
+    pub bump: u8,
+}
+
+/// Per-mint record of a launch made through the platform.
+#[account]
+#[derive(InitSpace)]
+pub struct LaunchConfig {
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub created_at: i64,
+    /// Decimals and extension settings the mint was created with
+    pub decimals: u8,
+    pub kind: TokenKind,
+    /// Set by the platform verifier; the source of truth for verified badges
+    pub verified: bool,
+    pub verified_at: i64,
+    #[max_len(MAX_URI_LEN)]
+    pub attestation_uri: String,
+    /// Made the top of a gauge epoch; `featured_epoch` is the latest such epoch
+    pub featured: bool,
+    pub featured_epoch: u64,
+    /// Wormhole NTT manager program the mint is registered with, if any
+    pub ntt_manager: Option<Pubkey>,
+    pub ntt_mode: Option<NttMode>,
+    /// Co-signed at creation by the platform attestor
+    pub platform_reviewed: bool,
+    /// White-label partner the launch was made through
+    pub partner: Option<Pubkey>,
+    /// Attestation rules holders must meet to be thawed, for security-token style launches
+    pub compliance: Option<ComplianceFlags>,
+    /// May freeze accounts by public freeze order, see `issue_freeze_order`
+    pub compliance_officer: Option<Pubkey>,
+    /// The token metadata's update authority was removed by `lock_metadata`, so its name,
+    /// symbol and URI can never change
+    pub metadata_locked: bool,
+    /// Counts towards the creator's active launches with no bond whose refund or slash would
+    /// stop that; `retire_launch` does once `bond_lock_seconds` have passed
+    pub unbonded_active: bool,
+    pub bump: u8,
+}
+
+/// How a Wormhole NTT manager moves tokens off Solana
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum NttMode {
+    /// Tokens are locked in the manager's custody; no authority changes
+    Locking,
+    /// Tokens are burned and minted by the manager, which takes over the mint authority
+    Burning,
+}
+
+/// Per-creator track record and profile, keyed by wallet so it follows the creator across
+/// launches. Created on the creator's first launch.
+#[account]
+#[derive(InitSpace)]
+pub struct CreatorReputation {
+    pub creator: Pubkey,
+    pub launches: u32,
+    /// Bonds reclaimed after a full lock period without an upheld dispute
+    pub bonds_refunded: u32,
+    pub disputes: u32,
+    pub disputes_upheld: u32,
+    pub slashes: u32,
+    /// Derived from the counters above; see `recompute_score`
+    pub score: i64,
+    /// Launches whose creator bond is still posted
+    pub active_launches: u32,
+    pub last_launch_at: i64,
+    /// Launches currently carrying the platform's verified flag
+    pub verified_launches: u32,
+    /// Transfer fees of the creator's launches as synced from their MintStats, so only the
+    /// self-reported share
+    pub total_fees: u128,
+    /// Newest launches first; older ones are found through `LaunchConfig::creator`
+    #[max_len(MAX_PROFILE_LAUNCHES)]
+    pub recent_launches: Vec<Pubkey>,
+    pub bump: u8,
+}
+
+impl CreatorReputation {
+    /// Clean bond periods earn the most, launches a little; upheld disputes and slashes cost
+    /// far more than a single launch earns, so spinning up new tokens can't wash them out.
+    pub fn recompute_score(&mut self) {
+        self.score = 10 * self.bonds_refunded as i64 + 2 * self.launches as i64
+            - 25 * self.disputes_upheld as i64
+            - 50 * self.slashes as i64;
+    }
+}
+
+/// An auditor the platform admin allows to publish attestations.
+#[account]
+#[derive(InitSpace)]
+pub struct AuditorRegistration {
+    pub auditor: Pubkey,
+    #[max_len(MAX_NAME_LEN)]
+    pub name: String,
+    pub bump: u8,
+}
+
+/// Latest audit verdict of one auditor for one launch.
+#[account]
+#[derive(InitSpace)]
+pub struct AuditAttestation {
+    pub mint: Pubkey,
+    pub auditor: Pubkey,
+    /// Hash of the report document, so buyers can check the file behind `report_uri`
+    pub report_hash: [u8; 32],
+    #[max_len(MAX_URI_LEN)]
+    pub report_uri: String,
+    pub passed: bool,
+    pub published_at: i64,
+    /// Incremented every time the auditor republishes
+    pub revision: u32,
+    pub bump: u8,
+}
+
+/// Creator-maintained social and display info for a launch, for explorers and frontends.
+#[account]
+#[derive(InitSpace)]
+pub struct LaunchInfo {
+    pub mint: Pubkey,
+    #[max_len(MAX_URI_LEN)]
+    pub website: String,
+    #[max_len(MAX_SOCIAL_LEN)]
+    pub twitter: String,
+    #[max_len(MAX_SOCIAL_LEN)]
+    pub telegram: String,
+    #[max_len(MAX_URI_LEN)]
+    pub logo_uri: String,
+    #[max_len(MAX_DESCRIPTION_LEN)]
+    pub description: String,
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct LaunchInfoParams {
+    pub website: String,
+    pub twitter: String,
+    pub telegram: String,
+    pub logo_uri: String,
+    pub description: String,
+}
+
+impl LaunchInfoParams {
+    pub fn validate(&self) -> Result<()> {
+        if self.website.len() > MAX_URI_LEN || self.logo_uri.len() > MAX_URI_LEN {
+            return Err(MyError::UriTooLong.into());
+        }
+        if self.twitter.len() > MAX_SOCIAL_LEN || self.telegram.len() > MAX_SOCIAL_LEN {
+            return Err(MyError::NameTooLong.into());
+        }
+        if self.description.len() > MAX_DESCRIPTION_LEN {
+            return Err(MyError::DescriptionTooLong.into());
+        }
+        Ok(())
+    }
+}
+
+/// Name, symbol and URI written into a mint's Token-2022 metadata.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct TokenMetadataParams {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+}
+
+impl TokenMetadataParams {
+    /// Names are letters, digits, spaces and `-_.'&` without leading or trailing spaces;
+    /// symbols are letters and digits; the URI uses one of METADATA_URI_SCHEMES and has no
+    /// whitespace or control characters.
+    pub fn validate(&self) -> Result<()> {
+        if self.name.len() > MAX_NAME_LEN || self.symbol.len() > MAX_SYMBOL_LEN {
+            return Err(MyError::NameTooLong.into());
+        }
+        if self.uri.len() > MAX_URI_LEN {
+            return Err(MyError::UriTooLong.into());
+        }
+        let name_char = |c: char| c.is_ascii_alphanumeric() || " -_.'&".contains(c);
+        if self.name.is_empty() || !self.name.chars().all(name_char) || self.name.trim() != self.name {
+            return Err(MyError::InvalidMetadataCharset.into());
+        }
+        if self.symbol.is_empty() || !self.symbol.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(MyError::InvalidMetadataCharset.into());
+        }
+        let rest = METADATA_URI_SCHEMES.iter().find_map(|scheme| self.uri.strip_prefix(scheme));
+        match rest {
+            Some(rest) if !rest.is_empty() && rest.chars().all(|c| c.is_ascii_graphic()) => Ok(()),
+            _ => Err(MyError::InvalidUriScheme.into()),
+        }
+    }
+
+    /// Key of the symbol's index: the symbol in upper case, so `tax` and `TAX` collide
+    pub fn symbol_key(&self) -> String {
+        self.symbol.to_ascii_uppercase()
+    }
+}
+
+/// Launches whose metadata uses a symbol, keyed by the symbol in upper case. Only counts, so
+/// indexers and the frontend can warn about reused symbols; it blocks nothing.
+#[account]
+#[derive(InitSpace)]
+pub struct SymbolIndex {
+    #[max_len(MAX_SYMBOL_LEN)]
+    pub symbol: String,
+    /// First launch to use the symbol
+    pub first_mint: Pubkey,
+    pub launches: u32,
+    pub bump: u8,
+}
+
+/// Whether `mint` already carries Token-2022 metadata.
+fn has_token_metadata(mint: &AccountInfo) -> Result<bool> {
+    let data = mint.try_borrow_data()?;
+    let state = StateWithExtensions::<Token2022Mint>::unpack(&data)?;
+    Ok(state.get_extension_types()?.contains(&ExtensionType::TokenMetadata))
+}
+
+#[derive(Accounts)]
+#[instruction(metadata: TokenMetadataParams)]
+pub struct InitTokenMetadata<'info> {
+    /// Becomes the metadata's update authority
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        has_one = creator @ MyError::Unauthorized,
+        has_one = mint,
+        seeds = [LAUNCH_CONFIG_SEED, mint.key().as_ref()],
+        bump = launch_config.bump,
+    )]
+    pub launch_config: Account<'info, LaunchConfig>,
+
+    /// CHECK: checked against launch_config.mint; Token-2022 checks the metadata pointer
+    #[account(mut, owner = spl_token_2022::id())]
+    pub mint: UncheckedAccount<'info>,
+
+    pub mint_authority: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = 8 + SymbolIndex::INIT_SPACE,
+        seeds = [SYMBOL_INDEX_SEED, metadata.symbol_key().as_bytes()],
+        bump,
+    )]
+    pub symbol_index: Account<'info, SymbolIndex>,
+
+    /// Token-2022 program
+    /// CHECK: must be Token-2022 id
+    #[account(address = spl_token_2022::id())]
+    pub token_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Metaplex CreateMetadataAccountV3 for `mint`, paid for by and updatable by `creator`, with
+/// no royalties, creators, collection or uses.
+fn create_metaplex_metadata_ix(
+    metadata_account: &Pubkey,
+    mint: &Pubkey,
+    mint_authority: &Pubkey,
+    creator: &Pubkey,
+    metadata: &TokenMetadata,
+    is_mutable: bool,
+) -> Result<Instruction> {
+    let mut data = vec![CREATE_METADATA_ACCOUNT_V3];
+    (&metadata.name, &metadata.symbol, &metadata.uri, 0u16).serialize(&mut data)?;
+    // creators, collection and uses are all None
+    data.extend_from_slice(&[0, 0, 0]);
+    is_mutable.serialize(&mut data)?;
+    // collection_details: None
+    data.push(0);
+    Ok(Instruction {
+        program_id: METAPLEX_METADATA_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*metadata_account, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*mint_authority, true),
+            AccountMeta::new(*creator, true),
+            AccountMeta::new_readonly(*creator, true),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data,
+    })
+}
+
+/// Leading fields of a Metaplex metadata account, up to `is_mutable`
+#[derive(AnchorDeserialize)]
+struct MetaplexMetadataHead {
+    _key: u8,
+    _update_authority: Pubkey,
+    _mint: Pubkey,
+    _name: String,
+    _symbol: String,
+    _uri: String,
+    _seller_fee_basis_points: u16,
+    _creators: Option<Vec<(Pubkey, bool, u8)>>,
+    _primary_sale_happened: bool,
+    is_mutable: bool,
+}
+
+/// Whether the Metaplex metadata account exists and can still be updated
+fn metaplex_metadata_is_mutable(metadata: &AccountInfo) -> Result<bool> {
+    if metadata.data_is_empty() {
+        return Ok(false);
+    }
+    let head = MetaplexMetadataHead::deserialize(&mut &metadata.try_borrow_data()?[..])?;
+    Ok(head.is_mutable)
+}
+
+/// Metaplex UpdateMetadataAccountV2 that only clears `is_mutable`, signed by the update authority
+fn lock_metaplex_metadata_ix(metadata_account: &Pubkey, update_authority: &Pubkey) -> Result<Instruction> {
+    let mut data = vec![UPDATE_METADATA_ACCOUNT_V2];
+    // data, new_update_authority and primary_sale_happened: None
+    data.extend_from_slice(&[0, 0, 0]);
+    Some(false).serialize(&mut data)?;
+    Ok(Instruction {
+        program_id: METAPLEX_METADATA_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*metadata_account, false),
+            AccountMeta::new_readonly(*update_authority, true),
+        ],
+        data,
+    })
+}
+
+#[derive(Accounts)]
+pub struct InitMetaplexMetadata<'info> {
+    /// Pays for the Metaplex account and becomes its update authority
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        has_one = creator @ MyError::Unauthorized,
+        has_one = mint,
+        seeds = [LAUNCH_CONFIG_SEED, mint.key().as_ref()],
+        bump = launch_config.bump,
+    )]
+    pub launch_config: Account<'info, LaunchConfig>,
+
+    /// CHECK: checked against launch_config.mint; its Token-2022 metadata is read by the handler
+    #[account(owner = spl_token_2022::id())]
+    pub mint: UncheckedAccount<'info>,
+
+    pub mint_authority: Signer<'info>,
+
+    /// CHECK: the mint's Metaplex metadata PDA, created by the Metaplex program
+    #[account(
+        mut,
+        seeds = [METAPLEX_METADATA_SEED, METAPLEX_METADATA_PROGRAM_ID.as_ref(), mint.key().as_ref()],
+        seeds::program = METAPLEX_METADATA_PROGRAM_ID,
+        bump,
+    )]
+    pub metaplex_metadata: UncheckedAccount<'info>,
+
+    /// CHECK: must be the Metaplex Token Metadata program
+    #[account(address = METAPLEX_METADATA_PROGRAM_ID)]
+    pub metaplex_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct LockMetadata<'info> {
+    /// Current update authority of the metadata
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = creator @ MyError::Unauthorized,
+        has_one = mint,
+        seeds = [LAUNCH_CONFIG_SEED, mint.key().as_ref()],
+        bump = launch_config.bump,
+    )]
+    pub launch_config: Account<'info, LaunchConfig>,
+
+    /// CHECK: checked against launch_config.mint
+    #[account(mut, owner = spl_token_2022::id())]
+    pub mint: UncheckedAccount<'info>,
+
+    /// Token-2022 program
+    /// CHECK: must be Token-2022 id
+    #[account(address = spl_token_2022::id())]
+    pub token_program: UncheckedAccount<'info>,
+
+    /// The mint's Metaplex metadata; may not exist
+    /// CHECK: PDA of the mint under the Metaplex program, which owns and updates it
+    #[account(
+        mut,
+        seeds = [METAPLEX_METADATA_SEED, METAPLEX_METADATA_PROGRAM_ID.as_ref(), mint.key().as_ref()],
+        seeds::program = METAPLEX_METADATA_PROGRAM_ID,
+        bump,
+    )]
+    pub metaplex_metadata: UncheckedAccount<'info>,
+
+    /// CHECK: must be the Metaplex Token Metadata program
+    #[account(address = METAPLEX_METADATA_PROGRAM_ID)]
+    pub metaplex_program: UncheckedAccount<'info>,
+}
+
+#[event(discriminator = [34, 56, 137, 145, 43, 8, 101, 95])]
+pub struct TokenMetadataInitialized {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub update_authority: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    /// Another launch already used the symbol
+    pub duplicate_symbol: bool,
+}
+
+#[event(discriminator = [168, 74, 109, 120, 173, 67, 201, 103])]
+pub struct MetaplexMetadataCreated {
+    pub version: u8,
+    pub mint: Pubkey,
+    /// The mint's Metaplex metadata account
+    pub metadata: Pubkey,
+    pub update_authority: Pubkey,
+    pub is_mutable: bool,
+}
+
+#[event(discriminator = [50, 191, 57, 252, 124, 239, 162, 209])]
+pub struct MetadataLocked {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub locked_at: i64,
+}
+
+/// Dispute against a launch, one per mint. Reopened in place by a new flag once dismissed.
+#[account]
+#[derive(InitSpace)]
+pub struct Dispute {
+    pub mint: Pubkey,
+    pub reporter: Pubkey,
+    #[max_len(MAX_URI_LEN)]
+    pub reason_uri: String,
+    pub opened_at: i64,
+    /// When the verifier last ruled on it; 0 while open
+    pub resolved_at: i64,
+    pub status: DisputeStatus,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum DisputeStatus {
+    Open,
+    Upheld,
+    Dismissed,
+}
+
+/// Holds slashed creator bonds and the insurance share of launch fees as lamports on top of
+/// its rent.
+#[account]
+#[derive(InitSpace)]
+pub struct InsuranceVault {
+    pub total_slashed: u64,
+    pub total_fees: u64,
+    pub total_paid: u64,
+    pub bump: u8,
+}
+
+#[event(discriminator = [201, 158, 104, 122, 159, 5, 78, 81])]
+pub struct CreatorBondPosted {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub lamports: u64,
+    pub unlock_at: i64,
+}
+
+#[event(discriminator = [98, 147, 45, 177, 189, 203, 59, 194])]
+pub struct CreatorBondRefunded {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub lamports: u64,
+}
+
+#[event(discriminator = [21, 154, 235, 55, 241, 242, 248, 245])]
+pub struct LaunchRetired {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    /// The creator's active launches left
+    pub active_launches: u32,
+}
+
+#[event(discriminator = [224, 170, 162, 183, 228, 160, 19, 111])]
+pub struct LaunchFlagged {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub reporter: Pubkey,
+    pub opened_at: i64,
+}
+
+#[event(discriminator = [121, 64, 249, 153, 139, 128, 236, 187])]
+pub struct DisputeResolved {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub upheld: bool,
+}
+
+#[event(discriminator = [59, 7, 252, 195, 234, 156, 42, 54])]
+pub struct BondSlashed {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    /// Bond plus the bond account's rent, moved to the insurance vault
+    pub lamports: u64,
+}
+
+#[event(discriminator = [153, 4, 13, 117, 185, 18, 143, 16])]
+pub struct TokenVerified {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub verifier: Pubkey,
+    pub attestation_uri: String,
+}
+
+#[event(discriminator = [29, 254, 168, 162, 205, 215, 229, 176])]
+pub struct VerificationRevoked {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub verifier: Pubkey,
+}
+
+#[event(discriminator = [85, 195, 57, 7, 104, 105, 114, 189])]
+pub struct AuditPublished {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub auditor: Pubkey,
+    pub report_hash: [u8; 32],
+    pub report_uri: String,
+    pub passed: bool,
+    pub revision: u32,
+}
+
+#[event(discriminator = [192, 156, 171, 111, 107, 227, 78, 164])]
+pub struct InsuranceClaimPaid {
+    pub version: u8,
+    pub recipient: Pubkey,
+    pub lamports: u64,
+    pub claim_uri: String,
+}
+
+/// Create a Token-2022 mint account sized for `kind`, initialize its extension and then the
+/// mint itself. Extensions must be initialized **before** the mint. PDA mints pass their
+/// seeds in `mint_signer_seeds`; keypair mints pass `&[]`.
+#[allow(clippy::too_many_arguments)]
+fn create_token_mint<'info>(
+    payer: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    mint_authority: &Pubkey,
+    freeze_authority: &Pubkey,
+    fee_withdraw_authority: Option<&Pubkey>,
+    decimals: u8,
+    kind: TokenKind,
+    metadata_pointer: bool,
+    mint_signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    // Calculate the exact account length required for a Mint with the kind's extension, plus
+    // the metadata pointer if asked for. The metadata itself is added by `init_token_metadata`.
+    let extension = match kind {
+        TokenKind::Taxed { .. } => ExtensionType::TransferFeeConfig,
+        TokenKind::Soulbound => ExtensionType::NonTransferable,
+        TokenKind::Indexed => ExtensionType::ScaledUiAmount,
+        TokenKind::InterestBearing { .. } => ExtensionType::InterestBearingConfig,
+    };
+    let extensions: &[ExtensionType] = if metadata_pointer {
+        &[extension, ExtensionType::MetadataPointer]
+    } else {
+        &[extension]
+    };
+    let mint_space = ExtensionType::try_calculate_account_len::<Token2022Mint>(extensions)?;
+    let lamports = Rent::get()?.minimum_balance(mint_space);
+
+    invoke_signed(
+        &system_instruction::create_account(
+            payer.key,
+            mint.key,
+            lamports,
+            mint_space as u64,
+            token_program.key,
+        ),
+        &[payer.clone(), mint.clone(), system_program.clone()],
+        mint_signer_seeds,
+    )?;
+
+    let init_extension_ix = match kind {
+        TokenKind::Taxed { transfer_fee_basis_points, maximum_fee } => initialize_transfer_fee_config(
+            token_program.key,
+            mint.key,
+            Some(mint_authority),
+            Some(fee_withdraw_authority.ok_or(MyError::MissingFeeWithdrawAuthority)?),
+            transfer_fee_basis_points,
+            maximum_fee,
+        )?,
+        TokenKind::Soulbound => token_instruction::initialize_non_transferable_mint(token_program.key, mint.key)?,
+        TokenKind::Indexed => {
+            let index_feed = Pubkey::find_program_address(&[INDEX_FEED_SEED, mint.key.as_ref()], &crate::ID).0;
+            scaled_ui_amount_instruction::initialize(token_program.key, mint.key, Some(index_feed), 1.0)?
+        }
+        TokenKind::InterestBearing { rate_bps } => {
+            let governor = Pubkey::find_program_address(&[INTEREST_RATE_GOVERNOR_SEED, mint.key.as_ref()], &crate::ID).0;
+            interest_bearing_instruction::initialize(token_program.key, mint.key, Some(governor), rate_bps)?
+        }
+    };
+    invoke(&init_extension_ix, &[mint.clone(), token_program.clone()])?;
+
+    if metadata_pointer {
+        // The metadata lives in the mint itself, and with no pointer authority it always will
+        let init_pointer_ix =
+            metadata_pointer_instruction::initialize(token_program.key, mint.key, None, Some(*mint.key))?;
+        invoke(&init_pointer_ix, &[mint.clone(), token_program.clone()])?;
+    }
+
+    let init_mint_ix = token_instruction::initialize_mint2(
+        token_program.key,
+        mint.key,
+        mint_authority,
+        Some(freeze_authority),
+        decimals,
+    )?;
+    invoke(&init_mint_ix, &[mint.clone(), token_program.clone()])?;
+
+    Ok(())
+}
+
+/// Set an interest-bearing mint's rate, signed by its governor PDA.
+fn update_interest_rate<'info>(
+    token_program: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    governor: &Account<'info, InterestRateGovernor>,
+    rate_bps: i16,
+) -> Result<()> {
+    let seeds: &[&[u8]] = &[INTEREST_RATE_GOVERNOR_SEED, governor.mint.as_ref(), &[governor.bump]];
+    let ix = interest_bearing_instruction::update_rate(token_program.key, mint.key, &governor.key(), &[], rate_bps)?;
+    invoke_signed(&ix, &[mint.clone(), governor.to_account_info(), token_program.clone()], &[seeds])?;
+    Ok(())
+}
+
+/// Create a program-owned account at a PDA of this program.
+fn create_pda_account<'info>(
+    payer: &AccountInfo<'info>,
+    account: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    space: usize,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let lamports = Rent::get()?.minimum_balance(space);
+    invoke_signed(
+        &system_instruction::create_account(payer.key, account.key, lamports, space as u64, &crate::ID),
+        &[payer.clone(), account.clone(), system_program.clone()],
+        signer_seeds,
+    )?;
+    Ok(())
+}
+
+/// Accounts and bumps of a single-mint creation instruction, as borrowed by `launch_accounts!`.
+struct LaunchAccounts<'a, 'info> {
+    payer: &'a Signer<'info>,
+    mint: &'a Signer<'info>,
+    mint_authority: Pubkey,
+    freeze_authority: Pubkey,
+    token_program: &'a UncheckedAccount<'info>,
+    platform_config: &'a mut Account<'info, PlatformConfig>,
+    creator_bond: Option<&'a mut Account<'info, CreatorBond>>,
+    devnet_faucet: Option<&'a mut Faucet>,
+    treasury: &'a UncheckedAccount<'info>,
+    insurance_vault: &'a mut Account<'info, InsuranceVault>,
+    featured_gauge: Option<&'a GaugeEpoch>,
+    price_update: Option<&'a AccountInfo<'info>>,
+    attestor: Option<Pubkey>,
+    partner: Option<&'a mut Account<'info, Partner>>,
+    staking_pool: Option<&'a mut Account<'info, PlatformStaking>>,
+    launch_config: &'a mut Account<'info, LaunchConfig>,
+    creator_reputation: &'a mut Account<'info, CreatorReputation>,
+    system_program: &'a Program<'info, System>,
+    creator_bond_bump: Option<u8>,
+    devnet_faucet_bump: Option<u8>,
+    launch_config_bump: u8,
+    creator_reputation_bump: u8,
+}
+
+/// Shared body of the single-mint creation instructions: post the creator bond, charge the
+/// launch fee, record the launch, open the devnet faucet if one was passed and create the mint.
+fn launch(mut accounts: LaunchAccounts, decimals: u8, kind: TokenKind, fee_withdraw_authority: Option<&Pubkey>) -> Result<()> {
+    let mint = accounts.mint.key();
+    ensure_launch_cooldown(accounts.platform_config, accounts.creator_reputation)?;
+    let bonded = post_creator_bond(
+        accounts.platform_config,
+        accounts.creator_bond,
+        accounts.creator_reputation,
+        accounts.payer,
+        &mint,
+        accounts.system_program,
+        accounts.creator_bond_bump,
+    )?;
+
+    let featured = is_featured(accounts.featured_gauge, &mint)?;
+    collect_launch_fee(
+        accounts.platform_config,
+        accounts.payer,
+        accounts.treasury,
+        accounts.insurance_vault,
+        accounts.system_program,
+        featured,
+        accounts.price_update,
+        accounts.partner.as_deref_mut(),
+        accounts.staking_pool,
+    )?;
+
+    record_launch(
+        accounts.platform_config,
+        accounts.launch_config,
+        accounts.creator_reputation,
+        &mint,
+        &accounts.payer.key(),
+        decimals,
+        kind,
+        accounts.attestor,
+        accounts.partner,
+        bonded,
+        accounts.launch_config_bump,
+        accounts.creator_reputation_bump,
+    )?;
+    if featured {
+        accounts.launch_config.featured = true;
+        accounts.launch_config.featured_epoch = Clock::get()?.epoch - 1;
+    }
+    if let Some(faucet) = accounts.devnet_faucet {
+        open_faucet(faucet, &mint, decimals, accounts.devnet_faucet_bump)?;
+    }
+
+    create_token_mint(
+        accounts.payer,
+        accounts.mint,
+        accounts.token_program,
+        accounts.system_program,
+        &accounts.mint_authority,
+        &accounts.freeze_authority,
+        fee_withdraw_authority,
+        decimals,
+        kind,
+        true,
+        &[],
+    )
+}
+
+/// Write the launch record for a new mint and count it towards the creator's reputation and
+/// active launches, enforcing the platform's cap on the latter. `bonded` launches stop being
+/// active when their bond is refunded or slashed, others through `retire_launch`.
+#[allow(clippy::too_many_arguments)]
+fn record_launch(
+    platform_config: &PlatformConfig,
+    launch_config: &mut LaunchConfig,
+    creator_reputation: &mut CreatorReputation,
+    mint: &Pubkey,
+    creator: &Pubkey,
+    decimals: u8,
+    kind: TokenKind,
+    attestor: Option<Pubkey>,
+    partner: Option<&mut Account<Partner>>,
+    bonded: bool,
+    launch_config_bump: u8,
+    reputation_bump: u8,
+) -> Result<()> {
+    let platform_reviewed = match attestor {
+        Some(attestor) if platform_config.launch_attestor == Some(attestor) => true,
+        Some(_) => return Err(MyError::Unauthorized.into()),
+        None if platform_config.attestation_required => return Err(MyError::AttestationRequired.into()),
+        None => false,
+    };
+    let max_active = platform_config.max_active_launches;
+    if max_active > 0 && creator_reputation.active_launches >= max_active {
+        return Err(MyError::TooManyActiveLaunches.into());
+    }
+    let now = Clock::get()?.unix_timestamp;
+
+    launch_config.mint = *mint;
+    launch_config.creator = *creator;
+    launch_config.decimals = decimals;
+    launch_config.kind = kind;
+    launch_config.created_at = now;
+    launch_config.verified = false;
+    launch_config.verified_at = 0;
+    launch_config.attestation_uri = String::new();
+    launch_config.featured = false;
+    launch_config.featured_epoch = 0;
+    launch_config.ntt_manager = None;
+    launch_config.ntt_mode = None;
+    launch_config.platform_reviewed = platform_reviewed;
+    launch_config.partner = partner.map(|partner| {
+        partner.launches = partner.launches.saturating_add(1);
+        partner.key()
+    });
+    launch_config.compliance = None;
+    launch_config.compliance_officer = None;
+    launch_config.metadata_locked = false;
+    launch_config.unbonded_active = !bonded;
+    launch_config.bump = launch_config_bump;
+
+    creator_reputation.creator = *creator;
+    creator_reputation.launches = creator_reputation.launches.saturating_add(1);
+    creator_reputation.active_launches = creator_reputation.active_launches.saturating_add(1);
+    creator_reputation.last_launch_at = now;
+    creator_reputation.recent_launches.insert(0, *mint);
+    creator_reputation.recent_launches.truncate(MAX_PROFILE_LAUNCHES);
+    creator_reputation.bump = reputation_bump;
+    creator_reputation.recompute_score();
+
+    Ok(())
+}
+
+/// Fail while the creator's last launch is within the platform's cooldown.
+fn ensure_launch_cooldown(platform_config: &PlatformConfig, creator_reputation: &CreatorReputation) -> Result<()> {
+    let cooldown = platform_config.launch_cooldown_seconds;
+    let next_launch_at = creator_reputation.last_launch_at.saturating_add(cooldown);
+    if cooldown > 0 && creator_reputation.launches > 0 && Clock::get()?.unix_timestamp < next_launch_at {
+        return Err(MyError::LaunchCooldownActive.into());
+    }
+    Ok(())
+}
+
+/// Whether `mint` is featured by `gauge`, which must be the previous epoch's gauge.
+fn is_featured(gauge: Option<&GaugeEpoch>, mint: &Pubkey) -> Result<bool> {
+    match gauge {
+        Some(gauge) if gauge.epoch + 1 == Clock::get()?.epoch => Ok(gauge.is_featured(mint)),
+        Some(_) => Err(MyError::NotFeatured.into()),
+        None => Ok(false),
+    }
+}
+
+/// Pyth pull-oracle receiver program, owner of PriceUpdateV2 accounts
+pub const PYTH_RECEIVER_ID: Pubkey = pubkey!("rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ");
+const PRICE_UPDATE_V2_DISCRIMINATOR: [u8; 8] = [34, 241, 35, 99, 157, 126, 244, 205];
+
+/// Price message of a fully verified Pyth PriceUpdateV2 account.
+struct PythPrice {
+    feed_id: [u8; 32],
+    price: i64,
+    conf: u64,
+    exponent: i32,
+    publish_time: i64,
+}
+
+/// Decode a PriceUpdateV2 account: discriminator, write authority, verification level, then the
+/// price feed message. Partially verified updates are rejected.
+fn read_pyth_price(price_update: &AccountInfo) -> Result<PythPrice> {
+    if price_update.owner != &PYTH_RECEIVER_ID {
+        return Err(MyError::InvalidPriceUpdate.into());
+    }
+    let data = price_update.try_borrow_data()?;
+    if data.len() < 8 + 32 + 1 + 32 + 8 + 8 + 4 + 8 || data[..8] != PRICE_UPDATE_V2_DISCRIMINATOR {
+        return Err(MyError::InvalidPriceUpdate.into());
+    }
+    // VerificationLevel::Full is variant 1 with no payload
+    if data[40] != 1 {
+        return Err(MyError::InvalidPriceUpdate.into());
+    }
+    let message = &data[41..];
+    let read_u64 = |offset: usize| u64::from_le_bytes(message[offset..offset + 8].try_into().unwrap());
+    Ok(PythPrice {
+        feed_id: message[..32].try_into().unwrap(),
+        price: read_u64(32) as i64,
+        conf: read_u64(40),
+        exponent: i32::from_le_bytes(message[48..52].try_into().unwrap()),
+        publish_time: read_u64(52) as i64,
+    })
+}
+
+/// Read `price_update` and check it is a positive price of `feed_id`, no older than
+/// `max_age_seconds` and with a confidence interval within `max_confidence_bps` of it.
+fn fresh_pyth_price(
+    price_update: &AccountInfo,
+    feed_id: &[u8; 32],
+    max_age_seconds: i64,
+    max_confidence_bps: u16,
+) -> Result<PythPrice> {
+    let price = read_pyth_price(price_update)?;
+    if price.feed_id != *feed_id || price.price <= 0 {
+        return Err(MyError::InvalidPriceUpdate.into());
+    }
+    if Clock::get()?.unix_timestamp - price.publish_time > max_age_seconds {
+        return Err(MyError::StalePrice.into());
+    }
+    let value = price.price as u128;
+    if price.conf as u128 * 10_000 > value * max_confidence_bps as u128 {
+        return Err(MyError::PriceTooUncertain.into());
+    }
+    Ok(price)
+}
+
+/// Convert `micro_usd` to lamports at the SOL/USD price in `price_update`, after checking the
+/// price is the configured feed, fresh and tight enough.
+fn usd_to_lamports(platform_config: &PlatformConfig, price_update: &AccountInfo, micro_usd: u64) -> Result<u64> {
+    let price = fresh_pyth_price(
+        price_update,
+        &platform_config.sol_usd_feed_id,
+        platform_config.max_price_age_seconds,
+        platform_config.max_price_confidence_bps,
+    )?;
+    math::micro_usd_to_lamports(micro_usd, price.price, price.exponent).ok_or(MyError::MathOverflow.into())
+}
+
+/// Set up the devnet faucet of a mint being created.
+fn open_faucet(faucet: &mut Faucet, mint: &Pubkey, decimals: u8, bump: Option<u8>) -> Result<()> {
+    faucet.mint = *mint;
+    faucet.claim_amount = 10u64
+        .checked_pow(decimals as u32)
+        .and_then(|unit| unit.checked_mul(FAUCET_DEFAULT_CLAIM_TOKENS))
+        .ok_or(MyError::MathOverflow)?;
+    faucet.bump = bump.ok_or(MyError::MathOverflow)?;
+    Ok(())
+}
+
+/// Charge the platform launch fee, splitting off the insurance share, then the partner's share
+/// if the launch came through one, then the stakers' share of what the platform keeps. A USD
+/// fee is priced with `price_update`; featured launches pay the discounted fee.
+#[allow(clippy::too_many_arguments)]
+fn collect_launch_fee<'info>(
+    platform_config: &mut PlatformConfig,
+    payer: &Signer<'info>,
+    treasury: &UncheckedAccount<'info>,
+    insurance_vault: &mut Account<'info, InsuranceVault>,
+    system_program: &Program<'info, System>,
+    featured: bool,
+    price_update: Option<&AccountInfo>,
+    partner: Option<&mut Account<'info, Partner>>,
+    staking_pool: Option<&mut Account<'info, PlatformStaking>>,
+) -> Result<()> {
+    let mut fee = if platform_config.launch_fee_usd > 0 {
+        let price_update = price_update.ok_or(MyError::PriceUpdateRequired)?;
+        usd_to_lamports(platform_config, price_update, platform_config.launch_fee_usd)?
+    } else {
+        platform_config.launch_fee_lamports
+    };
+    let epoch = Clock::get()?.epoch;
+    if platform_config.demand_epoch != epoch {
+        platform_config.demand_epoch = epoch;
+        platform_config.epoch_launches = 0;
+    }
+    let multiplier_bps = math::demand_multiplier_bps(
+        platform_config.epoch_launches,
+        platform_config.demand_free_launches,
+        platform_config.demand_fee_step_bps,
+        platform_config.max_demand_multiplier_bps,
+    );
+    platform_config.epoch_launches = platform_config.epoch_launches.saturating_add(1);
+    fee = math::scaled_fee(fee, multiplier_bps).ok_or(MyError::MathOverflow)?;
+    if featured {
+        fee -= math::bps_share(fee, platform_config.featured_fee_discount_bps);
+    }
+    if fee == 0 {
+        return Ok(());
+    }
+    let math::LaunchFeeSplit {
+        insurance: insurance_share,
+        partner: partner_share,
+        staking: staking_share,
+        treasury: treasury_share,
+    } = math::split_launch_fee(
+        fee,
+        platform_config.insurance_fee_bps,
+        partner.as_ref().map_or(0, |partner| partner.fee_share_bps),
+        platform_config.staking_fee_bps,
+    );
+
+    if treasury_share > 0 {
+        invoke(
+            &system_instruction::transfer(payer.key, treasury.key, treasury_share),
+            &[
+                payer.to_account_info(),
+                treasury.to_account_info(),
+                system_program.to_account_info(),
+            ],
+        )?;
+    }
+    if insurance_share > 0 {
+        invoke(
+            &system_instruction::transfer(payer.key, &insurance_vault.key(), insurance_share),
+            &[
+                payer.to_account_info(),
+                insurance_vault.to_account_info(),
+                system_program.to_account_info(),
+            ],
+        )?;
+        insurance_vault.total_fees = insurance_vault
+            .total_fees
+            .checked_add(insurance_share)
+            .ok_or(MyError::MathOverflow)?;
+    }
+    if let Some(partner) = partner.filter(|_| partner_share > 0) {
+        invoke(
+            &system_instruction::transfer(payer.key, &partner.key(), partner_share),
+            &[
+                payer.to_account_info(),
+                partner.to_account_info(),
+                system_program.to_account_info(),
+            ],
+        )?;
+        partner.fees_earned = partner.fees_earned.checked_add(partner_share).ok_or(MyError::MathOverflow)?;
+    }
+    if staking_share > 0 {
+        let staking_pool = staking_pool.ok_or(MyError::StakingPoolRequired)?;
+        invoke(
+            &system_instruction::transfer(payer.key, &staking_pool.key(), staking_share),
+            &[
+                payer.to_account_info(),
+                staking_pool.to_account_info(),
+                system_program.to_account_info(),
+            ],
+        )?;
+        staking_pool.ledger.pending_rewards =
+            staking_pool.ledger.pending_rewards.checked_add(staking_share).ok_or(MyError::MathOverflow)?;
+    }
+
+    Ok(())
+}
+
+/// Share out the launch fees the platform staking pool collected in earlier epochs; see
+/// `StakeLedger::roll`.
+fn roll_staking_epoch(staking_pool: &mut PlatformStaking) -> Result<()> {
+    let epoch = Clock::get()?.epoch;
+    if let Some(rewards) = staking_pool.ledger.roll(epoch)? {
+        emit!(StakingRewardsDistributed {
+            version: EVENT_VERSION,
+            epoch,
+            rewards,
+            reward_per_share: staking_pool.ledger.reward_per_share,
+        });
+    }
+    Ok(())
+}
+
+/// Share out what a dividend pool was funded with in earlier epochs; see `StakeLedger::roll`.
+fn roll_dividend_epoch(pool: &mut DividendPool) -> Result<()> {
+    let epoch = Clock::get()?.epoch;
+    if let Some(rewards) = pool.ledger.roll(epoch)? {
+        emit!(DividendsDistributed {
+            version: EVENT_VERSION,
+            mint: pool.mint,
+            epoch,
+            rewards,
+            reward_per_share: pool.ledger.reward_per_share,
+        });
+    }
+    Ok(())
+}
+
+/// Lock the platform's creator bond for a new mint, if one is required, escalated by the
+/// creator's earlier launches, and return whether one was. The launch counts as active until
+/// the bond is refunded or slashed.
+fn post_creator_bond<'info>(
+    platform_config: &PlatformConfig,
+    creator_bond: Option<&mut Account<'info, CreatorBond>>,
+    creator_reputation: &CreatorReputation,
+    payer: &Signer<'info>,
+    mint: &Pubkey,
+    system_program: &Program<'info, System>,
+    bump: Option<u8>,
+) -> Result<bool> {
+    let (creator_bond, bump) = match (creator_bond, bump) {
+        (Some(creator_bond), Some(bump)) if platform_config.bond_lamports > 0 => (creator_bond, bump),
+        (None, _) if platform_config.bond_lamports == 0 => return Ok(false),
+        (None, _) => return Err(MyError::CreatorBondRequired.into()),
+        _ => return Err(MyError::CreatorBondNotRequired.into()),
+    };
+
+    let bond_lamports = math::escalated_bond(
+        platform_config.bond_lamports,
+        platform_config.bond_escalation_bps,
+        creator_reputation.launches,
+    )
+    .ok_or(MyError::MathOverflow)?;
+
+    invoke(
+        &system_instruction::transfer(payer.key, &creator_bond.key(), bond_lamports),
+        &[
+            payer.to_account_info(),
+            creator_bond.to_account_info(),
+            system_program.to_account_info(),
+        ],
+    )?;
+
+    let unlock_at = Clock::get()?
+        .unix_timestamp
+        .checked_add(platform_config.bond_lock_seconds)
+        .ok_or(MyError::MathOverflow)?;
+    creator_bond.mint = *mint;
+    creator_bond.creator = payer.key();
+    creator_bond.lamports = bond_lamports;
+    creator_bond.unlock_at = unlock_at;
+    creator_bond.disputed = false;
+    creator_bond.bump = bump;
+
+    emit!(CreatorBondPosted {
+        version: EVENT_VERSION,
+        mint: *mint,
+        creator: payer.key(),
+        lamports: bond_lamports,
+        unlock_at,
+    });
+
+    Ok(true)
+}
+
+pub const TAX_HOLIDAY_SEED: &[u8] = b"tax_holiday";
+pub const MAX_TAX_HOLIDAY_WINDOWS: usize = 8;
+
+/// Per-mint schedule of zero-fee windows, acting as the mint's transfer fee config authority.
+#[account]
+#[derive(InitSpace)]
+pub struct TaxHolidaySchedule {
+    pub mint: Pubkey,
+    pub admin: Pubkey,
+    /// Fee restored outside of holiday windows
+    pub transfer_fee_basis_points: u16,
+    pub maximum_fee: u64,
+    #[max_len(MAX_TAX_HOLIDAY_WINDOWS)]
+    pub windows: Vec<TaxHolidayWindow>,
+    pub bump: u8,
+}
+
+impl TaxHolidaySchedule {
+    /// Fee (basis points, maximum fee) the schedule wants in effect during `epoch`
+    pub fn fee_for_epoch(&self, epoch: u64) -> (u16, u64) {
+        let on_holiday = self
+            .windows
+            .iter()
+            .any(|w| w.start_epoch <= epoch && epoch < w.end_epoch);
+        if on_holiday {
+            (0, 0)
+        } else {
+            (self.transfer_fee_basis_points, self.maximum_fee)
+        }
+    }
+}
+
+/// Zero-fee window covering epochs `start_epoch..end_epoch`. Windows are epoch based because
+/// Token-2022 fee changes only ever take effect on an epoch boundary.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, InitSpace)]
+pub struct TaxHolidayWindow {
+    pub start_epoch: u64,
+    pub end_epoch: u64,
+}
+
+/// Windows must be sorted, and every window and every gap between windows must last at least
+/// two epochs, otherwise a fee change would be overwritten before it ever took effect. An epoch
+/// is about two days on mainnet, so the shortest holiday or break is about four days; a holiday
+/// can't be pinned to a weekend.
+fn validate_tax_holiday_windows(windows: &[TaxHolidayWindow]) -> Result<()> {
+    if windows.len() > MAX_TAX_HOLIDAY_WINDOWS {
+        return Err(MyError::InvalidTaxHolidayWindows.into());
+    }
+    let mut previous_end: Option<u64> = None;
+    for w in windows {
+        if w.end_epoch < w.start_epoch.saturating_add(2) {
+            return Err(MyError::InvalidTaxHolidayWindows.into());
+        }
+        if let Some(end) = previous_end {
+            if w.start_epoch < end.saturating_add(2) {
+                return Err(MyError::InvalidTaxHolidayWindows.into());
+            }
+        }
+        previous_end = Some(w.end_epoch);
+    }
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitFeeExemptionList<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Withheld-fee withdraw authority of the mint; becomes the list authority
+    pub authority: Signer<'info>,
+
+    /// Taxed mint the list applies to
+    /// CHECK: must be a Token-2022 mint with the TransferFee extension
+    #[account(owner = spl_token_2022::id())]
+    pub mint: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + FeeExemptionList::INIT_SPACE,
+        seeds = [FEE_EXEMPTION_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub exemption_list: Account<'info, FeeExemptionList>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateFeeExemptionList<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = authority @ MyError::Unauthorized,
+        seeds = [FEE_EXEMPTION_SEED, exemption_list.mint.as_ref()],
+        bump = exemption_list.bump,
+    )]
+    pub exemption_list: Account<'info, FeeExemptionList>,
+}
+
+pub const FEE_EXEMPTION_SEED: &[u8] = b"fee_exemptions";
+pub const MAX_FEE_EXEMPTIONS: usize = 32;
+
+/// Per-mint set of wallets whose transfers through `transfer_with_fee` are rebated. Rebates
+/// only accrue on that path: Token-2022 withholds the fee on a direct transfer too, but the
+/// program never sees it, so an exempt wallet has to be paid through `transfer_with_fee` to be
+/// made whole.
+#[account]
+#[derive(InitSpace)]
+pub struct FeeExemptionList {
+    pub mint: Pubkey,
+    pub authority: Pubkey,
+    #[max_len(MAX_FEE_EXEMPTIONS)]
+    pub exemptions: Vec<FeeExemption>,
+    pub bump: u8,
+}
+
+impl FeeExemptionList {
+    /// Exemptions an account of `data_len` bytes can hold
+    pub fn capacity(data_len: usize) -> usize {
+        list_capacity(data_len, 8 + Self::INIT_SPACE, MAX_FEE_EXEMPTIONS, FeeExemption::INIT_SPACE)
+    }
+
+    pub fn position(&self, wallet: &Pubkey) -> Option<usize> {
+        self.exemptions.iter().position(|e| e.wallet == *wallet)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, InitSpace)]
+pub struct FeeExemption {
+    pub wallet: Pubkey,
+    /// Withheld fees recorded for this wallet and not yet rebated
+    pub rebate_owed: u64,
+}
+
+#[event(discriminator = [146, 167, 139, 121, 173, 47, 189, 154])]
+pub struct ExemptTransferRecorded {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub wallet: Pubkey,
+    pub fee: u64,
+    pub rebate_owed: u64,
+}
+
+#[derive(Accounts)]
+pub struct ClaimFeeRebate<'info> {
+    /// Exempt wallet claiming its rebate
+    pub wallet: Signer<'info>,
+
+    /// Taxed mint
+    /// CHECK: must be a Token-2022 mint with the TransferFee extension
+    #[account(owner = spl_token_2022::id())]
+    pub mint: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        has_one = mint,
+        seeds = [FEE_EXEMPTION_SEED, mint.key().as_ref()],
+        bump = exemption_list.bump,
+    )]
+    pub exemption_list: Account<'info, FeeExemptionList>,
+
+    /// Fee vault: token account of the mint owned by the exemption list PDA
+    /// CHECK: mint and owner are validated in the handler
+    #[account(mut)]
+    pub fee_vault: UncheckedAccount<'info>,
+
+    /// Token account of the wallet receiving the rebate
+    /// CHECK: mint and owner are validated in the handler
+    #[account(mut)]
+    pub destination: UncheckedAccount<'info>,
+
+    /// Token-2022 program
+    /// CHECK: must be Token-2022 id
+    #[account(address = spl_token_2022::id())]
+    pub token_program: UncheckedAccount<'info>,
+
+    /// Pause record of the mint; may not exist
+    /// CHECK: PDA of the mint, read by ensure_not_paused
+    #[account(seeds = [MINT_PAUSE_SEED, mint.key().as_ref()], bump)]
+    pub mint_pause: UncheckedAccount<'info>,
+}
+
+#[event(discriminator = [4, 181, 171, 161, 91, 96, 2, 198])]
+pub struct FeeRebateClaimed {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub wallet: Pubkey,
+    /// Amount the wallet received
+    pub rebate: u64,
+    /// Amount taken out of the fee vault, including the fee withheld on the payout
+    pub gross_amount: u64,
+}
+
+pub const EXCHANGE_LISTING_SEED: &[u8] = b"exchange_listing";
+pub const MAX_EXCHANGE_WALLETS: usize = 8;
+
+/// An exchange's request to have its wallets exempted from a mint's fee.
+#[account]
+#[derive(InitSpace)]
+pub struct ExchangeListing {
+    pub mint: Pubkey,
+    pub exchange: Pubkey,
+    #[max_len(MAX_NAME_LEN)]
+    pub name: String,
+    /// Deposit and withdrawal wallets, on the exemption list once approved
+    #[max_len(MAX_EXCHANGE_WALLETS)]
+    pub wallets: Vec<Pubkey>,
+    pub approved: bool,
+    pub bump: u8,
+}
+
+#[derive(Accounts)]
+pub struct RegisterExchange<'info> {
+    #[account(mut)]
+    pub exchange: Signer<'info>,
+
+    #[account(seeds = [FEE_EXEMPTION_SEED, exemption_list.mint.as_ref()], bump = exemption_list.bump)]
+    pub exemption_list: Account<'info, FeeExemptionList>,
+
+    #[account(
+        init,
+        payer = exchange,
+        space = 8 + ExchangeListing::INIT_SPACE,
+        seeds = [EXCHANGE_LISTING_SEED, exemption_list.mint.as_ref(), exchange.key().as_ref()],
+        bump,
+    )]
+    pub listing: Account<'info, ExchangeListing>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveExchange<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = authority @ MyError::Unauthorized,
+        seeds = [FEE_EXEMPTION_SEED, exemption_list.mint.as_ref()],
+        bump = exemption_list.bump,
+    )]
+    pub exemption_list: Account<'info, FeeExemptionList>,
+
+    #[account(
+        mut,
+        seeds = [EXCHANGE_LISTING_SEED, exemption_list.mint.as_ref(), listing.exchange.as_ref()],
+        bump = listing.bump,
+    )]
+    pub listing: Account<'info, ExchangeListing>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeExchange<'info> {
+    /// The exchange or the exemption list authority
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [FEE_EXEMPTION_SEED, exemption_list.mint.as_ref()],
+        bump = exemption_list.bump,
+    )]
+    pub exemption_list: Account<'info, FeeExemptionList>,
+
+    #[account(
+        mut,
+        close = exchange,
+        has_one = exchange,
+        seeds = [EXCHANGE_LISTING_SEED, exemption_list.mint.as_ref(), exchange.key().as_ref()],
+        bump = listing.bump,
+    )]
+    pub listing: Account<'info, ExchangeListing>,
+
+    /// Exchange that registered the listing, receiving the rent
+    /// CHECK: checked against listing.exchange
+    #[account(mut)]
+    pub exchange: UncheckedAccount<'info>,
+}
+
+#[event(discriminator = [56, 94, 77, 243, 47, 68, 185, 14])]
+pub struct ExchangeRegistered {
+    pub version: u8,
+    pub listing: Pubkey,
+    pub mint: Pubkey,
+    pub exchange: Pubkey,
+    pub wallets: u8,
+}
+
+#[event(discriminator = [205, 118, 63, 246, 2, 25, 115, 188])]
+pub struct ExchangeApproved {
+    pub version: u8,
+    pub listing: Pubkey,
+    pub mint: Pubkey,
+    pub exchange: Pubkey,
+}
+
+#[event(discriminator = [162, 124, 96, 65, 126, 3, 45, 196])]
+pub struct ExchangeRevoked {
+    pub version: u8,
+    pub listing: Pubkey,
+    pub mint: Pubkey,
+    pub exchange: Pubkey,
+}
+
+pub const FEE_DELEGATE_SEED: &[u8] = b"fee_delegate";
+pub const MAX_FEE_DESTINATIONS: usize = 4;
+
+/// Bot key, destination and cap of a FeeDelegate
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct FeeDelegateLimits {
+    pub delegate: Pubkey,
+    /// Only token account the delegate may withdraw to
+    pub destination: Pubkey,
+    pub period_seconds: i64,
+    /// Most the delegate may withdraw per period, in raw units of the mint
+    pub period_cap: u64,
+}
+
+impl FeeDelegateLimits {
+    pub fn validate(&self) -> Result<()> {
+        if self.period_seconds <= 0 {
+            return Err(MyError::InvalidFeeDelegateLimits.into());
+        }
+        Ok(())
+    }
+}
+
+/// Withheld-fee withdraw authority of a mint, letting a delegate key withdraw within a cap.
+#[account]
+#[derive(InitSpace)]
+pub struct FeeDelegate {
+    pub mint: Pubkey,
+    /// Former withdraw authority; withdraws uncapped and can close the delegate
+    pub authority: Pubkey,
+    pub delegate: Pubkey,
+    pub destination: Pubkey,
+    /// Caps the delegate's withdrawals; the authority's are uncapped
+    pub withdrawals: PeriodCap,
+    /// Token accounts any withdrawal must go to; empty allows any
+    #[max_len(MAX_FEE_DESTINATIONS)]
+    pub allowed_destinations: Vec<Pubkey>,
+    /// Wait between queuing an allowlist change and applying it
+    pub allowlist_delay_seconds: i64,
+    #[max_len(MAX_FEE_DESTINATIONS)]
+    pub pending_destinations: Vec<Pubkey>,
+    /// When the queued allowlist can be applied; 0 when none is queued
+    pub pending_at: i64,
+    pub bump: u8,
+}
+
+impl FeeDelegate {
+    pub fn allows(&self, destination: &Pubkey) -> bool {
+        self.allowed_destinations.is_empty() || self.allowed_destinations.contains(destination)
+    }
+}
+
+#[derive(Accounts)]
+pub struct InitFeeDelegate<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Withheld-fee withdraw authority of the mint; becomes the delegate's authority
+    pub authority: Signer<'info>,
+
+    /// Taxed mint whose withheld fees the delegate will withdraw
+    /// CHECK: must be a Token-2022 mint with the TransferFee extension
+    #[account(mut, owner = spl_token_2022::id())]
+    pub mint: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + FeeDelegate::INIT_SPACE,
+        seeds = [FEE_DELEGATE_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub fee_delegate: Account<'info, FeeDelegate>,
+
+    /// Token-2022 program
+    /// CHECK: must be Token-2022 id
+    #[account(address = spl_token_2022::id())]
+    pub token_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateFeeDelegate<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = authority @ MyError::Unauthorized,
+        seeds = [FEE_DELEGATE_SEED, fee_delegate.mint.as_ref()],
+        bump = fee_delegate.bump,
+    )]
+    pub fee_delegate: Account<'info, FeeDelegate>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawDelegatedFees<'info> {
+    /// The delegate, or the delegate's authority
+    pub signer: Signer<'info>,
+
+    /// Taxed mint holding the withheld fees
+    /// CHECK: must be a Token-2022 mint with the TransferFee extension
+    #[account(mut, owner = spl_token_2022::id())]
+    pub mint: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        has_one = mint,
+        seeds = [FEE_DELEGATE_SEED, mint.key().as_ref()],
+        bump = fee_delegate.bump,
+    )]
+    pub fee_delegate: Account<'info, FeeDelegate>,
+
+    /// Token account of the mint receiving the fees
+    /// CHECK: checked against the delegate's destination unless the project treasury owns it;
+    /// the mint is checked by Token-2022
+    #[account(mut)]
+    pub destination: UncheckedAccount<'info>,
+
+    /// Token-2022 program
+    /// CHECK: must be Token-2022 id
+    #[account(address = spl_token_2022::id())]
+    pub token_program: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseFeeDelegate<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Taxed mint the delegate withdraws from
+    /// CHECK: must be a Token-2022 mint with the TransferFee extension
+    #[account(mut, owner = spl_token_2022::id())]
+    pub mint: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        close = authority,
+        has_one = authority @ MyError::Unauthorized,
+        has_one = mint,
+        seeds = [FEE_DELEGATE_SEED, mint.key().as_ref()],
+        bump = fee_delegate.bump,
+    )]
+    pub fee_delegate: Account<'info, FeeDelegate>,
+
+    /// Token-2022 program
+    /// CHECK: must be Token-2022 id
+    #[account(address = spl_token_2022::id())]
+    pub token_program: UncheckedAccount<'info>,
+}
+
+#[event(discriminator = [30, 69, 47, 131, 75, 55, 152, 177])]
+pub struct DelegatedFeesWithdrawn {
+    pub version: u8,
+    pub mint: Pubkey,
+    /// Delegate or authority that withdrew
+    pub signer: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+}
+
+#[event(discriminator = [224, 171, 163, 246, 178, 213, 148, 180])]
+pub struct FeeDestinationsQueued {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub destinations: Vec<Pubkey>,
+    pub applies_at: i64,
+}
+
+pub const TIMELOCK_SEED: &[u8] = b"timelock";
+pub const TIMELOCK_OP_SEED: &[u8] = b"timelock_op";
+pub const MAX_TIMELOCK_DELAY_SECONDS: i64 = 30 * SECONDS_PER_DAY;
+pub const MAX_OP_ACCOUNTS: usize = 16;
+pub const MAX_OP_DATA: usize = 512;
+
+fn validate_timelock_delay(delay_seconds: i64) -> Result<()> {
+    if !(0..=MAX_TIMELOCK_DELAY_SECONDS).contains(&delay_seconds) {
+        return Err(MyError::InvalidTimelockDelay.into());
+    }
+    Ok(())
+}
+
+/// Per-mint timelock. Its PDA holds whichever authorities the project hands it.
+#[account]
+#[derive(InitSpace)]
+pub struct Timelock {
+    pub mint: Pubkey,
+    /// Queues and cancels ops
+    pub admin: Pubkey,
+    pub delay_seconds: i64,
+    pub next_op_id: u64,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Debug, PartialEq, Eq)]
+pub struct TimelockAccountMeta {
+    pub pubkey: Pubkey,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+/// Instruction waiting out a timelock's delay
+#[account]
+#[derive(InitSpace)]
+pub struct QueuedOp {
+    pub timelock: Pubkey,
+    pub id: u64,
+    pub program_id: Pubkey,
+    #[max_len(MAX_OP_ACCOUNTS)]
+    pub accounts: Vec<TimelockAccountMeta>,
+    #[max_len(MAX_OP_DATA)]
+    pub data: Vec<u8>,
+    /// Earliest time the op can be executed
+    pub eta: i64,
+    pub bump: u8,
+}
+
+#[derive(Accounts)]
+pub struct InitTimelock<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(seeds = [LAUNCH_CONFIG_SEED, launch_config.mint.as_ref()], bump = launch_config.bump)]
+    pub launch_config: Account<'info, LaunchConfig>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Timelock::INIT_SPACE,
+        seeds = [TIMELOCK_SEED, launch_config.mint.as_ref()],
+        bump,
+    )]
+    pub timelock: Account<'info, Timelock>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct QueueOp<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = admin @ MyError::Unauthorized,
+        seeds = [TIMELOCK_SEED, timelock.mint.as_ref()],
+        bump = timelock.bump,
+    )]
+    pub timelock: Account<'info, Timelock>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + QueuedOp::INIT_SPACE,
+        seeds = [TIMELOCK_OP_SEED, timelock.key().as_ref(), &timelock.next_op_id.to_le_bytes()],
+        bump,
+    )]
+    pub queued_op: Account<'info, QueuedOp>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CancelOp<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        has_one = admin @ MyError::Unauthorized,
+        seeds = [TIMELOCK_SEED, timelock.mint.as_ref()],
+        bump = timelock.bump,
+    )]
+    pub timelock: Account<'info, Timelock>,
+
+    #[account(
+        mut,
+        close = admin,
+        has_one = timelock,
+        seeds = [TIMELOCK_OP_SEED, timelock.key().as_ref(), &queued_op.id.to_le_bytes()],
+        bump = queued_op.bump,
+    )]
+    pub queued_op: Account<'info, QueuedOp>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteOp<'info> {
+    // Not `mut`: an op may update the timelock, and writing this copy back would undo it
+    #[account(seeds = [TIMELOCK_SEED, timelock.mint.as_ref()], bump = timelock.bump)]
+    pub timelock: Account<'info, Timelock>,
+
+    #[account(
+        mut,
+        close = admin,
+        has_one = timelock,
+        seeds = [TIMELOCK_OP_SEED, timelock.key().as_ref(), &queued_op.id.to_le_bytes()],
+        bump = queued_op.bump,
+    )]
+    pub queued_op: Account<'info, QueuedOp>,
+
+    /// Timelock admin, refunded the op's rent
+    /// CHECK: address checked against the timelock
+    #[account(mut, address = timelock.admin @ MyError::Unauthorized)]
+    pub admin: UncheckedAccount<'info>,
+
+    /// Program the op calls
+    /// CHECK: address checked against the op
+    #[account(address = queued_op.program_id @ MyError::InvalidTimelockOp)]
+    pub target_program: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateTimelock<'info> {
+    /// Signed only through `execute_op`
+    #[account(mut, signer, seeds = [TIMELOCK_SEED, timelock.mint.as_ref()], bump = timelock.bump)]
+    pub timelock: Account<'info, Timelock>,
+}
+
+#[event(discriminator = [229, 17, 74, 251, 142, 170, 106, 39])]
+pub struct OpQueued {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub op_id: u64,
+    pub program_id: Pubkey,
+    pub accounts: Vec<TimelockAccountMeta>,
+    pub data: Vec<u8>,
+    pub eta: i64,
+}
+
+#[event(discriminator = [221, 15, 212, 29, 35, 252, 255, 78])]
+pub struct OpExecuted {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub op_id: u64,
+}
+
+#[event(discriminator = [170, 253, 174, 29, 126, 177, 170, 190])]
+pub struct OpCancelled {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub op_id: u64,
+}
+
+/// Fail while the mint's pause record, if it exists, is still in force.
+fn ensure_not_paused(mint_pause: &AccountInfo) -> Result<()> {
+    if mint_pause.data_is_empty() {
+        return Ok(());
+    }
+    let pause = MintPause::try_deserialize(&mut &mint_pause.try_borrow_data()?[..])?;
+    let now = Clock::get()?.unix_timestamp;
+    if now < pause.opens_at {
+        return Err(MyError::LaunchNotOpen.into());
+    }
+    if now < pause.paused_until {
+        return Err(MyError::MintPaused.into());
+    }
+    Ok(())
+}
+
+/// Fail unless `mint`'s freeze authority is its compliance PDA
+fn check_compliance_authority(mint: &AccountInfo, compliance_authority: &Pubkey) -> Result<()> {
+    let mint_data = mint.try_borrow_data()?;
+    let freeze_authority = StateWithExtensions::<Token2022Mint>::unpack(&mint_data)?.base.freeze_authority;
+    if Option::<Pubkey>::from(freeze_authority) != Some(*compliance_authority) {
+        return Err(MyError::ComplianceNotConfigured.into());
+    }
+    Ok(())
+}
+
+/// Freeze or thaw `token_account` with the mint's compliance PDA
+fn set_compliance_frozen<'info>(
+    token_program: &AccountInfo<'info>,
+    token_account: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    compliance_authority: &AccountInfo<'info>,
+    bump: u8,
+    frozen: bool,
+) -> Result<()> {
+    let build = if frozen { token_instruction::freeze_account } else { token_instruction::thaw_account };
+    let ix = build(token_program.key, token_account.key, mint.key, compliance_authority.key, &[])?;
+    invoke_signed(
+        &ix,
+        &[
+            token_account.clone(),
+            mint.clone(),
+            compliance_authority.clone(),
+            token_program.clone(),
+        ],
+        &[&[COMPLIANCE_AUTHORITY_SEED, mint.key.as_ref(), &[bump]]],
+    )?;
+    Ok(())
+}
+
+/// Freeze orders must point at a published justification and commit to its content
+fn check_justification(uri: &str, hash: &[u8; 32]) -> Result<()> {
+    if uri.len() > MAX_URI_LEN {
+        return Err(MyError::UriTooLong.into());
+    }
+    if uri.is_empty() || *hash == [0; 32] {
+        return Err(MyError::JustificationRequired.into());
+    }
+    Ok(())
+}
+
+/// Copy of the TransferFeeConfig extension of a Token-2022 mint
+fn transfer_fee_config(mint: &AccountInfo) -> Result<TransferFeeConfig> {
+    let mint_data = mint.try_borrow_data()?;
+    let mint = StateWithExtensions::<Token2022Mint>::unpack(&mint_data)?;
+    Ok(*mint.get_extension::<TransferFeeConfig>()?)
+}
 
-    /// Authority that will be set as mint authority
-    /// CHECK: this account must be a signer (the mint authority)
-    pub mint_authority: Signer<'info>,
+/// Base state of a Token-2022 token account
+fn token_account(account: &AccountInfo) -> Result<Token2022Account> {
+    let account_data = account.try_borrow_data()?;
+    let account = StateWithExtensions::<Token2022Account>::unpack(&account_data)?;
+    Ok(account.base)
+}
 
-    /// Optional freeze authority
-    /// CHECK: any pubkey
-    pub freeze_authority: UncheckedAccount<'info>,
+/// Associated token account of `wallet` for a Token-2022 `mint`
+fn associated_token_address(wallet: &Pubkey, mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[wallet.as_ref(), spl_token_2022::id().as_ref(), mint.as_ref()],
+        &ASSOCIATED_TOKEN_PROGRAM_ID,
+    )
+    .0
+}
 
-    /// Token-2022 program
-    /// CHECK: must be the SPL Token-2022 program id
-    pub token_program: UncheckedAccount<'info>,
+/// Whether `destination`, the token account an airdrop to `wallet` is paid into, is the
+/// wallet's associated token account and frozen. Any other account, and a missing one, must be
+/// one of `wallet`'s for `mint`, so a caller can't skip recipients by passing junk or by
+/// cranking before they've opened an account.
+fn airdrop_destination_frozen(destination: &AccountInfo, mint: &Pubkey, wallet: &Pubkey) -> Result<bool> {
+    if destination.data_is_empty() {
+        return Err(MyError::InvalidTokenAccount.into());
+    }
+    let account = token_account(destination)?;
+    if account.mint != *mint || account.owner != *wallet {
+        return Err(MyError::InvalidTokenAccount.into());
+    }
+    Ok(account.is_frozen() && destination.key() == associated_token_address(wallet, mint))
+}
 
-    pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
+/// Owner of a Token-2022 token account
+fn token_account_owner(account: &AccountInfo) -> Result<Pubkey> {
+    Ok(token_account(account)?.owner)
 }
 
-#[derive(Accounts)]
-pub struct TransferWithFee<'info> {
-    /// Source token account (writable)
-    /// CHECK: this must be a token account for the specified mint
-    #[account(mut)]
-    pub source: UncheckedAccount<'info>,
+/// Whether token account `vault` holds at least `owed` tokens
+#[cfg(feature = "invariant-checks")]
+fn vault_covers(vault: &AccountInfo, owed: u64) -> Result<bool> {
+    Ok(token_account(vault)?.amount >= owed)
+}
 
-    /// Mint
-    /// CHECK: this must be the mint for the token
-    pub mint: UncheckedAccount<'info>,
+/// Whether `account` holds at least `owed` lamports on top of its rent
+#[cfg(feature = "invariant-checks")]
+fn lamports_cover(account: &AccountInfo, owed: u64) -> Result<bool> {
+    let rent = Rent::get()?.minimum_balance(account.data_len());
+    Ok(account.lamports() >= rent.saturating_add(owed))
+}
 
-    /// Destination token account (writable)
-    /// CHECK: this must be a token account for the specified mint
-    #[account(mut)]
-    pub destination: UncheckedAccount<'info>,
+/// Decimals of a Token-2022 mint and the fee it withholds this epoch on a transfer of `amount`.
+/// Mints without the TransferFee extension report `None` for the fee.
+fn mint_decimals_and_fee(mint: &AccountInfo, amount: u64) -> Result<(u8, Option<u64>)> {
+    let mint_data = mint.try_borrow_data()?;
+    let mint = StateWithExtensions::<Token2022Mint>::unpack(&mint_data)?;
+    let fee = match mint.get_extension::<TransferFeeConfig>() {
+        Ok(fee_config) => {
+            let (bps, maximum_fee) = epoch_fee_terms(fee_config)?;
+            Some(math::transfer_fee(amount, bps, maximum_fee))
+        }
+        Err(_) => None,
+    };
+    Ok((mint.base.decimals, fee))
+}
 
-    /// Authority signing for source
-    pub authority: Signer<'info>,
+/// Close an emptied vault owned by one of this program's PDAs, sending its rent to
+/// `destination`. Withheld transfer fees are harvested to the mint first, since Token-2022
+/// refuses to close an account still holding them.
+fn close_vault<'info>(
+    token_program: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    vault: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    destination: &AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    if transfer_fee_config(mint).is_ok() {
+        let harvest_ix = harvest_withheld_tokens_to_mint(token_program.key, mint.key, &[vault.key])?;
+        invoke(&harvest_ix, &[mint.clone(), vault.clone(), token_program.clone()])?;
+    }
+    let close_ix = token_instruction::close_account(token_program.key, vault.key, destination.key, authority.key, &[])?;
+    invoke_signed(
+        &close_ix,
+        &[vault.clone(), destination.clone(), authority.clone(), token_program.clone()],
+        signer_seeds,
+    )?;
+    Ok(())
+}
 
-    /// Token-2022 program
-    /// CHECK: must be Token-2022 id
-    pub token_program: UncheckedAccount<'info>,
+/// Whether the dispute PDA at `dispute`, which may not exist, is open or upheld.
+fn is_dispute_live(dispute: &AccountInfo) -> Result<bool> {
+    if dispute.data_is_empty() {
+        return Ok(false);
+    }
+    let dispute = Dispute::try_deserialize(&mut &dispute.try_borrow_data()?[..])?;
+    Ok(dispute.status != DisputeStatus::Dismissed)
+}
+
+/// Flag or clear the creator bond at `creator_bond`, a bond PDA that may not exist.
+fn set_bond_disputed(creator_bond: &AccountInfo, disputed: bool) -> Result<()> {
+    if creator_bond.data_is_empty() {
+        return Ok(());
+    }
+    let mut bond = CreatorBond::try_deserialize(&mut &creator_bond.try_borrow_data()?[..])?;
+    bond.disputed = disputed;
+    bond.try_serialize(&mut &mut creator_bond.try_borrow_mut_data()?[..])?;
+    Ok(())
+}
+
+/// Entries a list account of `data_len` bytes holds: its initial allocation's capacity plus
+/// whatever `expand_*` instructions have added since.
+fn list_capacity(data_len: usize, initial_space: usize, initial_capacity: usize, entry_space: usize) -> usize {
+    initial_capacity + data_len.saturating_sub(initial_space) / entry_space
+}
+
+/// Reject empty expansions and announce the account's new capacity.
+fn emit_expanded(account: &AccountInfo, additional: u16, capacity: usize) -> Result<()> {
+    if additional == 0 {
+        return Err(MyError::InvalidExpansion.into());
+    }
+    emit!(AccountExpanded {
+        version: EVENT_VERSION,
+        account: account.key(),
+        capacity: capacity as u32,
+    });
+    Ok(())
+}
+
+/// Gross amount to send so that `net` arrives after this epoch's transfer fee. Mints without
+/// the TransferFee extension need no gross-up.
+fn pre_fee_amount(mint: &AccountInfo, net: u64) -> Result<u64> {
+    let mint_data = mint.try_borrow_data()?;
+    let mint = StateWithExtensions::<Token2022Mint>::unpack(&mint_data)?;
+    match mint.get_extension::<TransferFeeConfig>() {
+        Ok(fee_config) => {
+            let (bps, maximum_fee) = epoch_fee_terms(fee_config)?;
+            math::pre_fee_amount(net, bps, maximum_fee).ok_or(MyError::MathOverflow.into())
+        }
+        Err(_) => Ok(net),
+    }
+}
+
+/// Basis points and maximum fee of the transfer fee in force this epoch
+fn epoch_fee_terms(fee_config: &TransferFeeConfig) -> Result<(u16, u64)> {
+    let fee = fee_config.get_epoch_fee(Clock::get()?.epoch);
+    Ok((u16::from(fee.transfer_fee_basis_points), u64::from(fee.maximum_fee)))
+}
+
+/// Refuse a mint whose transfer hook would run third-party code in the middle of one of this
+/// program's flows. The runtime already rejects a hook calling back into this program, but
+/// the hook could still observe or fail a transfer between our state changes, and these
+/// transfers don't pass a hook's extra accounts anyway.
+fn ensure_no_transfer_hook(mint: &AccountInfo) -> Result<()> {
+    let mint_data = mint.try_borrow_data()?;
+    let mint = StateWithExtensions::<Token2022Mint>::unpack(&mint_data)?;
+    let hook_program = mint
+        .get_extension::<TransferHook>()
+        .ok()
+        .and_then(|hook| Option::<Pubkey>::from(hook.program_id));
+    if hook_program.is_some() {
+        return Err(MyError::TransferHookNotSupported.into());
+    }
+    Ok(())
+}
+
+/// Transfer `amount` with TransferChecked, or TransferCheckedWithFee for taxed mints. The
+/// authority either signed the transaction or is a PDA signing through `signer_seeds`.
+/// Mints with a transfer hook are refused, as is any program other than Token-2022, since a
+/// PDA authority's signature would carry over to it. Returns the fee withheld from the transfer.
+fn transfer_tokens<'info>(
+    token_program: &AccountInfo<'info>,
+    source: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    destination: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+    amount: u64,
+) -> Result<u64> {
+    if *token_program.key != spl_token_2022::id() {
+        return Err(ProgramError::IncorrectProgramId.into());
+    }
+    ensure_no_transfer_hook(mint)?;
+    let (decimals, fee) = mint_decimals_and_fee(mint, amount)?;
+    let ix = match fee {
+        Some(fee) => spl_token_2022::extension::transfer_fee::instruction::transfer_checked_with_fee(
+            token_program.key,
+            source.key,
+            mint.key,
+            destination.key,
+            authority.key,
+            &[],
+            amount,
+            decimals,
+            fee,
+        )?,
+        None => token_instruction::transfer_checked(
+            token_program.key,
+            source.key,
+            mint.key,
+            destination.key,
+            authority.key,
+            &[],
+            amount,
+            decimals,
+        )?,
+    };
+
+    invoke_signed(
+        &ix,
+        &[
+            token_program.clone(),
+            source.clone(),
+            mint.clone(),
+            destination.clone(),
+            authority.clone(),
+        ],
+        signer_seeds,
+    )?;
+
+    Ok(fee.unwrap_or(0))
 }
 
 #[error_code]
@@ -286,5 +14240,683 @@ pub enum MyError {
 
     #[msg("Failed to initialize non-transferable extension")]
     NonTransferableInitFailed,
+
+    #[msg("Signer is not allowed to perform this action")]
+    Unauthorized,
+
+    #[msg("Tax holiday windows must be sorted and at least two epochs long and apart")]
+    InvalidTaxHolidayWindows,
+
+    #[msg("Arithmetic overflow")]
+    MathOverflow,
+
+    #[msg("Wallet is already exempt")]
+    AlreadyExempt,
+
+    #[msg("Wallet is not on the exemption list")]
+    NotExempt,
+
+    #[msg("Fee exemption list is full")]
+    ExemptionListFull,
+
+    #[msg("Nothing to claim")]
+    NothingToClaim,
+
+    #[msg("Token account has the wrong mint or owner")]
+    InvalidTokenAccount,
+
+    #[msg("Fee vault balance is too low to pay the rebate")]
+    InsufficientFeeVault,
+
+    #[msg("Invalid creator bond configuration")]
+    InvalidBondConfig,
+
+    #[msg("The platform requires a creator bond for this launch")]
+    CreatorBondRequired,
+
+    #[msg("The platform does not require a creator bond")]
+    CreatorBondNotRequired,
+
+    #[msg("Creator bond is still locked")]
+    BondLocked,
+
+    #[msg("Creator bond is under dispute")]
+    BondDisputed,
+
+    #[msg("URI is too long")]
+    UriTooLong,
+
+    #[msg("Dispute is not open")]
+    DisputeNotOpen,
+
+    #[msg("Dispute has not been upheld")]
+    DisputeNotUpheld,
+
+    #[msg("Treasury account does not match the platform config")]
+    InvalidTreasury,
+
+    #[msg("Insurance vault balance is too low to pay the claim")]
+    InsufficientInsuranceFunds,
+
+    #[msg("Launch is not verified")]
+    NotVerified,
+
+    #[msg("Name is too long")]
+    NameTooLong,
+
+    #[msg("Description is too long")]
+    DescriptionTooLong,
+
+    #[msg("Taxed mints need a fee withdraw authority")]
+    MissingFeeWithdrawAuthority,
+
+    #[msg("Batch accounts are missing, out of order or exceed the batch limit")]
+    InvalidBatch,
+
+    #[msg("Stream needs a non-zero deposit and an end time after its start time")]
+    InvalidStream,
+
+    #[msg("OTC offer needs non-zero amounts of two different mints")]
+    InvalidOtcOffer,
+
+    #[msg("Airdrop recipient list is full")]
+    AirdropListFull,
+
+    #[msg("Airdrop expiry must be in the future")]
+    InvalidAirdropExpiry,
+
+    #[msg("Airdrop window has closed")]
+    AirdropExpired,
+
+    #[msg("Airdrop window is still open")]
+    AirdropNotExpired,
+
+    #[msg("Operator daily issuance limit exceeded")]
+    DailyLimitExceeded,
+
+    #[msg("Snapshot slot is in the future")]
+    InvalidSnapshot,
+
+    #[msg("Balance proof does not match the snapshot")]
+    InvalidSnapshotProof,
+
+    #[msg("Proposal voting period must be positive and its quorum between MIN_PROPOSAL_QUORUM_BPS and 100%")]
+    InvalidProposal,
+
+    #[msg("Voting on this proposal has closed")]
+    VotingClosed,
+
+    #[msg("Voting on this proposal is still open")]
+    VotingOpen,
+
+    #[msg("Vote lock needs a non-zero amount and a duration up to the maximum lock")]
+    InvalidVoteLock,
+
+    #[msg("Vote lock has not expired yet")]
+    VoteLockActive,
+
+    #[msg("Vote lock has no weight left")]
+    VoteLockExpired,
+
+    #[msg("Launch is not featured by this gauge epoch")]
+    NotFeatured,
+
+    #[msg("Treasury needs a positive period and at most MAX_TREASURY_SPENDERS spenders")]
+    InvalidTreasuryLimits,
+
+    #[msg("Treasury spending cap for this period exceeded")]
+    SpendingCapExceeded,
+
+    #[msg("Recurring payment needs a non-zero amount and a positive interval")]
+    InvalidRecurringPayment,
+
+    #[msg("Recurring payment is not due yet")]
+    PaymentNotDue,
+
+    #[msg("A Pyth price update is required while the launch fee is set in USD")]
+    PriceUpdateRequired,
+
+    #[msg("Price update is not a verified update of the configured feed")]
+    InvalidPriceUpdate,
+
+    #[msg("Price update is too old")]
+    StalePrice,
+
+    #[msg("Price confidence interval is too wide")]
+    PriceTooUncertain,
+
+    #[msg("Mint is already registered with an NTT manager")]
+    NttAlreadyConfigured,
+
+    #[msg("Leaderboard epoch is closed or does not match")]
+    InvalidLeaderboardEpoch,
+
+    #[msg("Pause must last between one second and MAX_MINT_PAUSE_SECONDS")]
+    InvalidPauseDuration,
+
+    #[msg("This mint's program actions are paused")]
+    MintPaused,
+
+    #[msg("Pool is already registered for this mint")]
+    PoolAlreadyRegistered,
+
+    #[msg("Pool is not registered for this mint")]
+    PoolNotRegistered,
+
+    #[msg("Pool registry is full")]
+    PoolRegistryFull,
+
+    #[msg("Emission schedule needs a positive period, initial emission and curve length")]
+    InvalidEmissionSchedule,
+
+    #[msg("Faucet already claimed today")]
+    FaucetAlreadyClaimed,
+
+    #[msg("Stream has not been fully withdrawn")]
+    StreamNotFinished,
+
+    #[msg("Airdrop list has not been paid out in full")]
+    AirdropNotExhausted,
+
+    #[msg("Gauge vote is for the current epoch")]
+    GaugeEpochActive,
+
+    #[msg("Faucet claim is still in effect today")]
+    FaucetClaimActive,
+
+    #[msg("Expansion must add at least one entry")]
+    InvalidExpansion,
+
+    #[msg("Launch limits need a bond lock period, escalation needs bonding, and the cooldown can't be negative")]
+    InvalidLaunchLimits,
+
+    #[msg("Creator launched too recently")]
+    LaunchCooldownActive,
+
+    #[msg("Creator has too many active launches")]
+    TooManyActiveLaunches,
+
+    #[msg("Fee delegate period must be positive")]
+    InvalidFeeDelegateLimits,
+
+    #[msg("Delegate may only withdraw to its fixed destination")]
+    InvalidFeeDestination,
+
+    #[msg("No fee destination change is queued")]
+    NoPendingFeeDestinations,
+
+    #[msg("Fee destinations are timelocked")]
+    FeeDestinationsTimelocked,
+
+    #[msg("Timelock delay must be between zero and MAX_TIMELOCK_DELAY_SECONDS")]
+    InvalidTimelockDelay,
+
+    #[msg("Op must call this program or Token-2022, fit the size limits and only ask the timelock to sign")]
+    InvalidTimelockOp,
+
+    #[msg("Op is still timelocked")]
+    OpTimelocked,
+
+    #[msg("Voucher is missing or not signed by the expected key")]
+    InvalidVoucher,
+
+    #[msg("Event must end after it starts and admit at least one attendee")]
+    InvalidAttendanceEvent,
+
+    #[msg("Event is not open for check-in")]
+    CheckInClosed,
+
+    #[msg("Event has reached its attendee cap")]
+    EventFull,
+
+    #[msg("Voucher has already been claimed")]
+    VoucherAlreadyClaimed,
+
+    #[msg("Gift must hold tokens and expire in the future")]
+    InvalidGift,
+
+    #[msg("Gift has expired")]
+    GiftExpired,
+
+    #[msg("Only taxed launchpad mints can be wrapped")]
+    NotWrappable,
+
+    #[msg("Wrapping needs a non-zero amount after the transfer fee")]
+    InvalidWrapAmount,
+
+    #[msg("Exchange listing needs one to the maximum number of distinct wallets")]
+    InvalidExchangeWallets,
+
+    #[msg("Launch must be co-signed by the platform attestor")]
+    AttestationRequired,
+
+    #[msg("Platform staking pool must be passed while launch fees are shared with stakers")]
+    StakingPoolRequired,
+
+    #[msg("Stake amount must be non-zero and within the staked balance")]
+    InvalidStakeAmount,
+
+    #[msg("Launch must be scheduled in the future and at most 30 days ahead")]
+    InvalidLaunchSchedule,
+
+    #[msg("Launch is already scheduled")]
+    LaunchAlreadyScheduled,
+
+    #[msg("Launch calendar day is full")]
+    LaunchCalendarFull,
+
+    #[msg("Launch has not opened yet")]
+    LaunchNotOpen,
+
+    #[msg("Allocation plan must split a fresh mint's supply into at most 6 buckets totalling 100%")]
+    InvalidAllocationPlan,
+
+    #[msg("Launch has no compliance flags, or its freeze authority is not the compliance PDA")]
+    ComplianceNotConfigured,
+
+    #[msg("Wallet lacks a valid compliance attestation")]
+    NotCompliant,
+
+    #[msg("Wallet holds a valid compliance attestation")]
+    AttestationValid,
+
+    #[msg("Freeze orders need a justification URI and a non-zero justification hash")]
+    JustificationRequired,
+
+    #[msg("Account is frozen by an active freeze order")]
+    FreezeOrderActive,
+
+    #[msg("Freeze order has already been lifted")]
+    FreezeOrderInactive,
+
+    #[msg("Mint is not an index token")]
+    NotIndexed,
+
+    #[msg("Index feed settings are out of range")]
+    InvalidIndexFeed,
+
+    #[msg("Index multiplier was updated too recently")]
+    IndexUpdateTooSoon,
+
+    #[msg("Vault balance doesn't match the recorded accounting")]
+    InvariantViolated,
+
+    #[msg("Mints with a transfer hook can't move through the launchpad")]
+    TransferHookNotSupported,
+
+    #[msg("Fee doesn't match what the mint's transfer fee config withholds")]
+    FeeMismatch,
+
+    #[msg("Mint is not interest-bearing")]
+    NotInterestBearing,
+
+    #[msg("Interest rate bounds are out of range")]
+    InvalidInterestRateBounds,
+
+    #[msg("Interest rate is outside the mint's bounds; change it through the timelock")]
+    InterestRateOutOfBounds,
+
+    #[msg("Interest rate was updated too recently")]
+    InterestRateUpdateTooSoon,
+
+    #[msg("Each locked allocation bucket needs a vault of its own")]
+    SharedAllocationVault,
+
+    #[msg("A locked allocation bucket has not been released in full")]
+    AllocationNotReleased,
+
+    #[msg("Too many airdrop entries are skipped and unclaimed")]
+    AirdropSkipListFull,
+
+    #[msg("Airdrop entry was not skipped, or is already claimed")]
+    AirdropEntryNotSkipped,
+
+    #[msg("Allocation bucket is not revocable")]
+    AllocationNotRevocable,
+
+    #[msg("No beneficiary change is pending")]
+    NoPendingBeneficiary,
+
+    #[msg("Creator already holds the most slots allowed on this calendar day")]
+    CalendarCreatorLimit,
+
+    #[msg("Token metadata is locked")]
+    MetadataLocked,
+
+    #[msg("Mint has no token metadata yet")]
+    MetadataNotInitialized,
+
+    #[msg("Mint was paused too recently to pause again")]
+    PauseCooldownActive,
+
+    #[msg("Token name or symbol has characters outside the allowed set")]
+    InvalidMetadataCharset,
+
+    #[msg("Metadata URI must use https, ipfs or ar")]
+    InvalidUriScheme,
+
+    #[msg("Launch already has an open or upheld dispute")]
+    DisputeAlreadyOpen,
+
+    #[msg("Dispute was dismissed too recently to flag the launch again")]
+    ReflagTooSoon,
+
+    #[msg("Launch doesn't count as active without a bond")]
+    LaunchNotActive,
+
+    #[msg("Launch counts as active until the bond lock period has passed")]
+    LaunchStillActive,
+
+    #[msg("Airdrop still owes entries skipped over a frozen account")]
+    AirdropSkippedUnclaimed,
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bucket(kind: AllocationKind, bps: u16) -> AllocationBucketParams {
+        AllocationBucketParams {
+            kind,
+            bps,
+            beneficiary: Pubkey::new_unique(),
+            vesting_start: 100,
+            vesting_end: 200,
+            revocable: false,
+        }
+    }
+
+    fn is_error(result: Result<()>, expected: MyError) -> bool {
+        result.is_err_and(|error| error == expected.into())
+    }
+
+    #[test]
+    fn allocation_buckets_unlock_by_kind() {
+        let bucket = |kind| AllocationBucket {
+            kind,
+            bps: 5_000,
+            amount: 1_000,
+            vault: Pubkey::new_unique(),
+            beneficiary: Pubkey::new_unique(),
+            vesting_start: 100,
+            vesting_end: 200,
+            released: 0,
+            revocable: false,
+            pending_beneficiary: None,
+        };
+        let team = bucket(AllocationKind::Team);
+        assert_eq!([team.unlocked(50), team.unlocked(150), team.unlocked(200), team.unlocked(300)], [0, 500, 1_000, 1_000]);
+        let liquidity = bucket(AllocationKind::Liquidity);
+        assert_eq!([liquidity.unlocked(150), liquidity.unlocked(199), liquidity.unlocked(200)], [0, 0, 1_000]);
+        assert_eq!(bucket(AllocationKind::Sale).unlocked(0), 1_000);
+        assert_eq!(bucket(AllocationKind::Treasury).unlocked(0), 1_000);
+    }
+
+    #[test]
+    fn locked_buckets_need_their_own_vault() {
+        let params = AllocationParams {
+            total_supply: 1_000_000,
+            buckets: vec![
+                bucket(AllocationKind::Team, 2_000),
+                bucket(AllocationKind::Liquidity, 3_000),
+                bucket(AllocationKind::Sale, 5_000),
+            ],
+        };
+        params.validate().unwrap();
+        let [team, liquidity, sale] = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+        params.check_vaults(&[team, liquidity, sale]).unwrap();
+
+        assert!(is_error(params.check_vaults(&[team, team, sale]), MyError::SharedAllocationVault));
+        assert!(is_error(params.check_vaults(&[team, liquidity, team]), MyError::SharedAllocationVault));
+        assert!(is_error(params.check_vaults(&[team, liquidity]), MyError::InvalidAllocationPlan));
+    }
+
+    #[test]
+    fn tax_holidays_zero_the_fee_inside_their_windows() {
+        let schedule = TaxHolidaySchedule {
+            mint: Pubkey::new_unique(),
+            admin: Pubkey::new_unique(),
+            transfer_fee_basis_points: 250,
+            maximum_fee: 1_000,
+            windows: vec![TaxHolidayWindow { start_epoch: 10, end_epoch: 12 }, TaxHolidayWindow { start_epoch: 14, end_epoch: 20 }],
+            bump: 0,
+        };
+        let fees: Vec<_> = [9, 10, 11, 12, 13, 14, 19, 20].iter().map(|&epoch| schedule.fee_for_epoch(epoch)).collect();
+        assert_eq!(fees, [(250, 1_000), (0, 0), (0, 0), (250, 1_000), (250, 1_000), (0, 0), (0, 0), (250, 1_000)]);
+    }
+
+    #[test]
+    fn tax_holiday_windows_and_gaps_last_two_epochs() {
+        let window = |start_epoch, end_epoch| TaxHolidayWindow { start_epoch, end_epoch };
+        validate_tax_holiday_windows(&[]).unwrap();
+        validate_tax_holiday_windows(&[window(10, 12), window(14, 16)]).unwrap();
+
+        let invalid = |windows: &[TaxHolidayWindow]| is_error(validate_tax_holiday_windows(windows), MyError::InvalidTaxHolidayWindows);
+        assert!(invalid(&[window(10, 11)]));
+        assert!(invalid(&[window(10, 10)]));
+        assert!(invalid(&[window(10, 12), window(13, 16)]));
+        assert!(invalid(&[window(14, 16), window(10, 12)]));
+        let too_many: Vec<_> = (0..=MAX_TAX_HOLIDAY_WINDOWS as u64).map(|i| window(i * 4, i * 4 + 2)).collect();
+        assert!(invalid(&too_many));
+    }
+
+    #[test]
+    fn period_cap_resets_on_period_boundaries() {
+        let mut cap = PeriodCap::new(100, 1_000, 50);
+        cap.charge(600, 50).unwrap();
+        cap.charge(400, 149).unwrap();
+        assert!(is_error(cap.charge(1, 149), MyError::SpendingCapExceeded));
+        assert_eq!(cap.spent, 1_000);
+
+        // Skipping periods lands on the boundary of the current one, not on `now`
+        cap.charge(1_000, 420).unwrap();
+        assert_eq!((cap.period_start, cap.spent), (350, 1_000));
+        assert!(is_error(cap.charge(1, 449), MyError::SpendingCapExceeded));
+        cap.charge(1, 450).unwrap();
+
+        assert!(is_error(cap.charge(u64::MAX, 450), MyError::MathOverflow));
+        assert!(is_error(PeriodCap::new(100, 1_000, 0).charge(1_001, 0), MyError::SpendingCapExceeded));
+    }
+
+    #[test]
+    fn voucher_nonces_are_spent_once() {
+        let mut page = VoucherNoncePage {
+            campaign: Pubkey::new_unique(),
+            page: 0,
+            bits: [0; NONCE_PAGE_BYTES],
+            bump: 0,
+        };
+        let last = NONCES_PER_PAGE - 1;
+        for nonce in [0, 7, 8, last] {
+            assert!(!page.is_used(nonce));
+            page.consume(nonce).unwrap();
+            assert!(page.is_used(nonce));
+            assert!(is_error(page.consume(nonce), MyError::VoucherAlreadyClaimed));
+        }
+        assert!(!page.is_used(1) && !page.is_used(9) && !page.is_used(last - 1));
+        // A nonce's bit depends only on its offset within its page
+        assert!(page.is_used(NONCES_PER_PAGE + 7));
+    }
+
+    #[test]
+    fn leaderboard_keeps_the_highest_volume_mints_in_order() {
+        let mut leaderboard = Leaderboard { epoch: 0, entries: Vec::new(), finalized: false, bump: 0 };
+        let entry = |mint, volume| LeaderboardEntry { mint, volume, fees: volume / 100 };
+        let mints: Vec<Pubkey> = (0..=MAX_LEADERBOARD_ENTRIES).map(|_| Pubkey::new_unique()).collect();
+        for (i, mint) in mints.iter().take(MAX_LEADERBOARD_ENTRIES).enumerate() {
+            leaderboard.record(entry(*mint, 100 * (i as u64 + 1)));
+        }
+        assert_eq!(leaderboard.entries.len(), MAX_LEADERBOARD_ENTRIES);
+        assert_eq!(leaderboard.entries[0].mint, mints[MAX_LEADERBOARD_ENTRIES - 1]);
+
+        // A newcomer has to beat the lowest entry to displace it
+        let newcomer = mints[MAX_LEADERBOARD_ENTRIES];
+        leaderboard.record(entry(newcomer, 100));
+        assert!(leaderboard.entries.iter().all(|e| e.mint != newcomer));
+        leaderboard.record(entry(newcomer, 150));
+        assert!(leaderboard.entries.iter().all(|e| e.mint != mints[0]));
+        assert_eq!(leaderboard.entries.last().unwrap().volume, 150);
+
+        // Refreshing an entry replaces it and re-sorts
+        leaderboard.record(entry(newcomer, 5_000));
+        assert_eq!(leaderboard.entries[0], entry(newcomer, 5_000));
+        assert_eq!(leaderboard.entries.len(), MAX_LEADERBOARD_ENTRIES);
+        assert!(leaderboard.entries.windows(2).all(|pair| pair[0].volume >= pair[1].volume));
+    }
+
+    #[test]
+    fn calendar_orders_launches_and_caps_each_creator() {
+        let mut calendar = LaunchCalendar { day: 0, launches: Vec::new(), bump: 0 };
+        let launch = |creator, opens_at| ScheduledLaunch { mint: Pubkey::new_unique(), creator, opens_at };
+        let creator = Pubkey::new_unique();
+        calendar.schedule(launch(creator, 300)).unwrap();
+        calendar.schedule(launch(creator, 100)).unwrap();
+        assert!(is_error(calendar.schedule(launch(creator, 200)), MyError::CalendarCreatorLimit));
+
+        // Other creators fill the rest of the page
+        while calendar.launches.len() < MAX_CALENDAR_LAUNCHES {
+            let opens_at = calendar.launches.len() as i64 * 7 % 400;
+            calendar.schedule(launch(Pubkey::new_unique(), opens_at)).unwrap();
+        }
+        assert!(is_error(calendar.schedule(launch(Pubkey::new_unique(), 0)), MyError::LaunchCalendarFull));
+        assert!(calendar.launches.windows(2).all(|pair| pair[0].opens_at <= pair[1].opens_at));
+    }
+
+    #[test]
+    fn mint_pauses_cannot_be_renewed_back_to_back() {
+        let mut pause = MintPause {
+            mint: Pubkey::new_unique(),
+            paused_by: Pubkey::new_unique(),
+            paused_until: 0,
+            opens_at: 0,
+            next_pause_at: 0,
+            bump: 0,
+        };
+        assert!(is_error(pause.start(1_000, MAX_MINT_PAUSE_SECONDS + 1), MyError::InvalidPauseDuration));
+        pause.start(1_000, MAX_MINT_PAUSE_SECONDS).unwrap();
+        assert_eq!(pause.paused_until, 1_000 + MAX_MINT_PAUSE_SECONDS);
+
+        // Renewing while in force, or right after it lapses, is refused
+        let expiry = pause.paused_until;
+        assert!(is_error(pause.start(expiry - 1, MAX_MINT_PAUSE_SECONDS), MyError::PauseCooldownActive));
+        assert!(is_error(pause.start(expiry, 1), MyError::PauseCooldownActive));
+        assert!(is_error(pause.start(expiry + MAX_MINT_PAUSE_SECONDS - 1, 1), MyError::PauseCooldownActive));
+        assert_eq!(pause.paused_until, expiry);
+
+        // A cooldown as long as the pause has to pass first
+        pause.start(expiry + MAX_MINT_PAUSE_SECONDS, 60).unwrap();
+        assert_eq!(pause.paused_until, expiry + MAX_MINT_PAUSE_SECONDS + 60);
+    }
+
+    #[test]
+    fn proposals_pass_only_with_quorum_and_a_majority() {
+        let mut proposal = Proposal {
+            snapshot: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            proposal_id: 0,
+            proposer: Pubkey::new_unique(),
+            description_uri: String::new(),
+            created_at: 0,
+            voting_ends_at: 0,
+            quorum_weight: math::bps_share(1_000_000, MIN_PROPOSAL_QUORUM_BPS),
+            yes_weight: 1,
+            no_weight: 0,
+            status: ProposalStatus::Voting,
+            bump: 0,
+        };
+        // A lone small vote no longer carries a proposal
+        assert_eq!(proposal.outcome(), ProposalStatus::Rejected);
+
+        proposal.yes_weight = 60_000;
+        proposal.no_weight = 39_999;
+        assert_eq!(proposal.outcome(), ProposalStatus::Rejected);
+        proposal.no_weight = 40_000;
+        assert_eq!(proposal.outcome(), ProposalStatus::Passed);
+
+        // Quorum reached, but without a majority
+        proposal.no_weight = 60_000;
+        assert_eq!(proposal.outcome(), ProposalStatus::Rejected);
+    }
+
+    #[test]
+    fn token_metadata_needs_a_clean_charset_and_known_uri_scheme() {
+        let params = |name: &str, symbol: &str, uri: &str| TokenMetadataParams {
+            name: name.to_string(),
+            symbol: symbol.to_string(),
+            uri: uri.to_string(),
+        };
+        for uri in ["https://example.com/tax.json", "ipfs://bafy", "ar://tx"] {
+            params("Taxed Token", "TAX", uri).validate().unwrap();
+        }
+        params("Ben & Jerry's Coin-2.0", "BJ2", "https://a.io").validate().unwrap();
+
+        for bad in [params("", "TAX", "ipfs://x"), params(" Taxed", "TAX", "ipfs://x"), params("Tax\u{202e}", "TAX", "ipfs://x")] {
+            assert!(is_error(bad.validate(), MyError::InvalidMetadataCharset));
+        }
+        for bad in [params("Taxed", "", "ipfs://x"), params("Taxed", "T-X", "ipfs://x"), params("Taxed", "TAX ", "ipfs://x")] {
+            assert!(is_error(bad.validate(), MyError::InvalidMetadataCharset));
+        }
+        for uri in ["http://example.com", "javascript:alert(1)", "ipfs://", "https://a b", "HTTPS://a"] {
+            assert!(is_error(params("Taxed", "TAX", uri).validate(), MyError::InvalidUriScheme));
+        }
+        assert_eq!(params("Taxed", "tAx", "ipfs://x").symbol_key(), "TAX");
+    }
+
+    #[test]
+    fn metaplex_metadata_ix_is_create_metadata_account_v3() {
+        let (metadata_account, mint, creator) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let metadata = TokenMetadata {
+            name: "Taxed".to_string(),
+            symbol: "TAX".to_string(),
+            uri: "ar://tx".to_string(),
+            ..Default::default()
+        };
+        let ix = create_metaplex_metadata_ix(&metadata_account, &mint, &creator, &creator, &metadata, false).unwrap();
+        assert_eq!(ix.program_id, METAPLEX_METADATA_PROGRAM_ID);
+        let mut expected = vec![CREATE_METADATA_ACCOUNT_V3];
+        for field in ["Taxed", "TAX", "ar://tx"] {
+            expected.extend_from_slice(&(field.len() as u32).to_le_bytes());
+            expected.extend_from_slice(field.as_bytes());
+        }
+        // No royalties; no creators, collection or uses; immutable; no collection details
+        expected.extend_from_slice(&[0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(ix.data, expected);
+        let signers: Vec<bool> = ix.accounts.iter().map(|meta| meta.is_signer).collect();
+        assert_eq!(signers, [false, false, true, true, true, false]);
+
+        // Locking only sets is_mutable to Some(false)
+        let ix = lock_metaplex_metadata_ix(&metadata_account, &creator).unwrap();
+        assert_eq!(ix.data, [UPDATE_METADATA_ACCOUNT_V2, 0, 0, 0, 1, 0]);
+        assert!(ix.accounts[1].is_signer && ix.accounts[0].is_writable);
+
+        // is_mutable sits behind the padded strings and however many creators there are
+        let mut account = vec![4];
+        (creator, mint, "Taxed\0\0\0", "TAX\0", "ar://tx\0\0", 0u16).serialize(&mut account).unwrap();
+        (Some(vec![(creator, true, 100u8)]), false, true).serialize(&mut account).unwrap();
+        account.extend_from_slice(&[0; 64]);
+        assert!(MetaplexMetadataHead::deserialize(&mut &account[..]).unwrap().is_mutable);
+    }
+
+    #[test]
+    fn gauge_features_the_heaviest_mints_in_order() {
+        let mut gauge = GaugeEpoch { epoch: 0, total_weight: 0, featured: Vec::new(), bump: 0 };
+        let mints: Vec<Pubkey> = (0..=MAX_FEATURED_LAUNCHES).map(|_| Pubkey::new_unique()).collect();
+        for (i, mint) in mints.iter().take(MAX_FEATURED_LAUNCHES).enumerate() {
+            gauge.record(*mint, 10 * (i as u64 + 1));
+        }
+        assert_eq!(gauge.featured[0].mint, mints[MAX_FEATURED_LAUNCHES - 1]);
+
+        let newcomer = mints[MAX_FEATURED_LAUNCHES];
+        gauge.record(newcomer, 10);
+        assert!(!gauge.is_featured(&newcomer));
+        gauge.record(newcomer, 11);
+        assert!(gauge.is_featured(&newcomer) && !gauge.is_featured(&mints[0]));
+
+        // A featured mint's weight is replaced, not added to
+        gauge.record(mints[1], 100);
+        gauge.record(mints[1], 5);
+        assert_eq!(gauge.featured.last().unwrap(), &GaugeEntry { mint: mints[1], weight: 5 });
+        assert_eq!(gauge.featured.len(), MAX_FEATURED_LAUNCHES);
+        assert!(gauge.featured.windows(2).all(|pair| pair[0].weight >= pair[1].weight));
+    }
+}
@@ -0,0 +1,483 @@
+//! Pure arithmetic behind the instruction handlers: basis-point shares, Token-2022 transfer fees,
+//! linear vesting, the emission curves, the launch fee demand curve and staking reward shares.
+//! Nothing here touches accounts or sysvars.
+//!
+//! Rounding policy: what a user or recipient is credited rounds down, and every split hands its
+//! remainder back to the caller instead of dropping it. The remainder goes to the split's last
+//! recipient (launch fee treasury share, last allocation bucket) or is carried into the next
+//! round (staking rewards, kept in accumulator units down to the fraction of a lamport), so no
+//! lamport or token is created or stranded by rounding.
+
+/// 100% in basis points
+pub const ONE_IN_BASIS_POINTS: u128 = 10_000;
+
+/// `bps` of `amount`, rounded down. `bps` above 100% is clamped to 100%.
+pub fn bps_share(amount: u64, bps: u16) -> u64 {
+    let bps = (bps as u128).min(ONE_IN_BASIS_POINTS);
+    (amount as u128 * bps / ONE_IN_BASIS_POINTS) as u64
+}
+
+/// `bps` of `amount` rounded down, and the rest of `amount`. The two always sum to `amount`.
+pub fn split_bps(amount: u64, bps: u16) -> (u64, u64) {
+    let share = bps_share(amount, bps);
+    (share, amount - share)
+}
+
+/// Where a launch fee goes. The shares always sum to the fee.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LaunchFeeSplit {
+    pub insurance: u64,
+    pub partner: u64,
+    pub staking: u64,
+    /// Whatever the other shares leave, rounding remainders included
+    pub treasury: u64,
+}
+
+/// Split `fee`: `insurance_bps` of it to insurance, `partner_bps` of the rest to the partner,
+/// then `staking_bps` of what the platform keeps to stakers.
+pub fn split_launch_fee(fee: u64, insurance_bps: u16, partner_bps: u16, staking_bps: u16) -> LaunchFeeSplit {
+    let (insurance, rest) = split_bps(fee, insurance_bps);
+    let (partner, platform) = split_bps(rest, partner_bps);
+    let (staking, treasury) = split_bps(platform, staking_bps);
+    LaunchFeeSplit { insurance, partner, staking, treasury }
+}
+
+/// `total` split by each entry of `bps`, rounded down, with the last entry taking the
+/// remainder, so the amounts sum to `total`. `None` when the shares add up to more than 100%.
+pub fn allocate_by_bps(total: u64, bps: &[u16]) -> Option<Vec<u64>> {
+    if bps.iter().map(|share_bps| *share_bps as u128).sum::<u128>() > ONE_IN_BASIS_POINTS {
+        return None;
+    }
+    let mut allocated = 0u64;
+    let amounts = bps
+        .iter()
+        .enumerate()
+        .map(|(index, share_bps)| {
+            let amount = if index + 1 == bps.len() { total - allocated } else { bps_share(total, *share_bps) };
+            allocated += amount;
+            amount
+        })
+        .collect();
+    Some(amounts)
+}
+
+/// Fee Token-2022 withholds on a transfer of `amount`: `bps` rounded up, capped at `maximum_fee`.
+pub fn transfer_fee(amount: u64, bps: u16, maximum_fee: u64) -> u64 {
+    if bps == 0 || amount == 0 {
+        return 0;
+    }
+    let raw_fee = (amount as u128 * bps as u128).div_ceil(ONE_IN_BASIS_POINTS);
+    raw_fee.min(maximum_fee as u128) as u64
+}
+
+/// Smallest gross amount whose transfer nets `net` after [`transfer_fee`]. `None` when that
+/// amount doesn't fit in a u64.
+pub fn pre_fee_amount(net: u64, bps: u16, maximum_fee: u64) -> Option<u64> {
+    let bps = bps as u128;
+    if bps == 0 || net == 0 {
+        return Some(net);
+    }
+    if bps >= ONE_IN_BASIS_POINTS {
+        return net.checked_add(maximum_fee);
+    }
+    let raw = (net as u128 * ONE_IN_BASIS_POINTS).div_ceil(ONE_IN_BASIS_POINTS - bps);
+    if raw - net as u128 >= maximum_fee as u128 {
+        net.checked_add(maximum_fee)
+    } else {
+        u64::try_from(raw).ok()
+    }
+}
+
+/// Part of `total` released linearly between `start` and `end` by `now`, rounded down.
+pub fn linear_vested(total: u64, start: i64, end: i64, now: i64) -> u64 {
+    if now <= start {
+        return 0;
+    }
+    if now >= end {
+        return total;
+    }
+    let elapsed = (now - start) as u128;
+    let duration = (end - start) as u128;
+    (total as u128 * elapsed / duration) as u64
+}
+
+/// `amount` scaled by `remaining` over `max`, for weights that decay to zero. `remaining` past
+/// `max` counts as `max`.
+pub fn decayed_weight(amount: u64, remaining: i64, max: i64) -> u64 {
+    if remaining <= 0 || max <= 0 {
+        return 0;
+    }
+    let remaining = remaining.min(max) as u128;
+    (amount as u128 * remaining / max as u128) as u64
+}
+
+/// Total emitted over the first `periods` periods when the per-period emission starts at
+/// `initial` and halves every `periods_per_halving` periods.
+pub fn halving_released(initial: u64, periods_per_halving: u32, periods: u64) -> u128 {
+    let era_len = periods_per_halving as u64;
+    if era_len == 0 {
+        return 0;
+    }
+    let initial = initial as u128;
+    let mut total = 0u128;
+    let mut remaining = periods;
+    let mut era = 0u32;
+    while remaining > 0 && era < 64 && (initial >> era) > 0 {
+        let in_era = remaining.min(era_len);
+        total += (initial >> era) * in_era as u128;
+        remaining -= in_era;
+        era += 1;
+    }
+    total
+}
+
+/// Total emitted over the first `periods` periods when the per-period emission falls linearly
+/// from `initial` to zero over `total_periods`.
+pub fn linear_decay_released(initial: u64, total_periods: u32, periods: u64) -> u128 {
+    if total_periods == 0 {
+        return 0;
+    }
+    // sum over p < n of initial * (N - p) / N
+    let n_total = total_periods as u128;
+    let n = (periods as u128).min(n_total);
+    initial as u128 * (n * n_total - n * n.saturating_sub(1) / 2) / n_total
+}
+
+/// Lamports worth `micro_usd` at a SOL/USD price of `price * 10^exponent`. `None` when the price
+/// isn't positive or the result doesn't fit in a u64.
+pub fn micro_usd_to_lamports(micro_usd: u64, price: i64, exponent: i32) -> Option<u64> {
+    if price <= 0 {
+        return None;
+    }
+    // lamports = micro_usd * 10^9 / 10^6 / (price * 10^exponent)
+    let value = price as u128;
+    let scale = 10u128.checked_pow(exponent.unsigned_abs())?;
+    let numerator = micro_usd as u128 * 1_000;
+    let lamports = if exponent <= 0 {
+        numerator.checked_mul(scale)? / value
+    } else {
+        numerator / value.checked_mul(scale)?
+    };
+    u64::try_from(lamports).ok()
+}
+
+/// Bond for a creator with `prior_launches` earlier launches: `base` plus `escalation_bps` of
+/// it for each of them. `None` on overflow.
+pub fn escalated_bond(base: u64, escalation_bps: u16, prior_launches: u32) -> Option<u64> {
+    let surcharge = base as u128 * escalation_bps as u128 * prior_launches as u128 / ONE_IN_BASIS_POINTS;
+    u64::try_from(base as u128 + surcharge).ok()
+}
+
+/// Launch fee multiplier, in basis points, for a launch preceded by `prior_launches` others in
+/// the same epoch: 100% plus `step_bps` for each of them past `free_launches`, capped at
+/// `max_multiplier_bps`.
+pub fn demand_multiplier_bps(prior_launches: u32, free_launches: u32, step_bps: u16, max_multiplier_bps: u32) -> u64 {
+    let surge = prior_launches.saturating_sub(free_launches) as u64 * step_bps as u64;
+    (ONE_IN_BASIS_POINTS as u64 + surge).min(max_multiplier_bps.max(ONE_IN_BASIS_POINTS as u32) as u64)
+}
+
+/// `fee` scaled by a multiplier in basis points. `None` on overflow.
+pub fn scaled_fee(fee: u64, multiplier_bps: u64) -> Option<u64> {
+    u64::try_from(fee as u128 * multiplier_bps as u128 / ONE_IN_BASIS_POINTS).ok()
+}
+
+/// Fixed-point scale of an index token's UI multiplier; `MULTIPLIER_ONE` shows amounts as is
+pub const MULTIPLIER_ONE: u64 = 1_000_000_000;
+
+/// UI multiplier of an index now priced at `price`, scaled by `MULTIPLIER_ONE`, measured
+/// against the `base_price` where it was 1. `None` when a price isn't positive or on overflow.
+pub fn index_multiplier(base_price: i64, price: i64) -> Option<u64> {
+    if base_price <= 0 || price <= 0 {
+        return None;
+    }
+    u64::try_from(price as u128 * MULTIPLIER_ONE as u128 / base_price as u128).ok()
+}
+
+/// `target`, moved no further from `current` than `max_deviation_bps` of it.
+pub fn clamp_deviation(current: u64, target: u64, max_deviation_bps: u16) -> u64 {
+    let delta = u64::try_from(current as u128 * max_deviation_bps as u128 / ONE_IN_BASIS_POINTS).unwrap_or(u64::MAX);
+    target.clamp(current.saturating_sub(delta), current.saturating_add(delta))
+}
+
+/// Fixed-point scale of a staking pool's rewards per staked token
+pub const REWARD_PRECISION: u128 = 1_000_000_000_000;
+
+/// Share `rewards` lamports, plus `dust` left by earlier share-outs in accumulator units, over
+/// `total_staked`: the accumulator growth, rounded down, and the new dust. The growth credited to
+/// the stake and the new dust always add up to what was shared. Nothing is shared with nobody
+/// staked; `None` on overflow.
+pub fn distribute_rewards(rewards: u64, dust: u128, total_staked: u64) -> Option<(u128, u128)> {
+    let total = (rewards as u128).checked_mul(REWARD_PRECISION)?.checked_add(dust)?;
+    if total_staked == 0 {
+        return Some((0, total));
+    }
+    let per_share = total / total_staked as u128;
+    Some((per_share, total - per_share * total_staked as u128))
+}
+
+/// Rewards earned by `amount` staked tokens at a `per_share` accumulator, unrounded, in
+/// accumulator units. `None` on overflow.
+pub fn scaled_rewards(amount: u64, per_share: u128) -> Option<u128> {
+    (amount as u128).checked_mul(per_share)
+}
+
+/// Settle `amount` staked tokens at a `per_share` accumulator: the whole lamports earned since
+/// `reward_debt`, and the new debt. The fraction of a lamport the payout rounds off stays out of
+/// the debt, so the next settlement pays it.
+pub fn settle_rewards(amount: u64, per_share: u128, reward_debt: u128) -> Option<(u64, u128)> {
+    let earned = scaled_rewards(amount, per_share)?;
+    let accrued = earned.saturating_sub(reward_debt);
+    let lamports = u64::try_from(accrued / REWARD_PRECISION).ok()?;
+    Some((lamports, earned - accrued % REWARD_PRECISION))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use spl_token_2022::extension::transfer_fee::TransferFee;
+
+    fn spl_fee(bps: u16, maximum_fee: u64) -> TransferFee {
+        TransferFee {
+            epoch: 0.into(),
+            maximum_fee: maximum_fee.into(),
+            transfer_fee_basis_points: bps.into(),
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn bps_share_is_bounded(amount: u64, bps in 0u16..=10_000) {
+            let share = bps_share(amount, bps);
+            prop_assert!(share <= amount);
+            prop_assert_eq!(bps_share(amount, 10_000), amount);
+            prop_assert_eq!(bps_share(amount, 0), 0);
+        }
+
+        #[test]
+        fn bps_share_is_monotonic(amount: u64, bps in 0u16..10_000) {
+            prop_assert!(bps_share(amount, bps) <= bps_share(amount, bps + 1));
+        }
+
+        #[test]
+        fn transfer_fee_matches_token_2022(amount: u64, bps in 0u16..=10_000, maximum_fee: u64) {
+            prop_assert_eq!(Some(transfer_fee(amount, bps, maximum_fee)), spl_fee(bps, maximum_fee).calculate_fee(amount));
+        }
+
+        #[test]
+        fn transfer_fee_is_capped(amount: u64, bps in 0u16..=10_000, maximum_fee: u64) {
+            let fee = transfer_fee(amount, bps, maximum_fee);
+            prop_assert!(fee <= maximum_fee);
+            prop_assert!(fee <= amount);
+        }
+
+        #[test]
+        fn transfer_fee_is_monotonic(amount in 0u64..u64::MAX, bps in 0u16..=10_000, maximum_fee: u64) {
+            prop_assert!(transfer_fee(amount, bps, maximum_fee) <= transfer_fee(amount + 1, bps, maximum_fee));
+        }
+
+        #[test]
+        fn pre_fee_amount_matches_token_2022(net: u64, bps in 0u16..=10_000, maximum_fee: u64) {
+            prop_assert_eq!(pre_fee_amount(net, bps, maximum_fee), spl_fee(bps, maximum_fee).calculate_pre_fee_amount(net));
+        }
+
+        #[test]
+        fn pre_fee_amount_nets_the_target(net in 0u64..u64::MAX / 2, bps in 0u16..10_000, maximum_fee in 0u64..u64::MAX / 2) {
+            let gross = pre_fee_amount(net, bps, maximum_fee).unwrap();
+            prop_assert_eq!(gross - transfer_fee(gross, bps, maximum_fee), net);
+        }
+
+        #[test]
+        fn linear_vesting_is_bounded_and_monotonic(
+            total: u64,
+            start in -1_000_000_000i64..1_000_000_000,
+            duration in 1i64..1_000_000_000,
+            now in -2_000_000_000i64..3_000_000_000,
+        ) {
+            let end = start + duration;
+            let vested = linear_vested(total, start, end, now);
+            prop_assert!(vested <= total);
+            prop_assert!(vested <= linear_vested(total, start, end, now + 1));
+            prop_assert_eq!(linear_vested(total, start, end, start), 0);
+            prop_assert_eq!(linear_vested(total, start, end, end), total);
+        }
+
+        #[test]
+        fn decayed_weight_is_bounded_and_monotonic(amount: u64, remaining in -10i64..1_000_000, max in 1i64..1_000_000) {
+            let weight = decayed_weight(amount, remaining, max);
+            prop_assert!(weight <= amount);
+            prop_assert!(weight <= decayed_weight(amount, remaining + 1, max));
+            prop_assert_eq!(decayed_weight(amount, max, max), amount);
+        }
+
+        #[test]
+        fn halving_is_monotonic_and_bounded(initial: u64, periods_per_halving in 1u32..1_000, periods in 0u64..100_000) {
+            let released = halving_released(initial, periods_per_halving, periods);
+            prop_assert!(released <= halving_released(initial, periods_per_halving, periods + 1));
+            // geometric series: never more than two full eras at the initial rate
+            prop_assert!(released < 2 * initial as u128 * periods_per_halving as u128 + 1);
+            prop_assert!(released <= initial as u128 * periods as u128);
+        }
+
+        #[test]
+        fn halving_first_era_is_flat(initial: u64, periods_per_halving in 1u32..1_000, periods in 0u64..1_000) {
+            let periods = periods.min(periods_per_halving as u64);
+            prop_assert_eq!(halving_released(initial, periods_per_halving, periods), initial as u128 * periods as u128);
+        }
+
+        #[test]
+        fn linear_decay_is_monotonic_and_stops(initial: u64, total_periods in 1u32..10_000, periods in 0u64..20_000) {
+            let released = linear_decay_released(initial, total_periods, periods);
+            prop_assert!(released <= linear_decay_released(initial, total_periods, periods + 1));
+            let cap = linear_decay_released(initial, total_periods, total_periods as u64);
+            prop_assert!(released <= cap);
+            prop_assert_eq!(linear_decay_released(initial, total_periods, periods + total_periods as u64), cap);
+            // an arithmetic series from `initial` down to initial / N averages (N + 1) / 2N of it
+            prop_assert!(cap <= initial as u128 * (total_periods as u128 + 1) / 2);
+        }
+
+        #[test]
+        fn linear_decay_matches_per_period_sum(initial in 0u64..1_000_000_000, total_periods in 1u32..200, periods in 0u64..300) {
+            let n_total = total_periods as u128;
+            let exact: u128 = (0..periods.min(total_periods as u64) as u128)
+                .map(|p| initial as u128 * (n_total - p))
+                .sum();
+            prop_assert_eq!(linear_decay_released(initial, total_periods, periods), exact / n_total);
+        }
+
+        #[test]
+        fn demand_multiplier_is_monotonic_and_capped(
+            prior_launches in 0u32..100_000,
+            free_launches: u32,
+            step_bps: u16,
+            max_multiplier_bps in 10_000u32..1_000_000,
+        ) {
+            let multiplier = demand_multiplier_bps(prior_launches, free_launches, step_bps, max_multiplier_bps);
+            prop_assert!(multiplier >= ONE_IN_BASIS_POINTS as u64);
+            prop_assert!(multiplier <= max_multiplier_bps as u64);
+            prop_assert!(demand_multiplier_bps(prior_launches + 1, free_launches, step_bps, max_multiplier_bps) >= multiplier);
+            if prior_launches <= free_launches {
+                prop_assert_eq!(multiplier, ONE_IN_BASIS_POINTS as u64);
+            }
+        }
+
+        #[test]
+        fn escalated_bond_grows_with_launches(base in 0u64..u64::MAX / 1_000_000, escalation_bps: u16, prior_launches in 0u32..1_000) {
+            let bond = escalated_bond(base, escalation_bps, prior_launches).unwrap();
+            prop_assert!(bond >= base);
+            prop_assert!(escalated_bond(base, escalation_bps, prior_launches + 1).unwrap() >= bond);
+            prop_assert_eq!(escalated_bond(base, escalation_bps, 0), Some(base));
+        }
+
+        #[test]
+        fn usd_conversion_scales_with_price(micro_usd in 0u64..1_000_000_000_000, price in 1i64..1_000_000_000_000, exponent in -12i32..=0) {
+            let lamports = micro_usd_to_lamports(micro_usd, price, exponent).unwrap();
+            let doubled = micro_usd_to_lamports(micro_usd, price.saturating_mul(2), exponent).unwrap();
+            prop_assert!(doubled <= lamports);
+            prop_assert!(lamports <= micro_usd_to_lamports(micro_usd + 1, price, exponent).unwrap());
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn launch_fee_split_conserves_the_fee(fee: u64, insurance_bps in 0u16..=10_000, partner_bps in 0u16..=10_000, staking_bps in 0u16..=10_000) {
+            let split = split_launch_fee(fee, insurance_bps, partner_bps, staking_bps);
+            let total = split.insurance as u128 + split.partner as u128 + split.staking as u128 + split.treasury as u128;
+            prop_assert_eq!(total, fee as u128);
+            prop_assert_eq!(split.insurance, bps_share(fee, insurance_bps));
+        }
+
+        #[test]
+        fn allocation_conserves_the_supply(total: u64, bps in proptest::collection::vec(0u16..=2_500, 1..=4)) {
+            let amounts = allocate_by_bps(total, &bps).unwrap();
+            prop_assert_eq!(amounts.iter().map(|amount| *amount as u128).sum::<u128>(), total as u128);
+            for (amount, bps) in amounts.iter().zip(&bps).take(bps.len() - 1) {
+                prop_assert_eq!(*amount, bps_share(total, *bps));
+            }
+        }
+
+        #[test]
+        fn allocation_rejects_more_than_the_whole(total: u64, bps in proptest::collection::vec(2_501u16..=10_000, 5..=8)) {
+            prop_assert_eq!(allocate_by_bps(total, &bps), None);
+        }
+
+        #[test]
+        fn index_updates_stay_within_deviation(current in 1u64..u64::MAX / 2, target: u64, max_deviation_bps in 1u16..10_000) {
+            let next = clamp_deviation(current, target, max_deviation_bps);
+            let delta = current as u128 * max_deviation_bps as u128 / ONE_IN_BASIS_POINTS;
+            prop_assert!((next as i128 - current as i128).unsigned_abs() <= delta);
+            prop_assert!(next > 0);
+            if (target as i128 - current as i128).unsigned_abs() <= delta {
+                prop_assert_eq!(next, target);
+            }
+        }
+
+        #[test]
+        fn staking_rewards_are_shared_exactly(rewards: u64, dust in 0u128..REWARD_PRECISION, a in 1u64..u64::MAX / 4, b in 1u64..u64::MAX / 4) {
+            let (per_share, new_dust) = distribute_rewards(rewards, dust, a + b).unwrap();
+            let credited = scaled_rewards(a, per_share).unwrap() + scaled_rewards(b, per_share).unwrap();
+            prop_assert_eq!(credited + new_dust, rewards as u128 * REWARD_PRECISION + dust);
+            prop_assert!(new_dust < (a + b) as u128);
+        }
+
+        #[test]
+        fn reward_distribution_conserves_rewards(rounds in proptest::collection::vec(0u64..u64::MAX / 16, 1..8), a in 1u64..u64::MAX / 4, b in 1u64..u64::MAX / 4) {
+            let (mut per_share, mut dust) = (0u128, 0u128);
+            let (mut debt_a, mut debt_b, mut paid, mut total) = (0u128, 0u128, 0u128, 0u128);
+            for rewards in rounds {
+                let (growth, new_dust) = distribute_rewards(rewards, dust, a + b).unwrap();
+                per_share += growth;
+                dust = new_dust;
+                total += rewards as u128;
+                let (lamports_a, new_debt_a) = settle_rewards(a, per_share, debt_a).unwrap();
+                let (lamports_b, new_debt_b) = settle_rewards(b, per_share, debt_b).unwrap();
+                paid += lamports_a as u128 + lamports_b as u128;
+                (debt_a, debt_b) = (new_debt_a, new_debt_b);
+            }
+            // Every lamport shared is paid out or still owed, down to the accumulator's units
+            let owed = (scaled_rewards(a, per_share).unwrap() - debt_a) + (scaled_rewards(b, per_share).unwrap() - debt_b);
+            prop_assert!(paid <= total);
+            prop_assert_eq!(paid * REWARD_PRECISION + owed + dust, total * REWARD_PRECISION);
+        }
+
+        #[test]
+        fn vesting_releases_sum_to_the_total(total in 0u64..u64::MAX / 2, stray in 0u64..u64::MAX / 2, duration in 1i64..1_000_000, checkpoints in proptest::collection::vec(0i64..1_100_000, 0..8)) {
+            // A release pays the bucket's own vested remainder, never more than its vault holds;
+            // tokens sent to the vault on top wait there for the plan's close
+            let mut checkpoints = checkpoints;
+            checkpoints.sort();
+            checkpoints.push(duration);
+            let (mut released, mut vault) = (0u64, total + stray);
+            for now in checkpoints {
+                let payout = linear_vested(total, 0, duration, now).saturating_sub(released).min(vault);
+                released += payout;
+                vault -= payout;
+                prop_assert!(vault >= total - released);
+            }
+            prop_assert_eq!(released, total);
+            prop_assert_eq!(vault, stray);
+        }
+
+        #[test]
+        fn settlements_carry_their_rounding(amount in 1u64..u64::MAX / 16, growths in proptest::collection::vec(0u128..10 * REWARD_PRECISION, 1..8)) {
+            let (mut per_share, mut debt, mut paid) = (0u128, 0u128, 0u128);
+            for growth in growths {
+                per_share += growth;
+                let (lamports, new_debt) = settle_rewards(amount, per_share, debt).unwrap();
+                paid += lamports as u128;
+                debt = new_debt;
+            }
+            let earned = scaled_rewards(amount, per_share).unwrap();
+            prop_assert_eq!(paid * REWARD_PRECISION + (earned - debt), earned);
+            prop_assert!(earned - debt < REWARD_PRECISION);
+        }
+    }
+
+    #[test]
+    fn usd_conversion_examples() {
+        // $1 at $100/SOL is 0.01 SOL
+        assert_eq!(micro_usd_to_lamports(1_000_000, 10_000_000_000, -8), Some(10_000_000));
+        assert_eq!(micro_usd_to_lamports(1_000_000, 0, -8), None);
+        assert_eq!(micro_usd_to_lamports(1_000_000, -5, -8), None);
+    }
+}
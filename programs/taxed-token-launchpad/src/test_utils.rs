@@ -0,0 +1,392 @@
+//! Fixtures for exercising the launchpad under solana-program-test, for this crate's own
+//! integration tests and for programs that CPI into it. Enabled with the `test-utils` feature.
+
+use anchor_lang::solana_program::system_instruction;
+use anchor_lang::{AccountDeserialize, AccountSerialize, InstructionData, Space, ToAccountMetas};
+use solana_program_test::{BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    account::Account,
+    clock::Clock,
+    instruction::{AccountMeta, Instruction, InstructionError},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    transaction::{Transaction, TransactionError},
+};
+use spl_token_2022::extension::{transfer_fee::TransferFeeConfig, BaseStateWithExtensions, ExtensionType, StateWithExtensionsOwned};
+use spl_token_2022::state::{Account as TokenAccount, Mint};
+
+use crate::{
+    InsuranceVault, MyError, PlatformConfig, ASSOCIATED_TOKEN_PROGRAM_ID, INSURANCE_VAULT_SEED, LAUNCH_CONFIG_SEED,
+    PLATFORM_CONFIG_SEED, REPUTATION_SEED,
+};
+
+/// Where `anchor build` leaves the program, used unless `SBF_OUT_DIR` or `BPF_OUT_DIR` is set
+pub const DEFAULT_SBF_OUT_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../../target/deploy");
+
+/// ProgramTest running the SBF build of the launchpad, with a fee-free platform config and
+/// insurance vault administered by `admin` already in place. Token-2022 is part of the default
+/// genesis.
+///
+/// The program must be built first (`anchor build`). Anchor's CPI helpers only work on-chain, so
+/// the launchpad can't run as a native processor; the suites using this are ignored unless the
+/// `sbf-tests` feature is on.
+pub fn program_test(admin: &Pubkey) -> ProgramTest {
+    program_test_with(admin, |_| {})
+}
+
+/// Like [`program_test`], with `configure` adjusting the platform config first, e.g. to charge
+/// launch fees or name a platform mint.
+pub fn program_test_with(admin: &Pubkey, configure: impl FnOnce(&mut PlatformConfig)) -> ProgramTest {
+    if std::env::var_os("SBF_OUT_DIR").is_none() && std::env::var_os("BPF_OUT_DIR").is_none() {
+        std::env::set_var("SBF_OUT_DIR", DEFAULT_SBF_OUT_DIR);
+    }
+    let mut program_test = ProgramTest::new("taxed_token_launchpad", crate::ID, None);
+    program_test.prefer_bpf(true);
+
+    let (platform_config, config_bump) = Pubkey::find_program_address(&[PLATFORM_CONFIG_SEED], &crate::ID);
+    let mut config = PlatformConfig {
+        admin: *admin,
+        verifier: *admin,
+        treasury: *admin,
+        launch_fee_lamports: 0,
+        insurance_fee_bps: 0,
+        bond_lamports: 0,
+        bond_lock_seconds: 0,
+        snapshotter: *admin,
+        platform_mint: Pubkey::default(),
+        featured_fee_discount_bps: 0,
+        launch_fee_usd: 0,
+        sol_usd_feed_id: [0; 32],
+        max_price_age_seconds: 0,
+        max_price_confidence_bps: 0,
+        max_active_launches: 0,
+        launch_cooldown_seconds: 0,
+        bond_escalation_bps: 0,
+        launch_attestor: None,
+        attestation_required: false,
+        staking_fee_bps: 0,
+        demand_free_launches: 0,
+        demand_fee_step_bps: 0,
+        max_demand_multiplier_bps: 0,
+        demand_epoch: 0,
+        epoch_launches: 0,
+        bump: config_bump,
+    };
+    configure(&mut config);
+    program_test.add_account(platform_config, program_account(&config, 8 + PlatformConfig::INIT_SPACE));
+
+    let (insurance_vault, vault_bump) = Pubkey::find_program_address(&[INSURANCE_VAULT_SEED], &crate::ID);
+    let vault = InsuranceVault {
+        total_slashed: 0,
+        total_fees: 0,
+        total_paid: 0,
+        bump: vault_bump,
+    };
+    program_test.add_account(insurance_vault, program_account(&vault, 8 + InsuranceVault::INIT_SPACE));
+
+    program_test
+}
+
+fn program_account<T: AccountSerialize>(state: &T, space: usize) -> Account {
+    let mut data = Vec::with_capacity(space);
+    state.try_serialize(&mut data).unwrap();
+    data.resize(space, 0);
+    Account {
+        lamports: 1_000_000_000,
+        data,
+        owner: crate::ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+/// Platform accounts shared by the creation instructions, for `payer` launching `mint` on a
+/// platform set up by [`program_test`] for `admin`.
+pub struct LaunchAccounts {
+    pub platform_config: Pubkey,
+    pub treasury: Pubkey,
+    pub insurance_vault: Pubkey,
+    pub launch_config: Pubkey,
+    pub creator_reputation: Pubkey,
+}
+
+impl LaunchAccounts {
+    pub fn new(admin: &Pubkey, payer: &Pubkey, mint: &Pubkey) -> Self {
+        let pda = |seeds: &[&[u8]]| Pubkey::find_program_address(seeds, &crate::ID).0;
+        Self {
+            platform_config: pda(&[PLATFORM_CONFIG_SEED]),
+            treasury: *admin,
+            insurance_vault: pda(&[INSURANCE_VAULT_SEED]),
+            launch_config: pda(&[LAUNCH_CONFIG_SEED, mint.as_ref()]),
+            creator_reputation: pda(&[REPUTATION_SEED, payer.as_ref()]),
+        }
+    }
+}
+
+/// `create_taxed_token` with `payer` as creator and mint, fee-withdraw and freeze authority, and
+/// no bond, faucet, gauge or price update.
+pub fn create_taxed_token_ix(admin: &Pubkey, payer: &Pubkey, mint: &Pubkey, decimals: u8, transfer_fee_basis_points: u16, maximum_fee: u64) -> Instruction {
+    create_taxed_token_with_ix(create_taxed_token_accounts(admin, payer, mint), decimals, transfer_fee_basis_points, maximum_fee)
+}
+
+/// `create_taxed_token` with the given accounts, for launches that need more than
+/// [`create_taxed_token_ix`] passes, such as the staking pool.
+pub fn create_taxed_token_with_ix(accounts: crate::accounts::CreateTaxedToken, decimals: u8, transfer_fee_basis_points: u16, maximum_fee: u64) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: accounts.to_account_metas(None),
+        data: crate::instruction::CreateTaxedToken {
+            decimals,
+            transfer_fee_basis_points,
+            maximum_fee,
+        }
+        .data(),
+    }
+}
+
+/// Accounts of [`create_taxed_token_ix`].
+pub fn create_taxed_token_accounts(admin: &Pubkey, payer: &Pubkey, mint: &Pubkey) -> crate::accounts::CreateTaxedToken {
+    let launch = LaunchAccounts::new(admin, payer, mint);
+    crate::accounts::CreateTaxedToken {
+        payer: *payer,
+        mint: *mint,
+        mint_authority: *payer,
+        fee_withdraw_authority: *payer,
+        freeze_authority: *payer,
+        token_program: spl_token_2022::id(),
+        platform_config: launch.platform_config,
+        creator_bond: None,
+        devnet_faucet: None,
+        treasury: launch.treasury,
+        insurance_vault: launch.insurance_vault,
+        featured_gauge: None,
+        price_update: None,
+        attestor: None,
+        partner: None,
+        staking_pool: None,
+        launch_config: launch.launch_config,
+        creator_reputation: launch.creator_reputation,
+        system_program: solana_sdk::system_program::id(),
+        rent: solana_sdk::sysvar::rent::id(),
+    }
+}
+
+/// `create_soulbound_token` with `payer` as creator and mint and freeze authority.
+pub fn create_soulbound_token_ix(admin: &Pubkey, payer: &Pubkey, mint: &Pubkey, decimals: u8) -> Instruction {
+    let launch = LaunchAccounts::new(admin, payer, mint);
+    let accounts = crate::accounts::CreateSoulboundToken {
+        payer: *payer,
+        mint: *mint,
+        mint_authority: *payer,
+        freeze_authority: *payer,
+        token_program: spl_token_2022::id(),
+        platform_config: launch.platform_config,
+        creator_bond: None,
+        devnet_faucet: None,
+        treasury: launch.treasury,
+        insurance_vault: launch.insurance_vault,
+        featured_gauge: None,
+        price_update: None,
+        attestor: None,
+        partner: None,
+        staking_pool: None,
+        launch_config: launch.launch_config,
+        creator_reputation: launch.creator_reputation,
+        system_program: solana_sdk::system_program::id(),
+        rent: solana_sdk::sysvar::rent::id(),
+    };
+    Instruction {
+        program_id: crate::ID,
+        accounts: accounts.to_account_metas(None),
+        data: crate::instruction::CreateSoulboundToken { decimals }.data(),
+    }
+}
+
+/// `transfer_with_fee` without an exemption list, stats or pool registry.
+pub fn transfer_with_fee_ix(source: &Pubkey, mint: &Pubkey, destination: &Pubkey, authority: &Pubkey, amount: u64, decimals: u8, fee: u64) -> Instruction {
+    let accounts = crate::accounts::TransferWithFee {
+        source: *source,
+        mint: *mint,
+        destination: *destination,
+        authority: *authority,
+        token_program: spl_token_2022::id(),
+        exemption_list: Pubkey::find_program_address(&[crate::FEE_EXEMPTION_SEED, mint.as_ref()], &crate::ID).0,
+        mint_stats: None,
+        pool_registry: None,
+    };
+    Instruction {
+        program_id: crate::ID,
+        accounts: accounts.to_account_metas(None),
+        data: crate::instruction::TransferWithFee { amount, decimals, fee }.data(),
+    }
+}
+
+async fn signed_transaction(context: &mut ProgramTestContext, instructions: &[Instruction], signers: &[&Keypair]) -> Result<Transaction, BanksClientError> {
+    let mut all_signers = vec![&context.payer];
+    all_signers.extend_from_slice(signers);
+    let blockhash = context.banks_client.get_latest_blockhash().await?;
+    Ok(Transaction::new_signed_with_payer(instructions, Some(&context.payer.pubkey()), &all_signers, blockhash))
+}
+
+/// Sign with the context payer plus `signers` and process `instructions`.
+pub async fn send(context: &mut ProgramTestContext, instructions: &[Instruction], signers: &[&Keypair]) -> Result<(), BanksClientError> {
+    let transaction = signed_transaction(context, instructions, signers).await?;
+    context.banks_client.process_transaction(transaction).await
+}
+
+/// Like [`send`], returning the compute units the transaction consumed.
+pub async fn send_metered(context: &mut ProgramTestContext, instructions: &[Instruction], signers: &[&Keypair]) -> Result<u64, BanksClientError> {
+    let transaction = signed_transaction(context, instructions, signers).await?;
+    let outcome = context.banks_client.process_transaction_with_metadata(transaction).await?;
+    outcome.result?;
+    outcome
+        .metadata
+        .map(|metadata| metadata.compute_units_consumed)
+        .ok_or(BanksClientError::ClientError("transaction metadata unavailable"))
+}
+
+/// Create and fund a Token-2022 account of `mint` owned by `owner`, sized for the mint's
+/// required account extensions. Returns its address.
+pub async fn create_token_account(context: &mut ProgramTestContext, mint: &Pubkey, owner: &Pubkey) -> Pubkey {
+    let account = Keypair::new();
+    let mint_state = get_mint(context, mint).await;
+    let extensions = mint_state.get_extension_types().unwrap();
+    let required = ExtensionType::get_required_init_account_extensions(&extensions);
+    let space = ExtensionType::try_calculate_account_len::<TokenAccount>(&required).unwrap();
+    let lamports = context.banks_client.get_rent().await.unwrap().minimum_balance(space);
+
+    let instructions = [
+        system_instruction::create_account(&context.payer.pubkey(), &account.pubkey(), lamports, space as u64, &spl_token_2022::id()),
+        spl_token_2022::instruction::initialize_account3(&spl_token_2022::id(), &account.pubkey(), mint, owner).unwrap(),
+    ];
+    send(context, &instructions, &[&account]).await.unwrap();
+    account.pubkey()
+}
+
+/// Mint `amount` to `destination` with the context payer as mint authority.
+pub async fn mint_to(context: &mut ProgramTestContext, mint: &Pubkey, destination: &Pubkey, amount: u64) {
+    let payer = context.payer.pubkey();
+    let ix = spl_token_2022::instruction::mint_to(&spl_token_2022::id(), mint, destination, &payer, &[], amount).unwrap();
+    send(context, &[ix], &[]).await.unwrap();
+}
+
+/// Mint state with its extensions.
+pub async fn get_mint(context: &mut ProgramTestContext, mint: &Pubkey) -> StateWithExtensionsOwned<Mint> {
+    let account = context.banks_client.get_account(*mint).await.unwrap().expect("mint exists");
+    StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap()
+}
+
+/// Token account state with its extensions.
+pub async fn get_token_account(context: &mut ProgramTestContext, address: &Pubkey) -> StateWithExtensionsOwned<TokenAccount> {
+    let account = context.banks_client.get_account(*address).await.unwrap().expect("token account exists");
+    StateWithExtensionsOwned::<TokenAccount>::unpack(account.data).unwrap()
+}
+
+/// TransferFeeConfig of a taxed mint.
+pub async fn get_transfer_fee_config(context: &mut ProgramTestContext, mint: &Pubkey) -> TransferFeeConfig {
+    *get_mint(context, mint).await.get_extension::<TransferFeeConfig>().unwrap()
+}
+
+/// Create a plain Token-2022 mint, without extensions, with the context payer as mint authority.
+pub async fn create_mint(context: &mut ProgramTestContext, mint: &Keypair, decimals: u8) {
+    let payer = context.payer.pubkey();
+    let space = ExtensionType::try_calculate_account_len::<Mint>(&[]).unwrap();
+    let lamports = context.banks_client.get_rent().await.unwrap().minimum_balance(space);
+    let instructions = [
+        system_instruction::create_account(&payer, &mint.pubkey(), lamports, space as u64, &spl_token_2022::id()),
+        spl_token_2022::instruction::initialize_mint2(&spl_token_2022::id(), &mint.pubkey(), &payer, None, decimals).unwrap(),
+    ];
+    send(context, &instructions, &[mint]).await.unwrap();
+}
+
+/// Create `wallet`'s Token-2022 associated token account of `mint`, paid by the context payer.
+/// Returns its address.
+pub async fn create_associated_token_account(context: &mut ProgramTestContext, mint: &Pubkey, wallet: &Pubkey) -> Pubkey {
+    let address = associated_token_address(wallet, mint);
+    let ix = Instruction {
+        program_id: ASSOCIATED_TOKEN_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(context.payer.pubkey(), true),
+            AccountMeta::new(address, false),
+            AccountMeta::new_readonly(*wallet, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(solana_sdk::system_program::id(), false),
+            AccountMeta::new_readonly(spl_token_2022::id(), false),
+        ],
+        // CreateIdempotent
+        data: vec![1],
+    };
+    send(context, &[ix], &[]).await.unwrap();
+    address
+}
+
+/// Address of `wallet`'s Token-2022 associated token account of `mint`.
+pub fn associated_token_address(wallet: &Pubkey, mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[wallet.as_ref(), spl_token_2022::id().as_ref(), mint.as_ref()], &ASSOCIATED_TOKEN_PROGRAM_ID).0
+}
+
+/// Freeze, or with `frozen` unset thaw, a token account with the context payer as freeze authority.
+pub async fn set_frozen(context: &mut ProgramTestContext, mint: &Pubkey, account: &Pubkey, frozen: bool) {
+    let payer = context.payer.pubkey();
+    let ix = if frozen {
+        spl_token_2022::instruction::freeze_account(&spl_token_2022::id(), account, mint, &payer, &[]).unwrap()
+    } else {
+        spl_token_2022::instruction::thaw_account(&spl_token_2022::id(), account, mint, &payer, &[]).unwrap()
+    };
+    send(context, &[ix], &[]).await.unwrap();
+}
+
+/// Move `amount` of `mint` from `source`, owned by `authority`, to `destination`, paying the
+/// mint's transfer fee like any wallet transfer.
+pub async fn transfer(context: &mut ProgramTestContext, mint: &Pubkey, source: &Pubkey, destination: &Pubkey, authority: &Keypair, amount: u64) {
+    let decimals = get_mint(context, mint).await.base.decimals;
+    let ix = spl_token_2022::instruction::transfer_checked(&spl_token_2022::id(), source, mint, destination, &authority.pubkey(), &[], amount, decimals).unwrap();
+    send(context, &[ix], &[authority]).await.unwrap();
+}
+
+/// Send `lamports` from the context payer to `wallet`.
+pub async fn fund(context: &mut ProgramTestContext, wallet: &Pubkey, lamports: u64) {
+    let ix = system_instruction::transfer(&context.payer.pubkey(), wallet, lamports);
+    send(context, &[ix], &[]).await.unwrap();
+}
+
+/// Current clock sysvar.
+pub async fn clock(context: &mut ProgramTestContext) -> Clock {
+    context.banks_client.get_sysvar::<Clock>().await.unwrap()
+}
+
+/// Move to the next slot, so retried transactions get a fresh blockhash, and set the clock's
+/// unix timestamp to `unix_timestamp`.
+pub async fn set_clock(context: &mut ProgramTestContext, unix_timestamp: i64) {
+    let slot = clock(context).await.slot;
+    context.warp_to_slot(slot + 1).unwrap();
+    let mut clock = clock(context).await;
+    clock.unix_timestamp = unix_timestamp;
+    context.set_sysvar(&clock);
+}
+
+/// Lamport balance of `address`, zero when it doesn't exist.
+pub async fn lamports(context: &mut ProgramTestContext, address: &Pubkey) -> u64 {
+    context.banks_client.get_balance(*address).await.unwrap()
+}
+
+/// Whether an account exists at `address`.
+pub async fn account_exists(context: &mut ProgramTestContext, address: &Pubkey) -> bool {
+    context.banks_client.get_account(*address).await.unwrap().is_some()
+}
+
+/// Deserialized state of one of the launchpad's accounts.
+pub async fn get_program_account<T: AccountDeserialize>(context: &mut ProgramTestContext, address: &Pubkey) -> T {
+    let account = context.banks_client.get_account(*address).await.unwrap().expect("program account exists");
+    T::try_deserialize(&mut &account.data[..]).unwrap()
+}
+
+/// Whether `result` failed with the launchpad's `error`.
+pub fn is_program_error(result: Result<(), BanksClientError>, error: MyError) -> bool {
+    matches!(
+        result,
+        Err(BanksClientError::TransactionError(TransactionError::InstructionError(_, InstructionError::Custom(code))))
+            if code == u32::from(error)
+    )
+}
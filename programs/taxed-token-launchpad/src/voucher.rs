@@ -0,0 +1,101 @@
+//! Vouchers signed off-chain and checked through the ed25519 native program. The runtime
+//! verifies the signature in an ed25519 instruction placed right before the claiming one; the
+//! handler only checks that this instruction carries the expected signer and message.
+
+use anchor_lang::prelude::*;
+use solana_instructions_sysvar::{load_current_index_checked, load_instruction_at_checked};
+use solana_sdk_ids::ed25519_program;
+
+use crate::MyError;
+
+const SIGNATURE_OFFSETS_START: usize = 2;
+const SIGNATURE_OFFSETS_SIZE: usize = 14;
+/// Instruction index meaning "this instruction" in ed25519 signature offsets
+const CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+/// Signer and message of ed25519 instruction data verifying exactly one signature, with the
+/// signature, key and message all carried in that instruction. None for any other shape.
+pub fn signed_message(data: &[u8]) -> Option<(Pubkey, &[u8])> {
+    if data.first() != Some(&1) {
+        return None;
+    }
+    let offsets = data.get(SIGNATURE_OFFSETS_START..SIGNATURE_OFFSETS_START + SIGNATURE_OFFSETS_SIZE)?;
+    let field = |index: usize| u16::from_le_bytes([offsets[2 * index], offsets[2 * index + 1]]);
+    // Data taken from another instruction could be verified against a different key
+    if [field(1), field(3), field(6)] != [CURRENT_INSTRUCTION; 3] {
+        return None;
+    }
+    let public_key_offset = field(2) as usize;
+    let message_offset = field(4) as usize;
+    let message_size = field(5) as usize;
+    let public_key = data.get(public_key_offset..public_key_offset + 32)?;
+    let message = data.get(message_offset..message_offset + message_size)?;
+    Some((Pubkey::try_from(public_key).ok()?, message))
+}
+
+/// Fail unless the instruction before the current one verifies `signer`'s signature over
+/// `message`.
+pub fn verify(instructions_sysvar: &AccountInfo, signer: &Pubkey, message: &[u8]) -> Result<()> {
+    let current = load_current_index_checked(instructions_sysvar)?;
+    let previous = current.checked_sub(1).ok_or(MyError::InvalidVoucher)?;
+    let ix = load_instruction_at_checked(previous as usize, instructions_sysvar)?;
+    if ix.program_id != ed25519_program::ID {
+        return Err(MyError::InvalidVoucher.into());
+    }
+    match signed_message(&ix.data) {
+        Some((key, signed)) if key == *signer && signed == message => Ok(()),
+        _ => Err(MyError::InvalidVoucher.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Same layout as `new_ed25519_instruction_with_signature`
+    fn ed25519_data(signer: &Pubkey, message: &[u8], key_index: u16) -> Vec<u8> {
+        let public_key_offset = (SIGNATURE_OFFSETS_START + SIGNATURE_OFFSETS_SIZE) as u16;
+        let signature_offset = public_key_offset + 32;
+        let message_offset = signature_offset + 64;
+        let mut data = vec![1, 0];
+        for field in [
+            signature_offset,
+            CURRENT_INSTRUCTION,
+            public_key_offset,
+            key_index,
+            message_offset,
+            message.len() as u16,
+            CURRENT_INSTRUCTION,
+        ] {
+            data.extend_from_slice(&field.to_le_bytes());
+        }
+        data.extend_from_slice(signer.as_ref());
+        data.extend_from_slice(&[7; 64]);
+        data.extend_from_slice(message);
+        data
+    }
+
+    #[test]
+    fn reads_signer_and_message() {
+        let signer = Pubkey::new_unique();
+        let data = ed25519_data(&signer, b"voucher", CURRENT_INSTRUCTION);
+        assert_eq!(signed_message(&data), Some((signer, &b"voucher"[..])));
+    }
+
+    #[test]
+    fn rejects_other_shapes() {
+        let signer = Pubkey::new_unique();
+        // Key read from another instruction
+        assert_eq!(signed_message(&ed25519_data(&signer, b"voucher", 0)), None);
+
+        // Two signatures
+        let mut data = ed25519_data(&signer, b"voucher", CURRENT_INSTRUCTION);
+        data[0] = 2;
+        assert_eq!(signed_message(&data), None);
+
+        // Message running past the data
+        let data = ed25519_data(&signer, b"voucher", CURRENT_INSTRUCTION);
+        assert_eq!(signed_message(&data[..data.len() - 1]), None);
+        assert_eq!(signed_message(&[1, 0, 0]), None);
+    }
+}
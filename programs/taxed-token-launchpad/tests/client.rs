@@ -0,0 +1,2007 @@
+//! Keeps the client crate in step with the program: builders must produce the same data and
+//! account metas as Anchor's generated types, and decoders must read what the program writes.
+
+use anchor_lang::{AccountSerialize, Discriminator, Event, InstructionData, Space, ToAccountMetas};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey};
+use taxed_token_launchpad as program;
+use taxed_token_launchpad_client::{
+    self as client, events, events::Event as _, instructions, pda, state, state::ProgramAccount,
+};
+
+fn anchor_ix(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: program::ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+fn serialize<T: AccountSerialize>(account: &T, space: usize) -> Vec<u8> {
+    let mut data = Vec::new();
+    account.try_serialize(&mut data).unwrap();
+    data.resize(space, 0);
+    data
+}
+
+#[test]
+fn ids_and_discriminators_match() {
+    assert_eq!(client::ID, program::ID);
+    assert_eq!(client::TOKEN_2022_PROGRAM_ID, spl_token_2022::id());
+    assert_eq!(
+        client::instruction_discriminator("create_taxed_token"),
+        program::instruction::CreateTaxedToken::DISCRIMINATOR
+    );
+    assert_eq!(
+        client::account_discriminator("LaunchConfig"),
+        program::LaunchConfig::DISCRIMINATOR
+    );
+    assert_eq!(
+        client::event_discriminator("StreamClosed"),
+        program::StreamClosed::DISCRIMINATOR
+    );
+}
+
+#[test]
+fn pdas_match_program_seeds() {
+    let mint = Pubkey::new_unique();
+    let wallet = Pubkey::new_unique();
+    let find = |seeds: &[&[u8]]| Pubkey::find_program_address(seeds, &program::ID);
+    assert_eq!(
+        pda::launch_config(&mint),
+        find(&[program::LAUNCH_CONFIG_SEED, mint.as_ref()])
+    );
+    assert_eq!(
+        pda::faucet_claim(&mint, &wallet),
+        find(&[program::FAUCET_CLAIM_SEED, mint.as_ref(), wallet.as_ref()])
+    );
+    assert_eq!(
+        pda::stream(&wallet, &mint, 7),
+        find(&[
+            program::STREAM_SEED,
+            wallet.as_ref(),
+            mint.as_ref(),
+            &7u64.to_le_bytes()
+        ])
+    );
+    assert_eq!(
+        pda::vote_lock(&mint, &wallet),
+        find(&[program::VOTE_LOCK_SEED, mint.as_ref(), wallet.as_ref()])
+    );
+    assert_eq!(
+        pda::fee_delegate(&mint),
+        find(&[program::FEE_DELEGATE_SEED, mint.as_ref()])
+    );
+    assert_eq!(
+        pda::partner("studio"),
+        find(&[program::PARTNER_SEED, b"studio"])
+    );
+    assert_eq!(
+        pda::symbol_index("tax"),
+        find(&[program::SYMBOL_INDEX_SEED, b"TAX"])
+    );
+    assert_eq!(
+        pda::metaplex_metadata(&mint),
+        Pubkey::find_program_address(
+            &[
+                program::METAPLEX_METADATA_SEED,
+                program::METAPLEX_METADATA_PROGRAM_ID.as_ref(),
+                mint.as_ref()
+            ],
+            &program::METAPLEX_METADATA_PROGRAM_ID
+        )
+    );
+    assert_eq!(
+        pda::timelock_op(&wallet, 3),
+        find(&[
+            program::TIMELOCK_OP_SEED,
+            wallet.as_ref(),
+            &3u64.to_le_bytes()
+        ])
+    );
+}
+
+#[test]
+fn create_taxed_token_matches_anchor() {
+    let payer = Pubkey::new_unique();
+    let mint = Pubkey::new_unique();
+    let treasury = Pubkey::new_unique();
+    let gauge = Pubkey::new_unique();
+    let attestor = Pubkey::new_unique();
+    let launch = instructions::LaunchAccounts {
+        payer,
+        mint,
+        mint_authority: payer,
+        freeze_authority: payer,
+        treasury,
+        with_bond: true,
+        with_faucet: false,
+        featured_gauge: Some(gauge),
+        price_update: None,
+        attestor: Some(attestor),
+        partner: None,
+        with_staking: true,
+    };
+    let built = instructions::create_taxed_token(&launch, &payer, 6, 250, 1_000);
+
+    let expected = anchor_ix(
+        program::accounts::CreateTaxedToken {
+            payer,
+            mint,
+            mint_authority: payer,
+            fee_withdraw_authority: payer,
+            freeze_authority: payer,
+            token_program: spl_token_2022::id(),
+            platform_config: pda::platform_config().0,
+            creator_bond: Some(pda::creator_bond(&mint).0),
+            devnet_faucet: None,
+            treasury,
+            insurance_vault: pda::insurance_vault().0,
+            featured_gauge: Some(gauge),
+            price_update: None,
+            attestor: Some(attestor),
+            partner: None,
+            staking_pool: Some(pda::platform_staking().0),
+            launch_config: pda::launch_config(&mint).0,
+            creator_reputation: pda::creator_reputation(&payer).0,
+            system_program: solana_sdk::system_program::id(),
+            rent: solana_sdk::sysvar::rent::id(),
+        },
+        program::instruction::CreateTaxedToken {
+            decimals: 6,
+            transfer_fee_basis_points: 250,
+            maximum_fee: 1_000,
+        },
+    );
+    assert_eq!(built, expected);
+}
+
+#[test]
+fn create_from_template_matches_anchor() {
+    let payer = Pubkey::new_unique();
+    let mint = Pubkey::new_unique();
+    let template = Pubkey::new_unique();
+    let launch = instructions::LaunchAccounts {
+        payer,
+        mint,
+        mint_authority: payer,
+        freeze_authority: payer,
+        treasury: payer,
+        with_bond: false,
+        with_faucet: true,
+        featured_gauge: None,
+        price_update: None,
+        attestor: None,
+        partner: Some(pda::partner("studio").0),
+        with_staking: false,
+    };
+    let built = instructions::create_from_template(&launch, Some(&payer), &template);
+
+    let expected = anchor_ix(
+        program::accounts::CreateFromTemplate {
+            payer,
+            mint,
+            mint_authority: payer,
+            fee_withdraw_authority: Some(payer),
+            freeze_authority: payer,
+            template,
+            token_program: spl_token_2022::id(),
+            platform_config: pda::platform_config().0,
+            creator_bond: None,
+            devnet_faucet: Some(pda::faucet(&mint).0),
+            treasury: payer,
+            insurance_vault: pda::insurance_vault().0,
+            featured_gauge: None,
+            price_update: None,
+            attestor: None,
+            partner: Some(pda::partner("studio").0),
+            staking_pool: None,
+            launch_config: pda::launch_config(&mint).0,
+            creator_reputation: pda::creator_reputation(&payer).0,
+            system_program: solana_sdk::system_program::id(),
+        },
+        program::instruction::CreateFromTemplate {},
+    );
+    assert_eq!(built, expected);
+}
+
+#[test]
+fn keeper_instructions_match_anchor() {
+    let mint = Pubkey::new_unique();
+    let vault = Pubkey::new_unique();
+    let destination = Pubkey::new_unique();
+    let wallet = Pubkey::new_unique();
+
+    assert_eq!(
+        instructions::mint_emission(&mint, &vault),
+        anchor_ix(
+            program::accounts::MintEmission {
+                schedule: pda::emission_schedule(&mint).0,
+                mint,
+                vault,
+                token_program: spl_token_2022::id(),
+                mint_pause: pda::mint_pause(&mint).0,
+            },
+            program::instruction::MintEmission {},
+        )
+    );
+
+    assert_eq!(
+        instructions::crank_tax_holiday(&mint),
+        anchor_ix(
+            program::accounts::CrankTaxHoliday {
+                mint,
+                schedule: pda::tax_holiday_schedule(&mint).0,
+                token_program: spl_token_2022::id(),
+            },
+            program::instruction::CrankTaxHoliday {},
+        )
+    );
+
+    let treasury = pda::project_treasury(&mint).0;
+    assert_eq!(
+        instructions::execute_recurring_payment(&mint, 3, &vault, &destination),
+        anchor_ix(
+            program::accounts::ExecuteRecurringPayment {
+                treasury,
+                payment: pda::recurring_payment(&treasury, 3).0,
+                mint,
+                vault,
+                destination,
+                token_program: spl_token_2022::id(),
+                mint_pause: pda::mint_pause(&mint).0,
+            },
+            program::instruction::ExecuteRecurringPayment {},
+        )
+    );
+
+    assert_eq!(
+        instructions::claim_from_faucet(&wallet, &mint, &vault, &destination),
+        anchor_ix(
+            program::accounts::ClaimFromFaucet {
+                wallet,
+                faucet: pda::faucet(&mint).0,
+                claim: pda::faucet_claim(&mint, &wallet).0,
+                mint,
+                vault,
+                destination,
+                token_program: spl_token_2022::id(),
+                system_program: solana_sdk::system_program::id(),
+                mint_pause: pda::mint_pause(&mint).0,
+            },
+            program::instruction::ClaimFromFaucet {},
+        )
+    );
+
+    assert_eq!(
+        instructions::claim_fee_rebate(&wallet, &mint, &vault, &destination),
+        anchor_ix(
+            program::accounts::ClaimFeeRebate {
+                wallet,
+                mint,
+                exemption_list: pda::fee_exemption_list(&mint).0,
+                fee_vault: vault,
+                destination,
+                token_program: spl_token_2022::id(),
+                mint_pause: pda::mint_pause(&mint).0,
+            },
+            program::instruction::ClaimFeeRebate {},
+        )
+    );
+
+    assert_eq!(
+        instructions::transfer_with_fee(
+            &vault,
+            &mint,
+            &destination,
+            &wallet,
+            10,
+            6,
+            1,
+            true,
+            false
+        ),
+        anchor_ix(
+            program::accounts::TransferWithFee {
+                source: vault,
+                mint,
+                destination,
+                authority: wallet,
+                token_program: spl_token_2022::id(),
+                exemption_list: pda::fee_exemption_list(&mint).0,
+                mint_stats: Some(pda::mint_stats(&mint).0),
+                pool_registry: None,
+            },
+            program::instruction::TransferWithFee {
+                amount: 10,
+                decimals: 6,
+                fee: 1
+            },
+        )
+    );
+
+    assert_eq!(
+        instructions::withdraw_delegated_fees(&wallet, &mint, &destination),
+        anchor_ix(
+            program::accounts::WithdrawDelegatedFees {
+                signer: wallet,
+                mint,
+                fee_delegate: pda::fee_delegate(&mint).0,
+                destination,
+                token_program: spl_token_2022::id(),
+            },
+            program::instruction::WithdrawDelegatedFees {},
+        )
+    );
+
+    assert_eq!(
+        instructions::sync_creator_fees(&mint, &wallet),
+        anchor_ix(
+            program::accounts::SyncCreatorFees {
+                mint_stats: pda::mint_stats(&mint).0,
+                launch_config: pda::launch_config(&mint).0,
+                creator_reputation: pda::creator_reputation(&wallet).0,
+            },
+            program::instruction::SyncCreatorFees {},
+        )
+    );
+
+    assert_eq!(
+        instructions::retire_launch(&mint, &wallet),
+        anchor_ix(
+            program::accounts::RetireLaunch {
+                platform_config: pda::platform_config().0,
+                launch_config: pda::launch_config(&mint).0,
+                creator_reputation: pda::creator_reputation(&wallet).0,
+            },
+            program::instruction::RetireLaunch {},
+        )
+    );
+
+    assert_eq!(
+        instructions::rollover_epoch(&wallet, 9, true),
+        anchor_ix(
+            program::accounts::RolloverEpoch {
+                payer: wallet,
+                leaderboard: pda::leaderboard(9).0,
+                previous_leaderboard: Some(pda::leaderboard(8).0),
+                system_program: solana_sdk::system_program::id(),
+            },
+            program::instruction::RolloverEpoch {},
+        )
+    );
+
+    let stream = Pubkey::new_unique();
+    assert_eq!(
+        instructions::close_stream(&stream, &wallet, &mint, &vault, Some(destination)),
+        anchor_ix(
+            program::accounts::CloseStream {
+                sender: wallet,
+                stream,
+                mint,
+                vault,
+                sender_token_account: Some(destination),
+                token_program: spl_token_2022::id(),
+            },
+            program::instruction::CloseStream {},
+        )
+    );
+
+    let campaign = Pubkey::new_unique();
+    let mut expected = anchor_ix(
+        program::accounts::AirdropBatch {
+            campaign,
+            mint,
+            vault,
+            token_program: spl_token_2022::id(),
+            mint_pause: pda::mint_pause(&mint).0,
+        },
+        program::instruction::AirdropBatch {},
+    );
+    expected
+        .accounts
+        .push(solana_sdk::instruction::AccountMeta::new(
+            destination,
+            false,
+        ));
+    assert_eq!(
+        instructions::airdrop_batch(&campaign, &mint, &vault, &[destination]),
+        expected
+    );
+    assert_eq!(
+        instructions::claim_skipped_airdrop(&campaign, &mint, &vault, &destination, 3),
+        anchor_ix(
+            program::accounts::ClaimSkippedAirdrop {
+                campaign,
+                mint,
+                vault,
+                destination,
+                token_program: spl_token_2022::id(),
+                mint_pause: pda::mint_pause(&mint).0,
+            },
+            program::instruction::ClaimSkippedAirdrop { index: 3 },
+        )
+    );
+
+    // Only a wallet's associated token account can be skipped when frozen
+    let wallet = Pubkey::new_unique();
+    assert_eq!(
+        client::ASSOCIATED_TOKEN_PROGRAM_ID,
+        program::ASSOCIATED_TOKEN_PROGRAM_ID
+    );
+    assert_eq!(
+        pda::associated_token_account(&wallet, &mint),
+        Pubkey::find_program_address(
+            &[
+                wallet.as_ref(),
+                spl_token_2022::id().as_ref(),
+                mint.as_ref()
+            ],
+            &program::ASSOCIATED_TOKEN_PROGRAM_ID
+        )
+        .0
+    );
+}
+
+#[test]
+fn timelock_instructions_match_anchor() {
+    let mint = Pubkey::new_unique();
+    let admin = Pubkey::new_unique();
+    let timelock = pda::timelock(&mint).0;
+    let exemption_list = Pubkey::new_unique();
+    let op = anchor_ix(
+        program::accounts::UpdateFeeExemptionList {
+            authority: timelock,
+            exemption_list,
+        },
+        program::instruction::AddFeeExemption { wallet: admin },
+    );
+    let metas: Vec<program::TimelockAccountMeta> = op
+        .accounts
+        .iter()
+        .map(|meta| program::TimelockAccountMeta {
+            pubkey: meta.pubkey,
+            is_signer: meta.is_signer,
+            is_writable: meta.is_writable,
+        })
+        .collect();
+
+    assert_eq!(
+        instructions::queue_op(&admin, &mint, 4, &op),
+        anchor_ix(
+            program::accounts::QueueOp {
+                admin,
+                timelock,
+                queued_op: pda::timelock_op(&timelock, 4).0,
+                system_program: solana_sdk::system_program::id(),
+            },
+            program::instruction::QueueOp {
+                program_id: program::ID,
+                accounts: metas.clone(),
+                data: op.data.clone(),
+            },
+        )
+    );
+
+    // The client reads back what the program stores and executes it unsigned
+    let queued = program::QueuedOp {
+        timelock,
+        id: 4,
+        program_id: program::ID,
+        accounts: metas,
+        data: op.data.clone(),
+        eta: 100,
+        bump: 1,
+    };
+    let data = serialize(&queued, 8 + program::QueuedOp::INIT_SPACE);
+    let decoded = state::QueuedOp::decode(&data).unwrap();
+    let mut expected = anchor_ix(
+        program::accounts::ExecuteOp {
+            timelock,
+            queued_op: pda::timelock_op(&timelock, 4).0,
+            admin,
+            target_program: program::ID,
+        },
+        program::instruction::ExecuteOp {},
+    );
+    expected.accounts.extend([
+        solana_sdk::instruction::AccountMeta::new_readonly(timelock, false),
+        solana_sdk::instruction::AccountMeta::new(exemption_list, false),
+    ]);
+    assert_eq!(instructions::execute_op(&admin, &decoded), expected);
+}
+
+#[test]
+fn check_in_matches_anchor() {
+    let organizer = Pubkey::new_unique();
+    let attendee = Pubkey::new_unique();
+    let badge_account = Pubkey::new_unique();
+    let series = pda::event_series(&organizer, 2).0;
+    let event = pda::attendance_event(&series, 5).0;
+    assert_eq!(
+        series,
+        Pubkey::find_program_address(
+            &[
+                program::EVENT_SERIES_SEED,
+                organizer.as_ref(),
+                &2u64.to_le_bytes()
+            ],
+            &program::ID
+        )
+        .0
+    );
+    assert_eq!(
+        instructions::check_in_message(&event, &attendee),
+        program::check_in_message(&event, &attendee)
+    );
+    assert_eq!(
+        instructions::check_in(&attendee, &series, 5, &badge_account),
+        anchor_ix(
+            program::accounts::CheckIn {
+                attendee,
+                series,
+                event,
+                attendance: pda::attendance(&event, &attendee).0,
+                badge_mint: pda::attendance_badge(&event).0,
+                badge_account,
+                instructions: client::INSTRUCTIONS_SYSVAR_ID,
+                token_program: spl_token_2022::id(),
+                system_program: solana_sdk::system_program::id(),
+            },
+            program::instruction::CheckIn {},
+        )
+    );
+}
+
+#[test]
+fn claim_voucher_matches_anchor() {
+    let payer = Pubkey::new_unique();
+    let claim = instructions::VoucherClaim {
+        campaign: Pubkey::new_unique(),
+        mint: Pubkey::new_unique(),
+        vault: Pubkey::new_unique(),
+        wallet: Pubkey::new_unique(),
+        destination: Pubkey::new_unique(),
+        amount: 500,
+        nonce: 20_000,
+    };
+    assert_eq!(
+        instructions::voucher_claim_message(
+            &claim.campaign,
+            &claim.mint,
+            &claim.wallet,
+            500,
+            20_000
+        ),
+        program::voucher_claim_message(&claim.campaign, &claim.mint, &claim.wallet, 500, 20_000)
+    );
+    assert_eq!(
+        instructions::claim_voucher(&payer, &claim),
+        anchor_ix(
+            program::accounts::ClaimVoucher {
+                payer,
+                campaign: claim.campaign,
+                // 20_000 falls in the third page
+                nonces: Pubkey::find_program_address(
+                    &[
+                        program::VOUCHER_NONCES_SEED,
+                        claim.campaign.as_ref(),
+                        &2u64.to_le_bytes()
+                    ],
+                    &program::ID
+                )
+                .0,
+                mint: claim.mint,
+                vault: claim.vault,
+                destination: claim.destination,
+                instructions: client::INSTRUCTIONS_SYSVAR_ID,
+                token_program: spl_token_2022::id(),
+                mint_pause: pda::mint_pause(&claim.mint).0,
+                system_program: solana_sdk::system_program::id(),
+            },
+            program::instruction::ClaimVoucher {
+                wallet: claim.wallet,
+                amount: 500,
+                nonce: 20_000,
+            },
+        )
+    );
+
+    let mut page = program::VoucherNoncePage {
+        campaign: claim.campaign,
+        page: 2,
+        bits: [0; program::NONCE_PAGE_BYTES],
+        bump: 1,
+    };
+    page.consume(20_000).unwrap();
+    assert!(page.consume(20_000).is_err());
+    let data = serialize(&page, 8 + program::VoucherNoncePage::INIT_SPACE);
+    let decoded = state::VoucherNoncePage::decode(&data).unwrap();
+    assert!(decoded.is_used(20_000) && !decoded.is_used(20_001));
+}
+
+#[test]
+fn claim_gift_matches_anchor() {
+    let claim_key = Pubkey::new_unique();
+    let recipient = Pubkey::new_unique();
+    let gift = program::Gift {
+        sender: Pubkey::new_unique(),
+        claim_key,
+        mint: Pubkey::new_unique(),
+        vault: Pubkey::new_unique(),
+        amount: 990,
+        expires_at: 1_000,
+        bump: 3,
+    };
+    let address = pda::gift(&claim_key).0;
+    assert_eq!(
+        address,
+        Pubkey::find_program_address(&[program::GIFT_SEED, claim_key.as_ref()], &program::ID).0
+    );
+    let data = serialize(&gift, 8 + program::Gift::INIT_SPACE);
+    let decoded = state::Gift::decode(&data).unwrap();
+    assert_eq!((decoded.amount, decoded.expires_at), (990, 1_000));
+
+    assert_eq!(
+        instructions::claim_gift(&claim_key, &decoded, &recipient),
+        anchor_ix(
+            program::accounts::ClaimGift {
+                claim_key,
+                gift: address,
+                sender: gift.sender,
+                mint: gift.mint,
+                vault: gift.vault,
+                recipient_token_account: recipient,
+                token_program: spl_token_2022::id(),
+                mint_pause: pda::mint_pause(&gift.mint).0,
+            },
+            program::instruction::ClaimGift {},
+        )
+    );
+}
+
+#[test]
+fn wrap_instructions_match_anchor() {
+    let owner = Pubkey::new_unique();
+    let mint = Pubkey::new_unique();
+    let taxed = Pubkey::new_unique();
+    let wrapped = Pubkey::new_unique();
+    let wrapper = program::Wrapper {
+        mint,
+        wrapped_mint: pda::wrapped_mint(&mint).0,
+        vault: Pubkey::new_unique(),
+        bump: 2,
+    };
+    let address = pda::wrapper(&mint).0;
+    assert_eq!(
+        address,
+        Pubkey::find_program_address(&[program::WRAPPER_SEED, mint.as_ref()], &program::ID).0
+    );
+    assert_eq!(
+        wrapper.wrapped_mint,
+        Pubkey::find_program_address(&[program::WRAPPED_MINT_SEED, mint.as_ref()], &program::ID).0
+    );
+    let data = serialize(&wrapper, 8 + program::Wrapper::INIT_SPACE);
+    let decoded = state::Wrapper::decode(&data).unwrap();
+    assert_eq!(decoded.vault, wrapper.vault);
+
+    let accounts = || program::accounts::WrapTokens {
+        owner,
+        wrapper: address,
+        mint,
+        wrapped_mint: wrapper.wrapped_mint,
+        vault: wrapper.vault,
+        taxed_token_account: taxed,
+        wrapped_token_account: wrapped,
+        token_program: spl_token_2022::id(),
+        mint_pause: pda::mint_pause(&mint).0,
+    };
+    assert_eq!(
+        instructions::wrap(&owner, &decoded, &taxed, &wrapped, 500),
+        anchor_ix(accounts(), program::instruction::Wrap { amount: 500 })
+    );
+    assert_eq!(
+        instructions::unwrap(&owner, &decoded, &taxed, &wrapped, 400),
+        anchor_ix(accounts(), program::instruction::Unwrap { amount: 400 })
+    );
+}
+
+#[test]
+fn exchange_listing_instructions_match_anchor() {
+    let mint = Pubkey::new_unique();
+    let exchange = Pubkey::new_unique();
+    let authority = Pubkey::new_unique();
+    let wallets = [Pubkey::new_unique(), Pubkey::new_unique()];
+    let exemption_list = pda::fee_exemption_list(&mint).0;
+    let listing = pda::exchange_listing(&mint, &exchange).0;
+    assert_eq!(
+        listing,
+        Pubkey::find_program_address(
+            &[
+                program::EXCHANGE_LISTING_SEED,
+                mint.as_ref(),
+                exchange.as_ref()
+            ],
+            &program::ID
+        )
+        .0
+    );
+
+    assert_eq!(
+        instructions::register_exchange(&exchange, &mint, "Exchange", &wallets),
+        anchor_ix(
+            program::accounts::RegisterExchange {
+                exchange,
+                exemption_list,
+                listing,
+                system_program: solana_sdk::system_program::id(),
+            },
+            program::instruction::RegisterExchange {
+                name: "Exchange".to_string(),
+                wallets: wallets.to_vec(),
+            },
+        )
+    );
+    assert_eq!(
+        instructions::approve_exchange(&authority, &mint, &exchange),
+        anchor_ix(
+            program::accounts::ApproveExchange {
+                authority,
+                exemption_list,
+                listing,
+            },
+            program::instruction::ApproveExchange {},
+        )
+    );
+    assert_eq!(
+        instructions::revoke_exchange(&authority, &mint, &exchange),
+        anchor_ix(
+            program::accounts::RevokeExchange {
+                signer: authority,
+                exemption_list,
+                listing,
+                exchange,
+            },
+            program::instruction::RevokeExchange {},
+        )
+    );
+    for has_list in [true, false] {
+        assert_eq!(
+            instructions::get_declared_fees(&mint, has_list),
+            anchor_ix(
+                program::accounts::GetDeclaredFees {
+                    mint,
+                    exemption_list: has_list.then_some(exemption_list),
+                },
+                program::instruction::GetDeclaredFees {},
+            )
+        );
+    }
+
+    let view = program::DeclaredFeesView {
+        version: program::VIEW_VERSION,
+        mint,
+        transfer_fee_basis_points: 300,
+        maximum_fee: 1_000,
+        scheduled_fee: Some(program::DeclaredFee {
+            transfer_fee_basis_points: 100,
+            maximum_fee: 500,
+            epoch: 12,
+        }),
+        transfer_fee_config_authority: None,
+        exempt_wallets: Some(2),
+    };
+    let data = anchor_lang::AnchorSerialize::try_to_vec(&view).unwrap();
+    let decoded: client::views::DeclaredFeesView = client::views::decode(&data).unwrap();
+    assert_eq!(
+        decoded.scheduled_fee,
+        Some(client::views::DeclaredFee {
+            transfer_fee_basis_points: 100,
+            maximum_fee: 500,
+            epoch: 12,
+        })
+    );
+    assert_eq!(
+        (decoded.maximum_fee, decoded.exempt_wallets),
+        (1_000, Some(2))
+    );
+}
+
+#[test]
+fn compliance_instructions_match_anchor() {
+    let creator = Pubkey::new_unique();
+    let attestor = Pubkey::new_unique();
+    let mint = Pubkey::new_unique();
+    let wallet = Pubkey::new_unique();
+    let token_account = Pubkey::new_unique();
+    let hash = [7; 32];
+    let attestation = pda::wallet_attestation(&mint, &wallet).0;
+    assert_eq!(
+        attestation,
+        Pubkey::find_program_address(
+            &[
+                program::WALLET_ATTESTATION_SEED,
+                mint.as_ref(),
+                wallet.as_ref()
+            ],
+            &program::ID
+        )
+        .0
+    );
+
+    assert_eq!(
+        instructions::set_compliance_flags(
+            &creator,
+            &mint,
+            Some(state::ComplianceFlags {
+                attestor,
+                restricted_jurisdictions_hash: hash,
+                accreditation_required: true,
+            })
+        ),
+        anchor_ix(
+            program::accounts::SetComplianceFlags {
+                creator,
+                launch_config: pda::launch_config(&mint).0,
+            },
+            program::instruction::SetComplianceFlags {
+                flags: Some(program::ComplianceFlags {
+                    attestor,
+                    restricted_jurisdictions_hash: hash,
+                    accreditation_required: true,
+                }),
+            },
+        )
+    );
+    assert_eq!(
+        instructions::attest_wallet(&attestor, &mint, &wallet, hash, true, 1_800_000_000),
+        anchor_ix(
+            program::accounts::AttestWallet {
+                attestor,
+                launch_config: pda::launch_config(&mint).0,
+                attestation,
+                system_program: solana_sdk::system_program::id(),
+            },
+            program::instruction::AttestWallet {
+                wallet,
+                jurisdictions_hash: hash,
+                accredited: true,
+                expires_at: 1_800_000_000,
+            },
+        )
+    );
+
+    let accounts = |authority| program::accounts::ComplianceFreeze {
+        authority,
+        launch_config: pda::launch_config(&mint).0,
+        mint,
+        token_account,
+        wallet,
+        attestation,
+        freeze_order: pda::freeze_order(&mint, &token_account).0,
+        compliance_authority: pda::compliance_authority(&mint).0,
+        token_program: spl_token_2022::id(),
+    };
+    assert_eq!(
+        instructions::freeze_noncompliant(&creator, &mint, &token_account, &wallet),
+        anchor_ix(
+            accounts(creator),
+            program::instruction::FreezeNoncompliant {}
+        )
+    );
+    assert_eq!(
+        instructions::thaw_compliant(&wallet, &mint, &token_account, &wallet),
+        anchor_ix(accounts(wallet), program::instruction::ThawCompliant {})
+    );
+}
+
+#[test]
+fn freeze_order_instructions_match_anchor() {
+    let creator = Pubkey::new_unique();
+    let officer = Pubkey::new_unique();
+    let mint = Pubkey::new_unique();
+    let token_account = Pubkey::new_unique();
+    let hash = [9; 32];
+    let freeze_order = pda::freeze_order(&mint, &token_account).0;
+    assert_eq!(
+        freeze_order,
+        Pubkey::find_program_address(
+            &[
+                program::FREEZE_ORDER_SEED,
+                mint.as_ref(),
+                token_account.as_ref()
+            ],
+            &program::ID
+        )
+        .0
+    );
+
+    assert_eq!(
+        instructions::set_compliance_officer(&creator, &mint, Some(officer)),
+        anchor_ix(
+            program::accounts::SetComplianceFlags {
+                creator,
+                launch_config: pda::launch_config(&mint).0,
+            },
+            program::instruction::SetComplianceOfficer {
+                officer: Some(officer),
+            },
+        )
+    );
+    assert_eq!(
+        instructions::issue_freeze_order(&officer, &mint, &token_account, "ipfs://order", hash),
+        anchor_ix(
+            program::accounts::IssueFreezeOrder {
+                officer,
+                launch_config: pda::launch_config(&mint).0,
+                mint,
+                token_account,
+                freeze_order,
+                compliance_authority: pda::compliance_authority(&mint).0,
+                token_program: spl_token_2022::id(),
+                system_program: solana_sdk::system_program::id(),
+            },
+            program::instruction::IssueFreezeOrder {
+                justification_uri: "ipfs://order".to_string(),
+                justification_hash: hash,
+            },
+        )
+    );
+    assert_eq!(
+        instructions::lift_freeze_order(&officer, &mint, &token_account, "ipfs://lift", hash),
+        anchor_ix(
+            program::accounts::LiftFreezeOrder {
+                officer,
+                launch_config: pda::launch_config(&mint).0,
+                mint,
+                token_account,
+                freeze_order,
+                compliance_authority: pda::compliance_authority(&mint).0,
+                token_program: spl_token_2022::id(),
+            },
+            program::instruction::LiftFreezeOrder {
+                justification_uri: "ipfs://lift".to_string(),
+                justification_hash: hash,
+            },
+        )
+    );
+}
+
+#[test]
+fn release_allocation_matches_anchor() {
+    let mint = Pubkey::new_unique();
+    let bucket = program::AllocationBucket {
+        kind: program::AllocationKind::Team,
+        bps: 2_000,
+        amount: 200_000,
+        vault: Pubkey::new_unique(),
+        beneficiary: Pubkey::new_unique(),
+        vesting_start: 1_700_000_000,
+        vesting_end: 1_730_000_000,
+        released: 50_000,
+        revocable: true,
+        pending_beneficiary: Some(Pubkey::new_unique()),
+    };
+    let plan = program::AllocationPlan {
+        mint,
+        creator: Pubkey::new_unique(),
+        total_supply: 1_000_000,
+        created_at: 1_690_000_000,
+        buckets: vec![bucket.clone()],
+        bump: 249,
+    };
+    let decoded =
+        state::AllocationPlan::decode(&serialize(&plan, 8 + program::AllocationPlan::INIT_SPACE))
+            .unwrap();
+    let mirrored = &decoded.buckets[0];
+    assert_eq!(mirrored.kind, state::AllocationKind::Team);
+    assert_eq!(
+        (mirrored.amount, mirrored.released, mirrored.vesting_end),
+        (200_000, 50_000, bucket.vesting_end)
+    );
+    assert!(mirrored.revocable);
+    assert_eq!(mirrored.pending_beneficiary, bucket.pending_beneficiary);
+
+    assert_eq!(
+        instructions::release_allocation(&mint, 0, mirrored),
+        anchor_ix(
+            program::accounts::ReleaseAllocation {
+                plan: pda::allocation_plan(&mint).0,
+                mint,
+                vault: bucket.vault,
+                beneficiary: bucket.beneficiary,
+                token_program: spl_token_2022::id(),
+                mint_pause: pda::mint_pause(&mint).0,
+            },
+            program::instruction::ReleaseAllocation { index: 0 },
+        )
+    );
+
+    let destination = Pubkey::new_unique();
+    assert_eq!(
+        instructions::revoke_allocation(&plan.creator, &mint, 0, mirrored, &destination),
+        anchor_ix(
+            program::accounts::RevokeAllocation {
+                creator: plan.creator,
+                plan: pda::allocation_plan(&mint).0,
+                mint,
+                vault: bucket.vault,
+                destination,
+                token_program: spl_token_2022::id(),
+                mint_pause: pda::mint_pause(&mint).0,
+            },
+            program::instruction::RevokeAllocation { index: 0 },
+        )
+    );
+
+    let owner = Pubkey::new_unique();
+    let new_beneficiary = Pubkey::new_unique();
+    assert_eq!(
+        instructions::propose_allocation_beneficiary(&owner, &mint, 0, mirrored, &new_beneficiary),
+        anchor_ix(
+            program::accounts::ProposeAllocationBeneficiary {
+                owner,
+                plan: pda::allocation_plan(&mint).0,
+                beneficiary: bucket.beneficiary,
+                new_beneficiary,
+            },
+            program::instruction::ProposeAllocationBeneficiary { index: 0 },
+        )
+    );
+    assert_eq!(
+        instructions::accept_allocation_beneficiary(&plan.creator, &mint, 0, mirrored),
+        anchor_ix(
+            program::accounts::AcceptAllocationBeneficiary {
+                creator: plan.creator,
+                plan: pda::allocation_plan(&mint).0,
+                mint,
+                vault: bucket.vault,
+                beneficiary: bucket.beneficiary,
+                token_program: spl_token_2022::id(),
+                mint_pause: pda::mint_pause(&mint).0,
+            },
+            program::instruction::AcceptAllocationBeneficiary { index: 0 },
+        )
+    );
+
+    // Only locked buckets hand their vault and beneficiary to the close
+    let sale = state::AllocationBucket {
+        kind: state::AllocationKind::Sale,
+        ..mirrored.clone()
+    };
+    let mut expected = anchor_ix(
+        program::accounts::CloseAllocationPlan {
+            creator: plan.creator,
+            plan: pda::allocation_plan(&mint).0,
+            mint,
+            token_program: spl_token_2022::id(),
+        },
+        program::instruction::CloseAllocationPlan {},
+    );
+    expected.accounts.extend([
+        solana_sdk::instruction::AccountMeta::new(bucket.vault, false),
+        solana_sdk::instruction::AccountMeta::new(bucket.beneficiary, false),
+    ]);
+    assert_eq!(
+        instructions::close_allocation_plan(&plan.creator, &mint, &[mirrored.clone(), sale]),
+        expected
+    );
+}
+
+#[test]
+fn update_index_multiplier_matches_anchor() {
+    let mint = Pubkey::new_unique();
+    let price_update = Pubkey::new_unique();
+    let index_feed = pda::index_feed(&mint).0;
+    assert_eq!(
+        index_feed,
+        Pubkey::find_program_address(&[program::INDEX_FEED_SEED, mint.as_ref()], &program::ID).0
+    );
+    assert_eq!(state::MULTIPLIER_ONE, program::math::MULTIPLIER_ONE);
+
+    let feed = program::IndexFeed {
+        mint,
+        feed_id: [3; 32],
+        base_price: 150_000_000,
+        exponent: -8,
+        multiplier: 1_020_000_000,
+        max_deviation_bps: 200,
+        min_update_interval_seconds: 3_600,
+        max_price_age_seconds: 60,
+        max_confidence_bps: 100,
+        last_publish_time: 1_750_000_000,
+        updated_at: 1_750_000_005,
+        bump: 250,
+    };
+    let decoded =
+        state::IndexFeed::decode(&serialize(&feed, 8 + program::IndexFeed::INIT_SPACE)).unwrap();
+    assert_eq!(
+        (
+            decoded.multiplier,
+            decoded.exponent,
+            decoded.max_deviation_bps
+        ),
+        (1_020_000_000, -8, 200)
+    );
+
+    assert_eq!(
+        instructions::update_index_multiplier(&mint, &price_update),
+        anchor_ix(
+            program::accounts::UpdateIndexMultiplier {
+                index_feed,
+                mint,
+                price_update,
+                token_program: spl_token_2022::id(),
+            },
+            program::instruction::UpdateIndexMultiplier {},
+        )
+    );
+}
+
+#[test]
+fn interest_rate_instructions_match_anchor() {
+    let mint = Pubkey::new_unique();
+    let admin = Pubkey::new_unique();
+    let interest_rate_governor = pda::interest_rate_governor(&mint).0;
+    assert_eq!(
+        interest_rate_governor,
+        Pubkey::find_program_address(
+            &[program::INTEREST_RATE_GOVERNOR_SEED, mint.as_ref()],
+            &program::ID
+        )
+        .0
+    );
+
+    let governor = program::InterestRateGovernor {
+        mint,
+        admin,
+        bounds: program::InterestRateBounds {
+            min_rate_bps: -100,
+            max_rate_bps: 800,
+            min_update_interval_seconds: 86_400,
+        },
+        rate_bps: 500,
+        updated_at: 1_750_000_000,
+        bump: 251,
+    };
+    let decoded = state::InterestRateGovernor::decode(&serialize(
+        &governor,
+        8 + program::InterestRateGovernor::INIT_SPACE,
+    ))
+    .unwrap();
+    assert_eq!(
+        (decoded.admin, decoded.bounds.min_rate_bps, decoded.rate_bps),
+        (admin, -100, 500)
+    );
+
+    assert_eq!(
+        instructions::set_interest_rate(&admin, &mint, 650),
+        anchor_ix(
+            program::accounts::SetInterestRate {
+                admin,
+                interest_rate_governor,
+                mint,
+                token_program: spl_token_2022::id(),
+            },
+            program::instruction::SetInterestRate { rate_bps: 650 },
+        )
+    );
+    assert_eq!(
+        instructions::govern_interest_rate(
+            &mint,
+            1_500,
+            Some(state::InterestRateBounds {
+                min_rate_bps: 0,
+                max_rate_bps: 2_000,
+                min_update_interval_seconds: 3_600,
+            })
+        ),
+        anchor_ix(
+            program::accounts::GovernInterestRate {
+                timelock: pda::timelock(&mint).0,
+                interest_rate_governor,
+                mint,
+                token_program: spl_token_2022::id(),
+            },
+            program::instruction::GovernInterestRate {
+                rate_bps: 1_500,
+                bounds: Some(program::InterestRateBounds {
+                    min_rate_bps: 0,
+                    max_rate_bps: 2_000,
+                    min_update_interval_seconds: 3_600,
+                }),
+            },
+        )
+    );
+}
+
+#[test]
+fn schedule_launch_matches_anchor() {
+    let creator = Pubkey::new_unique();
+    let mint = Pubkey::new_unique();
+    let opens_at = 1_750_000_000;
+    let day = opens_at / program::SECONDS_PER_DAY;
+    let calendar = pda::launch_calendar(day).0;
+    assert_eq!(
+        calendar,
+        Pubkey::find_program_address(
+            &[program::LAUNCH_CALENDAR_SEED, &day.to_le_bytes()],
+            &program::ID
+        )
+        .0
+    );
+
+    assert_eq!(
+        instructions::schedule_launch(&creator, &mint, opens_at),
+        anchor_ix(
+            program::accounts::ScheduleLaunch {
+                creator,
+                launch_config: pda::launch_config(&mint).0,
+                mint_pause: pda::mint_pause(&mint).0,
+                calendar,
+                system_program: solana_sdk::system_program::id(),
+            },
+            program::instruction::ScheduleLaunch { opens_at },
+        )
+    );
+
+    let page = program::LaunchCalendar {
+        day,
+        launches: vec![program::ScheduledLaunch {
+            mint,
+            creator,
+            opens_at,
+        }],
+        bump: 250,
+    };
+    let decoded =
+        state::LaunchCalendar::decode(&serialize(&page, 8 + program::LaunchCalendar::INIT_SPACE))
+            .unwrap();
+    assert_eq!(decoded.day, day);
+    assert_eq!(
+        decoded.launches,
+        vec![state::ScheduledLaunch {
+            mint,
+            creator,
+            opens_at
+        }]
+    );
+}
+
+#[test]
+fn staking_instructions_match_anchor() {
+    let owner = Pubkey::new_unique();
+    let platform_mint = Pubkey::new_unique();
+    let vault = Pubkey::new_unique();
+    let owner_token_account = Pubkey::new_unique();
+    let staking_pool = pda::platform_staking().0;
+    let position = pda::stake_position(&owner).0;
+    assert_eq!(
+        position,
+        Pubkey::find_program_address(
+            &[program::STAKE_POSITION_SEED, owner.as_ref()],
+            &program::ID
+        )
+        .0
+    );
+
+    assert_eq!(
+        instructions::stake_platform_tokens(
+            &owner,
+            &platform_mint,
+            &vault,
+            &owner_token_account,
+            500
+        ),
+        anchor_ix(
+            program::accounts::StakePlatformTokens {
+                owner,
+                platform_config: pda::platform_config().0,
+                staking_pool,
+                position,
+                mint: platform_mint,
+                vault,
+                owner_token_account,
+                token_program: spl_token_2022::id(),
+                system_program: solana_sdk::system_program::id(),
+                mint_pause: pda::mint_pause(&platform_mint).0,
+            },
+            program::instruction::StakePlatformTokens { amount: 500 },
+        )
+    );
+    assert_eq!(
+        instructions::unstake_platform_tokens(
+            &owner,
+            &platform_mint,
+            &vault,
+            &owner_token_account,
+            200
+        ),
+        anchor_ix(
+            program::accounts::UnstakePlatformTokens {
+                owner,
+                platform_config: pda::platform_config().0,
+                staking_pool,
+                position,
+                mint: platform_mint,
+                vault,
+                owner_token_account,
+                token_program: spl_token_2022::id(),
+            },
+            program::instruction::UnstakePlatformTokens { amount: 200 },
+        )
+    );
+    assert_eq!(
+        instructions::claim_staking_rewards(&owner),
+        anchor_ix(
+            program::accounts::ClaimStakingRewards {
+                owner,
+                staking_pool,
+                position,
+            },
+            program::instruction::ClaimStakingRewards {},
+        )
+    );
+    assert_eq!(
+        instructions::activate_stake(&owner),
+        anchor_ix(
+            program::accounts::ActivateStake {
+                staking_pool,
+                position,
+            },
+            program::instruction::ActivateStake {},
+        )
+    );
+
+    let pool = program::PlatformStaking {
+        vault,
+        ledger: program::StakeLedger {
+            total_staked: 1_000,
+            warming_stake: 300,
+            reward_per_share: 3 * program::math::REWARD_PRECISION,
+            pending_rewards: 40,
+            reward_dust: 7,
+            rolled_epoch: 9,
+            total_rewards: 3_000,
+        },
+        bump: 251,
+    };
+    let decoded =
+        state::PlatformStaking::decode(&serialize(&pool, 8 + program::PlatformStaking::INIT_SPACE))
+            .unwrap();
+    assert_eq!(
+        (
+            decoded.ledger.warming_stake,
+            decoded.ledger.reward_per_share,
+            decoded.ledger.pending_rewards,
+            decoded.bump
+        ),
+        (300, pool.ledger.reward_per_share, 40, 251)
+    );
+}
+
+#[test]
+fn metadata_instructions_match_anchor() {
+    let creator = Pubkey::new_unique();
+    let mint = Pubkey::new_unique();
+    let mint_authority = Pubkey::new_unique();
+    let launch_config = pda::launch_config(&mint).0;
+
+    assert_eq!(
+        instructions::init_token_metadata(
+            &creator,
+            &mint,
+            &mint_authority,
+            &state::TokenMetadataParams {
+                name: "Taxed Token".to_string(),
+                symbol: "TAX".to_string(),
+                uri: "https://example.com/tax.json".to_string(),
+            }
+        ),
+        anchor_ix(
+            program::accounts::InitTokenMetadata {
+                creator,
+                launch_config,
+                mint,
+                mint_authority,
+                symbol_index: pda::symbol_index("TAX").0,
+                token_program: spl_token_2022::id(),
+                system_program: solana_sdk::system_program::id(),
+            },
+            program::instruction::InitTokenMetadata {
+                metadata: program::TokenMetadataParams {
+                    name: "Taxed Token".to_string(),
+                    symbol: "TAX".to_string(),
+                    uri: "https://example.com/tax.json".to_string(),
+                },
+            },
+        )
+    );
+    assert_eq!(
+        instructions::init_metaplex_metadata(&creator, &mint, &mint_authority),
+        anchor_ix(
+            program::accounts::InitMetaplexMetadata {
+                creator,
+                launch_config,
+                mint,
+                mint_authority,
+                metaplex_metadata: pda::metaplex_metadata(&mint).0,
+                metaplex_program: program::METAPLEX_METADATA_PROGRAM_ID,
+                system_program: solana_sdk::system_program::id(),
+            },
+            program::instruction::InitMetaplexMetadata {},
+        )
+    );
+    assert_eq!(
+        instructions::lock_metadata(&creator, &mint),
+        anchor_ix(
+            program::accounts::LockMetadata {
+                creator,
+                launch_config,
+                mint,
+                token_program: spl_token_2022::id(),
+                metaplex_metadata: pda::metaplex_metadata(&mint).0,
+                metaplex_program: program::METAPLEX_METADATA_PROGRAM_ID,
+            },
+            program::instruction::LockMetadata {},
+        )
+    );
+}
+
+#[test]
+fn dividend_instructions_match_anchor() {
+    let creator = Pubkey::new_unique();
+    let owner = Pubkey::new_unique();
+    let mint = Pubkey::new_unique();
+    let owner_account = Pubkey::new_unique();
+    let dividend_pool = pda::dividend_pool(&mint).0;
+    let position = pda::dividend_position(&mint, &owner).0;
+    assert_eq!(
+        position,
+        Pubkey::find_program_address(
+            &[
+                program::DIVIDEND_POSITION_SEED,
+                mint.as_ref(),
+                owner.as_ref()
+            ],
+            &program::ID
+        )
+        .0
+    );
+
+    let program_pool = program::DividendPool {
+        mint,
+        reward_mint: Pubkey::new_unique(),
+        stake_vault: Pubkey::new_unique(),
+        reward_vault: Pubkey::new_unique(),
+        ledger: program::StakeLedger {
+            pending_rewards: 900,
+            ..Default::default()
+        },
+        bump: 250,
+    };
+    let pool = state::DividendPool::decode(&serialize(
+        &program_pool,
+        8 + program::DividendPool::INIT_SPACE,
+    ))
+    .unwrap();
+    assert_eq!(
+        (pool.reward_vault, pool.ledger.pending_rewards, pool.bump),
+        (program_pool.reward_vault, 900, 250)
+    );
+
+    assert_eq!(
+        instructions::init_dividend_pool(
+            &creator,
+            &mint,
+            &pool.reward_mint,
+            &pool.stake_vault,
+            &pool.reward_vault
+        ),
+        anchor_ix(
+            program::accounts::InitDividendPool {
+                creator,
+                launch_config: pda::launch_config(&mint).0,
+                dividend_pool,
+                reward_mint: pool.reward_mint,
+                stake_vault: pool.stake_vault,
+                reward_vault: pool.reward_vault,
+                system_program: solana_sdk::system_program::id(),
+            },
+            program::instruction::InitDividendPool {},
+        )
+    );
+    assert_eq!(
+        instructions::fund_dividends(&creator, &pool, &owner_account, 700),
+        anchor_ix(
+            program::accounts::FundDividends {
+                funder: creator,
+                dividend_pool,
+                reward_mint: pool.reward_mint,
+                reward_vault: pool.reward_vault,
+                funder_token_account: owner_account,
+                token_program: spl_token_2022::id(),
+            },
+            program::instruction::FundDividends { amount: 700 },
+        )
+    );
+    assert_eq!(
+        instructions::stake_for_dividends(&owner, &pool, &owner_account, 500),
+        anchor_ix(
+            program::accounts::StakeForDividends {
+                owner,
+                dividend_pool,
+                position,
+                mint,
+                stake_vault: pool.stake_vault,
+                owner_token_account: owner_account,
+                token_program: spl_token_2022::id(),
+                system_program: solana_sdk::system_program::id(),
+                mint_pause: pda::mint_pause(&mint).0,
+            },
+            program::instruction::StakeForDividends { amount: 500 },
+        )
+    );
+    assert_eq!(
+        instructions::unstake_from_dividends(&owner, &pool, &owner_account, 200),
+        anchor_ix(
+            program::accounts::UnstakeFromDividends {
+                owner,
+                dividend_pool,
+                position,
+                mint,
+                stake_vault: pool.stake_vault,
+                owner_token_account: owner_account,
+                token_program: spl_token_2022::id(),
+            },
+            program::instruction::UnstakeFromDividends { amount: 200 },
+        )
+    );
+    assert_eq!(
+        instructions::claim_dividends(&owner, &pool, &owner_account),
+        anchor_ix(
+            program::accounts::ClaimDividends {
+                owner,
+                dividend_pool,
+                position,
+                reward_mint: pool.reward_mint,
+                reward_vault: pool.reward_vault,
+                owner_reward_account: owner_account,
+                token_program: spl_token_2022::id(),
+            },
+            program::instruction::ClaimDividends {},
+        )
+    );
+    assert_eq!(
+        instructions::activate_dividend_stake(&mint, &owner),
+        anchor_ix(
+            program::accounts::ActivateDividendStake {
+                dividend_pool,
+                position,
+            },
+            program::instruction::ActivateDividendStake {},
+        )
+    );
+}
+
+#[test]
+fn vesting_view_round_trips() {
+    let mint = Pubkey::new_unique();
+    let beneficiary = Pubkey::new_unique();
+    let vault = Pubkey::new_unique();
+    assert_eq!(
+        instructions::get_vesting_state(&mint),
+        anchor_ix(
+            program::accounts::GetVestingState {
+                plan: pda::allocation_plan(&mint).0,
+            },
+            program::instruction::GetVestingState {},
+        )
+    );
+
+    let view = program::AllocationPlanView {
+        version: program::VIEW_VERSION,
+        mint,
+        creator: beneficiary,
+        total_supply: 1_000_000,
+        buckets: vec![program::AllocationBucketView {
+            kind: program::AllocationKind::Team,
+            beneficiary,
+            vault,
+            amount: 200_000,
+            vesting_start: 100,
+            vesting_end: 1_100,
+            unlocked: 50_000,
+            released: 20_000,
+            releasable: 30_000,
+            revocable: true,
+        }],
+    };
+    let mut data = anchor_lang::AnchorSerialize::try_to_vec(&view).unwrap();
+    // A newer view only appends fields
+    data.push(7);
+    let decoded: client::views::AllocationPlanView = client::views::decode(&data).unwrap();
+    assert_eq!(decoded.version, client::views::VIEW_VERSION);
+    assert_eq!(
+        decoded.buckets,
+        vec![client::views::AllocationBucketView {
+            kind: client::state::AllocationKind::Team,
+            beneficiary,
+            vault,
+            amount: 200_000,
+            vesting_start: 100,
+            vesting_end: 1_100,
+            unlocked: 50_000,
+            released: 20_000,
+            releasable: 30_000,
+            revocable: true,
+        }]
+    );
+}
+
+#[test]
+fn mint_health_view_round_trips() {
+    let mint = Pubkey::new_unique();
+    let key = Pubkey::new_unique();
+    assert_eq!(
+        instructions::get_mint_health(&mint),
+        anchor_ix(
+            program::accounts::GetMintHealth { mint },
+            program::instruction::GetMintHealth {},
+        )
+    );
+
+    let view = program::MintHealthView {
+        version: program::VIEW_VERSION,
+        mint,
+        mint_authority: program::AuthorityHolder::EmissionSchedule,
+        freeze_authority: program::AuthorityHolder::Renounced,
+        fee_mutable: true,
+        transfer_fee_config_authority: Some(program::AuthorityHolder::Key(key)),
+        withdraw_withheld_authority: Some(program::AuthorityHolder::Timelock),
+        transfer_fee_basis_points: Some(250),
+        transfer_hook_program: None,
+        permanent_delegate: Some(key),
+    };
+    let mut data = anchor_lang::AnchorSerialize::try_to_vec(&view).unwrap();
+    // A newer view only appends fields
+    data.push(7);
+    let decoded: client::views::MintHealthView = client::views::decode(&data).unwrap();
+    assert_eq!(decoded.version, client::views::VIEW_VERSION);
+    assert_eq!(
+        decoded.mint_authority,
+        client::views::AuthorityHolder::EmissionSchedule
+    );
+    assert_eq!(
+        decoded.transfer_fee_config_authority,
+        Some(client::views::AuthorityHolder::Key(key))
+    );
+    assert_eq!(
+        (
+            decoded.fee_mutable,
+            decoded.transfer_fee_basis_points,
+            decoded.permanent_delegate
+        ),
+        (true, Some(250), Some(key))
+    );
+}
+
+#[test]
+fn decoders_read_program_accounts() {
+    let mint = Pubkey::new_unique();
+    let creator = Pubkey::new_unique();
+
+    let config = program::LaunchConfig {
+        mint,
+        creator,
+        created_at: 1_700_000_000,
+        decimals: 6,
+        kind: program::TokenKind::Taxed {
+            transfer_fee_basis_points: 250,
+            maximum_fee: 1_000,
+        },
+        verified: true,
+        verified_at: 1_700_000_100,
+        attestation_uri: "https://example.com/a".to_string(),
+        featured: false,
+        featured_epoch: 0,
+        ntt_manager: Some(creator),
+        ntt_mode: Some(program::NttMode::Burning),
+        platform_reviewed: true,
+        partner: Some(mint),
+        compliance: Some(program::ComplianceFlags {
+            attestor: creator,
+            restricted_jurisdictions_hash: [3; 32],
+            accreditation_required: true,
+        }),
+        compliance_officer: Some(creator),
+        metadata_locked: true,
+        unbonded_active: true,
+        bump: 254,
+    };
+    let decoded =
+        state::LaunchConfig::decode(&serialize(&config, 8 + program::LaunchConfig::INIT_SPACE))
+            .unwrap();
+    assert_eq!(decoded.mint, mint);
+    assert_eq!(
+        decoded.kind,
+        state::TokenKind::Taxed {
+            transfer_fee_basis_points: 250,
+            maximum_fee: 1_000
+        }
+    );
+    assert_eq!(decoded.attestation_uri, config.attestation_uri);
+    assert_eq!(decoded.ntt_mode, Some(state::NttMode::Burning));
+    assert!(decoded.platform_reviewed);
+    assert_eq!(decoded.partner, Some(mint));
+    assert_eq!(
+        decoded.compliance,
+        Some(state::ComplianceFlags {
+            attestor: creator,
+            restricted_jurisdictions_hash: [3; 32],
+            accreditation_required: true,
+        })
+    );
+    assert!(decoded.metadata_locked && decoded.unbonded_active);
+    assert_eq!(decoded.bump, 254);
+
+    let schedule = program::EmissionSchedule {
+        mint,
+        vault: creator,
+        start_time: 10,
+        period_seconds: 60,
+        initial_per_period: 1_000,
+        curve: program::EmissionCurve::LinearDecay { total_periods: 12 },
+        minted: 500,
+        bump: 7,
+    };
+    let decoded = state::EmissionSchedule::decode(&serialize(
+        &schedule,
+        8 + program::EmissionSchedule::INIT_SPACE,
+    ))
+    .unwrap();
+    assert_eq!(
+        decoded.curve,
+        state::EmissionCurve::LinearDecay { total_periods: 12 }
+    );
+    assert_eq!((decoded.minted, decoded.bump), (500, 7));
+
+    let stats = program::MintStats {
+        mint,
+        transfers: 4,
+        volume: 5,
+        fees: 6,
+        last_transfer_slot: 7,
+        epoch: 8,
+        epoch_volume: 9,
+        epoch_fees: 10,
+        buy_volume: 11,
+        sell_volume: 12,
+        fees_synced: 6,
+        bump: 13,
+    };
+    let decoded =
+        state::MintStats::decode(&serialize(&stats, 8 + program::MintStats::INIT_SPACE)).unwrap();
+    assert_eq!(
+        (decoded.sell_volume, decoded.fees_synced, decoded.bump),
+        (12, 6, 13)
+    );
+
+    let reputation = program::CreatorReputation {
+        creator,
+        launches: 2,
+        bonds_refunded: 1,
+        disputes: 0,
+        disputes_upheld: 0,
+        slashes: 0,
+        score: 14,
+        active_launches: 1,
+        last_launch_at: 1_700_000_000,
+        verified_launches: 1,
+        total_fees: 6,
+        recent_launches: vec![mint, creator],
+        bump: 9,
+    };
+    let decoded = state::CreatorReputation::decode(&serialize(
+        &reputation,
+        8 + program::CreatorReputation::INIT_SPACE,
+    ))
+    .unwrap();
+    assert_eq!(decoded.recent_launches, vec![mint, creator]);
+    assert_eq!(
+        (decoded.total_fees, decoded.verified_launches, decoded.bump),
+        (6, 1, 9)
+    );
+
+    // A stream's bytes are not a launch config
+    let stream = program::Stream {
+        sender: creator,
+        recipient: creator,
+        mint,
+        vault: mint,
+        stream_id: 1,
+        deposited: 100,
+        withdrawn: 0,
+        start_time: 0,
+        end_time: 10,
+        bump: 1,
+    };
+    let data = serialize(&stream, 8 + program::Stream::INIT_SPACE);
+    assert_eq!(state::Stream::decode(&data).unwrap().deposited, 100);
+    assert!(state::LaunchConfig::decode(&data).is_err());
+}
+
+#[test]
+fn event_discriminators_are_pinned() {
+    macro_rules! check {
+        ($($ty:ident),* $(,)?) => {
+            $(
+                assert_eq!(program::$ty::DISCRIMINATOR, events::$ty::DISCRIMINATOR, stringify!($ty));
+                // Pinned to what Anchor derived before they were made explicit
+                assert_eq!(program::$ty::DISCRIMINATOR, client::event_discriminator(stringify!($ty)), stringify!($ty));
+            )*
+        };
+    }
+    check!(
+        StreamCreated,
+        StreamWithdrawn,
+        StreamCancelled,
+        StreamClosed,
+        OtcOfferCreated,
+        OtcOfferFilled,
+        OtcOfferCancelled,
+        AirdropBatchSent,
+        AirdropSwept,
+        AirdropClosed,
+        PointsAwarded,
+        PointsRedeemed,
+        QuestCompleted,
+        SnapshotPosted,
+        ProposalCreated,
+        VoteCast,
+        ProposalFinalized,
+        VoteLockCreated,
+        GaugeVoteCast,
+        LaunchFeatured,
+        TreasuryDisbursed,
+        RecurringPaymentExecuted,
+        NttConfigured,
+        EpochRolledOver,
+        MintPaused,
+        MintUnpaused,
+        AccountExpanded,
+        PoolRegistered,
+        PoolDeregistered,
+        EmissionMinted,
+        CreatorBondPosted,
+        CreatorBondRefunded,
+        LaunchRetired,
+        LaunchFlagged,
+        DisputeResolved,
+        BondSlashed,
+        TokenVerified,
+        VerificationRevoked,
+        AuditPublished,
+        InsuranceClaimPaid,
+        ExemptTransferRecorded,
+        FeeRebateClaimed,
+        DelegatedFeesWithdrawn,
+        FeeDestinationsQueued,
+        OpQueued,
+        OpExecuted,
+        OpCancelled,
+        CheckedIn,
+        VoucherClaimed,
+        GiftCreated,
+        GiftClaimed,
+        GiftCancelled,
+        WrapperCreated,
+        Wrapped,
+        Unwrapped,
+        ExchangeRegistered,
+        ExchangeApproved,
+        ExchangeRevoked,
+        PartnerRegistered,
+        PartnerUpdated,
+        PartnerFeesWithdrawn,
+        PlatformTokensStaked,
+        PlatformTokensUnstaked,
+        StakingRewardsDistributed,
+        StakingRewardsClaimed,
+        LaunchScheduled,
+        AllocationPlanCreated,
+        AllocationReleased,
+        ComplianceFlagsUpdated,
+        WalletAttested,
+        ComplianceFrozen,
+        ComplianceThawed,
+        ComplianceOfficerSet,
+        FreezeOrderIssued,
+        FreezeOrderLifted,
+        IndexFeedInitialized,
+        IndexMultiplierUpdated,
+        InterestRateGovernorInitialized,
+        InterestRateUpdated,
+        AirdropRecipientSkipped,
+        AllocationPlanClosed,
+        AirdropSkippedClaimed,
+        AllocationRevoked,
+        AllocationBeneficiaryProposed,
+        AllocationBeneficiaryChanged,
+        DividendPoolCreated,
+        DividendsFunded,
+        DividendStaked,
+        DividendUnstaked,
+        DividendsDistributed,
+        DividendsClaimed,
+        TokenMetadataInitialized,
+        MetaplexMetadataCreated,
+        MetadataLocked,
+    );
+}
+
+#[test]
+fn events_parse_from_logs() {
+    let mint = Pubkey::new_unique();
+    let stream = Pubkey::new_unique();
+    let logged = |data: Vec<u8>| format!("Program data: {}", STANDARD.encode(data));
+    let closed = logged(
+        program::StreamClosed {
+            version: program::EVENT_VERSION,
+            stream,
+        }
+        .data(),
+    );
+    let minted = logged(
+        program::EmissionMinted {
+            version: program::EVENT_VERSION,
+            mint,
+            amount: 5,
+            total_minted: 10,
+        }
+        .data(),
+    );
+    let launchpad = program::ID.to_string();
+    let token = spl_token_2022::id().to_string();
+    let logs = vec![
+        format!("Program {launchpad} invoke [1]"),
+        "Program log: Instruction: CloseStream".to_string(),
+        format!("Program {token} invoke [2]"),
+        // Logged by the token program, not the launchpad
+        closed.clone(),
+        format!("Program {token} success"),
+        closed,
+        format!("Program {launchpad} consumed 1000 of 200000 compute units"),
+        format!("Program {launchpad} success"),
+        minted.clone(),
+        format!("Program {launchpad} invoke [1]"),
+        minted,
+        format!("Program {launchpad} failed: custom program error: 0x1770"),
+    ];
+
+    let parsed = events::parse_logs(&logs).unwrap();
+    assert_eq!(
+        parsed,
+        vec![
+            events::LaunchpadEvent::StreamClosed(events::StreamClosed { version: 1, stream }),
+            events::LaunchpadEvent::EmissionMinted(events::EmissionMinted {
+                version: 1,
+                mint,
+                amount: 5,
+                total_minted: 10,
+            }),
+        ]
+    );
+
+    // A newer version appending a field still decodes, and unknown events are skipped
+    let mut newer = program::StreamClosed { version: 2, stream }.data();
+    newer.extend_from_slice(&[1, 2, 3]);
+    assert_eq!(
+        events::decode(&newer).unwrap(),
+        Some(events::LaunchpadEvent::StreamClosed(events::StreamClosed {
+            version: 2,
+            stream
+        }))
+    );
+    assert_eq!(events::decode(&[0; 16]).unwrap(), None);
+}
@@ -0,0 +1,83 @@
+//! Compute-unit regression guards. Each test prints what the instruction consumed and fails when
+//! it crosses its budget, so growth in the creation flows shows up before a launch runs out of
+//! compute on chain.
+
+use solana_sdk::{signature::Keypair, signer::Signer};
+use taxed_token_launchpad::test_utils::*;
+
+/// Budgets per instruction. The runtime default is 200_000 per instruction; creation has to leave
+/// room for the bond, faucet and featured-gauge accounts riding along in the same transaction.
+///
+/// A budget is the instruction's consumption on the SBF build plus HEADROOM_PERCENT, rounded up
+/// to the next 1_000. These values are provisional ceilings that haven't been measured yet: the
+/// first `anchor build` run of this suite prints each instruction's consumption and the budget
+/// it implies, and fails while a budget sits further from it than the headroom allows, so the
+/// constants have to be replaced with the printed values (and the run recorded here) before it
+/// passes.
+const CREATE_TAXED_TOKEN_BUDGET: u64 = 80_000;
+const CREATE_SOULBOUND_TOKEN_BUDGET: u64 = 80_000;
+const TRANSFER_WITH_FEE_BUDGET: u64 = 30_000;
+
+/// Room left above measured consumption for growth before a budget has to be revisited.
+const HEADROOM_PERCENT: u64 = 20;
+
+/// The budget `consumed` implies: HEADROOM_PERCENT on top, rounded up to the next 1_000.
+fn budget_for(consumed: u64) -> u64 {
+    (consumed * (100 + HEADROOM_PERCENT) / 100).div_ceil(1_000) * 1_000
+}
+
+fn check_budget(name: &str, consumed: u64, budget: u64) {
+    let measured_budget = budget_for(consumed);
+    println!("{name}: {consumed} CU (budget {budget}, {measured_budget} from this run)");
+    assert!(consumed <= budget, "{name} used {consumed} CU, over its {budget} CU budget");
+    // A budget well above the implied one no longer guards anything
+    assert!(
+        budget <= budget_for(measured_budget),
+        "{name} budget {budget} CU is stale; set it to {measured_budget} CU from this run"
+    );
+}
+
+#[tokio::test]
+#[cfg_attr(not(feature = "sbf-tests"), ignore = "needs the SBF build; run `anchor build`, then test with `--features sbf-tests`")]
+async fn create_taxed_token_stays_under_budget() {
+    let admin = Keypair::new();
+    let mut context = program_test(&admin.pubkey()).start_with_context().await;
+    let payer = context.payer.insecure_clone();
+    let mint = Keypair::new();
+
+    let ix = create_taxed_token_ix(&admin.pubkey(), &payer.pubkey(), &mint.pubkey(), 6, 250, 1_000_000);
+    let consumed = send_metered(&mut context, &[ix], &[&mint]).await.unwrap();
+    check_budget("create_taxed_token", consumed, CREATE_TAXED_TOKEN_BUDGET);
+}
+
+#[tokio::test]
+#[cfg_attr(not(feature = "sbf-tests"), ignore = "needs the SBF build; run `anchor build`, then test with `--features sbf-tests`")]
+async fn create_soulbound_token_stays_under_budget() {
+    let admin = Keypair::new();
+    let mut context = program_test(&admin.pubkey()).start_with_context().await;
+    let payer = context.payer.insecure_clone();
+    let mint = Keypair::new();
+
+    let ix = create_soulbound_token_ix(&admin.pubkey(), &payer.pubkey(), &mint.pubkey(), 0);
+    let consumed = send_metered(&mut context, &[ix], &[&mint]).await.unwrap();
+    check_budget("create_soulbound_token", consumed, CREATE_SOULBOUND_TOKEN_BUDGET);
+}
+
+#[tokio::test]
+#[cfg_attr(not(feature = "sbf-tests"), ignore = "needs the SBF build; run `anchor build`, then test with `--features sbf-tests`")]
+async fn transfer_with_fee_stays_under_budget() {
+    let admin = Keypair::new();
+    let mut context = program_test(&admin.pubkey()).start_with_context().await;
+    let payer = context.payer.insecure_clone();
+    let mint = Keypair::new();
+
+    let ix = create_taxed_token_ix(&admin.pubkey(), &payer.pubkey(), &mint.pubkey(), 6, 250, 1_000_000);
+    send(&mut context, &[ix], &[&mint]).await.unwrap();
+    let source = create_token_account(&mut context, &mint.pubkey(), &payer.pubkey()).await;
+    let destination = create_token_account(&mut context, &mint.pubkey(), &Keypair::new().pubkey()).await;
+    mint_to(&mut context, &mint.pubkey(), &source, 1_000_000).await;
+
+    let ix = transfer_with_fee_ix(&source, &mint.pubkey(), &destination, &payer.pubkey(), 100_000, 6, 2_500);
+    let consumed = send_metered(&mut context, &[ix], &[]).await.unwrap();
+    check_budget("transfer_with_fee", consumed, TRANSFER_WITH_FEE_BUDGET);
+}
@@ -0,0 +1,326 @@
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_spl::token_2022_extensions::spl_token_metadata_interface::state::TokenMetadata;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+};
+use spl_token_2022::extension::{
+    non_transferable::NonTransferable, transfer_fee::TransferFeeAmount, BaseStateWithExtensions,
+};
+use taxed_token_launchpad::test_utils::*;
+use taxed_token_launchpad::{
+    accounts, instruction, CreatorReputation, Dispute, DisputeStatus, LaunchConfig, MyError, SymbolIndex, TokenKind,
+    TokenMetadataParams, BATCH_MINT_SEED, CREATOR_BOND_SEED, DISPUTE_REFLAG_COOLDOWN_SECONDS, DISPUTE_SEED,
+    LAUNCH_CONFIG_SEED, METAPLEX_METADATA_PROGRAM_ID, METAPLEX_METADATA_SEED, SYMBOL_INDEX_SEED,
+};
+
+#[tokio::test]
+#[cfg_attr(not(feature = "sbf-tests"), ignore = "needs the SBF build; run `anchor build`, then test with `--features sbf-tests`")]
+async fn taxed_token_fee_is_withheld_and_harvested() {
+    let admin = Keypair::new();
+    let mut context = program_test(&admin.pubkey()).start_with_context().await;
+    let payer = context.payer.insecure_clone();
+    let mint = Keypair::new();
+
+    let ix = create_taxed_token_ix(&admin.pubkey(), &payer.pubkey(), &mint.pubkey(), 6, 250, 1_000_000);
+    send(&mut context, &[ix], &[&mint]).await.unwrap();
+
+    let fee_config = get_transfer_fee_config(&mut context, &mint.pubkey()).await;
+    assert_eq!(u16::from(fee_config.newer_transfer_fee.transfer_fee_basis_points), 250);
+    assert_eq!(u64::from(fee_config.newer_transfer_fee.maximum_fee), 1_000_000);
+    assert_eq!(Option::from(fee_config.withdraw_withheld_authority), Some(payer.pubkey()));
+
+    let recipient = Keypair::new();
+    let source = create_token_account(&mut context, &mint.pubkey(), &payer.pubkey()).await;
+    let destination = create_token_account(&mut context, &mint.pubkey(), &recipient.pubkey()).await;
+    mint_to(&mut context, &mint.pubkey(), &source, 1_000_000).await;
+
+    // 2.5% of 100_000
+    let ix = transfer_with_fee_ix(&source, &mint.pubkey(), &destination, &payer.pubkey(), 100_000, 6, 2_500);
+    send(&mut context, &[ix], &[]).await.unwrap();
+
+    let account = get_token_account(&mut context, &destination).await;
+    assert_eq!(account.base.amount, 97_500);
+    let withheld = account.get_extension::<TransferFeeAmount>().unwrap().withheld_amount;
+    assert_eq!(u64::from(withheld), 2_500);
+
+    let fee_vault = create_token_account(&mut context, &mint.pubkey(), &payer.pubkey()).await;
+    let ix = spl_token_2022::extension::transfer_fee::instruction::withdraw_withheld_tokens_from_accounts(
+        &spl_token_2022::id(),
+        &mint.pubkey(),
+        &fee_vault,
+        &payer.pubkey(),
+        &[],
+        &[&destination],
+    )
+    .unwrap();
+    send(&mut context, &[ix], &[]).await.unwrap();
+
+    assert_eq!(get_token_account(&mut context, &fee_vault).await.base.amount, 2_500);
+}
+
+#[tokio::test]
+#[cfg_attr(not(feature = "sbf-tests"), ignore = "needs the SBF build; run `anchor build`, then test with `--features sbf-tests`")]
+async fn transfer_with_wrong_fee_fails() {
+    let admin = Keypair::new();
+    let mut context = program_test(&admin.pubkey()).start_with_context().await;
+    let payer = context.payer.insecure_clone();
+    let mint = Keypair::new();
+
+    let ix = create_taxed_token_ix(&admin.pubkey(), &payer.pubkey(), &mint.pubkey(), 6, 250, 1_000_000);
+    send(&mut context, &[ix], &[&mint]).await.unwrap();
+
+    let source = create_token_account(&mut context, &mint.pubkey(), &payer.pubkey()).await;
+    let destination = create_token_account(&mut context, &mint.pubkey(), &Keypair::new().pubkey()).await;
+    mint_to(&mut context, &mint.pubkey(), &source, 1_000_000).await;
+
+    let ix = transfer_with_fee_ix(&source, &mint.pubkey(), &destination, &payer.pubkey(), 100_000, 6, 0);
+    assert!(send(&mut context, &[ix], &[]).await.is_err());
+}
+
+#[tokio::test]
+#[cfg_attr(not(feature = "sbf-tests"), ignore = "needs the SBF build; run `anchor build`, then test with `--features sbf-tests`")]
+async fn soulbound_token_is_non_transferable() {
+    let admin = Keypair::new();
+    let mut context = program_test(&admin.pubkey()).start_with_context().await;
+    let payer = context.payer.insecure_clone();
+    let mint = Keypair::new();
+
+    let ix = create_soulbound_token_ix(&admin.pubkey(), &payer.pubkey(), &mint.pubkey(), 0);
+    send(&mut context, &[ix], &[&mint]).await.unwrap();
+
+    let mint_state = get_mint(&mut context, &mint.pubkey()).await;
+    assert!(mint_state.get_extension::<NonTransferable>().is_ok());
+    assert_eq!(mint_state.base.decimals, 0);
+}
+
+#[tokio::test]
+#[cfg_attr(not(feature = "sbf-tests"), ignore = "needs the SBF build; run `anchor build`, then test with `--features sbf-tests`")]
+async fn locked_metadata_has_no_update_authority() {
+    let admin = Keypair::new();
+    let mut context = program_test(&admin.pubkey()).start_with_context().await;
+    let payer = context.payer.insecure_clone();
+    let mint = Keypair::new();
+
+    let ix = create_taxed_token_ix(&admin.pubkey(), &payer.pubkey(), &mint.pubkey(), 6, 250, 1_000_000);
+    send(&mut context, &[ix], &[&mint]).await.unwrap();
+
+    let launch_config = Pubkey::find_program_address(&[LAUNCH_CONFIG_SEED, mint.pubkey().as_ref()], &taxed_token_launchpad::ID).0;
+    let symbol_index = Pubkey::find_program_address(&[SYMBOL_INDEX_SEED, b"TAX"], &taxed_token_launchpad::ID).0;
+    let init = Instruction {
+        program_id: taxed_token_launchpad::ID,
+        accounts: accounts::InitTokenMetadata {
+            creator: payer.pubkey(),
+            launch_config,
+            mint: mint.pubkey(),
+            mint_authority: payer.pubkey(),
+            symbol_index,
+            token_program: spl_token_2022::id(),
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None),
+        data: instruction::InitTokenMetadata {
+            metadata: TokenMetadataParams {
+                name: "Taxed Token".to_string(),
+                symbol: "TAX".to_string(),
+                uri: "https://example.com/tax.json".to_string(),
+            },
+        }
+        .data(),
+    };
+    let metaplex_metadata = Pubkey::find_program_address(
+        &[METAPLEX_METADATA_SEED, METAPLEX_METADATA_PROGRAM_ID.as_ref(), mint.pubkey().as_ref()],
+        &METAPLEX_METADATA_PROGRAM_ID,
+    )
+    .0;
+    // The Metaplex copy is taken from the Token-2022 metadata, so it can't come first
+    let metaplex = Instruction {
+        program_id: taxed_token_launchpad::ID,
+        accounts: accounts::InitMetaplexMetadata {
+            creator: payer.pubkey(),
+            launch_config,
+            mint: mint.pubkey(),
+            mint_authority: payer.pubkey(),
+            metaplex_metadata,
+            metaplex_program: METAPLEX_METADATA_PROGRAM_ID,
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None),
+        data: instruction::InitMetaplexMetadata {}.data(),
+    };
+    let result = send(&mut context, &[metaplex], &[]).await;
+    assert!(is_program_error(result, MyError::MetadataNotInitialized));
+    send(&mut context, &[init], &[]).await.unwrap();
+
+    let metadata = get_mint(&mut context, &mint.pubkey()).await.get_variable_len_extension::<TokenMetadata>().unwrap();
+    assert_eq!((metadata.name.as_str(), metadata.symbol.as_str()), ("Taxed Token", "TAX"));
+    assert_eq!(Option::<Pubkey>::from(metadata.update_authority), Some(payer.pubkey()));
+    let index = get_program_account::<SymbolIndex>(&mut context, &symbol_index).await;
+    assert_eq!((index.symbol.as_str(), index.first_mint, index.launches), ("TAX", mint.pubkey(), 1));
+
+    let lock = || Instruction {
+        program_id: taxed_token_launchpad::ID,
+        accounts: accounts::LockMetadata {
+            creator: payer.pubkey(),
+            launch_config,
+            mint: mint.pubkey(),
+            token_program: spl_token_2022::id(),
+            metaplex_metadata,
+            metaplex_program: METAPLEX_METADATA_PROGRAM_ID,
+        }
+        .to_account_metas(None),
+        data: instruction::LockMetadata {}.data(),
+    };
+    send(&mut context, &[lock()], &[]).await.unwrap();
+
+    let metadata = get_mint(&mut context, &mint.pubkey()).await.get_variable_len_extension::<TokenMetadata>().unwrap();
+    assert_eq!(Option::<Pubkey>::from(metadata.update_authority), None);
+    assert!(get_program_account::<LaunchConfig>(&mut context, &launch_config).await.metadata_locked);
+
+    let now = clock(&mut context).await.unix_timestamp;
+    set_clock(&mut context, now).await;
+    let result = send(&mut context, &[lock()], &[]).await;
+    assert!(is_program_error(result, MyError::MetadataLocked));
+}
+
+#[tokio::test]
+#[cfg_attr(not(feature = "sbf-tests"), ignore = "needs the SBF build; run `anchor build`, then test with `--features sbf-tests`")]
+async fn batch_is_one_launch_for_the_cooldown_and_unbonded_launches_stay_active_until_retired() {
+    let admin = Keypair::new();
+    let mut context = program_test_with(&admin.pubkey(), |config| {
+        config.launch_cooldown_seconds = 1_000;
+        config.max_active_launches = 2;
+        config.bond_lock_seconds = 5_000;
+    })
+    .start_with_context()
+    .await;
+    let payer = context.payer.insecure_clone();
+    let launch = LaunchAccounts::new(&admin.pubkey(), &payer.pubkey(), &Pubkey::default());
+
+    let pda = |seeds: &[&[u8]]| Pubkey::find_program_address(seeds, &taxed_token_launchpad::ID).0;
+    let mints: Vec<Pubkey> = (0..2u8).map(|index| pda(&[BATCH_MINT_SEED, payer.pubkey().as_ref(), &7u64.to_le_bytes(), &[index]])).collect();
+    let mut batch_accounts = accounts::CreateTokensBatch {
+        payer: payer.pubkey(),
+        mint_authority: payer.pubkey(),
+        fee_withdraw_authority: None,
+        freeze_authority: payer.pubkey(),
+        token_program: spl_token_2022::id(),
+        platform_config: launch.platform_config,
+        treasury: launch.treasury,
+        insurance_vault: launch.insurance_vault,
+        price_update: None,
+        attestor: None,
+        partner: None,
+        staking_pool: None,
+        creator_reputation: launch.creator_reputation,
+        system_program: solana_sdk::system_program::id(),
+    }
+    .to_account_metas(None);
+    for mint in &mints {
+        batch_accounts.push(AccountMeta::new(*mint, false));
+        batch_accounts.push(AccountMeta::new(pda(&[LAUNCH_CONFIG_SEED, mint.as_ref()]), false));
+    }
+    let batch = Instruction {
+        program_id: taxed_token_launchpad::ID,
+        accounts: batch_accounts,
+        data: instruction::CreateTokensBatch { batch_id: 7, decimals: 6, kind: TokenKind::Soulbound }.data(),
+    };
+    send(&mut context, &[batch], &[]).await.unwrap();
+    let reputation = get_program_account::<CreatorReputation>(&mut context, &launch.creator_reputation).await;
+    assert_eq!((reputation.launches, reputation.active_launches), (2, 2));
+
+    let mint = Keypair::new();
+    let create = || create_taxed_token_ix(&admin.pubkey(), &payer.pubkey(), &mint.pubkey(), 6, 250, 1_000_000);
+    let result = send(&mut context, &[create()], &[&mint]).await;
+    assert!(is_program_error(result, MyError::LaunchCooldownActive));
+
+    // Past the cooldown, the two batch mints still fill the active cap
+    let now = clock(&mut context).await.unix_timestamp;
+    set_clock(&mut context, now + 1_000).await;
+    let result = send(&mut context, &[create()], &[&mint]).await;
+    assert!(is_program_error(result, MyError::TooManyActiveLaunches));
+
+    let retire = || Instruction {
+        program_id: taxed_token_launchpad::ID,
+        accounts: accounts::RetireLaunch {
+            platform_config: launch.platform_config,
+            launch_config: pda(&[LAUNCH_CONFIG_SEED, mints[0].as_ref()]),
+            creator_reputation: launch.creator_reputation,
+        }
+        .to_account_metas(None),
+        data: instruction::RetireLaunch {}.data(),
+    };
+    let result = send(&mut context, &[retire()], &[]).await;
+    assert!(is_program_error(result, MyError::LaunchStillActive));
+
+    set_clock(&mut context, now + 5_000).await;
+    send(&mut context, &[retire()], &[]).await.unwrap();
+    send(&mut context, &[create()], &[&mint]).await.unwrap();
+    let reputation = get_program_account::<CreatorReputation>(&mut context, &launch.creator_reputation).await;
+    assert_eq!((reputation.launches, reputation.active_launches), (3, 2));
+
+    set_clock(&mut context, now + 5_000).await;
+    let result = send(&mut context, &[retire()], &[]).await;
+    assert!(is_program_error(result, MyError::LaunchNotActive));
+}
+
+#[tokio::test]
+#[cfg_attr(not(feature = "sbf-tests"), ignore = "needs the SBF build; run `anchor build`, then test with `--features sbf-tests`")]
+async fn dismissed_dispute_holds_off_a_new_flag_for_the_cooldown() {
+    let admin = Keypair::new();
+    let mut context = program_test(&admin.pubkey()).start_with_context().await;
+    let payer = context.payer.insecure_clone();
+    let mint = Keypair::new();
+
+    let ix = create_taxed_token_ix(&admin.pubkey(), &payer.pubkey(), &mint.pubkey(), 6, 250, 1_000_000);
+    send(&mut context, &[ix], &[&mint]).await.unwrap();
+
+    let launch = LaunchAccounts::new(&admin.pubkey(), &payer.pubkey(), &mint.pubkey());
+    let pda = |seeds: &[&[u8]]| Pubkey::find_program_address(seeds, &taxed_token_launchpad::ID).0;
+    let dispute = pda(&[DISPUTE_SEED, mint.pubkey().as_ref()]);
+    let creator_bond = pda(&[CREATOR_BOND_SEED, mint.pubkey().as_ref()]);
+    let flag = |reason_uri: &str| Instruction {
+        program_id: taxed_token_launchpad::ID,
+        accounts: accounts::FlagLaunch {
+            reporter: payer.pubkey(),
+            dispute,
+            launch_config: launch.launch_config,
+            creator_reputation: launch.creator_reputation,
+            creator_bond,
+            system_program: solana_sdk::system_program::id(),
+        }
+        .to_account_metas(None),
+        data: instruction::FlagLaunch { mint: mint.pubkey(), reason_uri: reason_uri.to_string() }.data(),
+    };
+    let dismiss = Instruction {
+        program_id: taxed_token_launchpad::ID,
+        accounts: accounts::ResolveDispute {
+            verifier: admin.pubkey(),
+            platform_config: launch.platform_config,
+            dispute,
+            creator_bond,
+            launch_config: launch.launch_config,
+            creator_reputation: launch.creator_reputation,
+        }
+        .to_account_metas(None),
+        data: instruction::ResolveDispute { upheld: false }.data(),
+    };
+
+    send(&mut context, &[flag("ipfs://first")], &[]).await.unwrap();
+    let result = send(&mut context, &[flag("ipfs://second")], &[]).await;
+    assert!(is_program_error(result, MyError::DisputeAlreadyOpen));
+
+    send(&mut context, &[dismiss], &[&admin]).await.unwrap();
+    let dismissed = get_program_account::<Dispute>(&mut context, &dispute).await;
+    assert_eq!(dismissed.status, DisputeStatus::Dismissed);
+
+    let result = send(&mut context, &[flag("ipfs://again")], &[]).await;
+    assert!(is_program_error(result, MyError::ReflagTooSoon));
+
+    set_clock(&mut context, dismissed.resolved_at + DISPUTE_REFLAG_COOLDOWN_SECONDS).await;
+    send(&mut context, &[flag("ipfs://again")], &[]).await.unwrap();
+    let reopened = get_program_account::<Dispute>(&mut context, &dispute).await;
+    assert_eq!((reopened.status, reopened.resolved_at), (DisputeStatus::Open, 0));
+}
@@ -0,0 +1,1235 @@
+//! ProgramTest suites for the escrow and payout flows: streams, OTC offers, airdrop batches,
+//! gifts, the wrapped companion mint, platform staking, dividend pools and allocation releases. Each walks the
+//! honest path and the abuse its checks are there to stop.
+
+use anchor_lang::{InstructionData, ToAccountMetas};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+};
+use taxed_token_launchpad::test_utils::*;
+use taxed_token_launchpad::{
+    accounts, instruction, AirdropCampaign, AirdropRecipient, AllocationBucketParams, AllocationKind, AllocationParams,
+    AllocationPlan, MyError, PlatformStaking, Stream, TreasuryLimits, Wrapper, AIRDROP_SEED, ALLOCATION_PLAN_SEED,
+    DISBURSEMENT_SEED, DIVIDEND_POOL_SEED, DIVIDEND_POSITION_SEED, GIFT_SEED, LAUNCH_CONFIG_SEED, MINT_PAUSE_SEED,
+    OTC_OFFER_SEED, PLATFORM_CONFIG_SEED, PLATFORM_STAKING_SEED, PROJECT_TREASURY_SEED, STAKE_POSITION_SEED,
+    STREAM_SEED, WRAPPED_MINT_SEED, WRAPPER_SEED,
+};
+
+const FEE_BPS: u16 = 100;
+const MAXIMUM_FEE: u64 = 1_000_000_000;
+
+/// Transfer fee Token-2022 withholds on `amount` of a FEE_BPS mint
+fn fee(amount: u64) -> u64 {
+    (amount * FEE_BPS as u64).div_ceil(10_000)
+}
+
+fn pda(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &taxed_token_launchpad::ID).0
+}
+
+fn mint_pause(mint: &Pubkey) -> Pubkey {
+    pda(&[MINT_PAUSE_SEED, mint.as_ref()])
+}
+
+fn launchpad_ix(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: taxed_token_launchpad::ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+/// Launch a FEE_BPS taxed mint with the context payer as creator and every authority.
+async fn taxed_launch(context: &mut solana_program_test::ProgramTestContext, admin: &Pubkey) -> Pubkey {
+    let payer = context.payer.insecure_clone();
+    let mint = Keypair::new();
+    let ix = create_taxed_token_ix(admin, &payer.pubkey(), &mint.pubkey(), 6, FEE_BPS, MAXIMUM_FEE);
+    send(context, &[ix], &[&mint]).await.unwrap();
+    mint.pubkey()
+}
+
+async fn balance(context: &mut solana_program_test::ProgramTestContext, account: &Pubkey) -> u64 {
+    get_token_account(context, account).await.base.amount
+}
+
+/// Escrow 100_000 of `mint_a` for 50_000 of `mint_b`, returning the offer and its vault
+async fn create_offer(
+    context: &mut solana_program_test::ProgramTestContext,
+    maker: &Keypair,
+    [mint_a, mint_b]: [Pubkey; 2],
+    maker_token_account_a: Pubkey,
+    offer_id: u64,
+    counterparty: Option<Pubkey>,
+) -> (Pubkey, Pubkey) {
+    let offer = pda(&[OTC_OFFER_SEED, maker.pubkey().as_ref(), &offer_id.to_le_bytes()]);
+    let vault = create_token_account(context, &mint_a, &offer).await;
+    let ix = launchpad_ix(
+        accounts::CreateOtcOffer {
+            maker: maker.pubkey(),
+            mint_a,
+            mint_b,
+            offer,
+            maker_token_account_a,
+            vault,
+            token_program: spl_token_2022::id(),
+            system_program: solana_sdk::system_program::id(),
+            mint_pause: mint_pause(&mint_a),
+        },
+        instruction::CreateOtcOffer {
+            offer_id,
+            amount_a: 100_000,
+            amount_b: 50_000,
+            counterparty,
+        },
+    );
+    send(context, &[ix], &[maker]).await.unwrap();
+    (offer, vault)
+}
+
+/// Gift 10_000 of `mint` from the context payer to whoever holds `claim_key`
+async fn create_gift(
+    context: &mut solana_program_test::ProgramTestContext,
+    mint: &Pubkey,
+    sender_token_account: Pubkey,
+    claim_key: Pubkey,
+    expires_at: i64,
+) -> (Pubkey, Pubkey) {
+    let gift = pda(&[GIFT_SEED, claim_key.as_ref()]);
+    let vault = create_token_account(context, mint, &gift).await;
+    let ix = launchpad_ix(
+        accounts::CreateGift {
+            sender: context.payer.pubkey(),
+            mint: *mint,
+            gift,
+            sender_token_account,
+            vault,
+            token_program: spl_token_2022::id(),
+            system_program: solana_sdk::system_program::id(),
+            mint_pause: mint_pause(mint),
+        },
+        instruction::CreateGift {
+            claim_key,
+            amount: 10_000,
+            expires_at,
+        },
+    );
+    send(context, &[ix], &[]).await.unwrap();
+    (gift, vault)
+}
+
+#[tokio::test]
+#[cfg_attr(not(feature = "sbf-tests"), ignore = "needs the SBF build; run `anchor build`, then test with `--features sbf-tests`")]
+async fn stream_pays_only_what_has_vested_and_cancel_empties_the_vault() {
+    let admin = Keypair::new();
+    let mut context = program_test(&admin.pubkey()).start_with_context().await;
+    let sender = context.payer.insecure_clone();
+    let mint = taxed_launch(&mut context, &admin.pubkey()).await;
+
+    let recipient = Keypair::new();
+    let sender_account = create_token_account(&mut context, &mint, &sender.pubkey()).await;
+    let recipient_account = create_token_account(&mut context, &mint, &recipient.pubkey()).await;
+    mint_to(&mut context, &mint, &sender_account, 1_000_000).await;
+
+    let stream_id = 1u64;
+    let stream = pda(&[STREAM_SEED, sender.pubkey().as_ref(), mint.as_ref(), &stream_id.to_le_bytes()]);
+    let vault = create_token_account(&mut context, &mint, &stream).await;
+    let start = clock(&mut context).await.unix_timestamp;
+    set_clock(&mut context, start).await;
+
+    let create = launchpad_ix(
+        accounts::CreateStream {
+            sender: sender.pubkey(),
+            mint,
+            stream,
+            sender_token_account: sender_account,
+            vault,
+            token_program: spl_token_2022::id(),
+            system_program: solana_sdk::system_program::id(),
+            mint_pause: mint_pause(&mint),
+        },
+        instruction::CreateStream {
+            stream_id,
+            recipient: recipient.pubkey(),
+            deposit: 100_000,
+            start_time: start,
+            end_time: start + 1_000,
+        },
+    );
+    send(&mut context, &[create], &[]).await.unwrap();
+    // The stream total is what reached the vault, not the gross deposit
+    let deposited = 100_000 - fee(100_000);
+    assert_eq!(get_program_account::<Stream>(&mut context, &stream).await.deposited, deposited);
+
+    let withdraw = |signer: &Keypair, destination: Pubkey| {
+        launchpad_ix(
+            accounts::WithdrawFromStream {
+                recipient: signer.pubkey(),
+                stream,
+                mint,
+                vault,
+                recipient_token_account: destination,
+                token_program: spl_token_2022::id(),
+                mint_pause: mint_pause(&mint),
+            },
+            instruction::WithdrawFromStream {},
+        )
+    };
+    let result = send(&mut context, &[withdraw(&recipient, recipient_account)], &[&recipient]).await;
+    assert!(is_program_error(result, MyError::NothingToClaim));
+
+    set_clock(&mut context, start + 250).await;
+    let thief = Keypair::new();
+    let thief_account = create_token_account(&mut context, &mint, &thief.pubkey()).await;
+    let result = send(&mut context, &[withdraw(&thief, thief_account)], &[&thief]).await;
+    assert!(is_program_error(result, MyError::Unauthorized));
+
+    send(&mut context, &[withdraw(&recipient, recipient_account)], &[&recipient]).await.unwrap();
+    let first_payout = deposited / 4;
+    assert_eq!(balance(&mut context, &recipient_account).await, first_payout - fee(first_payout));
+    assert_eq!(get_program_account::<Stream>(&mut context, &stream).await.withdrawn, first_payout);
+
+    // Tokens sent to the vault on top of the deposit mustn't keep it from closing
+    transfer(&mut context, &mint, &sender_account, &vault, &sender, 1_000).await;
+    let stray = 1_000 - fee(1_000);
+
+    set_clock(&mut context, start + 500).await;
+    let sender_before = balance(&mut context, &sender_account).await;
+    let recipient_before = balance(&mut context, &recipient_account).await;
+    let cancel = launchpad_ix(
+        accounts::CancelStream {
+            sender: sender.pubkey(),
+            stream,
+            mint,
+            vault,
+            sender_token_account: sender_account,
+            recipient_token_account: recipient_account,
+            token_program: spl_token_2022::id(),
+            mint_pause: mint_pause(&mint),
+        },
+        instruction::CancelStream {},
+    );
+    send(&mut context, &[cancel], &[]).await.unwrap();
+
+    let owed = deposited / 2 - first_payout;
+    let refund = deposited - deposited / 2 + stray;
+    assert_eq!(balance(&mut context, &recipient_account).await, recipient_before + owed - fee(owed));
+    assert_eq!(balance(&mut context, &sender_account).await, sender_before + refund - fee(refund));
+    assert!(!account_exists(&mut context, &vault).await);
+    assert!(!account_exists(&mut context, &stream).await);
+}
+
+#[tokio::test]
+#[cfg_attr(not(feature = "sbf-tests"), ignore = "needs the SBF build; run `anchor build`, then test with `--features sbf-tests`")]
+async fn paused_mint_holds_stream_withdrawals_and_treasury_transfers() {
+    let admin = Keypair::new();
+    let mut context = program_test(&admin.pubkey()).start_with_context().await;
+    let creator = context.payer.insecure_clone();
+    let mint = taxed_launch(&mut context, &admin.pubkey()).await;
+    let launch_config = pda(&[LAUNCH_CONFIG_SEED, mint.as_ref()]);
+
+    let recipient = Keypair::new();
+    let creator_account = create_token_account(&mut context, &mint, &creator.pubkey()).await;
+    let recipient_account = create_token_account(&mut context, &mint, &recipient.pubkey()).await;
+    mint_to(&mut context, &mint, &creator_account, 1_000_000).await;
+
+    let stream_id = 1u64;
+    let stream = pda(&[STREAM_SEED, creator.pubkey().as_ref(), mint.as_ref(), &stream_id.to_le_bytes()]);
+    let stream_vault = create_token_account(&mut context, &mint, &stream).await;
+    let start = clock(&mut context).await.unix_timestamp;
+    set_clock(&mut context, start).await;
+    let create = launchpad_ix(
+        accounts::CreateStream {
+            sender: creator.pubkey(),
+            mint,
+            stream,
+            sender_token_account: creator_account,
+            vault: stream_vault,
+            token_program: spl_token_2022::id(),
+            system_program: solana_sdk::system_program::id(),
+            mint_pause: mint_pause(&mint),
+        },
+        instruction::CreateStream {
+            stream_id,
+            recipient: recipient.pubkey(),
+            deposit: 100_000,
+            start_time: start,
+            end_time: start + 1_000,
+        },
+    );
+    send(&mut context, &[create], &[]).await.unwrap();
+
+    let treasury = pda(&[PROJECT_TREASURY_SEED, mint.as_ref()]);
+    let treasury_vault = create_token_account(&mut context, &mint, &treasury).await;
+    mint_to(&mut context, &mint, &treasury_vault, 10_000).await;
+    let init_treasury = launchpad_ix(
+        accounts::InitProjectTreasury {
+            creator: creator.pubkey(),
+            launch_config,
+            treasury,
+            system_program: solana_sdk::system_program::id(),
+        },
+        instruction::InitProjectTreasury {
+            limits: TreasuryLimits {
+                spenders: vec![creator.pubkey()],
+                period_seconds: 86_400,
+                period_cap: 10_000,
+            },
+        },
+    );
+    send(&mut context, &[init_treasury], &[]).await.unwrap();
+
+    let pause = launchpad_ix(
+        accounts::PauseMint {
+            authority: creator.pubkey(),
+            platform_config: pda(&[PLATFORM_CONFIG_SEED]),
+            launch_config,
+            mint_pause: mint_pause(&mint),
+            system_program: solana_sdk::system_program::id(),
+        },
+        instruction::PauseMint { duration_seconds: 500 },
+    );
+    send(&mut context, &[pause], &[]).await.unwrap();
+
+    let withdraw = launchpad_ix(
+        accounts::WithdrawFromStream {
+            recipient: recipient.pubkey(),
+            stream,
+            mint,
+            vault: stream_vault,
+            recipient_token_account: recipient_account,
+            token_program: spl_token_2022::id(),
+            mint_pause: mint_pause(&mint),
+        },
+        instruction::WithdrawFromStream {},
+    );
+    let disburse = launchpad_ix(
+        accounts::TreasuryTransfer {
+            spender: creator.pubkey(),
+            treasury,
+            disbursement: pda(&[DISBURSEMENT_SEED, treasury.as_ref(), &0u64.to_le_bytes()]),
+            mint,
+            vault: treasury_vault,
+            destination: recipient_account,
+            token_program: spl_token_2022::id(),
+            system_program: solana_sdk::system_program::id(),
+            mint_pause: mint_pause(&mint),
+        },
+        instruction::TreasuryTransfer {
+            amount: 1_000,
+            memo: "grant".to_string(),
+        },
+    );
+    set_clock(&mut context, start + 250).await;
+    let result = send(&mut context, std::slice::from_ref(&withdraw), &[&recipient]).await;
+    assert!(is_program_error(result, MyError::MintPaused));
+    let result = send(&mut context, std::slice::from_ref(&disburse), &[]).await;
+    assert!(is_program_error(result, MyError::MintPaused));
+    assert_eq!(balance(&mut context, &recipient_account).await, 0);
+
+    // Both pay out again once the pause lapses
+    set_clock(&mut context, start + 600).await;
+    send(&mut context, &[withdraw], &[&recipient]).await.unwrap();
+    send(&mut context, &[disburse], &[]).await.unwrap();
+    assert!(balance(&mut context, &recipient_account).await > 1_000);
+}
+
+#[tokio::test]
+#[cfg_attr(not(feature = "sbf-tests"), ignore = "needs the SBF build; run `anchor build`, then test with `--features sbf-tests`")]
+async fn otc_offer_pays_the_maker_in_full_and_only_to_the_named_taker() {
+    let admin = Keypair::new();
+    let mut context = program_test(&admin.pubkey()).start_with_context().await;
+    let maker = context.payer.insecure_clone();
+    let mint_a = taxed_launch(&mut context, &admin.pubkey()).await;
+    let mint_b = taxed_launch(&mut context, &admin.pubkey()).await;
+
+    let taker = Keypair::new();
+    let intruder = Keypair::new();
+    let maker_a = create_token_account(&mut context, &mint_a, &maker.pubkey()).await;
+    let maker_b = create_token_account(&mut context, &mint_b, &maker.pubkey()).await;
+    let taker_a = create_token_account(&mut context, &mint_a, &taker.pubkey()).await;
+    let taker_b = create_token_account(&mut context, &mint_b, &taker.pubkey()).await;
+    let intruder_a = create_token_account(&mut context, &mint_a, &intruder.pubkey()).await;
+    let intruder_b = create_token_account(&mut context, &mint_b, &intruder.pubkey()).await;
+    mint_to(&mut context, &mint_a, &maker_a, 1_000_000).await;
+    mint_to(&mut context, &mint_b, &taker_b, 1_000_000).await;
+    mint_to(&mut context, &mint_b, &intruder_b, 1_000_000).await;
+
+    let (offer, vault) = create_offer(&mut context, &maker, [mint_a, mint_b], maker_a, 1, Some(taker.pubkey())).await;
+    let fill = |taker: &Keypair, taker_a: Pubkey, taker_b: Pubkey, maker_b: Pubkey| {
+        launchpad_ix(
+            accounts::FillOtcOffer {
+                taker: taker.pubkey(),
+                maker: maker.pubkey(),
+                offer,
+                mint_a,
+                mint_b,
+                vault,
+                taker_token_account_a: taker_a,
+                taker_token_account_b: taker_b,
+                maker_token_account_a: maker_a,
+                maker_token_account_b: maker_b,
+                token_program: spl_token_2022::id(),
+                mint_pause: mint_pause(&mint_a),
+            },
+            instruction::FillOtcOffer {},
+        )
+    };
+
+    let result = send(&mut context, &[fill(&intruder, intruder_a, intruder_b, maker_b)], &[&intruder]).await;
+    assert!(is_program_error(result, MyError::Unauthorized));
+
+    // The taker can't route the maker's side of the trade to an account of their own
+    let decoy = create_token_account(&mut context, &mint_b, &taker.pubkey()).await;
+    let result = send(&mut context, &[fill(&taker, taker_a, taker_b, decoy)], &[&taker]).await;
+    assert!(is_program_error(result, MyError::InvalidTokenAccount));
+
+    // Tokens sent to the vault on top of the escrow go back to the maker
+    transfer(&mut context, &mint_a, &maker_a, &vault, &maker, 1_000).await;
+    let stray = 1_000 - fee(1_000);
+    let maker_before = balance(&mut context, &maker_a).await;
+
+    send(&mut context, &[fill(&taker, taker_a, taker_b, maker_b)], &[&taker]).await.unwrap();
+    let escrowed = 100_000 - fee(100_000);
+    assert_eq!(balance(&mut context, &maker_b).await, 50_000);
+    assert_eq!(balance(&mut context, &taker_a).await, escrowed - fee(escrowed));
+    assert_eq!(balance(&mut context, &maker_a).await, maker_before + stray - fee(stray));
+    assert!(!account_exists(&mut context, &vault).await);
+    assert!(!account_exists(&mut context, &offer).await);
+
+    let (offer, vault) = create_offer(&mut context, &maker, [mint_a, mint_b], maker_a, 2, None).await;
+    let cancel = |signer: &Keypair, destination: Pubkey| {
+        launchpad_ix(
+            accounts::CancelOtcOffer {
+                maker: signer.pubkey(),
+                offer,
+                mint_a,
+                vault,
+                maker_token_account_a: destination,
+                token_program: spl_token_2022::id(),
+            },
+            instruction::CancelOtcOffer {},
+        )
+    };
+    assert!(send(&mut context, &[cancel(&intruder, intruder_a)], &[&intruder]).await.is_err());
+
+    let maker_before = balance(&mut context, &maker_a).await;
+    send(&mut context, &[cancel(&maker, maker_a)], &[]).await.unwrap();
+    assert_eq!(balance(&mut context, &maker_a).await, maker_before + escrowed - fee(escrowed));
+    assert!(!account_exists(&mut context, &vault).await);
+    assert!(!account_exists(&mut context, &offer).await);
+}
+
+#[tokio::test]
+#[cfg_attr(not(feature = "sbf-tests"), ignore = "needs the SBF build; run `anchor build`, then test with `--features sbf-tests`")]
+async fn airdrop_batch_skips_only_frozen_accounts_and_keeps_their_share_claimable() {
+    let admin = Keypair::new();
+    let mut context = program_test(&admin.pubkey()).start_with_context().await;
+    let creator = context.payer.insecure_clone();
+    let mint = taxed_launch(&mut context, &admin.pubkey()).await;
+
+    let campaign_id = 1u64;
+    let campaign = pda(&[AIRDROP_SEED, creator.pubkey().as_ref(), mint.as_ref(), &campaign_id.to_le_bytes()]);
+    let vault = create_token_account(&mut context, &mint, &campaign).await;
+    mint_to(&mut context, &mint, &vault, 10_000).await;
+    let now = clock(&mut context).await.unix_timestamp;
+    let init = launchpad_ix(
+        accounts::InitAirdrop {
+            creator: creator.pubkey(),
+            mint,
+            campaign,
+            vault,
+            system_program: solana_sdk::system_program::id(),
+        },
+        instruction::InitAirdrop {
+            campaign_id,
+            expires_at: now + 10_000,
+        },
+    );
+    send(&mut context, &[init], &[]).await.unwrap();
+
+    let (alice, carol, bob) = (Keypair::new(), Keypair::new(), Keypair::new());
+    let recipients = [&alice, &carol, &bob]
+        .iter()
+        .map(|wallet| AirdropRecipient {
+            wallet: wallet.pubkey(),
+            amount: 1_000,
+        })
+        .collect();
+    let add = launchpad_ix(
+        accounts::AddAirdropRecipients {
+            creator: creator.pubkey(),
+            campaign,
+        },
+        instruction::AddAirdropRecipients { recipients },
+    );
+    send(&mut context, &[add], &[]).await.unwrap();
+
+    let alice_ata = create_associated_token_account(&mut context, &mint, &alice.pubkey()).await;
+    let carol_ata = create_associated_token_account(&mut context, &mint, &carol.pubkey()).await;
+    set_frozen(&mut context, &mint, &carol_ata, true).await;
+    let bob_ata = associated_token_address(&bob.pubkey(), &mint);
+
+    let batch = |destinations: &[Pubkey]| {
+        let mut ix = launchpad_ix(
+            accounts::AirdropBatch {
+                campaign,
+                mint,
+                vault,
+                token_program: spl_token_2022::id(),
+                mint_pause: mint_pause(&mint),
+            },
+            instruction::AirdropBatch {},
+        );
+        ix.accounts.extend(destinations.iter().map(|destination| AccountMeta::new(*destination, false)));
+        ix
+    };
+
+    // Cranking before Bob has an account fails instead of skipping him
+    let result = send(&mut context, &[batch(&[alice_ata, carol_ata, bob_ata])], &[]).await;
+    assert!(is_program_error(result, MyError::InvalidTokenAccount));
+    let mallory = Keypair::new();
+    let mallory_account = create_token_account(&mut context, &mint, &mallory.pubkey()).await;
+    let result = send(&mut context, &[batch(&[alice_ata, carol_ata, mallory_account])], &[]).await;
+    assert!(is_program_error(result, MyError::InvalidTokenAccount));
+    assert_eq!(get_program_account::<AirdropCampaign>(&mut context, &campaign).await.cursor, 0);
+
+    create_associated_token_account(&mut context, &mint, &bob.pubkey()).await;
+    set_clock(&mut context, now).await;
+    send(&mut context, &[batch(&[alice_ata, carol_ata, bob_ata])], &[]).await.unwrap();
+    let paid = 1_000 - fee(1_000);
+    assert_eq!(balance(&mut context, &alice_ata).await, paid);
+    assert_eq!(balance(&mut context, &bob_ata).await, paid);
+    assert_eq!(balance(&mut context, &carol_ata).await, 0);
+    let state = get_program_account::<AirdropCampaign>(&mut context, &campaign).await;
+    assert_eq!(state.cursor, 3);
+    assert_eq!(state.skipped, vec![1]);
+
+    // The list is paid out, but Carol's share is still owed
+    let creator_account = create_token_account(&mut context, &mint, &creator.pubkey()).await;
+    let close = launchpad_ix(
+        accounts::CloseAirdrop {
+            creator: creator.pubkey(),
+            campaign,
+            mint,
+            vault,
+            creator_token_account: Some(creator_account),
+            token_program: spl_token_2022::id(),
+        },
+        instruction::CloseAirdrop {},
+    );
+    let result = send(&mut context, std::slice::from_ref(&close), &[]).await;
+    assert!(is_program_error(result, MyError::AirdropSkippedUnclaimed));
+
+    let claim = |destination: Pubkey| {
+        launchpad_ix(
+            accounts::ClaimSkippedAirdrop {
+                campaign,
+                mint,
+                vault,
+                destination,
+                token_program: spl_token_2022::id(),
+                mint_pause: mint_pause(&mint),
+            },
+            instruction::ClaimSkippedAirdrop { index: 1 },
+        )
+    };
+    assert!(send(&mut context, &[claim(carol_ata)], &[]).await.is_err());
+    set_frozen(&mut context, &mint, &carol_ata, false).await;
+    let result = send(&mut context, &[claim(mallory_account)], &[]).await;
+    assert!(is_program_error(result, MyError::InvalidTokenAccount));
+
+    set_clock(&mut context, now).await;
+    send(&mut context, &[claim(carol_ata)], &[]).await.unwrap();
+    assert_eq!(balance(&mut context, &carol_ata).await, paid);
+    assert!(get_program_account::<AirdropCampaign>(&mut context, &campaign).await.skipped.is_empty());
+
+    set_clock(&mut context, now).await;
+    let result = send(&mut context, &[claim(carol_ata)], &[]).await;
+    assert!(is_program_error(result, MyError::AirdropEntryNotSkipped));
+
+    send(&mut context, &[close], &[]).await.unwrap();
+    let remainder = 10_000 - 3 * 1_000;
+    assert_eq!(balance(&mut context, &creator_account).await, remainder - fee(remainder));
+    assert!(!account_exists(&mut context, &campaign).await);
+}
+
+#[tokio::test]
+#[cfg_attr(not(feature = "sbf-tests"), ignore = "needs the SBF build; run `anchor build`, then test with `--features sbf-tests`")]
+async fn gift_pays_only_its_claim_key_and_stray_deposits_cannot_block_it() {
+    let admin = Keypair::new();
+    let mut context = program_test(&admin.pubkey()).start_with_context().await;
+    let sender = context.payer.insecure_clone();
+    let mint = taxed_launch(&mut context, &admin.pubkey()).await;
+    let sender_account = create_token_account(&mut context, &mint, &sender.pubkey()).await;
+    mint_to(&mut context, &mint, &sender_account, 1_000_000).await;
+    let now = clock(&mut context).await.unix_timestamp;
+    set_clock(&mut context, now).await;
+
+    let claim_gift = |signer: &Keypair, gift: Pubkey, vault: Pubkey, destination: Pubkey| {
+        launchpad_ix(
+            accounts::ClaimGift {
+                claim_key: signer.pubkey(),
+                gift,
+                sender: sender.pubkey(),
+                mint,
+                vault,
+                recipient_token_account: destination,
+                token_program: spl_token_2022::id(),
+                mint_pause: mint_pause(&mint),
+            },
+            instruction::ClaimGift {},
+        )
+    };
+
+    let claim_key = Keypair::new();
+    let (gift, vault) = create_gift(&mut context, &mint, sender_account, claim_key.pubkey(), now + 1_000).await;
+    transfer(&mut context, &mint, &sender_account, &vault, &sender, 1_000).await;
+    let friend = create_token_account(&mut context, &mint, &Keypair::new().pubkey()).await;
+
+    let impostor = Keypair::new();
+    assert!(send(&mut context, &[claim_gift(&impostor, gift, vault, friend)], &[&impostor]).await.is_err());
+
+    send(&mut context, &[claim_gift(&claim_key, gift, vault, friend)], &[&claim_key]).await.unwrap();
+    let held = 10_000 - fee(10_000) + 1_000 - fee(1_000);
+    assert_eq!(balance(&mut context, &friend).await, held - fee(held));
+    assert!(!account_exists(&mut context, &vault).await);
+    assert!(!account_exists(&mut context, &gift).await);
+
+    let claim_key = Keypair::new();
+    let (gift, vault) = create_gift(&mut context, &mint, sender_account, claim_key.pubkey(), now + 1_000).await;
+    set_clock(&mut context, now + 1_000).await;
+    let result = send(&mut context, &[claim_gift(&claim_key, gift, vault, friend)], &[&claim_key]).await;
+    assert!(is_program_error(result, MyError::GiftExpired));
+
+    let sender_before = balance(&mut context, &sender_account).await;
+    let cancel = launchpad_ix(
+        accounts::CancelGift {
+            sender: sender.pubkey(),
+            gift,
+            mint,
+            vault,
+            sender_token_account: sender_account,
+            token_program: spl_token_2022::id(),
+        },
+        instruction::CancelGift {},
+    );
+    send(&mut context, &[cancel], &[]).await.unwrap();
+    let escrowed = 10_000 - fee(10_000);
+    assert_eq!(balance(&mut context, &sender_account).await, sender_before + escrowed - fee(escrowed));
+    assert!(!account_exists(&mut context, &vault).await);
+}
+
+#[tokio::test]
+#[cfg_attr(not(feature = "sbf-tests"), ignore = "needs the SBF build; run `anchor build`, then test with `--features sbf-tests`")]
+async fn wrapped_supply_always_matches_the_vault() {
+    let admin = Keypair::new();
+    let mut context = program_test(&admin.pubkey()).start_with_context().await;
+    let owner = context.payer.insecure_clone();
+    let mint = taxed_launch(&mut context, &admin.pubkey()).await;
+
+    let init_wrapper = |mint: Pubkey, vault: Pubkey| {
+        launchpad_ix(
+            accounts::InitWrapper {
+                payer: owner.pubkey(),
+                mint,
+                launch_config: pda(&[LAUNCH_CONFIG_SEED, mint.as_ref()]),
+                wrapper: pda(&[WRAPPER_SEED, mint.as_ref()]),
+                wrapped_mint: pda(&[WRAPPED_MINT_SEED, mint.as_ref()]),
+                vault,
+                token_program: spl_token_2022::id(),
+                system_program: solana_sdk::system_program::id(),
+            },
+            instruction::InitWrapper {},
+        )
+    };
+
+    // Only taxed mints get a companion
+    let soulbound = Keypair::new();
+    let ix = create_soulbound_token_ix(&admin.pubkey(), &owner.pubkey(), &soulbound.pubkey(), 0);
+    send(&mut context, &[ix], &[&soulbound]).await.unwrap();
+    let soulbound_vault = create_token_account(&mut context, &soulbound.pubkey(), &pda(&[WRAPPER_SEED, soulbound.pubkey().as_ref()])).await;
+    let result = send(&mut context, &[init_wrapper(soulbound.pubkey(), soulbound_vault)], &[]).await;
+    assert!(is_program_error(result, MyError::NotWrappable));
+
+    let wrapper = pda(&[WRAPPER_SEED, mint.as_ref()]);
+    let vault = create_token_account(&mut context, &mint, &wrapper).await;
+    send(&mut context, &[init_wrapper(mint, vault)], &[]).await.unwrap();
+    let wrapped_mint = get_program_account::<Wrapper>(&mut context, &wrapper).await.wrapped_mint;
+
+    let taxed_account = create_token_account(&mut context, &mint, &owner.pubkey()).await;
+    let wrapped_account = create_token_account(&mut context, &wrapped_mint, &owner.pubkey()).await;
+    mint_to(&mut context, &mint, &taxed_account, 1_000_000).await;
+    let wrap_accounts = || accounts::WrapTokens {
+        owner: owner.pubkey(),
+        wrapper,
+        mint,
+        wrapped_mint,
+        vault,
+        taxed_token_account: taxed_account,
+        wrapped_token_account: wrapped_account,
+        token_program: spl_token_2022::id(),
+        mint_pause: mint_pause(&mint),
+    };
+
+    let ix = launchpad_ix(wrap_accounts(), instruction::Wrap { amount: 100_000 });
+    send(&mut context, &[ix], &[]).await.unwrap();
+    // Wrapping pays the transfer fee, and mints only against what reached the vault
+    let wrapped = 100_000 - fee(100_000);
+    assert_eq!(balance(&mut context, &wrapped_account).await, wrapped);
+    assert_eq!(balance(&mut context, &vault).await, wrapped);
+    assert_eq!(get_mint(&mut context, &wrapped_mint).await.base.supply, wrapped);
+
+    let ix = launchpad_ix(wrap_accounts(), instruction::Unwrap { amount: wrapped + 1 });
+    assert!(send(&mut context, &[ix], &[]).await.is_err());
+
+    let taxed_before = balance(&mut context, &taxed_account).await;
+    let ix = launchpad_ix(wrap_accounts(), instruction::Unwrap { amount: 50_000 });
+    send(&mut context, &[ix], &[]).await.unwrap();
+    assert_eq!(balance(&mut context, &taxed_account).await, taxed_before + 50_000 - fee(50_000));
+    assert_eq!(balance(&mut context, &vault).await, wrapped - 50_000);
+    assert_eq!(get_mint(&mut context, &wrapped_mint).await.base.supply, wrapped - 50_000);
+}
+
+#[tokio::test]
+#[cfg_attr(not(feature = "sbf-tests"), ignore = "needs the SBF build; run `anchor build`, then test with `--features sbf-tests`")]
+async fn stake_placed_before_a_rollover_earns_nothing_from_it() {
+    const LAUNCH_FEE: u64 = 1_000_000;
+    let admin = Keypair::new();
+    let platform_mint = Keypair::new();
+    let mut context = program_test_with(&admin.pubkey(), |config| {
+        config.platform_mint = platform_mint.pubkey();
+        config.launch_fee_lamports = LAUNCH_FEE;
+        config.staking_fee_bps = 10_000;
+    })
+    .start_with_context()
+    .await;
+    create_mint(&mut context, &platform_mint, 6).await;
+    let platform_mint = platform_mint.pubkey();
+
+    let staking_pool = pda(&[PLATFORM_STAKING_SEED]);
+    let vault = create_token_account(&mut context, &platform_mint, &staking_pool).await;
+    fund(&mut context, &admin.pubkey(), 1_000_000_000).await;
+    let init = launchpad_ix(
+        accounts::InitPlatformStaking {
+            admin: admin.pubkey(),
+            platform_config: pda(&[PLATFORM_CONFIG_SEED]),
+            staking_pool,
+            vault,
+            system_program: solana_sdk::system_program::id(),
+        },
+        instruction::InitPlatformStaking {},
+    );
+    send(&mut context, &[init], &[&admin]).await.unwrap();
+
+    let (alice, bob) = (Keypair::new(), Keypair::new());
+    let mut accounts_of = Vec::new();
+    for staker in [&alice, &bob] {
+        fund(&mut context, &staker.pubkey(), 1_000_000_000).await;
+        let account = create_token_account(&mut context, &platform_mint, &staker.pubkey()).await;
+        mint_to(&mut context, &platform_mint, &account, 1_000).await;
+        accounts_of.push(account);
+    }
+    let position = |owner: &Keypair| pda(&[STAKE_POSITION_SEED, owner.pubkey().as_ref()]);
+    let stake = |owner: &Keypair, owner_token_account: Pubkey| {
+        launchpad_ix(
+            accounts::StakePlatformTokens {
+                owner: owner.pubkey(),
+                platform_config: pda(&[PLATFORM_CONFIG_SEED]),
+                staking_pool,
+                position: position(owner),
+                mint: platform_mint,
+                vault,
+                owner_token_account,
+                token_program: spl_token_2022::id(),
+                system_program: solana_sdk::system_program::id(),
+                mint_pause: mint_pause(&platform_mint),
+            },
+            instruction::StakePlatformTokens { amount: 1_000 },
+        )
+    };
+    let claim = |owner: &Keypair| {
+        launchpad_ix(
+            accounts::ClaimStakingRewards {
+                owner: owner.pubkey(),
+                staking_pool,
+                position: position(owner),
+            },
+            instruction::ClaimStakingRewards {},
+        )
+    };
+
+    let epoch = clock(&mut context).await.epoch;
+    send(&mut context, &[stake(&alice, accounts_of[0])], &[&alice]).await.unwrap();
+    context.warp_to_epoch(epoch + 1).unwrap();
+    let activate = launchpad_ix(
+        accounts::ActivateStake {
+            staking_pool,
+            position: position(&alice),
+        },
+        instruction::ActivateStake {},
+    );
+    send(&mut context, &[activate], &[]).await.unwrap();
+
+    // Bob stakes in the epoch whose launch fees are about to be shared out
+    send(&mut context, &[stake(&bob, accounts_of[1])], &[&bob]).await.unwrap();
+    let payer = context.payer.pubkey();
+    let launch_mint = Keypair::new();
+    let mut launch = create_taxed_token_accounts(&admin.pubkey(), &payer, &launch_mint.pubkey());
+    launch.staking_pool = Some(staking_pool);
+    let ix = create_taxed_token_with_ix(launch, 6, FEE_BPS, MAXIMUM_FEE);
+    send(&mut context, &[ix], &[&launch_mint]).await.unwrap();
+    assert_eq!(get_program_account::<PlatformStaking>(&mut context, &staking_pool).await.ledger.pending_rewards, LAUNCH_FEE);
+
+    let result = send(&mut context, &[claim(&bob)], &[&bob]).await;
+    assert!(is_program_error(result, MyError::NothingToClaim));
+    context.warp_to_epoch(epoch + 2).unwrap();
+    let result = send(&mut context, &[claim(&bob)], &[&bob]).await;
+    assert!(is_program_error(result, MyError::NothingToClaim));
+
+    let alice_before = lamports(&mut context, &alice.pubkey()).await;
+    send(&mut context, &[claim(&alice)], &[&alice]).await.unwrap();
+    assert_eq!(lamports(&mut context, &alice.pubkey()).await, alice_before + LAUNCH_FEE);
+}
+
+#[tokio::test]
+#[cfg_attr(not(feature = "sbf-tests"), ignore = "needs the SBF build; run `anchor build`, then test with `--features sbf-tests`")]
+async fn dividends_go_to_stake_that_was_active_when_they_were_funded() {
+    let admin = Keypair::new();
+    let mut context = program_test(&admin.pubkey()).start_with_context().await;
+    let creator = context.payer.insecure_clone();
+    let mint = taxed_launch(&mut context, &admin.pubkey()).await;
+    let reward_mint = Keypair::new();
+    create_mint(&mut context, &reward_mint, 6).await;
+    let reward_mint = reward_mint.pubkey();
+
+    let dividend_pool = pda(&[DIVIDEND_POOL_SEED, mint.as_ref()]);
+    let stake_vault = create_token_account(&mut context, &mint, &dividend_pool).await;
+    let reward_vault = create_token_account(&mut context, &reward_mint, &dividend_pool).await;
+    let init = launchpad_ix(
+        accounts::InitDividendPool {
+            creator: creator.pubkey(),
+            launch_config: pda(&[LAUNCH_CONFIG_SEED, mint.as_ref()]),
+            dividend_pool,
+            reward_mint,
+            stake_vault,
+            reward_vault,
+            system_program: solana_sdk::system_program::id(),
+        },
+        instruction::InitDividendPool {},
+    );
+    send(&mut context, &[init], &[]).await.unwrap();
+
+    let (alice, bob) = (Keypair::new(), Keypair::new());
+    let mut accounts_of = Vec::new();
+    for staker in [&alice, &bob] {
+        fund(&mut context, &staker.pubkey(), 1_000_000_000).await;
+        let account = create_token_account(&mut context, &mint, &staker.pubkey()).await;
+        mint_to(&mut context, &mint, &account, 1_000).await;
+        let reward_account = create_token_account(&mut context, &reward_mint, &staker.pubkey()).await;
+        accounts_of.push((account, reward_account));
+    }
+    let position = |owner: &Keypair| pda(&[DIVIDEND_POSITION_SEED, mint.as_ref(), owner.pubkey().as_ref()]);
+    let stake = |owner: &Keypair, owner_token_account: Pubkey| {
+        launchpad_ix(
+            accounts::StakeForDividends {
+                owner: owner.pubkey(),
+                dividend_pool,
+                position: position(owner),
+                mint,
+                stake_vault,
+                owner_token_account,
+                token_program: spl_token_2022::id(),
+                system_program: solana_sdk::system_program::id(),
+                mint_pause: mint_pause(&mint),
+            },
+            instruction::StakeForDividends { amount: 1_000 },
+        )
+    };
+    let claim = |owner: &Keypair, owner_reward_account: Pubkey| {
+        launchpad_ix(
+            accounts::ClaimDividends {
+                owner: owner.pubkey(),
+                dividend_pool,
+                position: position(owner),
+                reward_mint,
+                reward_vault,
+                owner_reward_account,
+                token_program: spl_token_2022::id(),
+            },
+            instruction::ClaimDividends {},
+        )
+    };
+
+    let epoch = clock(&mut context).await.epoch;
+    send(&mut context, &[stake(&alice, accounts_of[0].0)], &[&alice]).await.unwrap();
+    context.warp_to_epoch(epoch + 1).unwrap();
+    let activate = launchpad_ix(
+        accounts::ActivateDividendStake {
+            dividend_pool,
+            position: position(&alice),
+        },
+        instruction::ActivateDividendStake {},
+    );
+    send(&mut context, &[activate], &[]).await.unwrap();
+
+    // Bob stakes in the epoch the dividends are paid in
+    send(&mut context, &[stake(&bob, accounts_of[1].0)], &[&bob]).await.unwrap();
+    let funder_account = create_token_account(&mut context, &reward_mint, &creator.pubkey()).await;
+    mint_to(&mut context, &reward_mint, &funder_account, 9_900).await;
+    let fund_ix = launchpad_ix(
+        accounts::FundDividends {
+            funder: creator.pubkey(),
+            dividend_pool,
+            reward_mint,
+            reward_vault,
+            funder_token_account: funder_account,
+            token_program: spl_token_2022::id(),
+        },
+        instruction::FundDividends { amount: 9_900 },
+    );
+    send(&mut context, &[fund_ix], &[]).await.unwrap();
+
+    context.warp_to_epoch(epoch + 2).unwrap();
+    let result = send(&mut context, &[claim(&bob, accounts_of[1].1)], &[&bob]).await;
+    assert!(is_program_error(result, MyError::NothingToClaim));
+    send(&mut context, &[claim(&alice, accounts_of[0].1)], &[&alice]).await.unwrap();
+    assert_eq!(balance(&mut context, &accounts_of[0].1).await, 9_900);
+}
+
+#[tokio::test]
+#[cfg_attr(not(feature = "sbf-tests"), ignore = "needs the SBF build; run `anchor build`, then test with `--features sbf-tests`")]
+async fn allocation_release_pays_each_bucket_only_its_own_tokens() {
+    let admin = Keypair::new();
+    let mut context = program_test(&admin.pubkey()).start_with_context().await;
+    let creator = context.payer.insecure_clone();
+    let mint = taxed_launch(&mut context, &admin.pubkey()).await;
+
+    let plan = pda(&[ALLOCATION_PLAN_SEED, mint.as_ref()]);
+    let (team, liquidity) = (Keypair::new(), Keypair::new());
+    let team_vault = create_token_account(&mut context, &mint, &plan).await;
+    let liquidity_vault = create_token_account(&mut context, &mint, &plan).await;
+    let team_beneficiary = create_token_account(&mut context, &mint, &team.pubkey()).await;
+    let liquidity_beneficiary = create_token_account(&mut context, &mint, &liquidity.pubkey()).await;
+    let sale_vault = create_token_account(&mut context, &mint, &creator.pubkey()).await;
+    let start = clock(&mut context).await.unix_timestamp;
+    set_clock(&mut context, start).await;
+
+    let params = AllocationParams {
+        total_supply: 1_000_000,
+        buckets: vec![
+            AllocationBucketParams {
+                kind: AllocationKind::Team,
+                bps: 5_000,
+                beneficiary: team_beneficiary,
+                vesting_start: start,
+                vesting_end: start + 1_000,
+                revocable: false,
+            },
+            AllocationBucketParams {
+                kind: AllocationKind::Liquidity,
+                bps: 3_000,
+                beneficiary: liquidity_beneficiary,
+                vesting_start: start,
+                vesting_end: start + 500,
+                revocable: false,
+            },
+            AllocationBucketParams {
+                kind: AllocationKind::Sale,
+                bps: 2_000,
+                beneficiary: sale_vault,
+                vesting_start: 0,
+                vesting_end: 0,
+                revocable: false,
+            },
+        ],
+    };
+    let init = |vaults: &[Pubkey]| {
+        let mut ix = launchpad_ix(
+            accounts::InitAllocationPlan {
+                creator: creator.pubkey(),
+                mint_authority: creator.pubkey(),
+                launch_config: pda(&[LAUNCH_CONFIG_SEED, mint.as_ref()]),
+                mint,
+                plan,
+                token_program: spl_token_2022::id(),
+                system_program: solana_sdk::system_program::id(),
+            },
+            instruction::InitAllocationPlan { params: params.clone() },
+        );
+        ix.accounts.extend(vaults.iter().map(|vault| AccountMeta::new(*vault, false)));
+        ix
+    };
+    let result = send(&mut context, &[init(&[team_vault, team_vault, sale_vault])], &[]).await;
+    assert!(is_program_error(result, MyError::SharedAllocationVault));
+    send(&mut context, &[init(&[team_vault, liquidity_vault, sale_vault])], &[]).await.unwrap();
+    assert_eq!(balance(&mut context, &sale_vault).await, 200_000);
+
+    let mut close = launchpad_ix(
+        accounts::CloseAllocationPlan {
+            creator: creator.pubkey(),
+            plan,
+            mint,
+            token_program: spl_token_2022::id(),
+        },
+        instruction::CloseAllocationPlan {},
+    );
+    close.accounts.extend(
+        [team_vault, team_beneficiary, liquidity_vault, liquidity_beneficiary]
+            .iter()
+            .map(|account| AccountMeta::new(*account, false)),
+    );
+    let result = send(&mut context, &[close.clone()], &[]).await;
+    assert!(is_program_error(result, MyError::AllocationNotReleased));
+
+    let release = |index: u8, vault: Pubkey, beneficiary: Pubkey| {
+        launchpad_ix(
+            accounts::ReleaseAllocation {
+                plan,
+                mint,
+                vault,
+                beneficiary,
+                token_program: spl_token_2022::id(),
+                mint_pause: mint_pause(&mint),
+            },
+            instruction::ReleaseAllocation { index },
+        )
+    };
+    let result = send(&mut context, &[release(0, team_vault, team_beneficiary)], &[]).await;
+    assert!(is_program_error(result, MyError::NothingToClaim));
+    let result = send(&mut context, &[release(0, liquidity_vault, team_beneficiary)], &[]).await;
+    assert!(is_program_error(result, MyError::InvalidTokenAccount));
+
+    set_clock(&mut context, start + 500).await;
+    send(&mut context, &[release(0, team_vault, team_beneficiary)], &[]).await.unwrap();
+    send(&mut context, &[release(1, liquidity_vault, liquidity_beneficiary)], &[]).await.unwrap();
+    assert_eq!(balance(&mut context, &team_beneficiary).await, 250_000 - fee(250_000));
+    assert_eq!(balance(&mut context, &liquidity_beneficiary).await, 300_000 - fee(300_000));
+
+    // Tokens sent to a locker on top of its bucket wait for the plan's close
+    transfer(&mut context, &mint, &sale_vault, &team_vault, &creator, 1_000).await;
+    let stray = 1_000 - fee(1_000);
+
+    set_clock(&mut context, start + 1_000).await;
+    send(&mut context, &[release(0, team_vault, team_beneficiary)], &[]).await.unwrap();
+    assert_eq!(balance(&mut context, &team_beneficiary).await, 2 * (250_000 - fee(250_000)));
+    assert_eq!(balance(&mut context, &team_vault).await, stray);
+    let state = get_program_account::<AllocationPlan>(&mut context, &plan).await;
+    assert!(state.buckets.iter().all(|bucket| bucket.released == bucket.amount));
+    let result = send(&mut context, &[release(1, liquidity_vault, liquidity_beneficiary)], &[]).await;
+    assert!(is_program_error(result, MyError::NothingToClaim));
+
+    set_clock(&mut context, start + 1_000).await;
+    send(&mut context, &[close], &[]).await.unwrap();
+    assert_eq!(balance(&mut context, &team_beneficiary).await, 2 * (250_000 - fee(250_000)) + stray - fee(stray));
+    assert!(!account_exists(&mut context, &team_vault).await);
+    assert!(!account_exists(&mut context, &liquidity_vault).await);
+    assert!(!account_exists(&mut context, &plan).await);
+}
+
+#[tokio::test]
+#[cfg_attr(not(feature = "sbf-tests"), ignore = "needs the SBF build; run `anchor build`, then test with `--features sbf-tests`")]
+async fn allocation_revoke_keeps_what_had_vested() {
+    let admin = Keypair::new();
+    let mut context = program_test(&admin.pubkey()).start_with_context().await;
+    let creator = context.payer.insecure_clone();
+    let mint = taxed_launch(&mut context, &admin.pubkey()).await;
+
+    let plan = pda(&[ALLOCATION_PLAN_SEED, mint.as_ref()]);
+    let team = Keypair::new();
+    let team_vault = create_token_account(&mut context, &mint, &plan).await;
+    let team_beneficiary = create_token_account(&mut context, &mint, &team.pubkey()).await;
+    let sale_vault = create_token_account(&mut context, &mint, &creator.pubkey()).await;
+    let start = clock(&mut context).await.unix_timestamp;
+    set_clock(&mut context, start).await;
+
+    let init = |team_kind: AllocationKind| {
+        let params = AllocationParams {
+            total_supply: 1_000_000,
+            buckets: vec![
+                AllocationBucketParams {
+                    kind: team_kind,
+                    bps: 5_000,
+                    beneficiary: team_beneficiary,
+                    vesting_start: start,
+                    vesting_end: start + 1_000,
+                    revocable: true,
+                },
+                AllocationBucketParams {
+                    kind: AllocationKind::Sale,
+                    bps: 5_000,
+                    beneficiary: sale_vault,
+                    vesting_start: 0,
+                    vesting_end: 0,
+                    revocable: false,
+                },
+            ],
+        };
+        let mut ix = launchpad_ix(
+            accounts::InitAllocationPlan {
+                creator: creator.pubkey(),
+                mint_authority: creator.pubkey(),
+                launch_config: pda(&[LAUNCH_CONFIG_SEED, mint.as_ref()]),
+                mint,
+                plan,
+                token_program: spl_token_2022::id(),
+                system_program: solana_sdk::system_program::id(),
+            },
+            instruction::InitAllocationPlan { params },
+        );
+        ix.accounts.extend([team_vault, sale_vault].iter().map(|vault| AccountMeta::new(*vault, false)));
+        ix
+    };
+    let result = send(&mut context, &[init(AllocationKind::Liquidity)], &[]).await;
+    assert!(is_program_error(result, MyError::InvalidAllocationPlan));
+    send(&mut context, &[init(AllocationKind::Team)], &[]).await.unwrap();
+
+    let revoke = launchpad_ix(
+        accounts::RevokeAllocation {
+            creator: creator.pubkey(),
+            plan,
+            mint,
+            vault: team_vault,
+            destination: sale_vault,
+            token_program: spl_token_2022::id(),
+            mint_pause: mint_pause(&mint),
+        },
+        instruction::RevokeAllocation { index: 0 },
+    );
+    set_clock(&mut context, start + 250).await;
+    send(&mut context, std::slice::from_ref(&revoke), &[]).await.unwrap();
+    assert_eq!(balance(&mut context, &sale_vault).await, 500_000 + 375_000 - fee(375_000));
+    assert_eq!(balance(&mut context, &team_vault).await, 125_000);
+    set_clock(&mut context, start + 260).await;
+    let result = send(&mut context, &[revoke], &[]).await;
+    assert!(is_program_error(result, MyError::AllocationNotRevocable));
+
+    // The vested part is all the beneficiary gets, however long they wait
+    set_clock(&mut context, start + 1_000).await;
+    let release = launchpad_ix(
+        accounts::ReleaseAllocation {
+            plan,
+            mint,
+            vault: team_vault,
+            beneficiary: team_beneficiary,
+            token_program: spl_token_2022::id(),
+            mint_pause: mint_pause(&mint),
+        },
+        instruction::ReleaseAllocation { index: 0 },
+    );
+    send(&mut context, &[release], &[]).await.unwrap();
+    assert_eq!(balance(&mut context, &team_beneficiary).await, 125_000 - fee(125_000));
+    let state = get_program_account::<AllocationPlan>(&mut context, &plan).await;
+    assert_eq!((state.buckets[0].amount, state.buckets[0].released), (125_000, 125_000));
+}
+
+#[tokio::test]
+#[cfg_attr(not(feature = "sbf-tests"), ignore = "needs the SBF build; run `anchor build`, then test with `--features sbf-tests`")]
+async fn allocation_beneficiary_changes_with_both_consents() {
+    let admin = Keypair::new();
+    let mut context = program_test(&admin.pubkey()).start_with_context().await;
+    let creator = context.payer.insecure_clone();
+    let mint = taxed_launch(&mut context, &admin.pubkey()).await;
+
+    let plan = pda(&[ALLOCATION_PLAN_SEED, mint.as_ref()]);
+    let (team, successor) = (Keypair::new(), Keypair::new());
+    let team_vault = create_token_account(&mut context, &mint, &plan).await;
+    let team_beneficiary = create_token_account(&mut context, &mint, &team.pubkey()).await;
+    let successor_beneficiary = create_token_account(&mut context, &mint, &successor.pubkey()).await;
+    let sale_vault = create_token_account(&mut context, &mint, &creator.pubkey()).await;
+    let start = clock(&mut context).await.unix_timestamp;
+    set_clock(&mut context, start).await;
+
+    let params = AllocationParams {
+        total_supply: 1_000_000,
+        buckets: vec![
+            AllocationBucketParams {
+                kind: AllocationKind::Team,
+                bps: 5_000,
+                beneficiary: team_beneficiary,
+                vesting_start: start,
+                vesting_end: start + 1_000,
+                revocable: false,
+            },
+            AllocationBucketParams {
+                kind: AllocationKind::Sale,
+                bps: 5_000,
+                beneficiary: sale_vault,
+                vesting_start: 0,
+                vesting_end: 0,
+                revocable: false,
+            },
+        ],
+    };
+    let mut init = launchpad_ix(
+        accounts::InitAllocationPlan {
+            creator: creator.pubkey(),
+            mint_authority: creator.pubkey(),
+            launch_config: pda(&[LAUNCH_CONFIG_SEED, mint.as_ref()]),
+            mint,
+            plan,
+            token_program: spl_token_2022::id(),
+            system_program: solana_sdk::system_program::id(),
+        },
+        instruction::InitAllocationPlan { params },
+    );
+    init.accounts.extend([team_vault, sale_vault].iter().map(|vault| AccountMeta::new(*vault, false)));
+    send(&mut context, &[init], &[]).await.unwrap();
+
+    let accept = |beneficiary: Pubkey| {
+        launchpad_ix(
+            accounts::AcceptAllocationBeneficiary {
+                creator: creator.pubkey(),
+                plan,
+                mint,
+                vault: team_vault,
+                beneficiary,
+                token_program: spl_token_2022::id(),
+                mint_pause: mint_pause(&mint),
+            },
+            instruction::AcceptAllocationBeneficiary { index: 0 },
+        )
+    };
+    let result = send(&mut context, &[accept(team_beneficiary)], &[]).await;
+    assert!(is_program_error(result, MyError::NoPendingBeneficiary));
+
+    let propose = |owner: &Keypair| {
+        launchpad_ix(
+            accounts::ProposeAllocationBeneficiary {
+                owner: owner.pubkey(),
+                plan,
+                beneficiary: team_beneficiary,
+                new_beneficiary: successor_beneficiary,
+            },
+            instruction::ProposeAllocationBeneficiary { index: 0 },
+        )
+    };
+    let result = send(&mut context, &[propose(&successor)], &[&successor]).await;
+    assert!(is_program_error(result, MyError::Unauthorized));
+    send(&mut context, &[propose(&team)], &[&team]).await.unwrap();
+
+    // What vested before the switch still goes to the old beneficiary
+    set_clock(&mut context, start + 400).await;
+    send(&mut context, &[accept(team_beneficiary)], &[]).await.unwrap();
+    assert_eq!(balance(&mut context, &team_beneficiary).await, 200_000 - fee(200_000));
+    let state = get_program_account::<AllocationPlan>(&mut context, &plan).await;
+    assert_eq!(state.buckets[0].beneficiary, successor_beneficiary);
+    assert_eq!(state.buckets[0].pending_beneficiary, None);
+
+    set_clock(&mut context, start + 1_000).await;
+    let release = launchpad_ix(
+        accounts::ReleaseAllocation {
+            plan,
+            mint,
+            vault: team_vault,
+            beneficiary: successor_beneficiary,
+            token_program: spl_token_2022::id(),
+            mint_pause: mint_pause(&mint),
+        },
+        instruction::ReleaseAllocation { index: 0 },
+    );
+    send(&mut context, &[release], &[]).await.unwrap();
+    assert_eq!(balance(&mut context, &successor_beneficiary).await, 300_000 - fee(300_000));
+}
@@ -19,6 +19,93 @@ describe("taxed-token-launchpad (Token-2022 tests)", () => {
 
   const program = anchor.workspace.taxedTokenLaunchpad as Program<TaxedTokenLaunchpad>;
 
+  const [platformConfig] = PublicKey.findProgramAddressSync(
+    [Buffer.from("platform_config")],
+    program.programId
+  );
+  const [insuranceVault] = PublicKey.findProgramAddressSync(
+    [Buffer.from("insurance_vault")],
+    program.programId
+  );
+
+  // Platform accounts every launch by the provider wallet needs (no bond required on localnet)
+  function launchAccounts(mint: PublicKey) {
+    return {
+      platformConfig,
+      creatorBond: null,
+      devnetFaucet: null,
+      featuredGauge: null,
+      priceUpdate: null,
+      attestor: null,
+      partner: null,
+      stakingPool: null,
+      treasury: provider.wallet.publicKey,
+      insuranceVault,
+      launchConfig: PublicKey.findProgramAddressSync(
+        [Buffer.from("launch_config"), mint.toBuffer()],
+        program.programId
+      )[0],
+      creatorReputation: PublicKey.findProgramAddressSync(
+        [Buffer.from("reputation"), provider.wallet.publicKey.toBuffer()],
+        program.programId
+      )[0],
+    };
+  }
+
+  before(async () => {
+    // The platform config is created once by the program's upgrade authority (the provider wallet on localnet)
+    const [programData] = PublicKey.findProgramAddressSync(
+      [program.programId.toBuffer()],
+      new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+    );
+    if ((await provider.connection.getAccountInfo(platformConfig)) === null) {
+      await program.methods
+        .initializePlatform({
+          verifier: provider.wallet.publicKey,
+          treasury: provider.wallet.publicKey,
+          launchFeeLamports: new anchor.BN(0),
+          insuranceFeeBps: 0,
+          bondLamports: new anchor.BN(0),
+          bondLockSeconds: new anchor.BN(0),
+          snapshotter: provider.wallet.publicKey,
+          platformMint: PublicKey.default,
+          featuredFeeDiscountBps: 0,
+          launchFeeUsd: new anchor.BN(0),
+          solUsdFeedId: Array(32).fill(0),
+          maxPriceAgeSeconds: new anchor.BN(0),
+          maxPriceConfidenceBps: 0,
+          maxActiveLaunches: 0,
+          launchCooldownSeconds: new anchor.BN(0),
+          bondEscalationBps: 0,
+          launchAttestor: null,
+          attestationRequired: false,
+          stakingFeeBps: 0,
+          demandFreeLaunches: 0,
+          demandFeeStepBps: 0,
+          maxDemandMultiplierBps: 0,
+        })
+        .accounts({
+          admin: provider.wallet.publicKey,
+          platformConfig,
+          program: program.programId,
+          programData,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+    }
+    if ((await provider.connection.getAccountInfo(insuranceVault)) === null) {
+      await program.methods
+        .initializeInsuranceVault()
+        .accounts({
+          admin: provider.wallet.publicKey,
+          platformConfig,
+          insuranceVault,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+    }
+  });
+
   it("Creates a taxed token and withholds fee on transfer", async () => {
     const payer = provider.wallet as any; // AnchorWallet
 
@@ -48,6 +135,7 @@ describe("taxed-token-launchpad (Token-2022 tests)", () => {
           feeWithdrawAuthority: payer.publicKey,
           freezeAuthority: payer.publicKey,
           tokenProgram: TOKEN_2022_PROGRAM_ID,
+          ...launchAccounts(mint.publicKey),
           systemProgram: anchor.web3.SystemProgram.programId,
           rent: anchor.web3.SYSVAR_RENT_PUBKEY,
         })
@@ -169,6 +257,7 @@ describe("taxed-token-launchpad (Token-2022 tests)", () => {
           mintAuthority: payer.publicKey,
           freezeAuthority: payer.publicKey,
           tokenProgram: TOKEN_2022_PROGRAM_ID,
+          ...launchAccounts(mint.publicKey),
           systemProgram: anchor.web3.SystemProgram.programId,
           rent: anchor.web3.SYSVAR_RENT_PUBKEY,
         })
@@ -243,4 +332,94 @@ describe("taxed-token-launchpad (Token-2022 tests)", () => {
 
     assert(transferFailed, "Transfer succeeded unexpectedly for soulbound token");
   });
+
+  it("Records a rebate for transfers to an exempt wallet", async () => {
+    const payer = provider.wallet as any;
+    const providerPayer = (provider.wallet as any).payer || payer;
+    const mint = Keypair.generate();
+    const treasury = Keypair.generate();
+    const decimals = 6;
+    const transferFeeBps = 100; // 1%
+
+    await program.methods
+      .createTaxedToken(decimals, transferFeeBps, new anchor.BN(1_000_000))
+      .accounts({
+        payer: payer.publicKey,
+        mint: mint.publicKey,
+        mintAuthority: payer.publicKey,
+        feeWithdrawAuthority: payer.publicKey,
+        freezeAuthority: payer.publicKey,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        ...launchAccounts(mint.publicKey),
+        systemProgram: anchor.web3.SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .signers([mint])
+      .rpc();
+
+    const [exemptionList] = PublicKey.findProgramAddressSync(
+      [Buffer.from("fee_exemptions"), mint.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .initFeeExemptionList()
+      .accounts({
+        payer: payer.publicKey,
+        authority: payer.publicKey,
+        mint: mint.publicKey,
+        exemptionList,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    await program.methods
+      .addFeeExemption(treasury.publicKey)
+      .accounts({ authority: payer.publicKey, exemptionList })
+      .rpc();
+
+    const payerAta = await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      providerPayer,
+      mint.publicKey,
+      payer.publicKey,
+      false,
+      undefined,
+      undefined,
+      TOKEN_2022_PROGRAM_ID,
+      ASSOCIATED_TOKEN_PROGRAM_ID
+    );
+    const treasuryAta = await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      providerPayer,
+      mint.publicKey,
+      treasury.publicKey,
+      false,
+      undefined,
+      undefined,
+      TOKEN_2022_PROGRAM_ID,
+      ASSOCIATED_TOKEN_PROGRAM_ID
+    );
+    await mintTo(provider.connection, providerPayer, mint.publicKey, payerAta.address, providerPayer, 1_000_000, [], undefined, TOKEN_2022_PROGRAM_ID);
+
+    const transferAmount = 100_000;
+    const fee = Math.ceil((transferAmount * transferFeeBps) / 10000);
+    await program.methods
+      .transferWithFee(new anchor.BN(transferAmount), decimals, new anchor.BN(fee))
+      .accounts({
+        source: payerAta.address,
+        mint: mint.publicKey,
+        destination: treasuryAta.address,
+        authority: payer.publicKey,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        exemptionList,
+        mintStats: null,
+        poolRegistry: null,
+      })
+      .rpc();
+
+    const list = await program.account.feeExemptionList.fetch(exemptionList);
+    assert.equal(list.exemptions.length, 1);
+    assert.equal(list.exemptions[0].rebateOwed.toNumber(), fee);
+  });
 });